@@ -7,7 +7,7 @@ use soroban_sdk::{Address, Env, Vec};
 use super::storage;
 use super::types::{
     AllowanceApprovedEvent, AllowanceError, AllowanceOperation, AllowanceRevokedEvent,
-    AllowanceSpentEvent, TokenAllowance,
+    AllowanceSpendRecord, AllowanceSpentEvent, TokenAllowance,
 };
 
 // â”€â”€ Approve â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€
@@ -178,28 +178,23 @@ pub fn revoke(
 
 // â”€â”€ Spend â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€
 
-/// Internal: consume `amount` from the allowance.
+/// Internal: check that `amount` could be consumed from the allowance
+/// without consuming it, returning the allowance.
 ///
 /// Validates expiry, operation type, and remaining balance.
-/// Called by treasury execution paths.
-pub fn spend(
+pub fn check_spend(
     env: &Env,
     spender: &Address,
     owner: &Address,
     token: &Option<Address>,
     amount: i128,
     op_type: &AllowanceOperation,
-) -> Result<(), AllowanceError> {
-    if amount <= 0 {
-        return Ok(());
-    }
-
-    let mut allowance =
+) -> Result<TokenAllowance, AllowanceError> {
+    let allowance =
         storage::get_allowance(env, owner, spender, token).ok_or(AllowanceError::NotFound)?;
 
     // Check expiry
-    let now = env.ledger().timestamp();
-    if allowance.is_expired(now) {
+    if allowance.is_expired(env.ledger().timestamp()) {
         return Err(AllowanceError::Expired);
     }
 
@@ -213,9 +208,38 @@ pub fn spend(
         return Err(AllowanceError::InsufficientAllowance);
     }
 
+    Ok(allowance)
+}
+
+/// Internal: consume `amount` from the allowance.
+///
+/// Validates as `check_spend` does. Called by treasury execution paths.
+pub fn spend(
+    env: &Env,
+    spender: &Address,
+    owner: &Address,
+    token: &Option<Address>,
+    amount: i128,
+    op_type: &AllowanceOperation,
+) -> Result<(), AllowanceError> {
+    if amount <= 0 {
+        return Ok(());
+    }
+
+    let mut allowance = check_spend(env, spender, owner, token, amount, op_type)?;
+    let now = env.ledger().timestamp();
+
     allowance.spent = allowance.spent.saturating_add(amount);
     storage::store_allowance(env, &allowance);
 
+    let record = AllowanceSpendRecord {
+        amount,
+        remaining: allowance.remaining(),
+        operation: op_type.clone(),
+        timestamp: now,
+    };
+    storage::append_spend_record(env, owner, spender, token, &record);
+
     let event = AllowanceSpentEvent {
         owner: owner.clone(),
         spender: spender.clone(),
@@ -249,3 +273,43 @@ pub fn get_owner_allowances(env: &Env, owner: &Address) -> Vec<TokenAllowance> {
 pub fn get_spender_allowances(env: &Env, spender: &Address) -> Vec<TokenAllowance> {
     storage::list_by_spender(env, spender)
 }
+
+/// Allowances granted by `owner` that still have unspent balance and expire
+/// within the next `within_seconds`. Allowances without an expiry and those
+/// that have already lapsed are left out.
+pub fn get_expiring_allowances(
+    env: &Env,
+    owner: &Address,
+    within_seconds: u64,
+) -> Vec<TokenAllowance> {
+    let now = env.ledger().timestamp();
+    let horizon = now.saturating_add(within_seconds);
+
+    let mut result = Vec::new(env);
+    for allowance in storage::list_by_owner(env, owner).iter() {
+        if allowance.expires_at == 0 || allowance.is_expired(now) {
+            continue;
+        }
+        if allowance.expires_at <= horizon && allowance.remaining() > 0 {
+            result.push_back(allowance);
+        }
+    }
+    result
+}
+
+/// Most recent `limit` spends against an (owner, spender, token) allowance,
+/// oldest first. Remains available after the allowance is revoked.
+pub fn get_allowance_spend_history(
+    env: &Env,
+    owner: &Address,
+    spender: &Address,
+    token: &Option<Address>,
+    limit: u32,
+) -> Vec<AllowanceSpendRecord> {
+    let all = storage::get_spend_history(env, owner, spender, token);
+    let len = all.len();
+    if len <= limit {
+        return all;
+    }
+    all.slice(len - limit..len)
+}
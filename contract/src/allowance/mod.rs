@@ -3,11 +3,12 @@ pub mod storage;
 pub mod types;
 
 pub use management::{
-    approve, decrease_allowance, get_allowance_detail, get_owner_allowances,
-    get_spender_allowances, increase_allowance, revoke, spend,
+    approve, check_spend, decrease_allowance, get_allowance_detail, get_allowance_spend_history,
+    get_expiring_allowances, get_owner_allowances, get_spender_allowances, increase_allowance,
+    revoke, spend,
 };
 
-pub use types::{AllowanceError, AllowanceOperation, TokenAllowance};
+pub use types::{AllowanceError, AllowanceOperation, AllowanceSpendRecord, TokenAllowance};
 
 #[cfg(test)]
 mod tests;
@@ -1,6 +1,6 @@
 ﻿use soroban_sdk::{symbol_short, Address, Env, Map, Symbol, Vec};
 
-use super::types::TokenAllowance;
+use super::types::{AllowanceSpendRecord, TokenAllowance};
 
 // â”€â”€ Storage Keys â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€
 
@@ -10,6 +10,12 @@ const ALLOWANCES_KEY: Symbol = symbol_short!("tkn_alw");
 /// Owner index: owner â†’ Vec<(spender, token)>
 const OWNER_IDX_KEY: Symbol = symbol_short!("alw_oidx");
 
+/// Spend history: (owner, spender, token) â†’ Vec<AllowanceSpendRecord>
+const SPEND_HISTORY_KEY: Symbol = symbol_short!("alw_hist");
+
+/// Maximum spend records retained per allowance; oldest are dropped first.
+pub const MAX_SPEND_HISTORY: u32 = 100;
+
 /// Spender index: spender â†’ Vec<(owner, token)>
 const SPENDER_IDX_KEY: Symbol = symbol_short!("alw_sidx");
 
@@ -196,3 +202,48 @@ pub fn list_by_spender(env: &Env, spender: &Address) -> Vec<TokenAllowance> {
     }
     result
 }
+
+/// Append a spend record, evicting the oldest once `MAX_SPEND_HISTORY` is reached.
+/// History is keyed independently of the allowance so it survives revocation.
+pub fn append_spend_record(
+    env: &Env,
+    owner: &Address,
+    spender: &Address,
+    token: &Option<Address>,
+    record: &AllowanceSpendRecord,
+) {
+    let key = (
+        SPEND_HISTORY_KEY,
+        owner.clone(),
+        spender.clone(),
+        token.clone(),
+    );
+    let mut history: Vec<AllowanceSpendRecord> = env
+        .storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or_else(|| Vec::new(env));
+    while history.len() >= MAX_SPEND_HISTORY {
+        history.pop_front();
+    }
+    history.push_back(record.clone());
+    env.storage().persistent().set(&key, &history);
+}
+
+pub fn get_spend_history(
+    env: &Env,
+    owner: &Address,
+    spender: &Address,
+    token: &Option<Address>,
+) -> Vec<AllowanceSpendRecord> {
+    let key = (
+        SPEND_HISTORY_KEY,
+        owner.clone(),
+        spender.clone(),
+        token.clone(),
+    );
+    env.storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or_else(|| Vec::new(env))
+}
@@ -376,6 +376,71 @@ mod tests {
         assert_eq!(list.len(), 2);
     }
 
+    #[test]
+    fn test_expiring_allowances_report() {
+        let (env, owner, spender, client, contract_id) = setup();
+        let spender2 = Address::generate(&env);
+        let spender3 = Address::generate(&env);
+        let spender4 = Address::generate(&env);
+
+        // Expires inside the window with balance left
+        client.approve_token_allowance(
+            &owner,
+            &spender,
+            &None,
+            &100,
+            &1_500,
+            &AllowanceOperation::Any,
+        );
+        // No expiry
+        client.approve_token_allowance(
+            &owner,
+            &spender2,
+            &None,
+            &100,
+            &0,
+            &AllowanceOperation::Any,
+        );
+        // Expires after the window
+        client.approve_token_allowance(
+            &owner,
+            &spender3,
+            &None,
+            &100,
+            &5_000,
+            &AllowanceOperation::Any,
+        );
+        // Expires inside the window but fully spent
+        client.approve_token_allowance(
+            &owner,
+            &spender4,
+            &None,
+            &100,
+            &1_200,
+            &AllowanceOperation::Any,
+        );
+        env.as_contract(&contract_id, || {
+            management::spend(
+                &env,
+                &spender4,
+                &owner,
+                &None,
+                100,
+                &AllowanceOperation::Any,
+            )
+            .unwrap();
+        });
+
+        let expiring = client.get_expiring_allowances(&owner, &1_000);
+        assert_eq!(expiring.len(), 1);
+        assert_eq!(expiring.get(0).unwrap().spender, spender);
+
+        // Lapsed allowances are no longer reported
+        set_ledger_timestamp(&env, 1_600);
+        assert_eq!(client.get_expiring_allowances(&owner, &1_000).len(), 0);
+        assert_eq!(client.get_expiring_allowances(&owner, &10_000).len(), 1);
+    }
+
     #[test]
     fn test_list_by_spender() {
         let (env, owner, spender, client, _) = setup();
@@ -438,4 +503,92 @@ mod tests {
         assert_eq!(allowance.spent, 0); // Reset
         assert_eq!(allowance.operation, AllowanceOperation::Withdrawal);
     }
+
+    // â”€â”€ Spend History â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€
+
+    #[test]
+    fn test_spend_history_recorded_and_survives_revoke() {
+        let (env, owner, spender, client, contract_id) = setup();
+
+        client.approve_token_allowance(
+            &owner,
+            &spender,
+            &None,
+            &1000,
+            &0,
+            &AllowanceOperation::Any,
+        );
+
+        env.as_contract(&contract_id, || {
+            management::spend(
+                &env,
+                &spender,
+                &owner,
+                &None,
+                100,
+                &AllowanceOperation::Withdrawal,
+            )
+        })
+        .unwrap();
+        set_ledger_timestamp(&env, 2_000);
+        env.as_contract(&contract_id, || {
+            management::spend(
+                &env,
+                &spender,
+                &owner,
+                &None,
+                250,
+                &AllowanceOperation::Escrow,
+            )
+        })
+        .unwrap();
+
+        let history = client.get_allowance_spend_history(&owner, &spender, &None, &10);
+        assert_eq!(history.len(), 2);
+        let first = history.get(0).unwrap();
+        assert_eq!(first.amount, 100);
+        assert_eq!(first.remaining, 900);
+        assert_eq!(first.timestamp, 1_000);
+        let second = history.get(1).unwrap();
+        assert_eq!(second.amount, 250);
+        assert_eq!(second.operation, AllowanceOperation::Escrow);
+        assert_eq!(second.timestamp, 2_000);
+
+        // Limit returns only the most recent entries
+        let latest = client.get_allowance_spend_history(&owner, &spender, &None, &1);
+        assert_eq!(latest.len(), 1);
+        assert_eq!(latest.get(0).unwrap().amount, 250);
+
+        client.revoke_token_allowance(&owner, &spender, &None);
+        let history = client.get_allowance_spend_history(&owner, &spender, &None, &10);
+        assert_eq!(history.len(), 2);
+    }
+
+    #[test]
+    fn test_spend_history_is_capped() {
+        let (env, owner, spender, client, contract_id) = setup();
+
+        let cap = crate::allowance::storage::MAX_SPEND_HISTORY;
+        client.approve_token_allowance(
+            &owner,
+            &spender,
+            &None,
+            &((cap as i128) + 10),
+            &0,
+            &AllowanceOperation::Any,
+        );
+
+        for _ in 0..(cap + 5) {
+            env.as_contract(&contract_id, || {
+                management::spend(&env, &spender, &owner, &None, 1, &AllowanceOperation::Any)
+            })
+            .unwrap();
+        }
+
+        let history = client.get_allowance_spend_history(&owner, &spender, &None, &(cap * 2));
+        assert_eq!(history.len(), cap);
+        // Oldest entries were evicted; the last record reflects the final spend
+        assert_eq!(history.get(cap - 1).unwrap().remaining, 5);
+        assert_eq!(history.get(0).unwrap().remaining, 104);
+    }
 }
@@ -58,6 +58,18 @@ impl TokenAllowance {
     }
 }
 
+/// A single consumption of an allowance, kept for auditing even after the
+/// allowance itself is revoked.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AllowanceSpendRecord {
+    pub amount: i128,
+    /// Remaining allowance immediately after this spend
+    pub remaining: i128,
+    pub operation: AllowanceOperation,
+    pub timestamp: u64,
+}
+
 // â”€â”€ Errors â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€
 
 #[contracterror]
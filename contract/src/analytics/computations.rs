@@ -1,9 +1,13 @@
-﻿use soroban_sdk::{Env, String, Vec};
+﻿use soroban_sdk::{token::Client as TokenClient, Address, Env, Map, String, Vec};
 
+use crate::analytics::storage::{cache_token_decimals, get_cached_token_decimals};
 use crate::analytics::types::{
-    BudgetUtilization, CategoryBreakdown, SpendingForecast, SpendingSummary, SpendingTrend,
+    BudgetUtilization, CategoryBreakdown, ForecastAccuracy, NormalizedSpendingSummary,
+    SpendingForecast, SpendingSummary, SpendingTrend, TokenFlow, UtilizationTrendPoint,
+};
+use crate::treasury::storage::{
+    get_budget_allocations, get_budget_spends, get_treasury_transactions, list_budgets_for_treasury,
 };
-use crate::treasury::storage::{get_treasury_transactions, list_budgets_for_treasury};
 use crate::treasury::types::{Transaction, TransactionStatus, TransactionType};
 
 /// Compute an aggregated spending summary for a treasury within [period_start, period_end].
@@ -33,11 +37,14 @@ pub fn compute_spending_summary(
                 total_deposits += tx.amount;
             }
             TransactionType::Withdrawal
+            | TransactionType::BatchWithdrawal
             | TransactionType::BountyFunding
             | TransactionType::MilestonePayment => {
                 total_withdrawals += tx.amount;
             }
-            TransactionType::AllowanceGrant => {}
+            TransactionType::AllowanceGrant
+            | TransactionType::EmergencyPause
+            | TransactionType::EmergencyUnpause => {}
         }
     }
 
@@ -60,6 +67,115 @@ pub fn compute_spending_summary(
     }
 }
 
+/// Decimals of the native asset (XLM), also the scale normalized totals use.
+pub const NATIVE_DECIMALS: u32 = 7;
+
+/// Largest decimals value that can still be rescaled within an `i128`.
+const MAX_SUPPORTED_DECIMALS: u32 = 38;
+
+/// Decimals of a treasury token: 7 for the native asset, otherwise fetched
+/// from the token contract once and cached. `None` if the token contract does
+/// not report usable decimals.
+pub fn resolve_token_decimals(env: &Env, token: &Option<Address>) -> Option<u32> {
+    let Some(token) = token else {
+        return Some(NATIVE_DECIMALS);
+    };
+    if let Some(decimals) = get_cached_token_decimals(env, token) {
+        return Some(decimals);
+    }
+
+    let decimals = match TokenClient::new(env, token).try_decimals() {
+        Ok(Ok(decimals)) if decimals <= MAX_SUPPORTED_DECIMALS => decimals,
+        _ => return None,
+    };
+    cache_token_decimals(env, token, decimals);
+    Some(decimals)
+}
+
+/// Rescale an amount from `decimals` places to `NATIVE_DECIMALS` places.
+/// Precision finer than the common scale is truncated.
+fn normalize_amount(amount: i128, decimals: u32) -> i128 {
+    if decimals >= NATIVE_DECIMALS {
+        amount / 10i128.pow(decimals - NATIVE_DECIMALS)
+    } else {
+        amount
+            .checked_mul(10i128.pow(NATIVE_DECIMALS - decimals))
+            .expect("normalized amount overflow")
+    }
+}
+
+/// Like `compute_spending_summary`, but amounts in tokens with different
+/// decimals are rescaled to a common scale before being summed.
+pub fn compute_normalized_spending_summary(
+    env: &Env,
+    treasury_id: u64,
+    period_start: u64,
+    period_end: u64,
+) -> NormalizedSpendingSummary {
+    let txs = get_treasury_transactions(env, treasury_id);
+
+    let mut total_deposits: i128 = 0;
+    let mut total_withdrawals: i128 = 0;
+    let mut tx_count: u32 = 0;
+    let mut unresolved: Map<Address, TokenFlow> = Map::new(env);
+
+    for tx in txs.iter() {
+        if !is_executed(&tx) {
+            continue;
+        }
+        if tx.created_at < period_start || tx.created_at > period_end {
+            continue;
+        }
+        let is_deposit = match tx.tx_type {
+            TransactionType::Deposit => true,
+            TransactionType::Withdrawal
+            | TransactionType::BatchWithdrawal
+            | TransactionType::BountyFunding
+            | TransactionType::MilestonePayment => false,
+            TransactionType::AllowanceGrant
+            | TransactionType::EmergencyPause
+            | TransactionType::EmergencyUnpause => continue,
+        };
+
+        if let Some(decimals) = resolve_token_decimals(env, &tx.token) {
+            let amount = normalize_amount(tx.amount, decimals);
+            if is_deposit {
+                total_deposits += amount;
+            } else {
+                total_withdrawals += amount;
+            }
+            tx_count += 1;
+        } else if let Some(token) = tx.token.clone() {
+            // only contract tokens can fail to resolve
+            let mut flow = unresolved.get(token.clone()).unwrap_or(TokenFlow {
+                token: token.clone(),
+                total_deposits: 0,
+                total_withdrawals: 0,
+                tx_count: 0,
+            });
+            if is_deposit {
+                flow.total_deposits += tx.amount;
+            } else {
+                flow.total_withdrawals += tx.amount;
+            }
+            flow.tx_count += 1;
+            unresolved.set(token, flow);
+        }
+    }
+
+    NormalizedSpendingSummary {
+        treasury_id,
+        decimals: NATIVE_DECIMALS,
+        total_deposits,
+        total_withdrawals,
+        net_flow: total_deposits - total_withdrawals,
+        tx_count,
+        period_start,
+        period_end,
+        unresolved: unresolved.values(),
+    }
+}
+
 /// Compute budget utilization for all categories of a given treasury.
 pub fn compute_budget_utilization(env: &Env, treasury_id: u64) -> Vec<BudgetUtilization> {
     let budgets = list_budgets_for_treasury(env, treasury_id);
@@ -97,6 +213,76 @@ pub fn compute_budget_utilization(env: &Env, treasury_id: u64) -> Vec<BudgetUtil
     result
 }
 
+/// Compute a category's budget utilization for each of the last `num_periods`
+/// periods ending now, oldest first.
+///
+/// Every period is reported so the series sits on a fixed time axis; periods
+/// before the budget was first set show zero allocation and utilization.
+/// Budget logs are capped at `MAX_BUDGET_LOG_ENTRIES`, so periods older than
+/// the retained entries read as empty.
+pub fn compute_utilization_trend(
+    env: &Env,
+    treasury_id: u64,
+    category: String,
+    num_periods: u32,
+    period_length_secs: u64,
+) -> Vec<UtilizationTrendPoint> {
+    let mut result = Vec::new(env);
+    if period_length_secs == 0 {
+        return result;
+    }
+
+    let allocations = get_budget_allocations(env, treasury_id, &category);
+    let spends = get_budget_spends(env, treasury_id, &category);
+    let now = env.ledger().timestamp();
+
+    for i in (0..num_periods as u64).rev() {
+        let period_end = now.saturating_sub(i.saturating_mul(period_length_secs));
+        let period_start = period_end.saturating_sub(period_length_secs);
+
+        let mut allocated: i128 = 0;
+        for entry in allocations.iter() {
+            if entry.timestamp > period_end {
+                break;
+            }
+            allocated = entry.amount;
+        }
+
+        let mut spent: i128 = 0;
+        for entry in spends.iter() {
+            if entry.timestamp > period_end {
+                break;
+            }
+            if entry.timestamp > period_start {
+                spent += entry.amount;
+            }
+        }
+        // A refund logged after its spend's period can leave a period net negative
+        let spent = spent.max(0);
+
+        let utilization_bps: u32 = if allocated > 0 {
+            let bps = (spent * 10000) / allocated;
+            if bps > 10000 {
+                10000u32
+            } else {
+                bps as u32
+            }
+        } else {
+            0
+        };
+
+        result.push_back(UtilizationTrendPoint {
+            period_start,
+            period_end,
+            allocated,
+            spent,
+            utilization_bps,
+        });
+    }
+
+    result
+}
+
 /// Group executed transactions by type and compute totals for each category.
 pub fn compute_category_breakdown(
     env: &Env,
@@ -129,7 +315,7 @@ pub fn compute_category_breakdown(
                 deposit_amount += tx.amount;
                 deposit_count += 1;
             }
-            TransactionType::Withdrawal => {
+            TransactionType::Withdrawal | TransactionType::BatchWithdrawal => {
                 withdrawal_amount += tx.amount;
                 withdrawal_count += 1;
             }
@@ -145,6 +331,7 @@ pub fn compute_category_breakdown(
                 allowance_amount += tx.amount;
                 allowance_count += 1;
             }
+            TransactionType::EmergencyPause | TransactionType::EmergencyUnpause => {}
         }
     }
 
@@ -263,6 +450,75 @@ pub fn compute_forecast(
     }
 }
 
+/// Most periods `compute_forecast_accuracy` backtests. Each one forecasts
+/// from as many periods again, so the work grows with the square.
+pub const MAX_BACKTEST_PERIODS: u32 = 24;
+
+/// Backtest `compute_forecast` over the last `num_periods` periods (capped at
+/// `MAX_BACKTEST_PERIODS`): each period is forecast from the `num_periods`
+/// periods before it and compared with what actually happened. Periods with no
+/// prior history to forecast from are skipped; if none remain,
+/// `sufficient_data` is false.
+pub fn compute_forecast_accuracy(
+    env: &Env,
+    treasury_id: u64,
+    num_periods: u32,
+    period_length_secs: u64,
+    current_time: u64,
+) -> ForecastAccuracy {
+    let num_periods = num_periods.min(MAX_BACKTEST_PERIODS);
+    let mut error_deposits: i128 = 0;
+    let mut error_withdrawals: i128 = 0;
+    let mut error_net_flow: i128 = 0;
+    let mut periods_tested: u32 = 0;
+
+    if period_length_secs > 0 {
+        for i in 0..num_periods {
+            let period_end = current_time.saturating_sub((i as u64) * period_length_secs);
+            let period_start = period_end.saturating_sub(period_length_secs);
+            if period_start >= period_end {
+                break;
+            }
+
+            let forecast = compute_forecast(
+                env,
+                treasury_id,
+                num_periods,
+                period_length_secs,
+                period_start,
+            );
+            if forecast.periods_analyzed == 0 {
+                continue;
+            }
+
+            let actual = compute_spending_summary(env, treasury_id, period_start, period_end);
+            error_deposits += (forecast.projected_deposits - actual.total_deposits).abs();
+            error_withdrawals += (forecast.projected_withdrawals - actual.total_withdrawals).abs();
+            error_net_flow += (forecast.projected_net_flow - actual.net_flow).abs();
+            periods_tested += 1;
+        }
+    }
+
+    if periods_tested == 0 {
+        return ForecastAccuracy {
+            mean_abs_error_deposits: 0,
+            mean_abs_error_withdrawals: 0,
+            mean_abs_error_net_flow: 0,
+            periods_tested: 0,
+            sufficient_data: false,
+        };
+    }
+
+    let tested = periods_tested as i128;
+    ForecastAccuracy {
+        mean_abs_error_deposits: error_deposits / tested,
+        mean_abs_error_withdrawals: error_withdrawals / tested,
+        mean_abs_error_net_flow: error_net_flow / tested,
+        periods_tested,
+        sufficient_data: true,
+    }
+}
+
 // â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€ Helpers â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€
 
 fn is_executed(tx: &Transaction) -> bool {
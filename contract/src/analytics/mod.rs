@@ -4,14 +4,15 @@ pub mod types;
 
 pub use computations::{
     compute_budget_utilization, compute_category_breakdown, compute_forecast,
-    compute_spending_summary, compute_trend,
+    compute_forecast_accuracy, compute_normalized_spending_summary, compute_spending_summary,
+    compute_trend, compute_utilization_trend, resolve_token_decimals,
 };
 
 pub use storage::{get_snapshot_count, get_snapshots, store_snapshot};
 
 pub use types::{
-    BudgetUtilization, CategoryBreakdown, SpendingForecast, SpendingSummary, SpendingTrend,
-    TreasurySnapshot,
+    BudgetUtilization, CategoryBreakdown, ForecastAccuracy, NormalizedSpendingSummary,
+    SpendingForecast, SpendingSummary, SpendingTrend, TreasurySnapshot, UtilizationTrendPoint,
 };
 
 #[cfg(test)]
@@ -1,9 +1,10 @@
-﻿use soroban_sdk::{symbol_short, Env, Map, Symbol, Vec};
+﻿use soroban_sdk::{symbol_short, Address, Env, Map, Symbol, Vec};
 
 use crate::analytics::types::TreasurySnapshot;
 
 const SNAPSHOTS_KEY: Symbol = symbol_short!("a_snaps");
 const SNAP_CNT_KEY: Symbol = symbol_short!("a_scnt");
+const TOKEN_DECIMALS_KEY: Symbol = symbol_short!("a_tdec");
 
 /// Maximum number of snapshots retained per treasury to bound storage growth.
 const MAX_SNAPSHOTS_PER_TREASURY: u32 = 200;
@@ -74,3 +75,16 @@ pub fn get_snapshot_count(env: &Env, treasury_id: u64) -> u32 {
 
     counts.get(treasury_id).unwrap_or(0u32)
 }
+
+/// Decimals previously fetched from a token contract, if any.
+pub fn get_cached_token_decimals(env: &Env, token: &Address) -> Option<u32> {
+    env.storage()
+        .persistent()
+        .get(&(TOKEN_DECIMALS_KEY, token.clone()))
+}
+
+pub fn cache_token_decimals(env: &Env, token: &Address, decimals: u32) {
+    env.storage()
+        .persistent()
+        .set(&(TOKEN_DECIMALS_KEY, token.clone()), &decimals);
+}
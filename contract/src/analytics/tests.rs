@@ -1,5 +1,6 @@
 ﻿#[cfg(test)]
 mod tests {
+    use crate::analytics::computations::MAX_BACKTEST_PERIODS;
     use crate::analytics::types::{
         BudgetUtilization, CategoryBreakdown, SpendingForecast, SpendingSummary, SpendingTrend,
         TreasurySnapshot,
@@ -354,4 +355,186 @@ mod tests {
         let last = snapshots.get(1).unwrap();
         assert_eq!(last.balance_xlm, 500);
     }
+
+    #[test]
+    fn test_forecast_accuracy_backtest() {
+        let env = setup_env();
+        set_ledger_timestamp(&env, 1000);
+        let contract_id = register_and_init_contract(&env);
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+        env.mock_all_auths();
+        let guild_owner = Address::generate(&env);
+        let guild_id = setup_guild(&client, &env, &guild_owner);
+
+        let (treasury_id, _, _, _) = create_treasury(&env, &client, guild_id);
+        let depositor = Address::generate(&env);
+
+        set_ledger_timestamp(&env, 1500);
+        client.deposit_treasury(&treasury_id, &depositor, &1000i128, &None);
+
+        // A single period of history leaves nothing to backtest against
+        set_ledger_timestamp(&env, 2000);
+        let accuracy = client.get_forecast_accuracy(&treasury_id, &2u32, &1000u64);
+        assert!(!accuracy.sufficient_data);
+        assert_eq!(accuracy.periods_tested, 0);
+
+        set_ledger_timestamp(&env, 2500);
+        client.deposit_treasury(&treasury_id, &depositor, &2000i128, &None);
+        set_ledger_timestamp(&env, 3500);
+        client.deposit_treasury(&treasury_id, &depositor, &3000i128, &None);
+
+        // Period 3000-4000: forecast (2000 + 1000) / 2 = 1500, actual 3000
+        // Period 2000-3000: forecast 1000, actual 2000
+        set_ledger_timestamp(&env, 4000);
+        let accuracy = client.get_forecast_accuracy(&treasury_id, &2u32, &1000u64);
+        assert!(accuracy.sufficient_data);
+        assert_eq!(accuracy.periods_tested, 2);
+        assert_eq!(accuracy.mean_abs_error_deposits, 1250);
+        assert_eq!(accuracy.mean_abs_error_withdrawals, 0);
+        assert_eq!(accuracy.mean_abs_error_net_flow, 1250);
+
+        // Oversized requests are capped rather than scanned in full
+        assert_eq!(
+            client.get_forecast_accuracy(&treasury_id, &u32::MAX, &1000u64),
+            client.get_forecast_accuracy(&treasury_id, &MAX_BACKTEST_PERIODS, &1000u64)
+        );
+    }
+
+    #[test]
+    fn test_utilization_trend_aligns_periods() {
+        let env = setup_env();
+        set_ledger_timestamp(&env, 1000);
+        let contract_id = register_and_init_contract(&env);
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+        env.mock_all_auths();
+        let guild_owner = Address::generate(&env);
+        let guild_id = setup_guild(&client, &env, &guild_owner);
+
+        let (treasury_id, owner, signer1, signer2) = create_treasury(&env, &client, guild_id);
+        let depositor = Address::generate(&env);
+        let recipient = Address::generate(&env);
+        let reason = String::from_str(&env, "test");
+        let category = String::from_str(&env, "withdrawal");
+        client.deposit_treasury(&treasury_id, &depositor, &5000i128, &None);
+
+        set_ledger_timestamp(&env, 2500);
+        client.set_budget(&treasury_id, &category, &1000i128, &3600u64, &owner);
+        set_ledger_timestamp(&env, 2800);
+        let tx_id =
+            client.propose_withdrawal(&treasury_id, &signer1, &recipient, &300i128, &None, &reason);
+        client.approve_transaction(&tx_id, &signer2);
+        client.execute_transaction(&tx_id, &owner);
+
+        set_ledger_timestamp(&env, 3500);
+        client.set_budget(&treasury_id, &category, &2000i128, &3600u64, &owner);
+        set_ledger_timestamp(&env, 4200);
+        let tx_id =
+            client.propose_withdrawal(&treasury_id, &signer1, &recipient, &500i128, &None, &reason);
+        client.approve_transaction(&tx_id, &signer2);
+        client.execute_transaction(&tx_id, &owner);
+
+        set_ledger_timestamp(&env, 5000);
+        let trend = client.get_treasury_utilization_trend(&treasury_id, &category, &4u32, &1000u64);
+        assert_eq!(trend.len(), 4);
+
+        // Before the budget existed the period is still reported, at zero
+        let first = trend.get(0).unwrap();
+        assert_eq!(first.period_start, 1000);
+        assert_eq!(first.period_end, 2000);
+        assert_eq!(first.allocated, 0);
+        assert_eq!(first.utilization_bps, 0);
+
+        let second = trend.get(1).unwrap();
+        assert_eq!(second.allocated, 1000);
+        assert_eq!(second.spent, 300);
+        assert_eq!(second.utilization_bps, 3000);
+
+        let third = trend.get(2).unwrap();
+        assert_eq!(third.allocated, 2000);
+        assert_eq!(third.spent, 0);
+        assert_eq!(third.utilization_bps, 0);
+
+        let fourth = trend.get(3).unwrap();
+        assert_eq!(fourth.period_end, 5000);
+        assert_eq!(fourth.allocated, 2000);
+        assert_eq!(fourth.spent, 500);
+        assert_eq!(fourth.utilization_bps, 2500);
+    }
+
+    mod six_decimal_token {
+        use soroban_sdk::{contract, contractimpl, Address, Env};
+
+        #[contract]
+        pub struct SixDecimalToken;
+
+        #[contractimpl]
+        impl SixDecimalToken {
+            pub fn transfer(_env: Env, _from: Address, _to: Address, _amount: i128) {}
+
+            pub fn decimals(_env: Env) -> u32 {
+                6
+            }
+        }
+    }
+
+    mod opaque_token {
+        use soroban_sdk::{contract, contractimpl, Address, Env};
+
+        /// Token that does not expose `decimals`
+        #[contract]
+        pub struct OpaqueToken;
+
+        #[contractimpl]
+        impl OpaqueToken {
+            pub fn transfer(_env: Env, _from: Address, _to: Address, _amount: i128) {}
+        }
+    }
+
+    #[test]
+    fn test_normalized_summary_rescales_mixed_decimals() {
+        let env = setup_env();
+        set_ledger_timestamp(&env, 1000);
+        let contract_id = register_and_init_contract(&env);
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+        env.mock_all_auths();
+        let guild_owner = Address::generate(&env);
+        let guild_id = setup_guild(&client, &env, &guild_owner);
+        let (treasury_id, _owner, _signer1, _signer2) = create_treasury(&env, &client, guild_id);
+        let depositor = Address::generate(&env);
+
+        let usdc = env.register_contract(None, six_decimal_token::SixDecimalToken);
+        let opaque = env.register_contract(None, opaque_token::OpaqueToken);
+        assert_eq!(client.get_token_decimals(&None), Some(7));
+        assert_eq!(client.get_token_decimals(&Some(usdc.clone())), Some(6));
+        assert_eq!(client.get_token_decimals(&Some(opaque.clone())), None);
+
+        set_ledger_timestamp(&env, 2000);
+        // 1 XLM, 2 USDC and an amount in a token of unknown precision
+        client.deposit_treasury(&treasury_id, &depositor, &10_000_000i128, &None);
+        client.deposit_treasury(
+            &treasury_id,
+            &depositor,
+            &2_000_000i128,
+            &Some(usdc.clone()),
+        );
+        client.deposit_treasury(&treasury_id, &depositor, &500i128, &Some(opaque.clone()));
+
+        // The raw summary adds base units across tokens as-is
+        let raw = client.get_spending_summary(&treasury_id, &0u64, &5000u64);
+        assert_eq!(raw.total_deposits, 12_000_500);
+
+        let summary = client.get_normalized_spending_summary(&treasury_id, &0u64, &5000u64);
+        assert_eq!(summary.decimals, 7);
+        assert_eq!(summary.total_deposits, 30_000_000);
+        assert_eq!(summary.net_flow, 30_000_000);
+        assert_eq!(summary.tx_count, 2);
+        assert_eq!(summary.unresolved.len(), 1);
+        let flow = summary.unresolved.get(0).unwrap();
+        assert_eq!(flow.token, opaque);
+        assert_eq!(flow.total_deposits, 500);
+        assert_eq!(flow.tx_count, 1);
+    }
 }
@@ -1,4 +1,4 @@
-﻿use soroban_sdk::{contracttype, String};
+﻿use soroban_sdk::{contracttype, Address, String, Vec};
 
 /// Reporting period options for analytics queries
 #[contracttype]
@@ -36,6 +36,19 @@ pub struct BudgetUtilization {
     pub utilization_bps: u32,
 }
 
+/// Budget utilization of one category over a single historical period
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UtilizationTrendPoint {
+    pub period_start: u64,
+    pub period_end: u64,
+    /// Allocation in effect at the end of the period (0 before the budget was set)
+    pub allocated: i128,
+    pub spent: i128,
+    /// Utilization percentage in basis points, capped at 10000
+    pub utilization_bps: u32,
+}
+
 /// Spending breakdown grouped by transaction type
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -75,3 +88,43 @@ pub struct SpendingForecast {
     pub projected_net_flow: i128,
     pub periods_analyzed: u32,
 }
+
+/// Backtest of the moving-average forecast against actual past periods.
+/// Errors are mean absolute differences; they are only meaningful when
+/// `sufficient_data` is true.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ForecastAccuracy {
+    pub mean_abs_error_deposits: i128,
+    pub mean_abs_error_withdrawals: i128,
+    pub mean_abs_error_net_flow: i128,
+    pub periods_tested: u32,
+    pub sufficient_data: bool,
+}
+
+/// Executed flows of a single token, in that token's own base units
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TokenFlow {
+    pub token: Address,
+    pub total_deposits: i128,
+    pub total_withdrawals: i128,
+    pub tx_count: u32,
+}
+
+/// Spending summary with every amount rescaled to `decimals` places.
+/// Tokens whose decimals could not be determined are not folded into the
+/// totals; they are reported per token in `unresolved` instead.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct NormalizedSpendingSummary {
+    pub treasury_id: u64,
+    pub decimals: u32,
+    pub total_deposits: i128,
+    pub total_withdrawals: i128,
+    pub net_flow: i128,
+    pub tx_count: u32,
+    pub period_start: u64,
+    pub period_end: u64,
+    pub unresolved: Vec<TokenFlow>,
+}
@@ -1,16 +1,58 @@
 ﻿use soroban_sdk::token::Client as TokenClient;
 use soroban_sdk::{Address, Env};
 
+use crate::bounty::storage::{get_escrow_total, get_native_token, set_escrow_total};
+use crate::bounty::types::EscrowShortfallEvent;
+use crate::events::emit::emit_event;
+use crate::events::topics::{ACT_ESCROW_SHORTFALL, MOD_BOUNTY};
+
+/// Resolve a bounty token to a token contract address. `None` denotes native
+/// XLM, which is moved through the configured native Stellar Asset Contract.
+fn resolve_token(env: &Env, token: &Option<Address>) -> Address {
+    match token {
+        Some(addr) => addr.clone(),
+        None => get_native_token(env).unwrap_or_else(|| panic!("Native XLM token not configured")),
+    }
+}
+
+/// Move the running escrow total for `token` by `delta`. Escrows locked
+/// before the total was tracked can release more than it recorded; the
+/// total then stays at zero and the shortfall is reported in an event so
+/// payouts are never blocked.
+pub(crate) fn adjust_escrow_total(env: &Env, token: &Option<Address>, delta: i128) {
+    let token = resolve_token(env, token);
+    let total = get_escrow_total(env, &token).saturating_add(delta);
+    if total < 0 {
+        debug_assert!(false, "escrow total went negative");
+        emit_event(
+            env,
+            MOD_BOUNTY,
+            ACT_ESCROW_SHORTFALL,
+            EscrowShortfallEvent {
+                token: token.clone(),
+                shortfall: -total,
+            },
+        );
+    }
+    set_escrow_total(env, &token, total.max(0));
+}
+
 /// Transfer funds from funder to contract
-pub fn lock_funds(env: &Env, token: &Address, funder: &Address, amount: i128) {
-    let client = TokenClient::new(env, token);
+pub fn lock_funds(env: &Env, token: &Option<Address>, funder: &Address, amount: i128) {
+    let client = TokenClient::new(env, &resolve_token(env, token));
     // Transfer tokens from funder to this contract
     client.transfer(funder, &env.current_contract_address(), &amount);
+    adjust_escrow_total(env, token, amount);
 }
 
 /// Release funds from contract to recipient (claimer or original funder)
-pub fn release_funds(env: &Env, token: &Address, recipient: &Address, amount: i128) {
-    let client = TokenClient::new(env, token);
+pub fn release_funds(env: &Env, token: &Option<Address>, recipient: &Address, amount: i128) {
+    // Nothing to move; also avoids requiring a native token for unfunded bounties
+    if amount <= 0 {
+        return;
+    }
+    let client = TokenClient::new(env, &resolve_token(env, token));
     // Transfer tokens from this contract to recipient
     client.transfer(&env.current_contract_address(), recipient, &amount);
+    adjust_escrow_total(env, token, -amount);
 }
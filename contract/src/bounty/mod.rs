@@ -18,26 +18,38 @@
 /// | Release escrow      | `(bounty, released)`     | `EscrowReleasedEvent`    |
 /// | Cancel bounty       | `(bounty, cancelled)`    | `BountyCancelledEvent`   |
 /// | Expire bounty       | `(bounty, expired)`      | `BountyExpiredEvent`     |
+/// | Commit claim        | `(bounty, committed)`    | `ClaimCommittedEvent`    |
 pub mod escrow;
 pub mod storage;
 pub mod types;
 
-use crate::bounty::escrow::{lock_funds, release_funds};
-use crate::bounty::storage::{get_bounty, get_guild_bounties, get_next_bounty_id, store_bounty};
+use crate::bounty::escrow::{adjust_escrow_total, lock_funds, release_funds};
+use crate::bounty::storage::{
+    get_bounty, get_claim_commitments, get_expiry_grace, get_guild_bounties,
+    get_max_expiry_horizon, get_next_bounty_id, get_reveal_window, get_reward_bounds, get_timeline,
+    record_transition, set_claim_commitments, set_reveal_window, set_reward_bounds, store_bounty,
+};
 use crate::bounty::types::{
     BountyApprovedEvent, BountyCancelledEvent, BountyClaimedEvent, BountyCreatedEvent,
-    BountyExpiredEvent, BountyFundedEvent, EscrowReleasedEvent, WorkSubmittedEvent,
+    BountyExpiredEvent, BountyFundedEvent, BountyRewardBounds, BountyTimelineEntry,
+    ClaimCommitment, ClaimCommittedEvent, ClaimEligibility, EscrowReleasedEvent,
+    WorkSubmittedEvent,
 };
 use crate::dispute::storage as dispute_storage;
 use crate::dispute::types::DisputeReference;
+use crate::events::critical::record_critical_event;
 use crate::events::emit::emit_event;
 use crate::events::topics::{
-    ACT_APPROVED, ACT_CANCELLED, ACT_CLAIMED, ACT_CREATED, ACT_EXPIRED, ACT_FUNDED, ACT_RELEASED,
-    ACT_SUBMITTED, MOD_BOUNTY,
+    ACT_APPROVED, ACT_CANCELLED, ACT_CLAIMED, ACT_COMMITTED, ACT_CREATED, ACT_EXPIRED, ACT_FUNDED,
+    ACT_RELEASED, ACT_SUBMITTED, MOD_BOUNTY,
 };
-use crate::guild::membership::has_permission;
+use crate::guild::membership::{enforce_permission, is_guest, is_suspended};
 use crate::guild::types::Role;
-use soroban_sdk::{Address, Env, String, Vec};
+use crate::treasury::management::{credit_deposit, execute_approved_transaction};
+use crate::treasury::storage as treasury_storage;
+use crate::treasury::types::TransactionType;
+use soroban_sdk::xdr::ToXdr;
+use soroban_sdk::{Address, Bytes, BytesN, Env, String, Vec};
 
 pub use types::{Bounty, BountyStatus};
 
@@ -52,22 +64,34 @@ pub fn create_bounty(
     title: String,
     description: String,
     reward_amount: i128,
-    token: Address,
+    token: Option<Address>,
     expiry: u64,
 ) -> u64 {
     creator.require_auth();
 
-    if !has_permission(env, guild_id, creator.clone(), Role::Admin) {
+    if !enforce_permission(env, guild_id, creator.clone(), Role::Admin, "create_bounty") {
         panic!("Unauthorized: Creator must be a guild admin or owner");
     }
     if reward_amount < 0 {
         panic!("Invalid reward amount: must be non-negative");
     }
 
+    let bounds = get_reward_bounds(env, guild_id);
+    if reward_amount < bounds.min_reward {
+        if reward_amount == 0 {
+            panic!("Zero-reward bounties are not allowed in this guild");
+        }
+        panic!("Reward is below the guild minimum");
+    }
+    if bounds.max_reward > 0 && reward_amount > bounds.max_reward {
+        panic!("Reward exceeds the guild maximum");
+    }
+
     let created_at = env.ledger().timestamp();
     if expiry <= created_at {
         panic!("Expiry must be in the future");
     }
+    assert_within_expiry_horizon(env, expiry, created_at);
     if title.len() == 0 || title.len() > 256 {
         panic!("Title must be between 1 and 256 characters");
     }
@@ -97,8 +121,10 @@ pub fn create_bounty(
         submission_url: None,
         created_at,
         expires_at: expiry,
+        funding_treasury_id: None,
     };
     store_bounty(env, &bounty);
+    record_transition(env, bounty_id, status, &creator);
 
     emit_event(
         env,
@@ -119,6 +145,9 @@ pub fn create_bounty(
 
 /// Fund a bounty with tokens
 ///
+/// Returns `false` without taking funds when the bounty is found to be past
+/// its expiry; the expiry is applied and kept rather than rolled back.
+///
 /// # Events emitted
 /// - `(bounty, funded)`  â†’ `BountyFundedEvent`
 /// - `(bounty, expired)` â†’ `BountyExpiredEvent`  (if bounty found to be expired)
@@ -133,14 +162,9 @@ pub fn fund_bounty(env: &Env, bounty_id: u64, funder: Address, amount: i128) ->
 
     let now = env.ledger().timestamp();
     if now > bounty.expires_at {
-        bounty.status = BountyStatus::Expired;
-        store_bounty(env, &bounty);
-        emit_event(
-            env,
-            MOD_BOUNTY,
-            ACT_EXPIRED,
-            BountyExpiredEvent { bounty_id },
-        );
+        if expire_lazily(env, &mut bounty, &funder) {
+            return false;
+        }
         panic!("Bounty has expired");
     }
 
@@ -154,10 +178,14 @@ pub fn fund_bounty(env: &Env, bounty_id: u64, funder: Address, amount: i128) ->
     bounty.funded_amount += amount;
     let is_fully_funded = bounty.funded_amount >= bounty.reward_amount;
 
-    if is_fully_funded && bounty.status == BountyStatus::AwaitingFunds {
+    let became_funded = is_fully_funded && bounty.status == BountyStatus::AwaitingFunds;
+    if became_funded {
         bounty.status = BountyStatus::Funded;
     }
     store_bounty(env, &bounty);
+    if became_funded {
+        record_transition(env, bounty_id, BountyStatus::Funded, &funder);
+    }
 
     emit_event(
         env,
@@ -175,8 +203,99 @@ pub fn fund_bounty(env: &Env, bounty_id: u64, funder: Address, amount: i128) ->
     true
 }
 
+/// Create a bounty whose reward is moved straight from a guild treasury into
+/// escrow. The move is an approved `BountyFunding` treasury transaction (see
+/// `treasury::propose_bounty_funding`), so it is subject to the treasury's
+/// multisig threshold; the transaction fixes the reward amount and token.
+///
+/// # Events emitted
+/// - `(bounty, created)` → `BountyCreatedEvent`
+/// - `(bounty, funded)`  → `BountyFundedEvent`
+#[allow(clippy::too_many_arguments)]
+pub fn create_and_fund_bounty_from_treasury(
+    env: &Env,
+    guild_id: u64,
+    treasury_id: u64,
+    funding_tx_id: u64,
+    creator: Address,
+    title: String,
+    description: String,
+    expiry: u64,
+) -> u64 {
+    let treasury = treasury_storage::get_treasury(env, treasury_id).expect("treasury not found");
+    if treasury.guild_id != guild_id {
+        panic!("Treasury does not belong to guild");
+    }
+    let tx = treasury_storage::get_transaction(env, funding_tx_id).expect("tx not found");
+    if tx.treasury_id != treasury_id || tx.tx_type != TransactionType::BountyFunding {
+        panic!("Not a bounty funding transaction for this treasury");
+    }
+    if tx.token.is_none() {
+        panic!("Treasury bounty funding requires a token");
+    }
+
+    let bounty_id = create_bounty(
+        env,
+        guild_id,
+        creator.clone(),
+        title,
+        description,
+        tx.amount,
+        tx.token.clone(),
+        expiry,
+    );
+
+    // Checks the creator is a treasury signer and the threshold was met
+    execute_approved_transaction(env, funding_tx_id, creator.clone());
+
+    let mut bounty = get_bounty(env, bounty_id).expect("Bounty not found");
+    bounty.funded_amount = tx.amount;
+    bounty.status = BountyStatus::Funded;
+    bounty.funding_treasury_id = Some(treasury_id);
+    store_bounty(env, &bounty);
+    record_transition(env, bounty_id, BountyStatus::Funded, &creator);
+    adjust_escrow_total(env, &bounty.token, tx.amount);
+
+    emit_event(
+        env,
+        MOD_BOUNTY,
+        ACT_FUNDED,
+        BountyFundedEvent {
+            bounty_id,
+            funder: env.current_contract_address(),
+            amount: tx.amount,
+            total_funded: tx.amount,
+            is_fully_funded: true,
+        },
+    );
+
+    bounty_id
+}
+
+/// Return escrowed funds to whoever funded the bounty: its treasury when it
+/// was treasury-funded, otherwise the creator.
+pub(crate) fn refund_bounty_funds(env: &Env, bounty: &Bounty, amount: i128) {
+    match bounty.funding_treasury_id {
+        Some(treasury_id) => {
+            credit_deposit(
+                env,
+                treasury_id,
+                env.current_contract_address(),
+                amount,
+                bounty.token.clone(),
+                false,
+            );
+            adjust_escrow_total(env, &bounty.token, -amount);
+        }
+        None => release_funds(env, &bounty.token, &bounty.creator, amount),
+    }
+}
+
 /// Claim a bounty after approval
 ///
+/// Returns `false` when the bounty is found to be past its expiry; the expiry
+/// is applied and kept rather than rolled back.
+///
 /// # Events emitted
 /// - `(bounty, claimed)`  â†’ `BountyClaimedEvent`
 /// - `(bounty, expired)`  â†’ `BountyExpiredEvent`  (if found expired during claim)
@@ -185,32 +304,243 @@ pub fn claim_bounty(env: &Env, bounty_id: u64, claimer: Address) -> bool {
 
     let mut bounty = get_bounty(env, bounty_id).expect("Bounty not found");
 
+    match claim_eligibility(env, &bounty, &claimer) {
+        ClaimEligibility::Eligible => {}
+        ClaimEligibility::Expired => {
+            if expire_lazily(env, &mut bounty, &claimer) {
+                return false;
+            }
+            panic!("Bounty has expired");
+        }
+        ClaimEligibility::BountyNotFound => panic!("Bounty not found"),
+        ClaimEligibility::NotOpen => panic!("Bounty is not open for claiming"),
+        ClaimEligibility::Suspended => panic!("Claimer is suspended from this guild"),
+        ClaimEligibility::Guest => panic!("Guests cannot claim bounties"),
+        ClaimEligibility::ReservedForOther => {
+            panic!("Bounty may only be claimed by the approved address")
+        }
+        ClaimEligibility::CommitRevealRequired => {
+            panic!("Bounty must be claimed through commit-reveal")
+        }
+    }
+
+    bounty.status = BountyStatus::Claimed;
+    bounty.claimer = Some(claimer.clone());
+    store_bounty(env, &bounty);
+    record_transition(env, bounty_id, BountyStatus::Claimed, &claimer);
+
+    emit_event(
+        env,
+        MOD_BOUNTY,
+        ACT_CLAIMED,
+        BountyClaimedEvent { bounty_id, claimer },
+    );
+
+    true
+}
+
+/// Report whether `claimer` could claim the bounty right now, without
+/// touching state
+///
+/// A bounty past its deadline reports `Expired` even though its stored
+/// status is only updated the next time someone interacts with it.
+pub fn can_claim_bounty(env: &Env, bounty_id: u64, claimer: Address) -> (bool, ClaimEligibility) {
+    let result = match get_bounty(env, bounty_id) {
+        Some(bounty) => claim_eligibility(env, &bounty, &claimer),
+        None => ClaimEligibility::BountyNotFound,
+    };
+    (result == ClaimEligibility::Eligible, result)
+}
+
+/// The checks `claim_bounty` applies, in order
+fn claim_eligibility(env: &Env, bounty: &Bounty, claimer: &Address) -> ClaimEligibility {
+    // The expiry grace only lets existing claims finish; it never admits new ones
+    if env.ledger().timestamp() > bounty.expires_at || bounty.status == BountyStatus::Expired {
+        return ClaimEligibility::Expired;
+    }
+    if bounty.status != BountyStatus::Open {
+        return ClaimEligibility::NotOpen;
+    }
+    if is_suspended(env, bounty.guild_id, claimer.clone()) {
+        return ClaimEligibility::Suspended;
+    }
+    if is_guest(env, bounty.guild_id, claimer.clone()) {
+        return ClaimEligibility::Guest;
+    }
+    match bounty.claimer.clone() {
+        Some(approved_claimer) if approved_claimer == *claimer => ClaimEligibility::Eligible,
+        Some(_) => ClaimEligibility::ReservedForOther,
+        None if get_reveal_window(env, bounty.id) > 0 => ClaimEligibility::CommitRevealRequired,
+        None => ClaimEligibility::Eligible,
+    }
+}
+
+/// Require claims on an unassigned bounty to go through commit-reveal
+///
+/// Claimants first call `commit_claim` with a hash of a secret nonce, then
+/// `reveal_claim` within `reveal_window` seconds; the earliest commitment
+/// still inside its window is the only one that can be revealed. A window of
+/// 0 turns commit-reveal off, drops pending commitments and restores direct
+/// claiming.
+pub fn set_claim_reveal_window(
+    env: &Env,
+    bounty_id: u64,
+    reveal_window: u64,
+    caller: Address,
+) -> bool {
+    caller.require_auth();
+
+    let bounty = get_bounty(env, bounty_id).expect("Bounty not found");
+
+    if !enforce_permission(env, bounty.guild_id, caller, Role::Admin, "set_claim_reveal_window") {
+        panic!("Unauthorized: Only guild admin can configure claiming");
+    }
+    match bounty.status {
+        BountyStatus::AwaitingFunds | BountyStatus::Funded | BountyStatus::Open => {}
+        _ => panic!("Bounty is no longer claimable"),
+    }
+
+    set_reveal_window(env, bounty_id, reveal_window);
+    if reveal_window == 0 {
+        set_claim_commitments(env, bounty_id, &Vec::new(env));
+    }
+    true
+}
+
+/// The commitment a claimant must submit to `commit_claim`:
+/// `sha256(bounty_id as big-endian u64 || nonce || claimer as XDR)`
+pub fn claim_commitment_hash(
+    env: &Env,
+    bounty_id: u64,
+    nonce: &BytesN<32>,
+    claimer: &Address,
+) -> BytesN<32> {
+    let mut preimage = Bytes::from_array(env, &bounty_id.to_be_bytes());
+    preimage.append(&Bytes::from(nonce.clone()));
+    preimage.append(&claimer.clone().to_xdr(env));
+    env.crypto().sha256(&preimage).into()
+}
+
+/// Commitments on a bounty whose reveal window has not yet lapsed, oldest first
+fn live_claim_commitments(env: &Env, bounty_id: u64, reveal_window: u64) -> Vec<ClaimCommitment> {
     let now = env.ledger().timestamp();
-    if now > bounty.expires_at {
-        bounty.status = BountyStatus::Expired;
-        store_bounty(env, &bounty);
-        emit_event(
-            env,
-            MOD_BOUNTY,
-            ACT_EXPIRED,
-            BountyExpiredEvent { bounty_id },
-        );
+    let mut live = Vec::new(env);
+    for commitment in get_claim_commitments(env, bounty_id).iter() {
+        if now <= commitment.committed_at.saturating_add(reveal_window) {
+            live.push_back(commitment);
+        }
+    }
+    live
+}
+
+/// Checks shared by both commit-reveal steps. Returns the reveal window, or
+/// `None` when the bounty was found past its expiry and has been expired.
+fn check_commit_reveal_claim(env: &Env, bounty: &mut Bounty, claimer: &Address) -> Option<u64> {
+    if env.ledger().timestamp() > bounty.expires_at {
+        if expire_lazily(env, bounty, claimer) {
+            return None;
+        }
         panic!("Bounty has expired");
     }
 
-    if bounty.status != BountyStatus::Open {
+    let reveal_window = get_reveal_window(env, bounty.id);
+    if reveal_window == 0 {
+        panic!("Commit-reveal claiming is not enabled for this bounty");
+    }
+    if bounty.status != BountyStatus::Open || bounty.claimer.is_some() {
         panic!("Bounty is not open for claiming");
     }
+    if is_suspended(env, bounty.guild_id, claimer.clone()) {
+        panic!("Claimer is suspended from this guild");
+    }
+    if is_guest(env, bounty.guild_id, claimer.clone()) {
+        panic!("Guests cannot claim bounties");
+    }
+    Some(reveal_window)
+}
 
-    match bounty.claimer.clone() {
-        Some(approved_claimer) if approved_claimer == claimer => {}
-        Some(_) => panic!("Bounty may only be claimed by the approved address"),
-        None => {}
+/// Commit to claiming a commit-reveal bounty
+///
+/// `commitment` must be `claim_commitment_hash` of a nonce the claimer keeps
+/// secret until `reveal_claim`. Commitments that were never revealed lapse
+/// at the end of their window and are pruned here, so they cannot hold the
+/// bounty. Returns `false` when the bounty is found to be past its expiry.
+///
+/// # Events emitted
+/// - `(bounty, committed)` → `ClaimCommittedEvent`
+/// - `(bounty, expired)`   → `BountyExpiredEvent`  (if found expired during commit)
+pub fn commit_claim(env: &Env, bounty_id: u64, commitment: BytesN<32>, claimer: Address) -> bool {
+    claimer.require_auth();
+
+    let mut bounty = get_bounty(env, bounty_id).expect("Bounty not found");
+    let reveal_window = match check_commit_reveal_claim(env, &mut bounty, &claimer) {
+        Some(window) => window,
+        None => return false,
+    };
+
+    let mut commitments = live_claim_commitments(env, bounty_id, reveal_window);
+    if commitments.iter().any(|c| c.claimer == claimer) {
+        panic!("Claimer already has a pending commitment");
+    }
+
+    let committed_at = env.ledger().timestamp();
+    commitments.push_back(ClaimCommitment {
+        claimer: claimer.clone(),
+        commitment,
+        committed_at,
+    });
+    set_claim_commitments(env, bounty_id, &commitments);
+
+    emit_event(
+        env,
+        MOD_BOUNTY,
+        ACT_COMMITTED,
+        ClaimCommittedEvent {
+            bounty_id,
+            claimer,
+            reveal_deadline: committed_at.saturating_add(reveal_window),
+        },
+    );
+
+    true
+}
+
+/// Reveal a claim commitment and take the bounty
+///
+/// Only the earliest commitment still inside its reveal window can be
+/// revealed; later committers wait until it is revealed or lapses. Returns
+/// `false` when the bounty is found to be past its expiry.
+///
+/// # Events emitted
+/// - `(bounty, claimed)` → `BountyClaimedEvent`
+/// - `(bounty, expired)` → `BountyExpiredEvent`  (if found expired during reveal)
+pub fn reveal_claim(env: &Env, bounty_id: u64, nonce: BytesN<32>, claimer: Address) -> bool {
+    claimer.require_auth();
+
+    let mut bounty = get_bounty(env, bounty_id).expect("Bounty not found");
+    let reveal_window = match check_commit_reveal_claim(env, &mut bounty, &claimer) {
+        Some(window) => window,
+        None => return false,
+    };
+
+    let commitments = live_claim_commitments(env, bounty_id, reveal_window);
+    let position = commitments
+        .iter()
+        .position(|c| c.claimer == claimer)
+        .expect("No pending commitment for this claimer");
+    let pending = commitments.get(position as u32).unwrap();
+    if pending.commitment != claim_commitment_hash(env, bounty_id, &nonce, &claimer) {
+        panic!("Revealed nonce does not match the commitment");
+    }
+    if position > 0 {
+        panic!("An earlier commitment is still awaiting reveal");
     }
 
     bounty.status = BountyStatus::Claimed;
     bounty.claimer = Some(claimer.clone());
     store_bounty(env, &bounty);
+    set_claim_commitments(env, bounty_id, &Vec::new(env));
+    record_transition(env, bounty_id, BountyStatus::Claimed, &claimer);
 
     emit_event(
         env,
@@ -235,6 +565,11 @@ pub fn submit_work(env: &Env, bounty_id: u64, submission_url: String) -> bool {
     if bounty.status != BountyStatus::Claimed {
         panic!("Bounty is not in claimed status");
     }
+    // Submissions are only cut off once a configured grace has run out
+    let grace = get_expiry_grace(env);
+    if grace > 0 && env.ledger().timestamp() > bounty.expires_at.saturating_add(grace) {
+        panic!("Bounty has expired");
+    }
     if submission_url.len() == 0 || submission_url.len() > 512 {
         panic!("Submission URL must be between 1 and 512 characters");
     }
@@ -242,6 +577,7 @@ pub fn submit_work(env: &Env, bounty_id: u64, submission_url: String) -> bool {
     bounty.status = BountyStatus::UnderReview;
     bounty.submission_url = Some(submission_url.clone());
     store_bounty(env, &bounty);
+    record_transition(env, bounty_id, BountyStatus::UnderReview, &claimer);
 
     emit_event(
         env,
@@ -266,7 +602,7 @@ pub fn approve_bounty(env: &Env, bounty_id: u64, approver: Address, claimer: Add
 
     let mut bounty = get_bounty(env, bounty_id).expect("Bounty not found");
 
-    if !has_permission(env, bounty.guild_id, approver.clone(), Role::Admin) {
+    if !enforce_permission(env, bounty.guild_id, approver.clone(), Role::Admin, "approve_bounty") {
         panic!("Unauthorized: Approver must be a guild admin or owner");
     }
     if bounty.status != BountyStatus::Funded {
@@ -276,6 +612,7 @@ pub fn approve_bounty(env: &Env, bounty_id: u64, approver: Address, claimer: Add
     bounty.status = BountyStatus::Open;
     bounty.claimer = Some(claimer.clone());
     store_bounty(env, &bounty);
+    record_transition(env, bounty_id, BountyStatus::Open, &approver);
 
     emit_event(
         env,
@@ -297,7 +634,7 @@ pub fn approve_completion(env: &Env, bounty_id: u64, approver: Address) -> bool
 
     let mut bounty = get_bounty(env, bounty_id).expect("Bounty not found");
 
-    if !has_permission(env, bounty.guild_id, approver.clone(), Role::Admin) {
+    if !enforce_permission(env, bounty.guild_id, approver.clone(), Role::Admin, "approve_completion") {
         panic!("Unauthorized: Approver must be a guild admin or owner");
     }
     if bounty.status != BountyStatus::UnderReview {
@@ -306,6 +643,7 @@ pub fn approve_completion(env: &Env, bounty_id: u64, approver: Address) -> bool
 
     bounty.status = BountyStatus::Completed;
     store_bounty(env, &bounty);
+    record_transition(env, bounty_id, BountyStatus::Completed, &approver);
 
     emit_event(
         env,
@@ -355,11 +693,50 @@ pub fn release_escrow(env: &Env, bounty_id: u64) -> bool {
                 token: bounty.token,
             },
         );
+        record_critical_event(env, bounty.guild_id, MOD_BOUNTY, ACT_RELEASED, bounty_id);
     }
 
     true
 }
 
+/// Set the reward range new bounties in a guild must fall within
+///
+/// A `max_reward` of 0 leaves the range open-ended. Zero-reward bounties
+/// are only accepted while `min_reward` is 0.
+pub fn set_bounty_reward_bounds(
+    env: &Env,
+    guild_id: u64,
+    min_reward: i128,
+    max_reward: i128,
+    caller: Address,
+) -> bool {
+    caller.require_auth();
+
+    if !enforce_permission(env, guild_id, caller, Role::Admin, "set_bounty_reward_bounds") {
+        panic!("Unauthorized: Only guild admin can set reward bounds");
+    }
+    if min_reward < 0 || max_reward < 0 {
+        panic!("Reward bounds must be non-negative");
+    }
+    if max_reward > 0 && max_reward < min_reward {
+        panic!("Maximum reward must not be below the minimum");
+    }
+
+    set_reward_bounds(
+        env,
+        guild_id,
+        &BountyRewardBounds {
+            min_reward,
+            max_reward,
+        },
+    );
+    true
+}
+
+pub fn get_bounty_reward_bounds(env: &Env, guild_id: u64) -> BountyRewardBounds {
+    get_reward_bounds(env, guild_id)
+}
+
 /// Cancel a bounty and refund escrowed funds to the creator
 ///
 /// # Events emitted
@@ -380,10 +757,18 @@ pub fn cancel_bounty(env: &Env, bounty_id: u64, canceller: Address) -> bool {
         _ => {}
     }
 
+    // The creator may always cancel, so only a non-creator's admin check is audited
     let is_creator = bounty.creator == canceller;
-    let is_admin = has_permission(env, bounty.guild_id, canceller.clone(), Role::Admin);
 
-    if !is_creator && !is_admin {
+    if !is_creator
+        && !enforce_permission(
+            env,
+            bounty.guild_id,
+            canceller.clone(),
+            Role::Admin,
+            "cancel_bounty",
+        )
+    {
         panic!("Unauthorized: Only creator or guild admin can cancel");
     }
 
@@ -391,12 +776,13 @@ pub fn cancel_bounty(env: &Env, bounty_id: u64, canceller: Address) -> bool {
     let refund_recipient = bounty.creator.clone();
 
     if refund_amount > 0 {
-        release_funds(env, &bounty.token, &refund_recipient, refund_amount);
+        refund_bounty_funds(env, &bounty, refund_amount);
         bounty.funded_amount = 0;
     }
 
     bounty.status = BountyStatus::Cancelled;
     store_bounty(env, &bounty);
+    record_transition(env, bounty_id, BountyStatus::Cancelled, &canceller);
 
     emit_event(
         env,
@@ -432,26 +818,68 @@ pub fn expire_bounty(env: &Env, bounty_id: u64) -> bool {
     }
 
     let now = env.ledger().timestamp();
-    if now <= bounty.expires_at {
+    if now <= bounty.expires_at || in_completion_grace(env, &bounty) {
         return false;
     }
 
+    apply_expiry(env, &mut bounty, &env.current_contract_address());
+
+    true
+}
+
+/// Whether a claimed or under-review bounty is still inside the expiry grace
+/// window, during which its claimer may finish and it must not be refunded
+fn in_completion_grace(env: &Env, bounty: &Bounty) -> bool {
+    matches!(
+        bounty.status,
+        BountyStatus::Claimed | BountyStatus::UnderReview
+    ) && env.ledger().timestamp() <= bounty.expires_at.saturating_add(get_expiry_grace(env))
+}
+
+/// Refund any escrow, mark the bounty expired and record who triggered it
+fn apply_expiry(env: &Env, bounty: &mut Bounty, actor: &Address) {
     if bounty.funded_amount > 0 {
-        release_funds(env, &bounty.token, &bounty.creator, bounty.funded_amount);
+        refund_bounty_funds(env, bounty, bounty.funded_amount);
         bounty.funded_amount = 0;
     }
 
     bounty.status = BountyStatus::Expired;
-    store_bounty(env, &bounty);
+    store_bounty(env, bounty);
+    record_transition(env, bounty.id, BountyStatus::Expired, actor);
 
     emit_event(
         env,
         MOD_BOUNTY,
         ACT_EXPIRED,
-        BountyExpiredEvent { bounty_id },
+        BountyExpiredEvent { bounty_id: bounty.id },
     );
+}
 
-    true
+/// Keep escrow from being locked indefinitely: any new or moved expiry must
+/// fall within the configured horizon of `now`
+fn assert_within_expiry_horizon(env: &Env, expiry: u64, now: u64) {
+    let horizon = get_max_expiry_horizon(env);
+    if horizon > 0 && expiry > now.saturating_add(horizon) {
+        panic!("Expiry is beyond the maximum horizon");
+    }
+}
+
+/// Expire a bounty found past its expiry by another entrypoint. Returns
+/// `false`, leaving it untouched, when it is disputed, still within its
+/// completion grace, or already terminal.
+fn expire_lazily(env: &Env, bounty: &mut Bounty, actor: &Address) -> bool {
+    if dispute_storage::is_reference_locked(env, &DisputeReference::Bounty, bounty.id)
+        || in_completion_grace(env, bounty)
+    {
+        return false;
+    }
+    match bounty.status {
+        BountyStatus::Expired | BountyStatus::Completed | BountyStatus::Cancelled => false,
+        _ => {
+            apply_expiry(env, bounty, actor);
+            true
+        }
+    }
 }
 
 /// Claim bounty payout - allows claimer to pull funds from escrow to their own address
@@ -514,6 +942,27 @@ pub fn get_guild_bounties_list(env: &Env, guild_id: u64) -> Vec<Bounty> {
     get_guild_bounties(env, guild_id)
 }
 
+/// Total of `token` held in bounty and escrow-backed project escrows that
+/// has not yet been released or refunded
+pub fn get_total_escrow_locked(env: &Env, token: &Address) -> i128 {
+    storage::get_escrow_total(env, token)
+}
+
+pub fn get_bounty_timeline(env: &Env, bounty_id: u64) -> Vec<BountyTimelineEntry> {
+    get_bounty(env, bounty_id).expect("Bounty not found");
+    get_timeline(env, bounty_id)
+}
+
+pub fn get_claim_reveal_window(env: &Env, bounty_id: u64) -> u64 {
+    get_bounty(env, bounty_id).expect("Bounty not found");
+    get_reveal_window(env, bounty_id)
+}
+
+pub fn get_pending_claim_commitments(env: &Env, bounty_id: u64) -> Vec<ClaimCommitment> {
+    get_bounty(env, bounty_id).expect("Bounty not found");
+    live_claim_commitments(env, bounty_id, get_reveal_window(env, bounty_id))
+}
+
 #[allow(dead_code)]
 pub fn cancel_bounty_auth(env: &Env, bounty_id: u64, canceller: Address) -> bool {
     cancel_bounty(env, bounty_id, canceller)
@@ -521,4 +970,3 @@ pub fn cancel_bounty_auth(env: &Env, bounty_id: u64, canceller: Address) -> bool
 
 #[cfg(test)]
 mod tests;
-
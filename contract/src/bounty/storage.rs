@@ -1,10 +1,24 @@
-﻿use crate::bounty::types::Bounty;
-use soroban_sdk::{symbol_short, Env, Map, Symbol, Vec};
+﻿use crate::bounty::types::{
+    Bounty, BountyRewardBounds, BountyStatus, BountyTimelineEntry, ClaimCommitment,
+};
+use soroban_sdk::{symbol_short, Address, Env, Map, Symbol, Vec};
 
 // Storage keys
 const BOUNTIES_KEY: Symbol = symbol_short!("bounties");
 const BOUNTY_CNT_KEY: Symbol = symbol_short!("b_cnt");
 const GUILD_BOUNTIES_KEY: Symbol = symbol_short!("g_bnties");
+const NATIVE_TOKEN_KEY: Symbol = symbol_short!("native_tk");
+const BOUNTY_TIMELINE_KEY: Symbol = symbol_short!("b_tline");
+const EXPIRY_GRACE_KEY: Symbol = symbol_short!("b_grace");
+const REWARD_BOUNDS_KEY: Symbol = symbol_short!("b_bounds");
+const REVEAL_WINDOW_KEY: Symbol = symbol_short!("b_reveal");
+const CLAIM_COMMITS_KEY: Symbol = symbol_short!("b_commits");
+const ESCROW_TOTAL_KEY: Symbol = symbol_short!("esc_total");
+const EXPIRY_HORIZON_KEY: Symbol = symbol_short!("b_horizon");
+const OPEN_COUNT_KEY: Symbol = symbol_short!("b_open");
+
+/// Furthest ahead a bounty may expire when no horizon is configured (1 year)
+pub const DEFAULT_MAX_EXPIRY_HORIZON: u64 = 365 * 24 * 60 * 60;
 
 /// Initialize bounty storage
 #[allow(dead_code)]
@@ -37,7 +51,26 @@ pub fn store_bounty(env: &Env, bounty: &Bounty) {
         .get(&BOUNTIES_KEY)
         .unwrap_or_else(|| Map::new(env));
 
-    let is_new = !bounties.contains_key(bounty.id);
+    let previous = bounties.get(bounty.id);
+    let is_new = previous.is_none();
+    let was_open = previous.map(|old| is_open(&old.status)).unwrap_or(false);
+
+    // Keep the guild's open-bounty counter in step with status changes. It is
+    // read before the write, so a guild with no stored count yet is scanned
+    // as it stood.
+    let now_open = is_open(&bounty.status);
+    if was_open != now_open {
+        let count = get_open_bounty_count(env, bounty.guild_id);
+        let count = if now_open {
+            count + 1
+        } else {
+            count.saturating_sub(1)
+        };
+        env.storage()
+            .persistent()
+            .set(&(OPEN_COUNT_KEY, bounty.guild_id), &count);
+    }
+
     bounties.set(bounty.id, bounty.clone());
     env.storage().persistent().set(&BOUNTIES_KEY, &bounties);
 
@@ -61,6 +94,28 @@ pub fn store_bounty(env: &Env, bounty: &Bounty) {
     }
 }
 
+/// Whether a bounty in this status still counts as open for its guild
+fn is_open(status: &BountyStatus) -> bool {
+    !matches!(
+        status,
+        BountyStatus::Completed | BountyStatus::Cancelled | BountyStatus::Expired
+    )
+}
+
+/// Bounties of a guild that have not been completed, cancelled or expired.
+/// Guilds whose bounties predate the stored count are scanned.
+pub fn get_open_bounty_count(env: &Env, guild_id: u64) -> u32 {
+    env.storage()
+        .persistent()
+        .get(&(OPEN_COUNT_KEY, guild_id))
+        .unwrap_or_else(|| {
+            get_guild_bounties(env, guild_id)
+                .iter()
+                .filter(|b| is_open(&b.status))
+                .count() as u32
+        })
+}
+
 /// Get a bounty by ID
 pub fn get_bounty(env: &Env, bounty_id: u64) -> Option<Bounty> {
     let bounties: Map<u64, Bounty> = env
@@ -72,6 +127,31 @@ pub fn get_bounty(env: &Env, bounty_id: u64) -> Option<Bounty> {
     bounties.get(bounty_id)
 }
 
+/// Get up to `limit` of the most recently created bounties, newest first,
+/// and whether older bounties were left out.
+pub fn get_recent_bounties(env: &Env, limit: u32) -> (Vec<Bounty>, bool) {
+    let count: u64 = env
+        .storage()
+        .persistent()
+        .get(&BOUNTY_CNT_KEY)
+        .unwrap_or(0u64);
+    let bounties_map: Map<u64, Bounty> = env
+        .storage()
+        .persistent()
+        .get(&BOUNTIES_KEY)
+        .unwrap_or_else(|| Map::new(env));
+
+    let mut result = Vec::new(env);
+    let mut id = count;
+    while id > 0 && result.len() < limit {
+        if let Some(b) = bounties_map.get(id) {
+            result.push_back(b);
+        }
+        id -= 1;
+    }
+    (result, id > 0)
+}
+
 /// Get all bounties for a guild
 pub fn get_guild_bounties(env: &Env, guild_id: u64) -> Vec<Bounty> {
     let guild_bounties: Map<u64, Vec<u64>> = env
@@ -98,3 +178,126 @@ pub fn get_guild_bounties(env: &Env, guild_id: u64) -> Vec<Bounty> {
     }
     result
 }
+
+/// Set the native XLM Stellar Asset Contract address used for native bounties
+pub fn set_native_token(env: &Env, token: &Address) {
+    env.storage().persistent().set(&NATIVE_TOKEN_KEY, token);
+}
+
+/// Get the configured native XLM Stellar Asset Contract address
+pub fn get_native_token(env: &Env) -> Option<Address> {
+    env.storage().persistent().get(&NATIVE_TOKEN_KEY)
+}
+
+/// Set how long past expiry a claimed bounty may still be completed
+pub fn set_expiry_grace(env: &Env, seconds: u64) {
+    env.storage().persistent().set(&EXPIRY_GRACE_KEY, &seconds);
+}
+
+/// Get the bounty expiry grace window in seconds (0 when unset)
+pub fn get_expiry_grace(env: &Env) -> u64 {
+    env.storage()
+        .persistent()
+        .get(&EXPIRY_GRACE_KEY)
+        .unwrap_or(0)
+}
+
+/// Set how far in the future a bounty may expire (0 removes the limit)
+pub fn set_max_expiry_horizon(env: &Env, seconds: u64) {
+    env.storage()
+        .persistent()
+        .set(&EXPIRY_HORIZON_KEY, &seconds);
+}
+
+/// Get the maximum bounty expiry horizon in seconds
+pub fn get_max_expiry_horizon(env: &Env) -> u64 {
+    env.storage()
+        .persistent()
+        .get(&EXPIRY_HORIZON_KEY)
+        .unwrap_or(DEFAULT_MAX_EXPIRY_HORIZON)
+}
+
+pub fn set_reward_bounds(env: &Env, guild_id: u64, bounds: &BountyRewardBounds) {
+    env.storage()
+        .persistent()
+        .set(&(REWARD_BOUNDS_KEY, guild_id), bounds);
+}
+
+/// Get a guild's bounty reward bounds (unbounded when unset)
+pub fn get_reward_bounds(env: &Env, guild_id: u64) -> BountyRewardBounds {
+    env.storage()
+        .persistent()
+        .get(&(REWARD_BOUNDS_KEY, guild_id))
+        .unwrap_or(BountyRewardBounds {
+            min_reward: 0,
+            max_reward: 0,
+        })
+}
+
+/// Append a status transition to a bounty's timeline
+pub fn record_transition(env: &Env, bounty_id: u64, status: BountyStatus, actor: &Address) {
+    let key = (BOUNTY_TIMELINE_KEY, bounty_id);
+    let mut timeline = get_timeline(env, bounty_id);
+    timeline.push_back(BountyTimelineEntry {
+        status,
+        timestamp: env.ledger().timestamp(),
+        actor: actor.clone(),
+    });
+    env.storage().persistent().set(&key, &timeline);
+}
+
+/// Get a bounty's status transitions, oldest first
+pub fn get_timeline(env: &Env, bounty_id: u64) -> Vec<BountyTimelineEntry> {
+    env.storage()
+        .persistent()
+        .get(&(BOUNTY_TIMELINE_KEY, bounty_id))
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+/// Set how long a claim commitment may wait for its reveal (0 = commit-reveal off)
+pub fn set_reveal_window(env: &Env, bounty_id: u64, seconds: u64) {
+    env.storage()
+        .persistent()
+        .set(&(REVEAL_WINDOW_KEY, bounty_id), &seconds);
+}
+
+/// Get a bounty's claim reveal window in seconds (0 when commit-reveal is off)
+pub fn get_reveal_window(env: &Env, bounty_id: u64) -> u64 {
+    env.storage()
+        .persistent()
+        .get(&(REVEAL_WINDOW_KEY, bounty_id))
+        .unwrap_or(0)
+}
+
+/// Store a bounty's claim commitments, oldest first
+pub fn set_claim_commitments(env: &Env, bounty_id: u64, commitments: &Vec<ClaimCommitment>) {
+    let key = (CLAIM_COMMITS_KEY, bounty_id);
+    if commitments.is_empty() {
+        env.storage().persistent().remove(&key);
+    } else {
+        env.storage().persistent().set(&key, commitments);
+    }
+}
+
+/// Get a bounty's stored claim commitments, oldest first, including lapsed ones
+pub fn get_claim_commitments(env: &Env, bounty_id: u64) -> Vec<ClaimCommitment> {
+    env.storage()
+        .persistent()
+        .get(&(CLAIM_COMMITS_KEY, bounty_id))
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+/// Set the amount of `token` currently held in bounty and project escrows
+pub fn set_escrow_total(env: &Env, token: &Address, amount: i128) {
+    env.storage()
+        .persistent()
+        .set(&(ESCROW_TOTAL_KEY, token.clone()), &amount);
+}
+
+/// Get the amount of `token` currently held in bounty and project escrows
+pub fn get_escrow_total(env: &Env, token: &Address) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&(ESCROW_TOTAL_KEY, token.clone()))
+        .unwrap_or(0)
+}
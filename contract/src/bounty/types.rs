@@ -1,4 +1,4 @@
-use soroban_sdk::{contracttype, Address, String};
+use soroban_sdk::{contracttype, Address, BytesN, String};
 
 /// Status of a bounty lifecycle
 #[contracttype]
@@ -14,6 +14,21 @@ pub enum BountyStatus {
     Funded = 7,
 }
 
+/// Outcome of the read-only claim pre-check; non-`Eligible` values explain
+/// why `claim_bounty` would be refused
+#[contracttype]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ClaimEligibility {
+    Eligible = 0,
+    BountyNotFound = 1,
+    Expired = 2,
+    NotOpen = 3,
+    Suspended = 4,
+    Guest = 5,
+    ReservedForOther = 6,
+    CommitRevealRequired = 7,
+}
+
 /// Bounty struct containing all bounty metadata and state
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -32,8 +47,8 @@ pub struct Bounty {
     pub reward_amount: i128,
     /// Amount of tokens currently funded
     pub funded_amount: i128,
-    /// Address of the token contract (XLM or custom)
-    pub token: Address,
+    /// Address of the token contract (None for native XLM)
+    pub token: Option<Address>,
     /// Current status of the bounty
     pub status: BountyStatus,
     /// Address of the contributor who claimed the bounty (optional)
@@ -44,6 +59,42 @@ pub struct Bounty {
     pub created_at: u64,
     /// Expiration timestamp (seconds)
     pub expires_at: u64,
+    /// Treasury the escrow was funded from; refunds are credited back to it
+    pub funding_treasury_id: Option<u64>,
+}
+
+/// One status transition in a bounty's history
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BountyTimelineEntry {
+    /// Status the bounty moved into
+    pub status: BountyStatus,
+    /// Ledger timestamp of the transition
+    pub timestamp: u64,
+    /// Address that triggered the transition (the contract for permissionless expiry)
+    pub actor: Address,
+}
+
+/// Per-guild limits on the reward a new bounty may carry
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BountyRewardBounds {
+    /// Smallest allowed reward; zero-reward bounties need this to be 0
+    pub min_reward: i128,
+    /// Largest allowed reward (0 = no ceiling)
+    pub max_reward: i128,
+}
+
+/// A sealed intent to claim a commit-reveal bounty
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ClaimCommitment {
+    /// Address that committed and must make the reveal
+    pub claimer: Address,
+    /// `sha256(bounty_id || nonce || claimer)`, see `bounty::claim_commitment_hash`
+    pub commitment: BytesN<32>,
+    /// Ledger timestamp of the commit; the reveal window runs from here
+    pub committed_at: u64,
 }
 
 /// Represents the state of funds locked in escrow for a bounty
@@ -52,7 +103,7 @@ pub struct Bounty {
 pub struct EscrowLockedState {
     pub bounty_id: u64,
     pub amount: i128,
-    pub token: Address,
+    pub token: Option<Address>,
     pub is_locked: bool,
 }
 
@@ -66,7 +117,7 @@ pub struct BountyCreatedEvent {
     pub guild_id: u64,
     pub creator: Address,
     pub reward_amount: i128,
-    pub token: Address,
+    pub token: Option<Address>,
     pub expires_at: u64,
 }
 
@@ -89,6 +140,15 @@ pub struct BountyClaimedEvent {
     pub claimer: Address,
 }
 
+/// Event emitted when a claim commitment is recorded
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct ClaimCommittedEvent {
+    pub bounty_id: u64,
+    pub claimer: Address,
+    pub reveal_deadline: u64,
+}
+
 /// Event emitted when work is submitted
 #[contracttype]
 #[derive(Clone, Debug)]
@@ -114,7 +174,7 @@ pub struct EscrowReleasedEvent {
     pub bounty_id: u64,
     pub recipient: Address,
     pub amount: i128,
-    pub token: Address,
+    pub token: Option<Address>,
 }
 
 /// Event emitted when a bounty is cancelled
@@ -127,6 +187,16 @@ pub struct BountyCancelledEvent {
     pub refund_recipient: Address,
 }
 
+/// Event emitted when more of a token leaves escrow than was recorded as
+/// locked, e.g. for escrows funded before the total was tracked
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct EscrowShortfallEvent {
+    pub token: Address,
+    /// Amount released beyond the recorded total
+    pub shortfall: i128,
+}
+
 /// Event emitted when a bounty expires
 #[contracttype]
 #[derive(Clone, Debug)]
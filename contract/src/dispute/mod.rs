@@ -8,17 +8,21 @@ pub mod storage;
 pub mod types;
 pub mod voting;
 
-use soroban_sdk::{Address, Env, String};
+use soroban_sdk::{Address, Env, String, Vec};
 
 use crate::bounty::storage as bounty_storage;
 use crate::bounty::types::BountyStatus;
 use crate::dispute::resolution as dispute_resolution;
 use crate::dispute::storage as dispute_storage;
 use crate::dispute::types::{
-    Dispute, DisputeCreatedEvent, DisputeReference, DisputeStatus, EvidenceSubmittedEvent,
+    Dispute, DisputeCreatedEvent, DisputeQuorumMode, DisputeReference, DisputeStatus,
+    DisputeWithdrawnEvent, EligibilityUpdatedEvent, EvidenceSubmittedEvent, QuorumModeUpdatedEvent,
+    VoterEligibility,
 };
 use crate::events::emit::emit_event;
-use crate::events::topics::{ACT_CREATED, ACT_EVIDENCE, MOD_DISPUTE};
+use crate::events::topics::{ACT_CANCELLED, ACT_CREATED, ACT_EVIDENCE, ACT_UPDATED, MOD_DISPUTE};
+use crate::guild::membership::enforce_permission;
+use crate::guild::types::Role;
 use crate::milestone::storage as milestone_storage;
 use crate::milestone::types::ProjectStatus;
 
@@ -111,10 +115,12 @@ pub fn create_dispute(
         vote_count: 0,
         resolved_at: None,
         resolution_executed: false,
+        eligibility: VoterEligibility::GuildMembers,
     };
 
     dispute_storage::store_dispute(env, &dispute);
     dispute_storage::lock_reference(env, &reference_type, reference_id, dispute_id);
+    dispute_storage::add_reference_dispute(env, &reference_type, reference_id, dispute_id);
 
     let event = DisputeCreatedEvent {
         dispute_id,
@@ -129,6 +135,22 @@ pub fn create_dispute(
     dispute_id
 }
 
+/// All disputes opened over a bounty or milestone, oldest first, including
+/// resolved ones so earlier rounds stay visible after a new dispute.
+pub fn get_disputes_for_reference(
+    env: &Env,
+    reference_type: DisputeReference,
+    reference_id: u64,
+) -> Vec<Dispute> {
+    let mut disputes = Vec::new(env);
+    for id in dispute_storage::get_reference_disputes(env, &reference_type, reference_id).iter() {
+        if let Some(dispute) = dispute_storage::get_dispute(env, id) {
+            disputes.push_back(dispute);
+        }
+    }
+    disputes
+}
+
 /// Submit evidence for an active dispute.
 ///
 /// Evidence can only be submitted by the plaintiff or defendant
@@ -141,7 +163,7 @@ pub fn submit_evidence(env: &Env, dispute_id: u64, party: Address, evidence_url:
     }
 
     let mut dispute = dispute_storage::get_dispute(env, dispute_id).expect("dispute not found");
-    if dispute.status == DisputeStatus::Resolved || dispute.status == DisputeStatus::Expired {
+    if dispute.status.is_closed() {
         panic!("dispute closed");
     }
 
@@ -166,6 +188,113 @@ pub fn submit_evidence(env: &Env, dispute_id: u64, party: Address, evidence_url:
     true
 }
 
+/// Withdraw an open dispute. Only the plaintiff may withdraw, and only before
+/// the first vote is cast. The hold on the bounty or milestone escrow is
+/// lifted, so it can be released or refunded through the normal flow again.
+pub fn withdraw_dispute(env: &Env, dispute_id: u64, plaintiff: Address) -> bool {
+    plaintiff.require_auth();
+
+    let mut dispute = dispute_storage::get_dispute(env, dispute_id).expect("dispute not found");
+    if dispute.plaintiff != plaintiff {
+        panic!("only the plaintiff can withdraw");
+    }
+    if dispute.status.is_closed() {
+        panic!("dispute closed");
+    }
+    if dispute.vote_count > 0 {
+        panic!("voting already started");
+    }
+
+    dispute.status = DisputeStatus::Withdrawn;
+    dispute.resolved_at = Some(env.ledger().timestamp());
+    dispute_storage::store_dispute(env, &dispute);
+    dispute_storage::unlock_reference(env, &dispute.reference_type, dispute.reference_id);
+
+    let event = DisputeWithdrawnEvent {
+        dispute_id,
+        plaintiff,
+    };
+    emit_event(env, MOD_DISPUTE, ACT_CANCELLED, event);
+
+    true
+}
+
+/// Restrict who may vote on a dispute. Only a guild admin who is not a party
+/// to the dispute may change the policy, and only before the first vote is
+/// cast.
+pub fn set_voter_eligibility(
+    env: &Env,
+    dispute_id: u64,
+    eligibility: VoterEligibility,
+    caller: Address,
+) -> bool {
+    caller.require_auth();
+
+    let mut dispute = dispute_storage::get_dispute(env, dispute_id).expect("dispute not found");
+    if caller == dispute.plaintiff || caller == dispute.defendant {
+        panic!("parties cannot choose the voters");
+    }
+    if !enforce_permission(
+        env,
+        dispute.guild_id,
+        caller,
+        Role::Admin,
+        "set_voter_eligibility",
+    ) {
+        panic!("caller must be guild admin");
+    }
+    if dispute.status != DisputeStatus::Open || dispute.vote_count > 0 {
+        panic!("voting already started");
+    }
+
+    if let VoterEligibility::ArbiterPanel(ref panel) = eligibility {
+        if panel.is_empty() {
+            panic!("arbiter panel cannot be empty");
+        }
+        if panel.contains(&dispute.plaintiff) || panel.contains(&dispute.defendant) {
+            panic!("parties cannot be arbiters");
+        }
+    }
+
+    dispute.eligibility = eligibility.clone();
+    dispute_storage::store_dispute(env, &dispute);
+
+    let event = EligibilityUpdatedEvent {
+        dispute_id,
+        eligibility,
+    };
+    emit_event(env, MOD_DISPUTE, ACT_UPDATED, event);
+
+    true
+}
+
+/// Choose how a guild's disputes reach quorum. Only a guild admin may change
+/// the mode; it applies to every dispute tallied afterwards.
+pub fn set_quorum_mode(env: &Env, guild_id: u64, mode: DisputeQuorumMode, caller: Address) -> bool {
+    caller.require_auth();
+
+    if !enforce_permission(env, guild_id, caller, Role::Admin, "set_quorum_mode") {
+        panic!("caller must be guild admin");
+    }
+    if let DisputeQuorumMode::ReputationShare(percentage) = mode {
+        if percentage == 0 || percentage > 100 {
+            panic!("quorum percentage must be between 1 and 100");
+        }
+    }
+
+    dispute_storage::set_quorum_mode(env, guild_id, &mode);
+
+    let event = QuorumModeUpdatedEvent { guild_id, mode };
+    emit_event(env, MOD_DISPUTE, ACT_UPDATED, event);
+
+    true
+}
+
+/// Get the quorum mode used by a guild's disputes.
+pub fn get_quorum_mode(env: &Env, guild_id: u64) -> DisputeQuorumMode {
+    dispute_storage::get_quorum_mode(env, guild_id)
+}
+
 /// Cast a weighted vote for a dispute.
 pub fn cast_vote(
     env: &Env,
@@ -1,29 +1,62 @@
-﻿use soroban_sdk::{Env, Vec};
+﻿use soroban_sdk::{Address, Env, Vec};
 
 use crate::bounty::escrow::release_funds;
+use crate::bounty::refund_bounty_funds;
 use crate::bounty::storage as bounty_storage;
 use crate::bounty::types::BountyStatus;
 use crate::dispute::storage;
 use crate::dispute::types::{
-    DisputeReference, DisputeStatus, FundDistribution, Resolution, VoteDecision,
+    Dispute, DisputeQuorumMode, DisputeReference, DisputeStatus, FundDistribution, Resolution,
+    VoteDecision, VoterEligibility,
 };
+use crate::events::critical::record_critical_event;
 use crate::events::emit::emit_event;
 use crate::events::topics::{ACT_EXECUTED, ACT_EXPIRED, ACT_RESOLVED, MOD_DISPUTE};
 use crate::guild::storage as guild_storage;
 use crate::milestone::storage as milestone_storage;
+use crate::milestone::tracker::{disburse_project_payment, refund_escrow_remainder};
 use crate::milestone::types::{MilestoneStatus, ProjectStatus};
-use crate::treasury::execute_milestone_payment;
+use crate::reputation::scoring::{apply_penalty, dispute_loss_penalty};
+use crate::reputation::storage as reputation_storage;
+use crate::utils::math::distribute_with_remainder;
 
 const QUORUM_PERCENTAGE: u32 = 30;
 
-fn quorum_reached(env: &Env, guild_id: u64, vote_count: u32) -> bool {
-    let members = guild_storage::get_all_members(env, guild_id);
-    let total = members.len() as u32;
+fn quorum_reached(env: &Env, dispute: &Dispute) -> bool {
+    if let DisputeQuorumMode::ReputationShare(percentage) =
+        storage::get_quorum_mode(env, dispute.guild_id)
+    {
+        if !matches!(dispute.eligibility, VoterEligibility::ArbiterPanel(_)) {
+            let total_reputation =
+                reputation_storage::get_guild_total_reputation(env, dispute.guild_id);
+            let mut participating = 0u64;
+            for vote in storage::get_votes(env, dispute.id).iter() {
+                participating = participating.saturating_add(
+                    reputation_storage::get_profile(env, &vote.voter, dispute.guild_id)
+                        .map(|p| p.total_score)
+                        .unwrap_or(0),
+                );
+            }
+            // a guild with no reputation yet falls back to the member count
+            if let Some(share) = participating
+                .saturating_mul(100)
+                .checked_div(total_reputation)
+            {
+                return share >= percentage as u64;
+            }
+        }
+    }
+
+    // an arbiter panel is the whole electorate; otherwise the guild is
+    let total = match dispute.eligibility {
+        VoterEligibility::ArbiterPanel(ref panel) => panel.len(),
+        _ => guild_storage::get_all_members(env, dispute.guild_id).len(),
+    };
     if total == 0 {
         return false;
     }
 
-    vote_count.saturating_mul(100) / total >= QUORUM_PERCENTAGE
+    dispute.vote_count.saturating_mul(100) / total >= QUORUM_PERCENTAGE
 }
 
 fn decide_winner(
@@ -43,7 +76,7 @@ fn decide_winner(
 /// Tally votes for a dispute without mutating state.
 pub fn tally_votes(env: &Env, dispute_id: u64) -> Resolution {
     let dispute = storage::get_dispute(env, dispute_id).expect("dispute not found");
-    let quorum = quorum_reached(env, dispute.guild_id, dispute.vote_count);
+    let quorum = quorum_reached(env, &dispute);
 
     let winner = if quorum {
         match decide_winner(
@@ -74,7 +107,7 @@ pub fn tally_votes(env: &Env, dispute_id: u64) -> Resolution {
 pub fn resolve_dispute(env: &Env, dispute_id: u64) -> Resolution {
     let mut dispute = storage::get_dispute(env, dispute_id).expect("dispute not found");
 
-    if dispute.status == DisputeStatus::Resolved || dispute.status == DisputeStatus::Expired {
+    if dispute.status.is_closed() {
         panic!("dispute already closed");
     }
 
@@ -100,7 +133,7 @@ pub fn resolve_dispute(env: &Env, dispute_id: u64) -> Resolution {
                 && bounty.status != BountyStatus::Expired
                 && bounty.funded_amount > 0
             {
-                release_funds(env, &bounty.token, &bounty.creator, bounty.funded_amount);
+                refund_bounty_funds(env, &bounty, bounty.funded_amount);
                 bounty.funded_amount = 0;
                 bounty_storage::store_bounty(env, &bounty);
             }
@@ -129,6 +162,13 @@ pub fn resolve_dispute(env: &Env, dispute_id: u64) -> Resolution {
     resolution
 }
 
+/// Split a disputed amount in half; an odd unit goes to the plaintiff.
+fn split_evenly(env: &Env, total: i128) -> (i128, i128) {
+    let shares = distribute_with_remainder(env, total, &Vec::from_array(env, [1u32, 1u32]))
+        .expect("invalid split amount");
+    (shares.get_unchecked(0), shares.get_unchecked(1))
+}
+
 /// Execute fund redistribution for a resolved dispute.
 pub fn execute_resolution(env: &Env, dispute_id: u64) -> Vec<FundDistribution> {
     let mut dispute = storage::get_dispute(env, dispute_id).expect("dispute not found");
@@ -148,6 +188,8 @@ pub fn execute_resolution(env: &Env, dispute_id: u64) -> Vec<FundDistribution> {
     );
 
     let mut distributions = Vec::new(env);
+    let stakes: i128;
+    let stakes_token: Option<Address>;
 
     match dispute.reference_type {
         DisputeReference::Bounty => {
@@ -155,6 +197,8 @@ pub fn execute_resolution(env: &Env, dispute_id: u64) -> Vec<FundDistribution> {
                 bounty_storage::get_bounty(env, dispute.reference_id).expect("bounty not found");
 
             let total = bounty.funded_amount;
+            stakes = total;
+            stakes_token = bounty.token.clone();
             if total > 0 {
                 match decision {
                     VoteDecision::FavorPlaintiff => {
@@ -172,17 +216,16 @@ pub fn execute_resolution(env: &Env, dispute_id: u64) -> Vec<FundDistribution> {
                         });
                     }
                     VoteDecision::Split => {
-                        let half = total / 2;
-                        let remainder = total - half;
-                        release_funds(env, &bounty.token, &dispute.plaintiff, half);
-                        release_funds(env, &bounty.token, &dispute.defendant, remainder);
+                        let (plaintiff_amt, defendant_amt) = split_evenly(env, total);
+                        release_funds(env, &bounty.token, &dispute.plaintiff, plaintiff_amt);
+                        release_funds(env, &bounty.token, &dispute.defendant, defendant_amt);
                         distributions.push_back(FundDistribution {
                             recipient: dispute.plaintiff.clone(),
-                            amount: half,
+                            amount: plaintiff_amt,
                         });
                         distributions.push_back(FundDistribution {
                             recipient: dispute.defendant.clone(),
-                            amount: remainder,
+                            amount: defendant_amt,
                         });
                     }
                 }
@@ -198,29 +241,28 @@ pub fn execute_resolution(env: &Env, dispute_id: u64) -> Vec<FundDistribution> {
                 .expect("project not found");
 
             let total = milestone.payment_amount;
+            stakes = total;
+            stakes_token = milestone.token.clone();
             if total > 0 {
                 let (plaintiff_amt, defendant_amt) = match decision {
                     VoteDecision::FavorPlaintiff => (total, 0),
                     VoteDecision::FavorDefendant => (0, total),
-                    VoteDecision::Split => {
-                        let half = total / 2;
-                        (half, total - half)
-                    }
+                    VoteDecision::Split => split_evenly(env, total),
                 };
 
-                let new_released = project
-                    .released_amount
-                    .checked_add(total)
-                    .expect("overflow");
-                if new_released > project.total_amount {
-                    panic!("project budget exceeded");
+                let mut new_released = project.released_amount;
+                if project.budgets_token(&milestone.token) {
+                    new_released = new_released.checked_add(total).expect("overflow");
+                    if new_released > project.total_amount {
+                        panic!("project budget exceeded");
+                    }
                 }
 
                 if plaintiff_amt > 0 {
-                    execute_milestone_payment(
+                    disburse_project_payment(
                         env,
-                        project.treasury_id,
-                        project.token.clone(),
+                        &project,
+                        &milestone.token,
                         dispute.plaintiff.clone(),
                         plaintiff_amt,
                     );
@@ -230,10 +272,10 @@ pub fn execute_resolution(env: &Env, dispute_id: u64) -> Vec<FundDistribution> {
                     });
                 }
                 if defendant_amt > 0 {
-                    execute_milestone_payment(
+                    disburse_project_payment(
                         env,
-                        project.treasury_id,
-                        project.token.clone(),
+                        &project,
+                        &milestone.token,
                         dispute.defendant.clone(),
                         defendant_amt,
                     );
@@ -259,7 +301,10 @@ pub fn execute_resolution(env: &Env, dispute_id: u64) -> Vec<FundDistribution> {
                 let mut all_done = true;
                 for id in ids.iter() {
                     if let Some(m) = milestone_storage::get_milestone(env, id) {
-                        if !m.is_payment_released && m.status != MilestoneStatus::Expired {
+                        if !m.is_payment_released
+                            && m.status != MilestoneStatus::Expired
+                            && m.status != MilestoneStatus::Failed
+                        {
                             all_done = false;
                             break;
                         }
@@ -269,16 +314,35 @@ pub fn execute_resolution(env: &Env, dispute_id: u64) -> Vec<FundDistribution> {
                 if all_done && project.status != ProjectStatus::Completed {
                     project.status = ProjectStatus::Completed;
                     milestone_storage::store_project(env, &project);
+                    refund_escrow_remainder(env, &project);
                 }
             }
         }
     }
 
+    // The losing party's reputation takes a hit scaled by what was at stake;
+    // a split finds neither side at fault
+    let loser = match decision {
+        VoteDecision::FavorPlaintiff => Some(&dispute.defendant),
+        VoteDecision::FavorDefendant => Some(&dispute.plaintiff),
+        VoteDecision::Split => None,
+    };
+    if let Some(loser) = loser {
+        apply_penalty(
+            env,
+            dispute.guild_id,
+            loser,
+            dispute_loss_penalty(env, &stakes_token, stakes),
+            dispute_id,
+        );
+    }
+
     dispute.resolution_executed = true;
     storage::store_dispute(env, &dispute);
 
     let event = crate::dispute::types::ResolutionExecutedEvent { dispute_id };
     emit_event(env, MOD_DISPUTE, ACT_EXECUTED, event);
+    record_critical_event(env, dispute.guild_id, MOD_DISPUTE, ACT_EXECUTED, dispute_id);
 
     distributions
 }
@@ -1,11 +1,13 @@
-﻿use soroban_sdk::{symbol_short, Address, Env, Map, Symbol};
+﻿use soroban_sdk::{symbol_short, Address, Env, Map, Symbol, Vec};
 
-use crate::dispute::types::{Dispute, DisputeReference, Vote};
+use crate::dispute::types::{Dispute, DisputeQuorumMode, DisputeReference, Vote};
 
 const DISPUTES_KEY: Symbol = symbol_short!("dsp_all");
 const DISPUTE_COUNTER_KEY: Symbol = symbol_short!("dsp_cnt");
 const DISPUTE_VOTES_KEY: Symbol = symbol_short!("dsp_vot");
 const REF_LOCKS_KEY: Symbol = symbol_short!("dsp_ref");
+const REF_INDEX_KEY: Symbol = symbol_short!("dsp_ridx");
+const QUORUM_MODE_KEY: Symbol = symbol_short!("dsp_qmod");
 
 /// Get the next dispute ID and increment the counter.
 pub fn get_next_dispute_id(env: &Env) -> u64 {
@@ -42,6 +44,35 @@ pub fn get_dispute(env: &Env, dispute_id: u64) -> Option<Dispute> {
     disputes.get(dispute_id)
 }
 
+/// Record a dispute against its bounty/milestone reference.
+pub fn add_reference_dispute(
+    env: &Env,
+    reference_type: &DisputeReference,
+    reference_id: u64,
+    dispute_id: u64,
+) {
+    let key = (REF_INDEX_KEY, reference_type.clone(), reference_id);
+    let mut ids: Vec<u64> = env
+        .storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or_else(|| Vec::new(env));
+    ids.push_back(dispute_id);
+    env.storage().persistent().set(&key, &ids);
+}
+
+/// IDs of every dispute opened over a reference, oldest first.
+pub fn get_reference_disputes(
+    env: &Env,
+    reference_type: &DisputeReference,
+    reference_id: u64,
+) -> Vec<u64> {
+    env.storage()
+        .persistent()
+        .get(&(REF_INDEX_KEY, reference_type.clone(), reference_id))
+        .unwrap_or_else(|| Vec::new(env))
+}
+
 /// Persist a vote for a dispute.
 pub fn store_vote(env: &Env, vote: &Vote) {
     let mut votes_map: Map<u64, Map<Address, Vote>> = env
@@ -74,6 +105,35 @@ pub fn get_vote(env: &Env, dispute_id: u64, voter: &Address) -> Option<Vote> {
     dispute_votes.get(voter.clone())
 }
 
+/// Every vote cast on a dispute.
+pub fn get_votes(env: &Env, dispute_id: u64) -> Vec<Vote> {
+    let votes_map: Map<u64, Map<Address, Vote>> = env
+        .storage()
+        .persistent()
+        .get(&DISPUTE_VOTES_KEY)
+        .unwrap_or_else(|| Map::new(env));
+
+    votes_map
+        .get(dispute_id)
+        .map(|votes| votes.values())
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+/// Quorum mode for a guild's disputes, defaulting to member count.
+pub fn get_quorum_mode(env: &Env, guild_id: u64) -> DisputeQuorumMode {
+    env.storage()
+        .persistent()
+        .get(&(QUORUM_MODE_KEY, guild_id))
+        .unwrap_or(DisputeQuorumMode::MemberCount)
+}
+
+/// Persist the quorum mode for a guild's disputes.
+pub fn set_quorum_mode(env: &Env, guild_id: u64, mode: &DisputeQuorumMode) {
+    env.storage()
+        .persistent()
+        .set(&(QUORUM_MODE_KEY, guild_id), mode);
+}
+
 /// Check whether a reference is locked by an active dispute.
 pub fn is_reference_locked(
     env: &Env,
@@ -1,11 +1,14 @@
 ﻿//! Dispute Resolution Contract Tests
 
-use crate::dispute::types::VoteDecision;
+use crate::dispute::types::{
+    DisputeQuorumMode, DisputeReference, DisputeStatus, VoteDecision, VoterEligibility,
+};
 use crate::guild::types::Role;
+use crate::reputation::ContributionType;
 use crate::StellarGuildsContract;
 use crate::StellarGuildsContractClient;
 use soroban_sdk::testutils::{Address as _, Ledger, LedgerInfo};
-use soroban_sdk::{token, Address, Env, String, Vec};
+use soroban_sdk::{token, vec, Address, Env, String, Vec};
 
 fn setup_env() -> Env {
     let env = Env::default();
@@ -140,6 +143,7 @@ fn test_create_dispute_milestone_success() {
         description: String::from_str(&env, "First milestone"),
         payment_amount: 100,
         deadline: 2000,
+        token: None,
     });
 
     client.create_project(
@@ -446,3 +450,298 @@ fn test_dispute_status_updates_after_resolution() {
     let dispute = client.tally_dispute_votes(&dispute_id);
     assert_eq!(dispute.vote_count, 2);
 }
+
+#[test]
+fn test_arbiter_panel_restricts_electorate() {
+    let env = setup_env();
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    let (guild_id, owner, admin, _member, contributor) = setup_guild_with_members(&client, &env);
+    let token = create_mock_token(&env, &owner);
+    let bounty_id = create_funded_bounty(&client, &env, guild_id, &owner, &owner, &token);
+
+    let reason = String::from_str(&env, "Dispute reason");
+    let evidence = String::from_str(&env, "ipfs://evidence");
+    let dispute_id = client.create_dispute(&bounty_id, &contributor, &owner, &reason, &evidence);
+
+    // arbiters need not belong to the guild
+    let arbiter1 = Address::generate(&env);
+    let arbiter2 = Address::generate(&env);
+    let panel = VoterEligibility::ArbiterPanel(vec![&env, arbiter1.clone(), arbiter2.clone()]);
+
+    // the defendant is a guild owner but may not pick the panel, and
+    // neither party may sit on it
+    assert!(client
+        .try_set_dispute_eligibility(&dispute_id, &panel, &owner)
+        .is_err());
+    let with_party =
+        VoterEligibility::ArbiterPanel(vec![&env, arbiter1.clone(), contributor.clone()]);
+    assert!(client
+        .try_set_dispute_eligibility(&dispute_id, &with_party, &admin)
+        .is_err());
+
+    assert!(client.set_dispute_eligibility(&dispute_id, &panel, &admin));
+
+    // guild admins are not on the panel
+    assert!(client
+        .try_cast_dispute_vote(&dispute_id, &admin, &VoteDecision::FavorPlaintiff)
+        .is_err());
+
+    client.cast_dispute_vote(&dispute_id, &arbiter1, &VoteDecision::FavorDefendant);
+    let tally = client.tally_dispute_votes(&dispute_id);
+    assert_eq!(tally.votes_for_defendant, 1);
+    // 1 of 2 panelists is above the quorum
+    assert!(tally.quorum_reached);
+
+    // the policy is frozen once voting has started
+    assert!(client
+        .try_set_dispute_eligibility(&dispute_id, &VoterEligibility::GuildMembers, &admin)
+        .is_err());
+}
+
+#[test]
+#[should_panic(expected = "insufficient reputation to vote")]
+fn test_min_reputation_eligibility_rejects_low_reputation() {
+    let env = setup_env();
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    let (guild_id, owner, admin, member, contributor) = setup_guild_with_members(&client, &env);
+    let token = create_mock_token(&env, &owner);
+    let bounty_id = create_funded_bounty(&client, &env, guild_id, &owner, &owner, &token);
+
+    let reason = String::from_str(&env, "Dispute reason");
+    let evidence = String::from_str(&env, "ipfs://evidence");
+    let dispute_id = client.create_dispute(&bounty_id, &contributor, &owner, &reason, &evidence);
+
+    client.set_dispute_eligibility(&dispute_id, &VoterEligibility::MinReputation(50), &admin);
+    client.cast_dispute_vote(&dispute_id, &member, &VoteDecision::FavorPlaintiff);
+}
+
+#[test]
+fn test_get_disputes_for_reference_lists_every_round() {
+    let env = setup_env();
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    let (guild_id, owner, _admin, _member, contributor) = setup_guild_with_members(&client, &env);
+
+    let mut milestones: Vec<crate::milestone::types::MilestoneInput> = Vec::new(&env);
+    milestones.push_back(crate::milestone::types::MilestoneInput {
+        title: String::from_str(&env, "Milestone 1"),
+        description: String::from_str(&env, "First milestone"),
+        payment_amount: 100,
+        deadline: 2_000_000,
+        token: None,
+    });
+    client.create_project(
+        &guild_id,
+        &contributor,
+        &milestones,
+        &100i128,
+        &1u64,
+        &None,
+        &true,
+    );
+    let milestone_id = 1u64;
+    assert_eq!(
+        client
+            .get_disputes_for_reference(&DisputeReference::Milestone, &milestone_id)
+            .len(),
+        0
+    );
+
+    let reason = String::from_str(&env, "Milestone dispute");
+    let evidence = String::from_str(&env, "https://example.com/evidence");
+    let first = client.create_dispute(&milestone_id, &contributor, &owner, &reason, &evidence);
+
+    // Without votes the first round expires and the milestone can be disputed again
+    set_ledger_timestamp(&env, 1000 + 7 * 24 * 60 * 60 + 1);
+    client.resolve_dispute(&first);
+    let second = client.create_dispute(&milestone_id, &contributor, &owner, &reason, &evidence);
+
+    let disputes = client.get_disputes_for_reference(&DisputeReference::Milestone, &milestone_id);
+    assert_eq!(disputes.len(), 2);
+    assert_eq!(disputes.get(0).unwrap().id, first);
+    assert_eq!(disputes.get(0).unwrap().status, DisputeStatus::Expired);
+    assert_eq!(disputes.get(1).unwrap().id, second);
+    assert_eq!(disputes.get(1).unwrap().status, DisputeStatus::Open);
+    assert_eq!(client.get_dispute(&second).reference_id, milestone_id);
+
+    // The index is per reference type, so a bounty sharing the ID sees none
+    assert_eq!(
+        client
+            .get_disputes_for_reference(&DisputeReference::Bounty, &milestone_id)
+            .len(),
+        0
+    );
+}
+
+#[test]
+fn test_reputation_share_quorum_falls_back_without_reputation() {
+    let env = setup_env();
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    let (guild_id, owner, admin, member, contributor) = setup_guild_with_members(&client, &env);
+    let member2 = Address::generate(&env);
+    client.add_member(&guild_id, &member2, &Role::Member, &owner);
+
+    assert!(client
+        .try_set_dispute_quorum_mode(&guild_id, &DisputeQuorumMode::ReputationShare(50), &member)
+        .is_err());
+    assert!(client
+        .try_set_dispute_quorum_mode(&guild_id, &DisputeQuorumMode::ReputationShare(101), &admin)
+        .is_err());
+    client.set_dispute_quorum_mode(&guild_id, &DisputeQuorumMode::ReputationShare(50), &admin);
+    assert_eq!(
+        client.get_dispute_quorum_mode(&guild_id),
+        DisputeQuorumMode::ReputationShare(50)
+    );
+
+    let token = create_mock_token(&env, &owner);
+    let bounty_id = create_funded_bounty(&client, &env, guild_id, &owner, &owner, &token);
+    let reason = String::from_str(&env, "Dispute reason");
+    let evidence = String::from_str(&env, "ipfs://evidence");
+    let dispute_id = client.create_dispute(&bounty_id, &contributor, &owner, &reason, &evidence);
+
+    client.cast_dispute_vote(&dispute_id, &admin, &VoteDecision::FavorPlaintiff);
+    client.cast_dispute_vote(&dispute_id, &member, &VoteDecision::FavorPlaintiff);
+
+    // No reputation in the guild yet: 2 of 5 members is enough
+    assert_eq!(client.get_guild_total_reputation(&guild_id), 0);
+    assert!(client.tally_dispute_votes(&dispute_id).quorum_reached);
+
+    client.record_contribution(&guild_id, &member2, &ContributionType::BountyCompleted, &1);
+    client.record_contribution(&guild_id, &admin, &ContributionType::VoteCast, &2);
+    client.record_contribution(&guild_id, &member, &ContributionType::VoteCast, &3);
+    assert_eq!(client.get_guild_total_reputation(&guild_id), 110);
+
+    // Voters now hold 10 of 110 reputation points
+    assert!(!client.tally_dispute_votes(&dispute_id).quorum_reached);
+
+    client.cast_dispute_vote(&dispute_id, &member2, &VoteDecision::FavorDefendant);
+    assert!(client.tally_dispute_votes(&dispute_id).quorum_reached);
+}
+
+#[test]
+fn test_losing_party_reputation_is_slashed_to_zero_floor() {
+    let env = setup_env();
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    let (guild_id, owner, admin, member, contributor) = setup_guild_with_members(&client, &env);
+    client.record_contribution(&guild_id, &owner, &ContributionType::BountyCompleted, &1);
+    client.record_contribution(&guild_id, &contributor, &ContributionType::VoteCast, &2);
+
+    let token = create_mock_token(&env, &owner);
+    let reason = String::from_str(&env, "Dispute reason");
+    let evidence = String::from_str(&env, "ipfs://evidence");
+
+    // Defendant loses: base penalty, stakes are below one whole token
+    let bounty_id = create_funded_bounty(&client, &env, guild_id, &owner, &owner, &token);
+    let dispute_id = client.create_dispute(&bounty_id, &contributor, &owner, &reason, &evidence);
+    client.cast_dispute_vote(&dispute_id, &admin, &VoteDecision::FavorPlaintiff);
+    client.cast_dispute_vote(&dispute_id, &member, &VoteDecision::FavorPlaintiff);
+    set_ledger_timestamp(&env, 1000 + 7 * 24 * 60 * 60 + 1);
+    client.resolve_dispute(&dispute_id);
+
+    assert_eq!(client.get_reputation(&guild_id, &owner).total_score, 75);
+    assert_eq!(client.get_reputation(&guild_id, &contributor).total_score, 5);
+    assert_eq!(client.get_guild_total_reputation(&guild_id), 80);
+
+    // Plaintiff loses with less reputation than the penalty: floored at zero
+    let bounty_id = create_funded_bounty(&client, &env, guild_id, &owner, &owner, &token);
+    let dispute_id = client.create_dispute(&bounty_id, &contributor, &owner, &reason, &evidence);
+    client.cast_dispute_vote(&dispute_id, &admin, &VoteDecision::FavorDefendant);
+    client.cast_dispute_vote(&dispute_id, &member, &VoteDecision::FavorDefendant);
+    set_ledger_timestamp(&env, 1000 + 14 * 24 * 60 * 60 + 2);
+    client.resolve_dispute(&dispute_id);
+
+    let profile = client.get_reputation(&guild_id, &contributor);
+    assert_eq!(profile.total_score, 0);
+    assert_eq!(profile.decayed_score, 0);
+    assert_eq!(client.get_reputation(&guild_id, &owner).total_score, 75);
+}
+
+#[test]
+fn test_withdrawn_dispute_lifts_escrow_hold() {
+    let env = setup_env();
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    let (guild_id, owner, admin, _member, contributor) = setup_guild_with_members(&client, &env);
+    let token = create_mock_token(&env, &owner);
+    let bounty_id = create_funded_bounty(&client, &env, guild_id, &owner, &owner, &token);
+
+    client.approve_bounty(&bounty_id, &owner, &contributor);
+    client.claim_bounty(&bounty_id, &contributor);
+    client.submit_work(
+        &bounty_id,
+        &String::from_str(&env, "https://github.com/pr/1"),
+    );
+    client.approve_completion(&bounty_id, &owner);
+
+    let reason = String::from_str(&env, "Payout disputed");
+    let evidence = String::from_str(&env, "ipfs://evidence");
+    let dispute_id = client.create_dispute(&bounty_id, &owner, &contributor, &reason, &evidence);
+
+    // The escrow is held while the dispute is open
+    assert!(client.try_release_escrow(&bounty_id).is_err());
+
+    // Only the plaintiff may withdraw
+    assert!(client.try_withdraw_dispute(&dispute_id, &admin).is_err());
+    assert!(client.withdraw_dispute(&dispute_id, &owner));
+    assert!(client.try_withdraw_dispute(&dispute_id, &owner).is_err());
+
+    let disputes = client.get_disputes_for_reference(&DisputeReference::Bounty, &bounty_id);
+    assert_eq!(disputes.get(0).unwrap().status, DisputeStatus::Withdrawn);
+
+    // Votes are refused once withdrawn and the normal release goes through
+    assert!(client
+        .try_cast_dispute_vote(&dispute_id, &admin, &VoteDecision::FavorPlaintiff)
+        .is_err());
+    assert!(client.release_escrow(&bounty_id));
+    assert_eq!(get_token_balance(&env, &token, &contributor), 100);
+}
+
+#[test]
+#[should_panic(expected = "voting already started")]
+fn test_dispute_cannot_be_withdrawn_once_voting_starts() {
+    let env = setup_env();
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    let (guild_id, owner, admin, _member, contributor) = setup_guild_with_members(&client, &env);
+    let token = create_mock_token(&env, &owner);
+    let bounty_id = create_funded_bounty(&client, &env, guild_id, &owner, &owner, &token);
+
+    let reason = String::from_str(&env, "Dispute reason");
+    let evidence = String::from_str(&env, "ipfs://evidence");
+    let dispute_id = client.create_dispute(&bounty_id, &contributor, &owner, &reason, &evidence);
+    client.cast_dispute_vote(&dispute_id, &admin, &VoteDecision::FavorDefendant);
+
+    client.withdraw_dispute(&dispute_id, &contributor);
+}
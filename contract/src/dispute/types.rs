@@ -14,6 +14,17 @@ pub enum DisputeStatus {
     Voting,
     Resolved,
     Expired,
+    Withdrawn,
+}
+
+impl DisputeStatus {
+    /// Whether the dispute has ended, one way or another
+    pub fn is_closed(&self) -> bool {
+        matches!(
+            self,
+            DisputeStatus::Resolved | DisputeStatus::Expired | DisputeStatus::Withdrawn
+        )
+    }
 }
 
 #[contracttype]
@@ -24,6 +35,28 @@ pub enum VoteDecision {
     Split,
 }
 
+/// Who may vote on a dispute
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum VoterEligibility {
+    /// Members of the dispute's guild, weighted by role (default)
+    GuildMembers,
+    /// Only the listed arbiters, one vote each
+    ArbiterPanel(Vec<Address>),
+    /// Guild members whose decayed reputation in the guild meets the bar
+    MinReputation(u64),
+}
+
+/// How a guild's disputes decide whether enough voters took part
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum DisputeQuorumMode {
+    /// A fixed share of the electorate must vote (default)
+    MemberCount,
+    /// Voters must together hold this percentage of the guild's total reputation
+    ReputationShare(u32),
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct Dispute {
@@ -45,6 +78,7 @@ pub struct Dispute {
     pub vote_count: u32,
     pub resolved_at: Option<u64>,
     pub resolution_executed: bool,
+    pub eligibility: VoterEligibility,
 }
 
 #[contracttype]
@@ -105,6 +139,20 @@ pub struct VoteCastEvent {
     pub weight: i128,
 }
 
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EligibilityUpdatedEvent {
+    pub dispute_id: u64,
+    pub eligibility: VoterEligibility,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct QuorumModeUpdatedEvent {
+    pub guild_id: u64,
+    pub mode: DisputeQuorumMode,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct DisputeResolvedEvent {
@@ -123,3 +171,10 @@ pub struct ResolutionExecutedEvent {
 pub struct DisputeExpiredEvent {
     pub dispute_id: u64,
 }
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DisputeWithdrawnEvent {
+    pub dispute_id: u64,
+    pub plaintiff: Address,
+}
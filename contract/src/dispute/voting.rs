@@ -1,11 +1,12 @@
 ﻿use soroban_sdk::Env;
 
 use crate::dispute::storage;
-use crate::dispute::types::{DisputeStatus, Vote, VoteDecision};
+use crate::dispute::types::{Dispute, DisputeStatus, Vote, VoteDecision, VoterEligibility};
 use crate::events::emit::emit_event;
 use crate::events::topics::{ACT_VOTE_CAST, MOD_DISPUTE};
 use crate::governance::types::role_weight;
 use crate::guild::storage as guild_storage;
+use crate::reputation::scoring::get_decayed_profile;
 
 /// Calculate voting weight for a guild member based on role.
 pub fn calculate_vote_weight(env: &Env, guild_id: u64, voter: &soroban_sdk::Address) -> u32 {
@@ -20,6 +21,38 @@ pub fn calculate_vote_weight(env: &Env, guild_id: u64, voter: &soroban_sdk::Addr
     }
 }
 
+/// Check `voter` against the dispute's eligibility policy and return the
+/// weight of their vote.
+fn eligible_vote_weight(env: &Env, dispute: &Dispute, voter: &soroban_sdk::Address) -> i128 {
+    if let Some(member) = guild_storage::get_member(env, dispute.guild_id, voter) {
+        if member.role.is_read_only() {
+            panic!("guests cannot vote");
+        }
+    }
+
+    match dispute.eligibility {
+        VoterEligibility::GuildMembers => {
+            calculate_vote_weight(env, dispute.guild_id, voter) as i128
+        }
+        VoterEligibility::ArbiterPanel(ref panel) => {
+            if !panel.contains(voter) {
+                panic!("voter not on arbiter panel");
+            }
+            1
+        }
+        VoterEligibility::MinReputation(min_score) => {
+            let weight = calculate_vote_weight(env, dispute.guild_id, voter) as i128;
+            let score = get_decayed_profile(env, voter, dispute.guild_id)
+                .map(|p| p.decayed_score)
+                .unwrap_or(0);
+            if score < min_score {
+                panic!("insufficient reputation to vote");
+            }
+            weight
+        }
+    }
+}
+
 /// Cast a weighted vote for a dispute.
 pub fn cast_vote(
     env: &Env,
@@ -31,7 +64,7 @@ pub fn cast_vote(
 
     let mut dispute = storage::get_dispute(env, dispute_id).expect("dispute not found");
 
-    if dispute.status == DisputeStatus::Resolved || dispute.status == DisputeStatus::Expired {
+    if dispute.status.is_closed() {
         panic!("dispute is closed");
     }
 
@@ -48,11 +81,7 @@ pub fn cast_vote(
         panic!("voter already voted");
     }
 
-    // ensure voter is a guild member
-    let _member = guild_storage::get_member(env, dispute.guild_id, &voter)
-        .unwrap_or_else(|| panic!("voter must be guild member"));
-
-    let weight = calculate_vote_weight(env, dispute.guild_id, &voter) as i128;
+    let weight = eligible_vote_weight(env, &dispute, &voter);
 
     let vote = Vote {
         voter: voter.clone(),
@@ -28,9 +28,11 @@ fn store_emergency_op(env: &Env, id: u64, proposer: &Address, status: OperationS
         description: String::from_str(env, "emergency"),
         proposer: proposer.clone(),
         signatures: Vec::new(env),
+        required_signatures: 1,
         nonce: 0,
         created_at: env.ledger().timestamp(),
         expires_at: env.ledger().timestamp() + 100,
+        max_expires_at: env.ledger().timestamp() + 200,
         status,
     };
     multisig_storage::store_operation(env, id, &op);
@@ -0,0 +1,61 @@
+/// Bounded on-chain log of recent critical events, kept per guild.
+///
+/// Soroban events are only visible to clients with event infrastructure.
+/// Light clients that just read contract state can audit the most recent
+/// fund-moving actions (treasury executions, escrow releases, dispute
+/// resolutions) through this log instead.
+///
+/// # Bounds
+/// Each guild keeps at most `MAX_CRITICAL_EVENTS` entries. Recording into a
+/// full buffer evicts the oldest entry first, so storage stays constant no
+/// matter how much activity the guild sees.
+use crate::events::types::CriticalEvent;
+use soroban_sdk::{symbol_short, Env, Symbol, Vec};
+
+/// Maximum entries retained per guild.
+pub const MAX_CRITICAL_EVENTS: u32 = 50;
+
+const CRITICAL_EVENTS_KEY: Symbol = symbol_short!("evt_crit");
+
+fn load(env: &Env, guild_id: u64) -> Vec<CriticalEvent> {
+    env.storage()
+        .persistent()
+        .get(&(CRITICAL_EVENTS_KEY, guild_id))
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+/// Append a critical event to the guild's log, evicting the oldest entry
+/// when the buffer is full.
+pub fn record_critical_event(
+    env: &Env,
+    guild_id: u64,
+    module: &str,
+    action: &str,
+    reference_id: u64,
+) {
+    let mut events = load(env, guild_id);
+    while events.len() >= MAX_CRITICAL_EVENTS {
+        events.pop_front();
+    }
+    events.push_back(CriticalEvent {
+        module: Symbol::new(env, module),
+        action: Symbol::new(env, action),
+        reference_id,
+        timestamp: env.ledger().timestamp(),
+    });
+    env.storage()
+        .persistent()
+        .set(&(CRITICAL_EVENTS_KEY, guild_id), &events);
+}
+
+/// Most recent critical events for a guild, newest first.
+pub fn get_recent_critical_events(env: &Env, guild_id: u64, limit: u32) -> Vec<CriticalEvent> {
+    let events = load(env, guild_id);
+    let mut recent = Vec::new(env);
+    let mut idx = events.len();
+    while idx > 0 && recent.len() < limit {
+        idx -= 1;
+        recent.push_back(events.get(idx).unwrap());
+    }
+    recent
+}
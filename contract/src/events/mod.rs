@@ -17,6 +17,7 @@
 ///   types.rs  â† EventEnvelope + schema version constant
 ///   topics.rs â† MOD_* and ACT_* string constants (the controlled vocabulary)
 ///   emit.rs   â† emit_event() â€” the only function modules should call
+///   critical.rs â† bounded per-guild log of recent critical events
 /// ```
 ///
 /// # Two-event-per-action design
@@ -53,6 +54,7 @@
 /// 4. Replace every `env.events().publish(...)` call in the module with
 ///    `emit_event(env, MOD_<MODULE>, ACT_<ACTION>, payload)`.
 /// 5. Document the new events in the module's top-level doc comment.
+pub mod critical;
 pub mod emit;
 pub mod topics;
 pub mod types;
@@ -63,11 +63,16 @@ pub const ACT_MEMBER_ADDED: &str = "member_added";
 pub const ACT_MEMBER_REMOVED: &str = "member_removed";
 pub const ACT_ROLE_UPDATED: &str = "role_updated";
 pub const ACT_JOINED: &str = "joined";
+pub const ACT_SUSPENDED: &str = "suspended";
+pub const ACT_REINSTATED: &str = "reinstated";
+pub const ACT_PERMISSION_DENIED: &str = "permission_denied";
 
 // =========== Bounty-specific actions ===========
 
 pub const ACT_CLAIMED: &str = "claimed";
+pub const ACT_COMMITTED: &str = "committed";
 pub const ACT_SUBMITTED: &str = "submitted";
+pub const ACT_ESCROW_SHORTFALL: &str = "esc_short";
 
 // =========== Payment-specific actions ===========
 
@@ -80,16 +85,23 @@ pub const ACT_VOTED: &str = "voted";
 pub const ACT_DELEGATED: &str = "delegated";
 pub const ACT_FINALIZED: &str = "finalized";
 pub const ACT_PROPOSED: &str = "proposed";
+pub const ACT_FORFEITED: &str = "forfeited";
 
 // =========== Milestone-specific actions ===========
 
 pub const ACT_STARTED: &str = "started";
 pub const ACT_COMPLETED: &str = "completed";
+pub const ACT_EXTENSION_REQUESTED: &str = "ext_requested";
+pub const ACT_DEADLINE_EXTENDED: &str = "deadline_extended";
+pub const ACT_PAYMENT_QUEUED: &str = "payment_queued";
+pub const ACT_PROGRESS: &str = "progress";
 
 // =========== Reputation-specific actions ===========
 
 pub const ACT_CONTRIBUTION: &str = "contribution";
 pub const ACT_BADGE_EARNED: &str = "badge_earned";
+pub const ACT_PENALIZED: &str = "penalized";
+pub const ACT_EXPORTED: &str = "exported";
 
 // =========== Dispute-specific actions ===========
 
@@ -106,17 +118,25 @@ pub const ACT_PAYMENT_PROCESSED: &str = "payment_ok";
 pub const ACT_PAYMENT_FAILED: &str = "payment_fail";
 pub const ACT_PAYMENT_RETRIED: &str = "payment_retry";
 pub const ACT_GRACE_STARTED: &str = "grace_started";
+pub const ACT_FEE_COLLECTED: &str = "fee_collected";
 
 // =========== Multisig-specific actions ===========
 
 pub const ACT_PROPOSED_OP: &str = "proposed_op";
 pub const ACT_SIGNED: &str = "signed";
+pub const ACT_SIG_REQUESTED: &str = "sig_requested";
+pub const ACT_THRESHOLD_REACHED: &str = "threshold_met";
 pub const ACT_SIGNER_ADDED: &str = "signer_added";
 pub const ACT_SIGNER_REMOVED: &str = "signer_removed";
 pub const ACT_FROZEN: &str = "frozen";
 pub const ACT_UNFROZEN: &str = "unfrozen";
 pub const ACT_POLICY_SET: &str = "policy_set";
 
+// =========== Treasury-specific actions ===========
+
+pub const ACT_RECLAIMED: &str = "reclaimed";
+pub const ACT_BATCH_PROPOSED: &str = "batch_proposed";
+
 // =========== Allowance-specific actions ===========
 
 pub const ACT_GRANTED: &str = "granted";
@@ -128,4 +148,5 @@ pub const ACT_DECREASED: &str = "decreased";
 
 pub const ACT_UPGRADE_PROPOSED: &str = "upgrade_proposed";
 pub const ACT_UPGRADE_EXECUTED: &str = "upgrade_executed";
+pub const ACT_UPGRADE_ROLLED_BACK: &str = "upgrade_rolled_back";
 pub const ACT_EMERGENCY_UPGRADE: &str = "emerg_upgrade";
@@ -51,3 +51,19 @@ pub struct EventEnvelope {
     /// Contract-global monotonic sequence number.
     pub sequence: u64,
 }
+
+/// Entry in a guild's bounded critical-event log.
+///
+/// Fields:
+/// - `module`       â€” The sub-module that produced the event.
+/// - `action`       â€” The action taken (e.g. `"executed"`, `"released"`).
+/// - `reference_id` â€” ID of the transaction, bounty, project or dispute.
+/// - `timestamp`    â€” Ledger timestamp when the event was recorded.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CriticalEvent {
+    pub module: Symbol,
+    pub action: Symbol,
+    pub reference_id: u64,
+    pub timestamp: u64,
+}
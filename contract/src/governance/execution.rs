@@ -8,6 +8,7 @@ use crate::governance::types::{
     ExecutionPayload, Proposal, ProposalExecutedEvent, ProposalStatus, ProposalType,
 };
 use crate::governance::voting::finalize_proposal;
+use crate::subscription::lifecycle::apply_governed_plan_update;
 
 const EXECUTION_DEADLINE_SECONDS: u64 = 3 * 24 * 60 * 60; // 3 days after passing
 
@@ -46,6 +47,19 @@ pub fn execute_proposal(env: &Env, proposal_id: u64, executor: Address) -> bool
             true
         }
         (ProposalType::GeneralDecision, ExecutionPayload::GeneralDecision) => true,
+        (ProposalType::SubscriptionPlanUpdate, ExecutionPayload::SubscriptionPlanUpdate(data)) => {
+            // Ownership is checked again here, not only when the proposal was created
+            match apply_governed_plan_update(
+                env,
+                proposal.guild_id,
+                data.plan_id,
+                data.new_price,
+                data.active,
+            ) {
+                Ok(result) => result,
+                Err(e) => panic!("subscription plan update error: {}", e as u32),
+            }
+        }
         _ => false,
     };
 
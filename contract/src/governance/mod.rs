@@ -5,14 +5,20 @@ pub mod types;
 pub mod voting;
 
 pub use types::{
-    ExecutionPayload, GovernanceConfig, Proposal, ProposalStatus, ProposalType, VoteDecision,
+    ExecutionPayload, GovernanceConfig, GovernanceConfigOverride, GovernanceStats, Proposal,
+    ProposalStatus, ProposalType, ProposalTypeRule, VoteDecision,
 };
 
 pub use proposals::{
-    cancel_proposal, create_proposal, get_active_proposals, get_proposal, update_governance_config,
+    cancel_proposal, create_proposal, get_active_proposals, get_forfeited_deposits,
+    get_governance_config, get_governance_stats, get_proposal, get_proposal_type_rule,
+    get_proposals_by_tag, set_default_governance_config, set_governance_config_override,
+    set_proposal_deposit, update_governance_config, withdraw_forfeited_deposits,
 };
 
-pub use voting::{delegate_vote, finalize_proposal, undelegate_vote, vote};
+pub use storage::{get_delegate, get_delegators};
+
+pub use voting::{batch_vote, delegate_vote, finalize_proposal, undelegate_vote, vote};
 
 pub use execution::execute_proposal;
 
@@ -1,17 +1,46 @@
 use crate::events::emit::emit_event;
-use crate::events::topics::{ACT_CANCELLED, ACT_PROPOSED, ACT_UPDATED, MOD_GOVERNANCE};
-use soroban_sdk::{Address, Env, String, Vec};
+use crate::events::topics::{
+    ACT_CANCELLED, ACT_PROPOSED, ACT_TRANSFERRED, ACT_UPDATED, MOD_GOVERNANCE,
+};
+use soroban_sdk::{token::Client as TokenClient, Address, Env, String, Vec};
 
 use crate::governance::storage::{
-    get_config, get_guild_proposals, get_next_proposal_id, get_proposal as load_proposal,
-    set_config, store_proposal,
+    get_active_proposal_count, get_all_votes, get_config, get_default_config, get_deposit_config,
+    get_guild_proposals, get_last_proposal_at, get_next_proposal_id, get_proposal as load_proposal,
+    get_proposal_deposit, get_tagged_proposal_ids, index_proposal_tag, remove_proposal_deposit,
+    set_config, set_config_override, set_default_config, set_deposit_config, set_last_proposal_at,
+    store_proposal, store_proposal_deposit, take_forfeited_balance,
 };
 use crate::governance::types::{
-    ExecutionPayload, GovernanceConfig, GovernanceConfigUpdatedEvent, Proposal,
-    ProposalCreatedEvent, ProposalStatus, ProposalType,
+    ExecutionPayload, ForfeitedDepositsWithdrawnEvent, GovernanceConfig, GovernanceConfigOverride,
+    GovernanceConfigUpdatedEvent, GovernanceStats, Proposal, ProposalCreatedEvent, ProposalDeposit,
+    ProposalDepositConfig, ProposalStatus, ProposalType, ProposalTypeRule,
 };
 use crate::guild::storage as guild_storage;
-use crate::guild::types::Member;
+use crate::guild::types::{Member, Role};
+use crate::reputation::scoring::get_decayed_profile;
+use crate::subscription::storage as subscription_storage;
+
+/// Most tags a single proposal may carry
+pub const MAX_PROPOSAL_TAGS: u32 = 5;
+/// Longest allowed tag, in bytes
+pub const MAX_PROPOSAL_TAG_LEN: u32 = 32;
+/// Most recent proposals whose votes are counted toward average turnout
+pub const MAX_TURNOUT_SAMPLE: u32 = 20;
+
+fn validate_tags(tags: &Vec<String>) {
+    if tags.len() > MAX_PROPOSAL_TAGS {
+        panic!("too many proposal tags");
+    }
+    for (i, tag) in tags.iter().enumerate() {
+        if tag.is_empty() || tag.len() > MAX_PROPOSAL_TAG_LEN {
+            panic!("proposal tag length invalid");
+        }
+        if tags.iter().take(i).any(|earlier| earlier == tag) {
+            panic!("duplicate proposal tag");
+        }
+    }
+}
 
 fn validate_execution_payload(
     env: &Env,
@@ -26,6 +55,16 @@ fn validate_execution_payload(
         (ProposalType::RemoveMember, ExecutionPayload::RemoveMember) => {}
         (ProposalType::RuleChange, ExecutionPayload::RuleChange) => {}
         (ProposalType::GeneralDecision, ExecutionPayload::GeneralDecision) => {}
+        (ProposalType::SubscriptionPlanUpdate, ExecutionPayload::SubscriptionPlanUpdate(data)) => {
+            let plan = subscription_storage::get_plan(env, data.plan_id)
+                .unwrap_or_else(|| panic!("subscription plan not found"));
+            if plan.guild_id != guild_id {
+                panic!("subscription plan does not belong to this guild");
+            }
+            if data.new_price <= 0 {
+                panic!("invalid subscription plan price");
+            }
+        }
         _ => {
             panic!("execution payload does not match proposal type");
         }
@@ -47,6 +86,7 @@ fn ensure_guild_member(env: &Env, guild_id: u64, address: &Address) {
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn create_proposal(
     env: &Env,
     guild_id: u64,
@@ -55,12 +95,16 @@ pub fn create_proposal(
     title: String,
     description: String,
     execution_payload: ExecutionPayload,
+    tags: Vec<String>,
 ) -> u64 {
     proposer.require_auth();
 
     // must be guild member
     let member = get_member(env, guild_id, &proposer)
         .unwrap_or_else(|| panic!("proposer must be a guild member"));
+    if member.role.is_read_only() {
+        panic!("guests cannot create proposals");
+    }
 
     let cfg: GovernanceConfig = get_config(env, guild_id);
 
@@ -70,6 +114,31 @@ pub fn create_proposal(
         panic!("insufficient reputation to create proposal");
     }
 
+    // earned (decayed) reputation gate; owners are exempt
+    if cfg.min_reputation_to_propose > 0 && member.role != Role::Owner {
+        let score = get_decayed_profile(env, &proposer, guild_id)
+            .map(|p| p.decayed_score)
+            .unwrap_or(0);
+        if score < cfg.min_reputation_to_propose as u64 {
+            panic!("insufficient reputation to create proposal");
+        }
+    }
+
+    if cfg.max_active_proposals > 0
+        && get_active_proposal_count(env, guild_id) >= cfg.max_active_proposals
+    {
+        panic!("too many active proposals");
+    }
+
+    let now = env.ledger().timestamp();
+    if cfg.proposal_cooldown_seconds > 0 {
+        if let Some(last) = get_last_proposal_at(env, guild_id, &proposer) {
+            if now < last.saturating_add(cfg.proposal_cooldown_seconds) {
+                panic!("proposal cooldown active");
+            }
+        }
+    }
+
     if title.len() == 0 || title.len() > 200 {
         panic!("proposal title length invalid");
     }
@@ -79,10 +148,11 @@ pub fn create_proposal(
     }
 
     validate_execution_payload(env, guild_id, &proposal_type, &execution_payload);
+    validate_tags(&tags);
 
     let id = get_next_proposal_id(env);
-    let now = env.ledger().timestamp();
-    let voting_period_secs = (cfg.voting_period_days as u64) * 24 * 60 * 60;
+    let rule = cfg.rule_for(&proposal_type);
+    let voting_period_secs = (rule.voting_period_days as u64) * 24 * 60 * 60;
 
     let proposal = Proposal {
         id,
@@ -100,9 +170,30 @@ pub fn create_proposal(
         execution_payload,
         passed_at: None,
         executed_at: None,
+        tags: tags.clone(),
     };
 
     store_proposal(env, &proposal);
+    set_last_proposal_at(env, guild_id, &proposer, now);
+    for tag in tags.iter() {
+        index_proposal_tag(env, guild_id, &tag, id);
+    }
+
+    // lock the guild's proposal deposit, if one is configured
+    if let Some(deposit_cfg) = get_deposit_config(env, guild_id) {
+        let client = TokenClient::new(env, &deposit_cfg.token);
+        client.transfer(
+            &proposer,
+            &env.current_contract_address(),
+            &deposit_cfg.amount,
+        );
+        let deposit = ProposalDeposit {
+            proposer: proposer.clone(),
+            token: deposit_cfg.token,
+            amount: deposit_cfg.amount,
+        };
+        store_proposal_deposit(env, id, &deposit);
+    }
 
     let event = ProposalCreatedEvent {
         proposal_id: id,
@@ -129,6 +220,13 @@ pub fn cancel_proposal(env: &Env, proposal_id: u64, canceller: Address) -> bool
         panic!("only active or draft proposals can be cancelled");
     }
 
+    // once voting is over the outcome is known; finalizing decides whether
+    // the deposit is refunded or forfeited
+    if proposal.status == ProposalStatus::Active && env.ledger().timestamp() >= proposal.voting_end
+    {
+        panic!("voting period has ended");
+    }
+
     // allow proposer or guild owner to cancel
     let guild = guild_storage::get_guild(env, proposal.guild_id)
         .unwrap_or_else(|| panic!("guild not found"));
@@ -139,6 +237,7 @@ pub fn cancel_proposal(env: &Env, proposal_id: u64, canceller: Address) -> bool
 
     proposal.status = ProposalStatus::Cancelled;
     store_proposal(env, &proposal);
+    refund_proposal_deposit(env, proposal_id);
 
     let event = crate::governance::types::ProposalCancelledEvent { proposal_id };
     emit_event(env, MOD_GOVERNANCE, ACT_CANCELLED, event);
@@ -146,10 +245,107 @@ pub fn cancel_proposal(env: &Env, proposal_id: u64, canceller: Address) -> bool
     true
 }
 
+/// Return a proposal's deposit to its proposer.
+pub(crate) fn refund_proposal_deposit(env: &Env, proposal_id: u64) {
+    if let Some(deposit) = get_proposal_deposit(env, proposal_id) {
+        let client = TokenClient::new(env, &deposit.token);
+        client.transfer(
+            &env.current_contract_address(),
+            &deposit.proposer,
+            &deposit.amount,
+        );
+        remove_proposal_deposit(env, proposal_id);
+    }
+}
+
+/// Require proposers in a guild to lock `amount` of `token` per proposal.
+/// The deposit is refunded when the proposal reaches quorum or is cancelled
+/// while voting is open, and forfeited to the guild otherwise. An amount of zero disables it.
+pub fn set_proposal_deposit(
+    env: &Env,
+    guild_id: u64,
+    caller: Address,
+    token: Address,
+    amount: i128,
+) -> bool {
+    let guild =
+        guild_storage::get_guild(env, guild_id).unwrap_or_else(|| panic!("guild not found"));
+    if caller != guild.owner {
+        panic!("only guild owner can update governance config");
+    }
+    caller.require_auth();
+
+    if amount < 0 {
+        panic!("deposit amount cannot be negative");
+    }
+    let config = if amount == 0 {
+        None
+    } else {
+        Some(ProposalDepositConfig { token, amount })
+    };
+    set_deposit_config(env, guild_id, config);
+
+    let event = GovernanceConfigUpdatedEvent { guild_id };
+    emit_event(env, MOD_GOVERNANCE, ACT_UPDATED, event);
+
+    true
+}
+
+pub fn get_forfeited_deposits(env: &Env, guild_id: u64, token: Address) -> i128 {
+    crate::governance::storage::get_forfeited_balance(env, guild_id, &token)
+}
+
+/// Pay out everything the contract holds in `token` for a guild's forfeited
+/// deposits to `recipient` (guild owner only). Returns the amount moved.
+pub fn withdraw_forfeited_deposits(
+    env: &Env,
+    guild_id: u64,
+    caller: Address,
+    token: Address,
+    recipient: Address,
+) -> i128 {
+    let guild =
+        guild_storage::get_guild(env, guild_id).unwrap_or_else(|| panic!("guild not found"));
+    if caller != guild.owner {
+        panic!("only guild owner can withdraw forfeited deposits");
+    }
+    caller.require_auth();
+
+    let amount = take_forfeited_balance(env, guild_id, &token);
+    if amount > 0 {
+        TokenClient::new(env, &token).transfer(
+            &env.current_contract_address(),
+            &recipient,
+            &amount,
+        );
+
+        let event = ForfeitedDepositsWithdrawnEvent {
+            guild_id,
+            token,
+            amount,
+            recipient,
+        };
+        emit_event(env, MOD_GOVERNANCE, ACT_TRANSFERRED, event);
+    }
+
+    amount
+}
+
 pub fn get_proposal(env: &Env, proposal_id: u64) -> Proposal {
     load_proposal(env, proposal_id).unwrap_or_else(|| panic!("proposal not found"))
 }
 
+/// Proposals in a guild carrying `tag`, oldest first
+pub fn get_proposals_by_tag(env: &Env, guild_id: u64, tag: String) -> Vec<Proposal> {
+    let mut result = Vec::new(env);
+    for id in get_tagged_proposal_ids(env, guild_id, &tag).iter() {
+        if let Some(proposal) = load_proposal(env, id) {
+            result.push_back(proposal);
+        }
+    }
+    result
+}
+
 pub fn get_active_proposals(env: &Env, guild_id: u64) -> Vec<Proposal> {
     let all = get_guild_proposals(env, guild_id);
     let mut active = Vec::new(env);
@@ -161,6 +357,46 @@ pub fn get_active_proposals(env: &Env, guild_id: u64) -> Vec<Proposal> {
     active
 }
 
+pub fn get_governance_stats(env: &Env, guild_id: u64) -> GovernanceStats {
+    let proposals = get_guild_proposals(env, guild_id);
+    let member_count = guild_storage::get_guild(env, guild_id)
+        .map(|guild| guild.member_count as u64)
+        .unwrap_or(0);
+
+    let mut stats = GovernanceStats {
+        guild_id,
+        total_proposals: proposals.len(),
+        passed_count: 0,
+        failed_count: 0,
+        cancelled_count: 0,
+        active_count: 0,
+        average_turnout_percentage: 0,
+    };
+
+    // Loading votes is costly, so turnout only samples the latest proposals
+    let sample_start = stats.total_proposals.saturating_sub(MAX_TURNOUT_SAMPLE);
+    let mut total_voters: u64 = 0;
+    for (i, p) in proposals.iter().enumerate() {
+        match p.status {
+            ProposalStatus::Passed | ProposalStatus::Executed => stats.passed_count += 1,
+            ProposalStatus::Rejected | ProposalStatus::Expired => stats.failed_count += 1,
+            ProposalStatus::Cancelled => stats.cancelled_count += 1,
+            ProposalStatus::Active => stats.active_count += 1,
+            ProposalStatus::Draft => {}
+        }
+        if i as u32 >= sample_start {
+            total_voters += get_all_votes(env, p.id).len() as u64;
+        }
+    }
+
+    let sampled = (stats.total_proposals - sample_start) as u64;
+    let possible_voters = sampled * member_count;
+    stats.average_turnout_percentage =
+        (total_voters * 100).checked_div(possible_voters).unwrap_or(0) as u32;
+
+    stats
+}
+
 pub fn update_governance_config(
     env: &Env,
     guild_id: u64,
@@ -176,17 +412,100 @@ pub fn update_governance_config(
     }
     caller.require_auth();
 
+    validate_config(&config);
+
+    set_config(env, guild_id, &config);
+
+    let event = GovernanceConfigUpdatedEvent { guild_id };
+    emit_event(env, MOD_GOVERNANCE, ACT_UPDATED, event);
+
+    true
+}
+
+fn validate_config(config: &GovernanceConfig) {
     if config.quorum_percentage == 0 || config.quorum_percentage > 100 {
         panic!("invalid quorum percentage");
     }
     if config.approval_threshold == 0 || config.approval_threshold > 100 {
         panic!("invalid approval threshold");
     }
+    // A zero cap leaves weights uncapped
+    if config.max_vote_weight > 0 && config.min_vote_weight > config.max_vote_weight {
+        panic!("min vote weight exceeds max vote weight");
+    }
 
-    set_config(env, guild_id, &config);
+    for (i, rule) in config.type_rules.iter().enumerate() {
+        if rule.quorum_percentage == 0 || rule.quorum_percentage > 100 {
+            panic!("invalid quorum percentage");
+        }
+        if rule.approval_threshold == 0 || rule.approval_threshold > 100 {
+            panic!("invalid approval threshold");
+        }
+        if rule.voting_period_days == 0 {
+            panic!("invalid voting period");
+        }
+        let duplicate = config
+            .type_rules
+            .iter()
+            .skip(i + 1)
+            .any(|other| other.proposal_type == rule.proposal_type);
+        if duplicate {
+            panic!("duplicate proposal type rule");
+        }
+    }
+}
+
+/// Set the guild's partial override; unset fields follow the platform
+/// default, including future changes to it.
+pub fn set_governance_config_override(
+    env: &Env,
+    guild_id: u64,
+    caller: Address,
+    config: GovernanceConfigOverride,
+) -> bool {
+    let guild =
+        guild_storage::get_guild(env, guild_id).unwrap_or_else(|| panic!("guild not found"));
+
+    if caller != guild.owner {
+        panic!("only guild owner can update governance config");
+    }
+    caller.require_auth();
+
+    // Validate the merged result so a partial override can't break the config
+    validate_config(&config.apply(get_default_config(env)));
+
+    set_config_override(env, guild_id, &config);
 
     let event = GovernanceConfigUpdatedEvent { guild_id };
     emit_event(env, MOD_GOVERNANCE, ACT_UPDATED, event);
 
     true
 }
+
+/// Replace the platform default config (contract admin only). Emits the
+/// update event with `guild_id` 0.
+pub fn set_default_governance_config(env: &Env, caller: Address, config: GovernanceConfig) -> bool {
+    crate::integration::auth::require_admin(env, &caller);
+
+    validate_config(&config);
+
+    set_default_config(env, &config);
+
+    let event = GovernanceConfigUpdatedEvent { guild_id: 0 };
+    emit_event(env, MOD_GOVERNANCE, ACT_UPDATED, event);
+
+    true
+}
+
+pub fn get_governance_config(env: &Env, guild_id: u64) -> GovernanceConfig {
+    get_config(env, guild_id)
+}
+
+/// Effective voting rule for a proposal type in a guild.
+pub fn get_proposal_type_rule(
+    env: &Env,
+    guild_id: u64,
+    proposal_type: ProposalType,
+) -> ProposalTypeRule {
+    get_config(env, guild_id).rule_for(&proposal_type)
+}
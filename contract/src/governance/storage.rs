@@ -1,16 +1,30 @@
-﻿use soroban_sdk::{symbol_short, Address, Env, Map, Symbol, Vec};
+﻿use soroban_sdk::{symbol_short, Address, Env, Map, String, Symbol, Vec};
 
-use crate::governance::types::{GovernanceConfig, Proposal, Vote};
+use crate::governance::types::{
+    GovernanceConfig, GovernanceConfigOverride, LegacyGovernanceConfig, Proposal, ProposalDeposit,
+    ProposalDepositConfig, ProposalStatus, Vote,
+};
 
 const PROPOSALS_KEY: Symbol = symbol_short!("g_props");
 const PROPOSAL_COUNTER_KEY: Symbol = symbol_short!("g_pcnt");
 const GUILD_PROPOSALS_KEY: Symbol = symbol_short!("g_pidx");
+const TAG_INDEX_KEY: Symbol = symbol_short!("g_ptags");
+const ACTIVE_COUNT_KEY: Symbol = symbol_short!("g_pactv");
 
 const VOTES_KEY: Symbol = symbol_short!("g_votes");
 
 const DELEGATIONS_KEY: Symbol = symbol_short!("g_deleg");
+const DELEGATORS_KEY: Symbol = symbol_short!("g_dlgrs");
 
-const GOV_CONFIG_KEY: Symbol = symbol_short!("g_conf");
+/// Full per-guild configs written before overrides existed
+const LEGACY_GOV_CONFIG_KEY: Symbol = symbol_short!("g_conf");
+const GOV_CONFIG_KEY: Symbol = symbol_short!("g_confo");
+const GOV_DEFAULT_CONFIG_KEY: Symbol = symbol_short!("g_dconf");
+
+const DEPOSIT_CONFIG_KEY: Symbol = symbol_short!("g_depcfg");
+const DEPOSITS_KEY: Symbol = symbol_short!("g_deps");
+const FORFEIT_POOL_KEY: Symbol = symbol_short!("g_forf");
+const LAST_PROPOSAL_KEY: Symbol = symbol_short!("g_lastp");
 
 pub fn get_next_proposal_id(env: &Env) -> u64 {
     let current: u64 = env
@@ -30,6 +44,26 @@ pub fn store_proposal(env: &Env, proposal: &Proposal) {
         .get(&PROPOSALS_KEY)
         .unwrap_or_else(|| Map::new(env));
 
+    let was_active = proposals
+        .get(proposal.id)
+        .map(|old| old.status == ProposalStatus::Active)
+        .unwrap_or(false);
+
+    // Counted before the write, so a guild with no stored count yet is
+    // scanned as it stood
+    let is_active = proposal.status == ProposalStatus::Active;
+    if was_active != is_active {
+        let count = get_active_proposal_count(env, proposal.guild_id);
+        let count = if is_active {
+            count + 1
+        } else {
+            count.saturating_sub(1)
+        };
+        env.storage()
+            .persistent()
+            .set(&(ACTIVE_COUNT_KEY, proposal.guild_id), &count);
+    }
+
     proposals.set(proposal.id, proposal.clone());
     env.storage().persistent().set(&PROPOSALS_KEY, &proposals);
 
@@ -60,6 +94,21 @@ pub fn get_proposal(env: &Env, proposal_id: u64) -> Option<Proposal> {
     proposals.get(proposal_id)
 }
 
+/// Proposals of a guild currently in `Active` status, including those whose
+/// voting period has ended but which have not been finalized yet. Guilds
+/// whose proposals predate the stored count are scanned.
+pub fn get_active_proposal_count(env: &Env, guild_id: u64) -> u32 {
+    env.storage()
+        .persistent()
+        .get(&(ACTIVE_COUNT_KEY, guild_id))
+        .unwrap_or_else(|| {
+            get_guild_proposals(env, guild_id)
+                .iter()
+                .filter(|p| p.status == ProposalStatus::Active)
+                .count() as u32
+        })
+}
+
 pub fn get_guild_proposals(env: &Env, guild_id: u64) -> Vec<Proposal> {
     let index: Map<u64, Vec<u64>> = env
         .storage()
@@ -84,6 +133,24 @@ pub fn get_guild_proposals(env: &Env, guild_id: u64) -> Vec<Proposal> {
     result
 }
 
+pub fn index_proposal_tag(env: &Env, guild_id: u64, tag: &String, proposal_id: u64) {
+    let key = (TAG_INDEX_KEY, guild_id, tag.clone());
+    let mut ids: Vec<u64> = env
+        .storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or_else(|| Vec::new(env));
+    ids.push_back(proposal_id);
+    env.storage().persistent().set(&key, &ids);
+}
+
+pub fn get_tagged_proposal_ids(env: &Env, guild_id: u64, tag: &String) -> Vec<u64> {
+    env.storage()
+        .persistent()
+        .get(&(TAG_INDEX_KEY, guild_id, tag.clone()))
+        .unwrap_or_else(|| Vec::new(env))
+}
+
 pub fn store_vote(env: &Env, vote: &Vote) {
     let mut votes_map: Map<u64, Map<Address, Vote>> = env
         .storage()
@@ -130,10 +197,26 @@ pub fn set_delegation(env: &Env, guild_id: u64, delegator: &Address, delegate: &
         .get(&DELEGATIONS_KEY)
         .unwrap_or_else(|| Map::new(env));
 
+    let previous = delegations.get((guild_id, delegator.clone()));
     delegations.set((guild_id, delegator.clone()), delegate.clone());
     env.storage()
         .persistent()
         .set(&DELEGATIONS_KEY, &delegations);
+
+    // keep the reverse index in step: leave the old delegate's list, join the new one
+    if let Some(old) = previous.filter(|old| old != delegate) {
+        unindex_delegator(env, guild_id, &old, delegator);
+    }
+    let key = (DELEGATORS_KEY, guild_id, delegate.clone());
+    let mut delegators: Vec<Address> = env
+        .storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or_else(|| Vec::new(env));
+    if !delegators.contains(delegator) {
+        delegators.push_back(delegator.clone());
+        env.storage().persistent().set(&key, &delegators);
+    }
 }
 
 pub fn remove_delegation(env: &Env, guild_id: u64, delegator: &Address) {
@@ -143,14 +226,40 @@ pub fn remove_delegation(env: &Env, guild_id: u64, delegator: &Address) {
         .get(&DELEGATIONS_KEY)
         .unwrap_or_else(|| Map::new(env));
 
-    if delegations.contains_key((guild_id, delegator.clone())) {
+    if let Some(delegate) = delegations.get((guild_id, delegator.clone())) {
         delegations.remove((guild_id, delegator.clone()));
         env.storage()
             .persistent()
             .set(&DELEGATIONS_KEY, &delegations);
+        unindex_delegator(env, guild_id, &delegate, delegator);
+    }
+}
+
+fn unindex_delegator(env: &Env, guild_id: u64, delegate: &Address, delegator: &Address) {
+    let key = (DELEGATORS_KEY, guild_id, delegate.clone());
+    let mut delegators: Vec<Address> = env
+        .storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or_else(|| Vec::new(env));
+    if let Some(index) = delegators.first_index_of(delegator) {
+        delegators.remove(index);
+        if delegators.is_empty() {
+            env.storage().persistent().remove(&key);
+        } else {
+            env.storage().persistent().set(&key, &delegators);
+        }
     }
 }
 
+/// Members currently delegating directly to `delegate` in a guild
+pub fn get_delegators(env: &Env, guild_id: u64, delegate: &Address) -> Vec<Address> {
+    env.storage()
+        .persistent()
+        .get(&(DELEGATORS_KEY, guild_id, delegate.clone()))
+        .unwrap_or_else(|| Vec::new(env))
+}
+
 pub fn get_delegate(env: &Env, guild_id: u64, delegator: &Address) -> Option<Address> {
     let delegations: Map<(u64, Address), Address> = env
         .storage()
@@ -161,25 +270,179 @@ pub fn get_delegate(env: &Env, guild_id: u64, delegator: &Address) -> Option<Add
     delegations.get((guild_id, delegator.clone()))
 }
 
-pub fn get_config(env: &Env, guild_id: u64) -> GovernanceConfig {
-    let configs: Map<u64, GovernanceConfig> = env
+/// Platform-wide config inherited by every guild that does not override it.
+pub fn get_default_config(env: &Env) -> GovernanceConfig {
+    env.storage()
+        .persistent()
+        .get(&GOV_DEFAULT_CONFIG_KEY)
+        .unwrap_or_else(|| GovernanceConfig::default(env))
+}
+
+pub fn set_default_config(env: &Env, config: &GovernanceConfig) {
+    env.storage()
+        .persistent()
+        .set(&GOV_DEFAULT_CONFIG_KEY, config);
+}
+
+/// The guild's override, or the full config it stored under the legacy key
+/// if it has never set one since.
+pub fn get_config_override(env: &Env, guild_id: u64) -> Option<GovernanceConfigOverride> {
+    let overrides: Map<u64, GovernanceConfigOverride> = env
         .storage()
         .persistent()
         .get(&GOV_CONFIG_KEY)
         .unwrap_or_else(|| Map::new(env));
 
-    configs
-        .get(guild_id)
-        .unwrap_or_else(GovernanceConfig::default)
+    overrides.get(guild_id).or_else(|| {
+        let legacy: Map<u64, LegacyGovernanceConfig> = env
+            .storage()
+            .persistent()
+            .get(&LEGACY_GOV_CONFIG_KEY)
+            .unwrap_or_else(|| Map::new(env));
+        legacy.get(guild_id).map(|config| config.to_override())
+    })
 }
 
-pub fn set_config(env: &Env, guild_id: u64, config: &GovernanceConfig) {
-    let mut configs: Map<u64, GovernanceConfig> = env
+pub fn set_config_override(env: &Env, guild_id: u64, config: &GovernanceConfigOverride) {
+    let mut overrides: Map<u64, GovernanceConfigOverride> = env
         .storage()
         .persistent()
         .get(&GOV_CONFIG_KEY)
         .unwrap_or_else(|| Map::new(env));
 
-    configs.set(guild_id, config.clone());
-    env.storage().persistent().set(&GOV_CONFIG_KEY, &configs);
+    overrides.set(guild_id, config.clone());
+    env.storage().persistent().set(&GOV_CONFIG_KEY, &overrides);
+}
+
+/// Effective config for a guild: its override merged over the platform default.
+pub fn get_config(env: &Env, guild_id: u64) -> GovernanceConfig {
+    let default = get_default_config(env);
+    match get_config_override(env, guild_id) {
+        Some(config) => config.apply(default),
+        None => default,
+    }
+}
+
+/// Pin every field of a guild's config, ignoring later default changes.
+pub fn set_config(env: &Env, guild_id: u64, config: &GovernanceConfig) {
+    set_config_override(
+        env,
+        guild_id,
+        &GovernanceConfigOverride::from_config(config),
+    );
+}
+
+pub fn get_deposit_config(env: &Env, guild_id: u64) -> Option<ProposalDepositConfig> {
+    let configs: Map<u64, ProposalDepositConfig> = env
+        .storage()
+        .persistent()
+        .get(&DEPOSIT_CONFIG_KEY)
+        .unwrap_or_else(|| Map::new(env));
+
+    configs.get(guild_id)
+}
+
+pub fn set_deposit_config(env: &Env, guild_id: u64, config: Option<ProposalDepositConfig>) {
+    let mut configs: Map<u64, ProposalDepositConfig> = env
+        .storage()
+        .persistent()
+        .get(&DEPOSIT_CONFIG_KEY)
+        .unwrap_or_else(|| Map::new(env));
+
+    match config {
+        Some(c) => configs.set(guild_id, c),
+        None => {
+            configs.remove(guild_id);
+        }
+    }
+    env.storage()
+        .persistent()
+        .set(&DEPOSIT_CONFIG_KEY, &configs);
+}
+
+pub fn get_proposal_deposit(env: &Env, proposal_id: u64) -> Option<ProposalDeposit> {
+    let deposits: Map<u64, ProposalDeposit> = env
+        .storage()
+        .persistent()
+        .get(&DEPOSITS_KEY)
+        .unwrap_or_else(|| Map::new(env));
+
+    deposits.get(proposal_id)
+}
+
+pub fn store_proposal_deposit(env: &Env, proposal_id: u64, deposit: &ProposalDeposit) {
+    let mut deposits: Map<u64, ProposalDeposit> = env
+        .storage()
+        .persistent()
+        .get(&DEPOSITS_KEY)
+        .unwrap_or_else(|| Map::new(env));
+
+    deposits.set(proposal_id, deposit.clone());
+    env.storage().persistent().set(&DEPOSITS_KEY, &deposits);
+}
+
+pub fn remove_proposal_deposit(env: &Env, proposal_id: u64) {
+    let mut deposits: Map<u64, ProposalDeposit> = env
+        .storage()
+        .persistent()
+        .get(&DEPOSITS_KEY)
+        .unwrap_or_else(|| Map::new(env));
+
+    if deposits.contains_key(proposal_id) {
+        deposits.remove(proposal_id);
+        env.storage().persistent().set(&DEPOSITS_KEY, &deposits);
+    }
+}
+
+/// Forfeited deposits held by the contract for a guild with no treasury.
+pub fn get_forfeited_balance(env: &Env, guild_id: u64, token: &Address) -> i128 {
+    let pool: Map<(u64, Address), i128> = env
+        .storage()
+        .persistent()
+        .get(&FORFEIT_POOL_KEY)
+        .unwrap_or_else(|| Map::new(env));
+
+    pool.get((guild_id, token.clone())).unwrap_or(0)
+}
+
+pub fn add_forfeited_balance(env: &Env, guild_id: u64, token: &Address, amount: i128) {
+    let mut pool: Map<(u64, Address), i128> = env
+        .storage()
+        .persistent()
+        .get(&FORFEIT_POOL_KEY)
+        .unwrap_or_else(|| Map::new(env));
+
+    let current = pool.get((guild_id, token.clone())).unwrap_or(0);
+    pool.set((guild_id, token.clone()), current + amount);
+    env.storage().persistent().set(&FORFEIT_POOL_KEY, &pool);
+}
+
+/// Empty a guild's forfeit pool for `token`, returning what it held.
+pub fn take_forfeited_balance(env: &Env, guild_id: u64, token: &Address) -> i128 {
+    let mut pool: Map<(u64, Address), i128> = env
+        .storage()
+        .persistent()
+        .get(&FORFEIT_POOL_KEY)
+        .unwrap_or_else(|| Map::new(env));
+
+    let amount = pool.get((guild_id, token.clone())).unwrap_or(0);
+    if amount != 0 {
+        pool.remove((guild_id, token.clone()));
+        env.storage().persistent().set(&FORFEIT_POOL_KEY, &pool);
+    }
+    amount
+}
+
+/// When a member last created a proposal in a guild; cancelling the
+/// proposal does not clear it.
+pub fn get_last_proposal_at(env: &Env, guild_id: u64, proposer: &Address) -> Option<u64> {
+    env.storage()
+        .persistent()
+        .get(&(LAST_PROPOSAL_KEY, guild_id, proposer.clone()))
+}
+
+pub fn set_last_proposal_at(env: &Env, guild_id: u64, proposer: &Address, timestamp: u64) {
+    env.storage()
+        .persistent()
+        .set(&(LAST_PROPOSAL_KEY, guild_id, proposer.clone()), &timestamp);
 }
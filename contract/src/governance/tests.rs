@@ -2,14 +2,16 @@
 mod tests {
     use crate::governance::{proposals, storage};
     use crate::governance::types::{
-        ExecutionPayload, GovernanceConfig, Proposal, ProposalStatus, ProposalType, Vote,
-        VoteDecision,
+        ExecutionPayload, GovernanceConfig, GovernanceConfigOverride, LegacyGovernanceConfig,
+        Proposal, ProposalStatus, ProposalType, ProposalTypeRule, Vote, VoteDecision,
     };
     use crate::guild::types::Role;
+    use crate::reputation::ContributionType;
     use crate::StellarGuildsContract;
     use crate::StellarGuildsContractClient;
     use soroban_sdk::testutils::{Address as _, Ledger, LedgerInfo};
-    use soroban_sdk::{Address, Env, String};
+    use soroban_sdk::token::{StellarAssetClient, TokenClient};
+    use soroban_sdk::{symbol_short, vec, Address, Env, Map, String, Vec};
 
     fn setup_env() -> Env {
         let env = Env::default();
@@ -81,6 +83,7 @@ mod tests {
             &ProposalType::GeneralDecision,
             &String::from_str(&env, "Test Proposal"),
             &String::from_str(&env, "Description"),
+            &None,
         );
 
         assert_eq!(proposal_id, 1);
@@ -111,6 +114,7 @@ mod tests {
             &ProposalType::GeneralDecision,
             &String::from_str(&env, "Test Proposal"),
             &String::from_str(&env, "Description"),
+            &None,
         );
 
         client.vote(&proposal_id, &owner, &VoteDecision::For);
@@ -158,6 +162,7 @@ mod tests {
             &ProposalType::GeneralDecision,
             &String::from_str(&env, "Delegation Proposal"),
             &String::from_str(&env, "Delegation"),
+            &None,
         );
 
         client.delegate_vote(&guild_id, &member, &admin);
@@ -201,6 +206,7 @@ mod tests {
             &ProposalType::GeneralDecision,
             &String::from_str(&env, "Low Quorum"),
             &String::from_str(&env, "Low quorum"),
+            &None,
         );
 
         client.vote(&proposal_id, &contributor, &VoteDecision::For);
@@ -245,6 +251,7 @@ mod tests {
                 execution_payload: ExecutionPayload::GeneralDecision,
                 passed_at: None,
                 executed_at: None,
+                tags: Vec::new(&env),
             };
 
             storage::store_proposal(&env, &proposal);
@@ -271,18 +278,56 @@ mod tests {
             storage::remove_delegation(&env, guild_id, &voter);
             assert_eq!(storage::get_delegate(&env, guild_id, &voter), None);
 
-            assert_eq!(storage::get_config(&env, guild_id), GovernanceConfig::default());
+            assert_eq!(storage::get_config(&env, guild_id), GovernanceConfig::default(&env));
             let updated = GovernanceConfig {
                 quorum_percentage: 45,
                 approval_threshold: 70,
                 voting_period_days: 5,
                 min_proposer_reputation: 2,
+                min_reputation_to_propose: 0,
+                max_active_proposals: 0,
+                min_vote_weight: 0,
+                max_vote_weight: 0,
+                proposal_cooldown_seconds: 0,
+                type_rules: Vec::new(&env),
             };
             storage::set_config(&env, guild_id, &updated);
             assert_eq!(storage::get_config(&env, guild_id), updated);
         });
     }
 
+    #[test]
+    fn test_config_stored_under_legacy_key_still_applies() {
+        let env = setup_env();
+        env.mock_all_auths();
+        let contract_id = register_and_init_contract(&env);
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+        let owner = Address::generate(&env);
+        let guild_id = setup_guild(&client, &env, &owner);
+
+        env.as_contract(&contract_id, || {
+            let mut legacy: Map<u64, LegacyGovernanceConfig> = Map::new(&env);
+            legacy.set(
+                guild_id,
+                LegacyGovernanceConfig {
+                    quorum_percentage: 40,
+                    approval_threshold: 60,
+                    voting_period_days: 3,
+                    min_proposer_reputation: 1,
+                },
+            );
+            env.storage().persistent().set(&symbol_short!("g_conf"), &legacy);
+
+            let config = storage::get_config(&env, guild_id);
+            let default = GovernanceConfig::default(&env);
+            assert_eq!(config.quorum_percentage, 40);
+            assert_eq!(config.approval_threshold, 60);
+            assert_eq!(config.voting_period_days, 3);
+            assert_eq!(config.min_proposer_reputation, 1);
+            assert_eq!(config.max_active_proposals, default.max_active_proposals);
+        });
+    }
+
     #[test]
     fn test_cancel_proposal_updates_active_list_and_config() {
         let env = setup_env();
@@ -299,6 +344,7 @@ mod tests {
             &ProposalType::GeneralDecision,
             &String::from_str(&env, "A"),
             &String::from_str(&env, "first"),
+            &None,
         );
         let proposal_b = client.create_proposal(
             &guild_id,
@@ -306,6 +352,7 @@ mod tests {
             &ProposalType::GeneralDecision,
             &String::from_str(&env, "B"),
             &String::from_str(&env, "second"),
+            &None,
         );
 
         assert_eq!(client.get_active_proposals(&guild_id).len(), 2);
@@ -319,6 +366,12 @@ mod tests {
             approval_threshold: 66,
             voting_period_days: 10,
             min_proposer_reputation: 1,
+            min_reputation_to_propose: 0,
+            max_active_proposals: 0,
+            min_vote_weight: 0,
+            max_vote_weight: 0,
+            proposal_cooldown_seconds: 0,
+            type_rules: Vec::new(&env),
         };
         assert!(client.update_governance_config(&guild_id, &owner, &new_cfg));
 
@@ -327,6 +380,35 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_active_count_missing_for_existing_proposals_is_scanned() {
+        let env = setup_env();
+        let owner = Address::generate(&env);
+        env.mock_all_auths();
+
+        let contract_id = register_and_init_contract(&env);
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+        let (guild_id, admin, _member, _contributor) =
+            setup_guild_with_members(&env, &client, &owner);
+        let title = String::from_str(&env, "Before");
+        let desc = String::from_str(&env, "created before the count existed");
+        let general = ProposalType::GeneralDecision;
+
+        client.create_proposal(&guild_id, &owner, &general, &title, &desc, &None);
+        let second = client.create_proposal(&guild_id, &admin, &general, &title, &desc, &None);
+
+        // proposals stored before the count was kept
+        env.as_contract(&contract_id, || {
+            env.storage().persistent().remove(&(symbol_short!("g_pactv"), guild_id));
+            assert_eq!(storage::get_active_proposal_count(&env, guild_id), 2);
+        });
+
+        assert!(client.cancel_proposal(&second, &owner));
+        env.as_contract(&contract_id, || {
+            assert_eq!(storage::get_active_proposal_count(&env, guild_id), 1);
+        });
+    }
+
     #[test]
     #[should_panic(expected = "execution payload does not match proposal type")]
     fn test_create_proposal_rejects_mismatched_payload() {
@@ -347,6 +429,7 @@ mod tests {
                 String::from_str(&env, "Bad payload"),
                 String::from_str(&env, "mismatch"),
                 ExecutionPayload::GeneralDecision,
+                Vec::new(&env),
             );
         });
     }
@@ -370,7 +453,987 @@ mod tests {
                 approval_threshold: 60,
                 voting_period_days: 7,
                 min_proposer_reputation: 0,
+                min_reputation_to_propose: 0,
+                max_active_proposals: 0,
+                min_vote_weight: 0,
+                max_vote_weight: 0,
+                proposal_cooldown_seconds: 0,
+                type_rules: Vec::new(&env),
             },
         );
     }
+
+    #[test]
+    fn test_governance_stats_empty_guild_returns_zeros() {
+        let env = setup_env();
+        let owner = Address::generate(&env);
+        env.mock_all_auths();
+
+        let contract_id = register_and_init_contract(&env);
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+        let guild_id = setup_guild(&client, &env, &owner);
+
+        let stats = client.get_governance_stats(&guild_id);
+        assert_eq!(stats.guild_id, guild_id);
+        assert_eq!(stats.total_proposals, 0);
+        assert_eq!(stats.passed_count, 0);
+        assert_eq!(stats.failed_count, 0);
+        assert_eq!(stats.cancelled_count, 0);
+        assert_eq!(stats.active_count, 0);
+        assert_eq!(stats.average_turnout_percentage, 0);
+    }
+
+    #[test]
+    fn test_governance_stats_counts_outcomes_and_turnout() {
+        let env = setup_env();
+        let owner = Address::generate(&env);
+
+        set_ledger_timestamp(&env, 1000);
+        env.mock_all_auths();
+
+        let contract_id = register_and_init_contract(&env);
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+        let (guild_id, admin, member, contributor) =
+            setup_guild_with_members(&env, &client, &owner);
+
+        let title = String::from_str(&env, "Stats");
+        let desc = String::from_str(&env, "stats");
+        let passed = client.create_proposal(
+            &guild_id,
+            &owner,
+            &ProposalType::GeneralDecision,
+            &title,
+            &desc,
+            &None,
+        );
+        let rejected = client.create_proposal(
+            &guild_id,
+            &owner,
+            &ProposalType::GeneralDecision,
+            &title,
+            &desc,
+            &None,
+        );
+        let cancelled = client.create_proposal(
+            &guild_id,
+            &owner,
+            &ProposalType::GeneralDecision,
+            &title,
+            &desc,
+            &None,
+        );
+
+        // 4 of 4 members vote on the first, 1 of 4 on the second
+        client.vote(&passed, &owner, &VoteDecision::For);
+        client.vote(&passed, &admin, &VoteDecision::For);
+        client.vote(&passed, &member, &VoteDecision::For);
+        client.vote(&passed, &contributor, &VoteDecision::Against);
+        client.vote(&rejected, &contributor, &VoteDecision::For);
+        client.cancel_proposal(&cancelled, &owner);
+
+        let end = client.get_proposal(&passed).voting_end;
+        set_ledger_timestamp(&env, end + 1);
+        client.finalize_proposal(&passed);
+        client.finalize_proposal(&rejected);
+
+        let active = client.create_proposal(
+            &guild_id,
+            &owner,
+            &ProposalType::GeneralDecision,
+            &title,
+            &desc,
+            &None,
+        );
+        assert_eq!(client.get_proposal(&active).status, ProposalStatus::Active);
+
+        let stats = client.get_governance_stats(&guild_id);
+        assert_eq!(stats.total_proposals, 4);
+        assert_eq!(stats.passed_count, 1);
+        assert_eq!(stats.failed_count, 1);
+        assert_eq!(stats.cancelled_count, 1);
+        assert_eq!(stats.active_count, 1);
+        // 5 votes over 4 proposals * 4 members
+        assert_eq!(stats.average_turnout_percentage, 31);
+
+        // Only the latest proposals are sampled for turnout
+        for _ in 0..crate::governance::proposals::MAX_TURNOUT_SAMPLE {
+            client.create_proposal(
+                &guild_id,
+                &owner,
+                &ProposalType::GeneralDecision,
+                &title,
+                &desc,
+                &None,
+            );
+        }
+        let stats = client.get_governance_stats(&guild_id);
+        assert_eq!(stats.total_proposals, 4 + crate::governance::proposals::MAX_TURNOUT_SAMPLE);
+        assert_eq!(stats.average_turnout_percentage, 0);
+    }
+
+    #[test]
+    fn test_partial_override_merges_with_platform_default() {
+        let env = setup_env();
+        let owner = Address::generate(&env);
+        let platform_admin = Address::generate(&env);
+        env.mock_all_auths();
+
+        let contract_id = env.register_contract(None, StellarGuildsContract);
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+        client.initialize(&platform_admin);
+        let guild_id = setup_guild(&client, &env, &owner);
+
+        assert_eq!(client.get_governance_config(&guild_id), GovernanceConfig::default(&env));
+
+        let platform_default = GovernanceConfig {
+            quorum_percentage: 50,
+            approval_threshold: 55,
+            voting_period_days: 3,
+            min_proposer_reputation: 0,
+            min_reputation_to_propose: 0,
+            max_active_proposals: 0,
+            min_vote_weight: 0,
+            max_vote_weight: 0,
+            proposal_cooldown_seconds: 0,
+            type_rules: Vec::new(&env),
+        };
+        assert!(client.set_default_governance_config(&platform_admin, &platform_default));
+        assert_eq!(client.get_governance_config(&guild_id), platform_default);
+
+        let partial = GovernanceConfigOverride {
+            quorum_percentage: None,
+            approval_threshold: Some(75),
+            voting_period_days: None,
+            min_proposer_reputation: None,
+            min_reputation_to_propose: None,
+            max_active_proposals: None,
+            min_vote_weight: None,
+            max_vote_weight: None,
+            proposal_cooldown_seconds: None,
+            type_rules: None,
+        };
+        assert!(client.set_governance_config_override(&guild_id, &owner, &partial));
+        let effective = client.get_governance_config(&guild_id);
+        assert_eq!(effective.quorum_percentage, 50);
+        assert_eq!(effective.approval_threshold, 75);
+        assert_eq!(effective.voting_period_days, 3);
+
+        // Inherited fields follow later default changes; overridden ones don't
+        let new_default = GovernanceConfig {
+            quorum_percentage: 20,
+            approval_threshold: 51,
+            voting_period_days: 14,
+            min_proposer_reputation: 0,
+            min_reputation_to_propose: 0,
+            max_active_proposals: 0,
+            min_vote_weight: 0,
+            max_vote_weight: 0,
+            proposal_cooldown_seconds: 0,
+            type_rules: Vec::new(&env),
+        };
+        client.set_default_governance_config(&platform_admin, &new_default);
+        let effective = client.get_governance_config(&guild_id);
+        assert_eq!(effective.quorum_percentage, 20);
+        assert_eq!(effective.approval_threshold, 75);
+        assert_eq!(effective.voting_period_days, 14);
+
+        // Proposals created now use the effective voting period
+        set_ledger_timestamp(&env, 1_000);
+        let proposal_id = client.create_proposal(
+            &guild_id,
+            &owner,
+            &ProposalType::GeneralDecision,
+            &String::from_str(&env, "P"),
+            &String::from_str(&env, "uses merged config"),
+            &None,
+        );
+        let proposal = client.get_proposal(&proposal_id);
+        assert_eq!(proposal.voting_end, 1_000 + 14 * 86_400);
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid quorum percentage")]
+    fn test_override_rejected_when_merged_config_invalid() {
+        let env = setup_env();
+        let owner = Address::generate(&env);
+        env.mock_all_auths();
+
+        let contract_id = register_and_init_contract(&env);
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+        let guild_id = setup_guild(&client, &env, &owner);
+
+        let partial = GovernanceConfigOverride {
+            quorum_percentage: Some(0),
+            approval_threshold: None,
+            voting_period_days: None,
+            min_proposer_reputation: None,
+            min_reputation_to_propose: None,
+            max_active_proposals: None,
+            min_vote_weight: None,
+            max_vote_weight: None,
+            proposal_cooldown_seconds: None,
+            type_rules: None,
+        };
+        client.set_governance_config_override(&guild_id, &owner, &partial);
+    }
+
+    #[test]
+    fn test_batch_vote_skips_closed_and_already_voted() {
+        let env = setup_env();
+        let owner = Address::generate(&env);
+
+        set_ledger_timestamp(&env, 1000);
+        env.mock_all_auths();
+
+        let contract_id = register_and_init_contract(&env);
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+        let (guild_id, admin, _member, _contributor) =
+            setup_guild_with_members(&env, &client, &owner);
+
+        let title = String::from_str(&env, "Batch");
+        let desc = String::from_str(&env, "batch");
+        let expired = client.create_proposal(
+            &guild_id,
+            &owner,
+            &ProposalType::GeneralDecision,
+            &title,
+            &desc,
+            &None,
+        );
+        let end = client.get_proposal(&expired).voting_end;
+        set_ledger_timestamp(&env, end + 1);
+
+        let voted = client.create_proposal(
+            &guild_id,
+            &owner,
+            &ProposalType::GeneralDecision,
+            &title,
+            &desc,
+            &None,
+        );
+        let open = client.create_proposal(
+            &guild_id,
+            &owner,
+            &ProposalType::GeneralDecision,
+            &title,
+            &desc,
+            &None,
+        );
+        client.vote(&voted, &admin, &VoteDecision::For);
+
+        let votes = vec![
+            &env,
+            (expired, VoteDecision::For),
+            (voted, VoteDecision::Against),
+            (open, VoteDecision::For),
+        ];
+        let results = client.batch_vote(&votes, &admin);
+        assert_eq!(results, vec![&env, false, false, true]);
+
+        let (prior, cast) = env.as_contract(&contract_id, || {
+            (storage::get_vote(&env, voted, &admin), storage::get_vote(&env, open, &admin))
+        });
+        assert_eq!(prior.unwrap().decision, VoteDecision::For);
+        assert_eq!(cast.unwrap().decision, VoteDecision::For);
+        assert!(env
+            .as_contract(&contract_id, || storage::get_vote(&env, expired, &admin))
+            .is_none());
+
+        let outsider = Address::generate(&env);
+        let results = client.batch_vote(&vec![&env, (open, VoteDecision::For)], &outsider);
+        assert_eq!(results, vec![&env, false]);
+
+        let mut oversized = Vec::new(&env);
+        for _ in 0..=crate::governance::voting::MAX_BATCH_VOTES {
+            oversized.push_back((open, VoteDecision::Against));
+        }
+        assert!(client.try_batch_vote(&oversized, &owner).is_err());
+    }
+
+    #[test]
+    fn test_min_reputation_to_propose_gates_non_owners() {
+        let env = setup_env();
+        let owner = Address::generate(&env);
+
+        set_ledger_timestamp(&env, 1000);
+        env.mock_all_auths();
+
+        let contract_id = register_and_init_contract(&env);
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+        let (guild_id, admin, _member, _contributor) =
+            setup_guild_with_members(&env, &client, &owner);
+
+        let title = String::from_str(&env, "Gated");
+        let desc = String::from_str(&env, "gated");
+
+        // zero threshold keeps proposing open to every member
+        assert!(client
+            .try_create_proposal(
+                &guild_id,
+                &admin,
+                &ProposalType::GeneralDecision,
+                &title,
+                &desc,
+                &None,
+            )
+            .is_ok());
+
+        let partial = GovernanceConfigOverride {
+            quorum_percentage: None,
+            approval_threshold: None,
+            voting_period_days: None,
+            min_proposer_reputation: None,
+            min_reputation_to_propose: Some(100),
+            max_active_proposals: None,
+            min_vote_weight: None,
+            max_vote_weight: None,
+            proposal_cooldown_seconds: None,
+            type_rules: None,
+        };
+        client.set_governance_config_override(&guild_id, &owner, &partial);
+
+        assert!(client
+            .try_create_proposal(
+                &guild_id,
+                &admin,
+                &ProposalType::GeneralDecision,
+                &title,
+                &desc,
+                &None,
+            )
+            .is_err());
+        // the owner bypasses the requirement
+        assert!(client
+            .try_create_proposal(
+                &guild_id,
+                &owner,
+                &ProposalType::GeneralDecision,
+                &title,
+                &desc,
+                &None,
+            )
+            .is_ok());
+
+        client.record_contribution(&guild_id, &admin, &ContributionType::BountyCompleted, &1);
+        assert!(client
+            .try_create_proposal(
+                &guild_id,
+                &admin,
+                &ProposalType::GeneralDecision,
+                &title,
+                &desc,
+                &None,
+            )
+            .is_ok());
+    }
+
+    #[test]
+    fn test_max_active_proposals_caps_open_proposals() {
+        let env = setup_env();
+        let owner = Address::generate(&env);
+
+        set_ledger_timestamp(&env, 1000);
+        env.mock_all_auths();
+
+        let contract_id = register_and_init_contract(&env);
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+        let (guild_id, admin, _member, _contributor) =
+            setup_guild_with_members(&env, &client, &owner);
+
+        let cap = GovernanceConfigOverride {
+            quorum_percentage: None,
+            approval_threshold: None,
+            voting_period_days: None,
+            min_proposer_reputation: None,
+            min_reputation_to_propose: None,
+            max_active_proposals: Some(2),
+            min_vote_weight: None,
+            max_vote_weight: None,
+            proposal_cooldown_seconds: None,
+            type_rules: None,
+        };
+        client.set_governance_config_override(&guild_id, &owner, &cap);
+
+        let title = String::from_str(&env, "Capped");
+        let desc = String::from_str(&env, "capped");
+        let general = ProposalType::GeneralDecision;
+        let first = client.create_proposal(&guild_id, &admin, &general, &title, &desc, &None);
+        let second = client.create_proposal(&guild_id, &owner, &general, &title, &desc, &None);
+        assert!(client
+            .try_create_proposal(&guild_id, &admin, &general, &title, &desc, &None)
+            .is_err());
+
+        // cancelling frees a slot
+        client.cancel_proposal(&first, &admin);
+        client.create_proposal(&guild_id, &admin, &general, &title, &desc, &None);
+        assert!(client
+            .try_create_proposal(&guild_id, &admin, &general, &title, &desc, &None)
+            .is_err());
+
+        // so does finalizing once the voting period runs out
+        set_ledger_timestamp(&env, 1000 + 7 * 24 * 60 * 60);
+        assert!(client
+            .try_create_proposal(&guild_id, &admin, &general, &title, &desc, &None)
+            .is_err());
+        client.finalize_proposal(&second);
+        assert!(client
+            .try_create_proposal(&guild_id, &admin, &general, &title, &desc, &None)
+            .is_ok());
+    }
+
+    fn setup_deposit_token(env: &Env, holders: &[&Address]) -> Address {
+        let token = env
+            .register_stellar_asset_contract_v2(Address::generate(env))
+            .address();
+        let admin_client = StellarAssetClient::new(env, &token);
+        for holder in holders.iter() {
+            admin_client.mint(holder, &1_000);
+        }
+        token
+    }
+
+    #[test]
+    fn test_forfeited_deposit_held_when_guild_has_no_treasury() {
+        let env = setup_env();
+        let owner = Address::generate(&env);
+
+        set_ledger_timestamp(&env, 1000);
+        env.mock_all_auths();
+
+        let contract_id = register_and_init_contract(&env);
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+        let (guild_id, admin, _member, _contributor) =
+            setup_guild_with_members(&env, &client, &owner);
+
+        let token = setup_deposit_token(&env, &[&admin]);
+        let token_client = TokenClient::new(&env, &token);
+        client.set_proposal_deposit(&guild_id, &owner, &token, &100);
+
+        let proposal_id = client.create_proposal(
+            &guild_id,
+            &admin,
+            &ProposalType::GeneralDecision,
+            &String::from_str(&env, "Spam"),
+            &String::from_str(&env, "nobody votes"),
+            &None,
+        );
+        assert_eq!(token_client.balance(&admin), 900);
+
+        let end = client.get_proposal(&proposal_id).voting_end;
+        set_ledger_timestamp(&env, end + 1);
+        // a failed proposal can no longer be cancelled to dodge forfeiture
+        assert!(client.try_cancel_proposal(&proposal_id, &admin).is_err());
+        assert_eq!(client.finalize_proposal(&proposal_id), ProposalStatus::Rejected);
+
+        assert_eq!(token_client.balance(&admin), 900);
+        assert_eq!(client.get_forfeited_deposits(&guild_id, &token), 100);
+        assert_eq!(token_client.balance(&contract_id), 100);
+
+        // only the owner may sweep the pool
+        assert!(client
+            .try_withdraw_forfeited_deposits(&guild_id, &admin, &token, &admin)
+            .is_err());
+        let recipient = Address::generate(&env);
+        assert_eq!(
+            client.withdraw_forfeited_deposits(&guild_id, &owner, &token, &recipient),
+            100
+        );
+        assert_eq!(token_client.balance(&recipient), 100);
+        assert_eq!(client.get_forfeited_deposits(&guild_id, &token), 0);
+    }
+
+    #[test]
+    fn test_deposit_refunded_on_quorum_and_forfeited_to_treasury() {
+        let env = setup_env();
+        let owner = Address::generate(&env);
+
+        set_ledger_timestamp(&env, 1000);
+        env.mock_all_auths();
+
+        let contract_id = register_and_init_contract(&env);
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+        let (guild_id, admin, _member, _contributor) =
+            setup_guild_with_members(&env, &client, &owner);
+        let treasury_id =
+            client.initialize_treasury(&guild_id, &Vec::from_array(&env, [owner.clone()]), &1u32);
+
+        let token = setup_deposit_token(&env, &[&admin]);
+        let token_client = TokenClient::new(&env, &token);
+        client.set_proposal_deposit(&guild_id, &owner, &token, &100);
+
+        let title = String::from_str(&env, "Deposit");
+        let desc = String::from_str(&env, "deposit");
+        let passed = client.create_proposal(
+            &guild_id,
+            &admin,
+            &ProposalType::GeneralDecision,
+            &title,
+            &desc,
+            &None,
+        );
+        let ignored = client.create_proposal(
+            &guild_id,
+            &admin,
+            &ProposalType::GeneralDecision,
+            &title,
+            &desc,
+            &None,
+        );
+        assert_eq!(token_client.balance(&admin), 800);
+
+        client.vote(&passed, &owner, &VoteDecision::For);
+        client.vote(&passed, &admin, &VoteDecision::For);
+
+        let end = client.get_proposal(&passed).voting_end;
+        set_ledger_timestamp(&env, end + 1);
+        assert_eq!(client.finalize_proposal(&passed), ProposalStatus::Passed);
+        assert_eq!(client.finalize_proposal(&ignored), ProposalStatus::Rejected);
+
+        // quorum reached: refunded; quorum missed: credited to the treasury
+        assert_eq!(token_client.balance(&admin), 900);
+        assert_eq!(client.get_treasury_balance(&treasury_id, &Some(token.clone())), 100);
+        assert_eq!(client.get_forfeited_deposits(&guild_id, &token), 0);
+    }
+
+    #[test]
+    fn test_proposal_type_rules_override_general_config() {
+        let env = setup_env();
+        let owner = Address::generate(&env);
+
+        set_ledger_timestamp(&env, 1000);
+        env.mock_all_auths();
+
+        let contract_id = register_and_init_contract(&env);
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+        let (guild_id, admin, member, contributor) =
+            setup_guild_with_members(&env, &client, &owner);
+
+        // Without type rules every type uses the general config
+        let spend_rule = client.get_proposal_type_rule(&guild_id, &ProposalType::TreasurySpend);
+        assert_eq!(spend_rule.quorum_percentage, 30);
+        assert_eq!(spend_rule.approval_threshold, 60);
+        let general_rule = client.get_proposal_type_rule(&guild_id, &ProposalType::AddMember);
+        assert_eq!(general_rule.quorum_percentage, 30);
+        assert_eq!(general_rule.approval_threshold, 60);
+
+        let decision_rule = ProposalTypeRule {
+            proposal_type: ProposalType::GeneralDecision,
+            quorum_percentage: 30,
+            approval_threshold: 90,
+            voting_period_days: 1,
+        };
+        let mut config = GovernanceConfig::default(&env);
+        config.type_rules = vec![&env, decision_rule.clone(), decision_rule.clone()];
+        assert!(client.try_update_governance_config(&guild_id, &owner, &config).is_err());
+
+        config.type_rules = vec![&env, decision_rule];
+        client.update_governance_config(&guild_id, &owner, &config);
+
+        let title = String::from_str(&env, "Rule");
+        let desc = String::from_str(&env, "Desc");
+        let decision = client.create_proposal(
+            &guild_id, &owner, &ProposalType::GeneralDecision, &title, &desc,
+            &None,
+        );
+        assert_eq!(client.get_proposal(&decision).voting_end, 1000 + 86400);
+        // Types without a rule keep the general config
+        let spend_rule = client.get_proposal_type_rule(&guild_id, &ProposalType::TreasurySpend);
+        assert_eq!(spend_rule.quorum_percentage, 30);
+        assert_eq!(spend_rule.voting_period_days, 7);
+
+        // 15 of 17 counted weight is ~88%: enough generally, short of the 90% rule
+        client.vote(&decision, &owner, &VoteDecision::For);
+        client.vote(&decision, &admin, &VoteDecision::For);
+        client.vote(&decision, &member, &VoteDecision::Against);
+        client.vote(&decision, &contributor, &VoteDecision::Abstain);
+
+        set_ledger_timestamp(&env, 1000 + 86400 + 1);
+        assert_eq!(client.finalize_proposal(&decision), ProposalStatus::Rejected);
+    }
+
+    #[test]
+    fn test_proposal_tags_filter_and_validation() {
+        let env = setup_env();
+        let owner = Address::generate(&env);
+        env.mock_all_auths();
+
+        let contract_id = register_and_init_contract(&env);
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+        let guild_id = setup_guild(&client, &env, &owner);
+
+        let treasury = String::from_str(&env, "treasury");
+        let membership = String::from_str(&env, "membership");
+        let title = String::from_str(&env, "Tagged");
+        let desc = String::from_str(&env, "tagged proposal");
+        let general = ProposalType::GeneralDecision;
+
+        let spend = client.create_proposal(
+            &guild_id,
+            &owner,
+            &general,
+            &title,
+            &desc,
+            &Some(vec![&env, treasury.clone()]),
+        );
+        let both = client.create_proposal_with_payload(
+            &guild_id,
+            &owner,
+            &general,
+            &title,
+            &desc,
+            &ExecutionPayload::GeneralDecision,
+            &vec![&env, membership.clone(), treasury.clone()],
+        );
+        let untagged = client.create_proposal(&guild_id, &owner, &general, &title, &desc, &None);
+        assert_eq!(client.get_proposal(&untagged).tags.len(), 0);
+
+        let tagged = client.get_proposals_by_tag(&guild_id, &treasury);
+        assert_eq!(tagged.len(), 2);
+        assert_eq!(tagged.get(0).unwrap().id, spend);
+        assert_eq!(tagged.get(1).unwrap().id, both);
+        assert_eq!(client.get_proposals_by_tag(&guild_id, &membership).len(), 1);
+        let other_guild = guild_id + 1;
+        assert_eq!(client.get_proposals_by_tag(&other_guild, &treasury).len(), 0);
+
+        // Tags are bounded in count and length, and must be distinct
+        let mut too_many = Vec::new(&env);
+        for name in ["a", "b", "c", "d", "e", "f"] {
+            too_many.push_back(String::from_str(&env, name));
+        }
+        let too_long = String::from_str(&env, "abcdefghijklmnopqrstuvwxyz0123456");
+        for tags in [
+            too_many,
+            vec![&env, String::from_str(&env, "")],
+            vec![&env, too_long],
+            vec![&env, treasury.clone(), treasury.clone()],
+        ] {
+            assert!(client
+                .try_create_proposal_with_payload(
+                    &guild_id,
+                    &owner,
+                    &general,
+                    &title,
+                    &desc,
+                    &ExecutionPayload::GeneralDecision,
+                    &tags,
+                )
+                .is_err());
+        }
+    }
+
+    #[test]
+    fn test_create_proposal_with_payload_checks_type() {
+        let env = setup_env();
+        let owner = Address::generate(&env);
+        env.mock_all_auths();
+
+        let contract_id = register_and_init_contract(&env);
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+        let guild_id = setup_guild(&client, &env, &owner);
+
+        let title = String::from_str(&env, "Spend");
+        let desc = String::from_str(&env, "fund the audit");
+        let id = client.create_proposal_with_payload(
+            &guild_id,
+            &owner,
+            &ProposalType::TreasurySpend,
+            &title,
+            &desc,
+            &ExecutionPayload::TreasurySpend,
+            &vec![&env, String::from_str(&env, "audit")],
+        );
+        let proposal = client.get_proposal(&id);
+        assert_eq!(proposal.proposal_type, ProposalType::TreasurySpend);
+        assert_eq!(proposal.execution_payload, ExecutionPayload::TreasurySpend);
+        let audit = client.get_proposals_by_tag(&guild_id, &String::from_str(&env, "audit"));
+        assert_eq!(audit.len(), 1);
+        assert_eq!(audit.get(0).unwrap().id, id);
+
+        assert!(client
+            .try_create_proposal_with_payload(
+                &guild_id,
+                &owner,
+                &ProposalType::GeneralDecision,
+                &title,
+                &desc,
+                &ExecutionPayload::TreasurySpend,
+                &Vec::new(&env),
+            )
+            .is_err());
+        assert!(client
+            .try_create_proposal_with_payload(
+                &guild_id,
+                &owner,
+                &ProposalType::TreasurySpend,
+                &title,
+                &desc,
+                &ExecutionPayload::RuleChange,
+                &Vec::new(&env),
+            )
+            .is_err());
+    }
+
+    #[test]
+    fn test_vote_weight_floor_and_cap() {
+        let env = setup_env();
+        let owner = Address::generate(&env);
+
+        set_ledger_timestamp(&env, 1000);
+        env.mock_all_auths();
+
+        let contract_id = register_and_init_contract(&env);
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+        let (guild_id, admin, member, contributor) =
+            setup_guild_with_members(&env, &client, &owner);
+
+        let bounds = GovernanceConfigOverride {
+            quorum_percentage: None,
+            approval_threshold: None,
+            voting_period_days: None,
+            min_proposer_reputation: None,
+            min_reputation_to_propose: None,
+            max_active_proposals: None,
+            min_vote_weight: Some(6),
+            max_vote_weight: Some(3),
+            proposal_cooldown_seconds: None,
+            type_rules: None,
+        };
+        // An inverted range is rejected
+        assert!(client
+            .try_set_governance_config_override(&guild_id, &owner, &bounds)
+            .is_err());
+
+        let bounds = GovernanceConfigOverride {
+            min_vote_weight: Some(3),
+            max_vote_weight: Some(6),
+            ..bounds
+        };
+        client.set_governance_config_override(&guild_id, &owner, &bounds);
+
+        let proposal_id = client.create_proposal(
+            &guild_id,
+            &owner,
+            &ProposalType::GeneralDecision,
+            &String::from_str(&env, "Bounded"),
+            &String::from_str(&env, "bounded weights"),
+            &None,
+        );
+
+        client.vote(&proposal_id, &owner, &VoteDecision::For);
+        client.vote(&proposal_id, &admin, &VoteDecision::For);
+        client.vote(&proposal_id, &member, &VoteDecision::Against);
+        client.vote(&proposal_id, &contributor, &VoteDecision::Abstain);
+
+        // non-members still cannot vote at all
+        let outsider = Address::generate(&env);
+        assert!(client
+            .try_vote(&proposal_id, &outsider, &VoteDecision::Against)
+            .is_err());
+
+        let end = client.get_proposal(&proposal_id).voting_end;
+        set_ledger_timestamp(&env, end + 1);
+        client.finalize_proposal(&proposal_id);
+
+        // owner 10 is capped at 6; member 2 and contributor 1 are raised to 3
+        let proposal = client.get_proposal(&proposal_id);
+        assert_eq!(proposal.votes_for, 11);
+        assert_eq!(proposal.votes_against, 3);
+        assert_eq!(proposal.votes_abstain, 3);
+    }
+
+    #[test]
+    fn test_subscription_plan_update_executes_only_for_own_guild_plans() {
+        use crate::governance::types::SubscriptionPlanUpdateData;
+        use crate::subscription::types::{BillingCycle, MembershipTier};
+
+        let env = setup_env();
+        let owner = Address::generate(&env);
+
+        set_ledger_timestamp(&env, 1000);
+        env.mock_all_auths();
+
+        let contract_id = register_and_init_contract(&env);
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+        let (guild_id, admin, member, _contributor) =
+            setup_guild_with_members(&env, &client, &owner);
+        let other_owner = Address::generate(&env);
+        let other_guild = setup_guild(&client, &env, &other_owner);
+
+        let new_plan = |guild: u64, creator: &Address| {
+            client.create_subscription_plan(
+                &guild,
+                &String::from_str(&env, "Pro"),
+                &String::from_str(&env, "Pro plan"),
+                &MembershipTier::Premium,
+                &1_000i128,
+                &None,
+                &BillingCycle::Monthly,
+                &Vec::new(&env),
+                creator,
+            )
+        };
+        let own_plan = new_plan(guild_id, &owner);
+        let foreign_plan = new_plan(other_guild, &other_owner);
+
+        let payload = |plan_id: u64, new_price: i128| {
+            ExecutionPayload::SubscriptionPlanUpdate(SubscriptionPlanUpdateData {
+                plan_id,
+                new_price,
+                active: false,
+            })
+        };
+        let title = String::from_str(&env, "Reprice Pro");
+        let desc = String::from_str(&env, "Raise price and close signups");
+
+        // Another guild's plan, a missing plan and a bad price are refused up front
+        for bad in [payload(foreign_plan, 2_000), payload(99, 2_000), payload(own_plan, 0)] {
+            assert!(client
+                .try_create_proposal_with_payload(
+                    &guild_id,
+                    &owner,
+                    &ProposalType::SubscriptionPlanUpdate,
+                    &title,
+                    &desc,
+                    &bad,
+                    &Vec::new(&env),
+                )
+                .is_err());
+        }
+        assert!(client
+            .try_create_proposal_with_payload(
+                &guild_id,
+                &owner,
+                &ProposalType::GeneralDecision,
+                &title,
+                &desc,
+                &payload(own_plan, 2_000),
+                &Vec::new(&env),
+            )
+            .is_err());
+
+        let proposal_id = client.create_proposal_with_payload(
+            &guild_id,
+            &owner,
+            &ProposalType::SubscriptionPlanUpdate,
+            &title,
+            &desc,
+            &payload(own_plan, 2_000),
+            &Vec::new(&env),
+        );
+        client.vote(&proposal_id, &owner, &VoteDecision::For);
+        client.vote(&proposal_id, &admin, &VoteDecision::For);
+        client.vote(&proposal_id, &member, &VoteDecision::For);
+
+        set_ledger_timestamp(&env, client.get_proposal(&proposal_id).voting_end + 1);
+        assert!(client.execute_proposal(&proposal_id, &member));
+
+        let plan = client.get_subscription_plan(&own_plan);
+        assert_eq!(plan.price, 2_000);
+        assert!(!plan.is_active);
+        assert_eq!(client.get_subscription_plan(&foreign_plan).price, 1_000);
+        assert_eq!(
+            client.get_proposal(&proposal_id).status,
+            ProposalStatus::Executed
+        );
+    }
+
+    #[test]
+    fn test_proposal_cooldown_survives_cancellation() {
+        let env = setup_env();
+        let owner = Address::generate(&env);
+
+        set_ledger_timestamp(&env, 1000);
+        env.mock_all_auths();
+
+        let contract_id = register_and_init_contract(&env);
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+        let (guild_id, admin, _member, _contributor) =
+            setup_guild_with_members(&env, &client, &owner);
+
+        let cooldown = GovernanceConfigOverride {
+            quorum_percentage: None,
+            approval_threshold: None,
+            voting_period_days: None,
+            min_proposer_reputation: None,
+            min_reputation_to_propose: None,
+            max_active_proposals: None,
+            min_vote_weight: None,
+            max_vote_weight: None,
+            proposal_cooldown_seconds: Some(3600),
+            type_rules: None,
+        };
+        client.set_governance_config_override(&guild_id, &owner, &cooldown);
+        assert_eq!(
+            client
+                .get_governance_config(&guild_id)
+                .proposal_cooldown_seconds,
+            3600
+        );
+
+        let title = String::from_str(&env, "Cooldown");
+        let desc = String::from_str(&env, "cooldown");
+        let general = ProposalType::GeneralDecision;
+        let first = client.create_proposal(&guild_id, &admin, &general, &title, &desc, &None);
+        assert!(client
+            .try_create_proposal(&guild_id, &admin, &general, &title, &desc, &None)
+            .is_err());
+
+        // Cancelling does not hand the slot back early
+        client.cancel_proposal(&first, &admin);
+        assert!(client
+            .try_create_proposal(&guild_id, &admin, &general, &title, &desc, &None)
+            .is_err());
+
+        // The cooldown is per member
+        client.create_proposal(&guild_id, &owner, &general, &title, &desc, &None);
+
+        set_ledger_timestamp(&env, 1000 + 3600);
+        client.create_proposal(&guild_id, &admin, &general, &title, &desc, &None);
+    }
+
+    #[test]
+    fn test_delegation_reverse_index_follows_redelegation_and_undelegation() {
+        let env = setup_env();
+        let owner = Address::generate(&env);
+
+        set_ledger_timestamp(&env, 1000);
+        env.mock_all_auths();
+
+        let contract_id = register_and_init_contract(&env);
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+        let (guild_id, admin, member, contributor) =
+            setup_guild_with_members(&env, &client, &owner);
+
+        assert_eq!(client.get_delegate(&guild_id, &member), None);
+        assert_eq!(client.get_delegators(&guild_id, &admin).len(), 0);
+
+        client.delegate_vote(&guild_id, &member, &admin);
+        client.delegate_vote(&guild_id, &contributor, &admin);
+        assert_eq!(client.get_delegate(&guild_id, &member), Some(admin.clone()));
+        assert_eq!(
+            client.get_delegators(&guild_id, &admin),
+            vec![&env, member.clone(), contributor.clone()]
+        );
+
+        // Delegating again to the same address does not duplicate the entry
+        client.delegate_vote(&guild_id, &member, &admin);
+        assert_eq!(client.get_delegators(&guild_id, &admin).len(), 2);
+
+        // Re-delegating moves the delegator to the new target's list
+        client.delegate_vote(&guild_id, &member, &owner);
+        assert_eq!(
+            client.get_delegators(&guild_id, &admin),
+            vec![&env, contributor.clone()]
+        );
+        assert_eq!(
+            client.get_delegators(&guild_id, &owner),
+            vec![&env, member.clone()]
+        );
+
+        client.undelegate_vote(&guild_id, &contributor);
+        assert_eq!(client.get_delegate(&guild_id, &contributor), None);
+        assert_eq!(client.get_delegators(&guild_id, &admin).len(), 0);
+        assert_eq!(client.get_delegators(&guild_id, &owner).len(), 1);
+    }
 }
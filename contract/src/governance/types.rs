@@ -1,4 +1,4 @@
-﻿use soroban_sdk::{contracttype, Address, String};
+﻿use soroban_sdk::{contracttype, Address, Env, String, Vec};
 
 use crate::guild::types::Role;
 
@@ -10,6 +10,7 @@ pub enum ProposalType {
     RemoveMember,
     RuleChange,
     GeneralDecision,
+    SubscriptionPlanUpdate,
 }
 
 #[contracttype]
@@ -32,6 +33,17 @@ pub enum VoteDecision {
     Abstain,
 }
 
+/// Voting rules for one proposal type, replacing the general quorum,
+/// approval threshold and voting period of the config it belongs to.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProposalTypeRule {
+    pub proposal_type: ProposalType,
+    pub quorum_percentage: u32,
+    pub approval_threshold: u32,
+    pub voting_period_days: u32,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct GovernanceConfig {
@@ -39,15 +51,152 @@ pub struct GovernanceConfig {
     pub approval_threshold: u32,
     pub voting_period_days: u32,
     pub min_proposer_reputation: u32,
+    /// Minimum decayed reputation score required to create proposals.
+    /// Zero leaves proposing open; the guild owner is always exempt.
+    pub min_reputation_to_propose: u32,
+    /// Most proposals that may be active at once, until finalized or
+    /// cancelled (0 = unlimited)
+    pub max_active_proposals: u32,
+    /// Least vote weight any voting member carries, whatever their
+    /// reputation (0 = no floor)
+    pub min_vote_weight: u32,
+    /// Most vote weight a single member carries, floor included
+    /// (0 = uncapped)
+    pub max_vote_weight: u32,
+    /// Seconds a member must wait after creating a proposal before creating
+    /// another (0 = no cooldown)
+    pub proposal_cooldown_seconds: u64,
+    /// Per-type rules; types without one use the general fields above.
+    pub type_rules: Vec<ProposalTypeRule>,
 }
 
 impl GovernanceConfig {
-    pub fn default() -> Self {
+    pub fn default(env: &Env) -> Self {
         Self {
             quorum_percentage: 30,
             approval_threshold: 60,
             voting_period_days: 7,
             min_proposer_reputation: 0,
+            min_reputation_to_propose: 0,
+            max_active_proposals: 0,
+            min_vote_weight: 0,
+            max_vote_weight: 0,
+            proposal_cooldown_seconds: 0,
+            type_rules: Vec::new(env),
+        }
+    }
+
+    /// Raise `weight` to the configured floor, then clamp it to the cap.
+    pub fn bound_vote_weight(&self, weight: i128) -> i128 {
+        let mut bounded = weight.max(self.min_vote_weight as i128);
+        if self.max_vote_weight > 0 {
+            bounded = bounded.min(self.max_vote_weight as i128);
+        }
+        bounded
+    }
+
+    /// Rule that applies to `proposal_type`, falling back to the general
+    /// settings when the type has no specific rule.
+    pub fn rule_for(&self, proposal_type: &ProposalType) -> ProposalTypeRule {
+        for rule in self.type_rules.iter() {
+            if rule.proposal_type == *proposal_type {
+                return rule;
+            }
+        }
+        ProposalTypeRule {
+            proposal_type: proposal_type.clone(),
+            quorum_percentage: self.quorum_percentage,
+            approval_threshold: self.approval_threshold,
+            voting_period_days: self.voting_period_days,
+        }
+    }
+}
+
+/// Per-guild governance settings layered over the platform default config.
+/// `None` fields inherit the platform default.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GovernanceConfigOverride {
+    pub quorum_percentage: Option<u32>,
+    pub approval_threshold: Option<u32>,
+    pub voting_period_days: Option<u32>,
+    pub min_proposer_reputation: Option<u32>,
+    pub min_reputation_to_propose: Option<u32>,
+    pub max_active_proposals: Option<u32>,
+    pub min_vote_weight: Option<u32>,
+    pub max_vote_weight: Option<u32>,
+    pub proposal_cooldown_seconds: Option<u64>,
+    /// Replaces the default's per-type rules as a whole when set
+    pub type_rules: Option<Vec<ProposalTypeRule>>,
+}
+
+impl GovernanceConfigOverride {
+    /// Override every field with the values of `config`.
+    pub fn from_config(config: &GovernanceConfig) -> Self {
+        Self {
+            quorum_percentage: Some(config.quorum_percentage),
+            approval_threshold: Some(config.approval_threshold),
+            voting_period_days: Some(config.voting_period_days),
+            min_proposer_reputation: Some(config.min_proposer_reputation),
+            min_reputation_to_propose: Some(config.min_reputation_to_propose),
+            max_active_proposals: Some(config.max_active_proposals),
+            min_vote_weight: Some(config.min_vote_weight),
+            max_vote_weight: Some(config.max_vote_weight),
+            proposal_cooldown_seconds: Some(config.proposal_cooldown_seconds),
+            type_rules: Some(config.type_rules.clone()),
+        }
+    }
+
+    /// Merge field-by-field onto `base`.
+    pub fn apply(&self, base: GovernanceConfig) -> GovernanceConfig {
+        GovernanceConfig {
+            quorum_percentage: self.quorum_percentage.unwrap_or(base.quorum_percentage),
+            approval_threshold: self.approval_threshold.unwrap_or(base.approval_threshold),
+            voting_period_days: self.voting_period_days.unwrap_or(base.voting_period_days),
+            min_proposer_reputation: self
+                .min_proposer_reputation
+                .unwrap_or(base.min_proposer_reputation),
+            min_reputation_to_propose: self
+                .min_reputation_to_propose
+                .unwrap_or(base.min_reputation_to_propose),
+            max_active_proposals: self
+                .max_active_proposals
+                .unwrap_or(base.max_active_proposals),
+            min_vote_weight: self.min_vote_weight.unwrap_or(base.min_vote_weight),
+            max_vote_weight: self.max_vote_weight.unwrap_or(base.max_vote_weight),
+            proposal_cooldown_seconds: self
+                .proposal_cooldown_seconds
+                .unwrap_or(base.proposal_cooldown_seconds),
+            type_rules: self.type_rules.clone().unwrap_or(base.type_rules),
+        }
+    }
+}
+
+/// Full per-guild config as stored before guilds could inherit the platform
+/// default. Only read to carry those configs over.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LegacyGovernanceConfig {
+    pub quorum_percentage: u32,
+    pub approval_threshold: u32,
+    pub voting_period_days: u32,
+    pub min_proposer_reputation: u32,
+}
+
+impl LegacyGovernanceConfig {
+    /// Pin the fields the legacy config had; newer ones follow the default.
+    pub fn to_override(&self) -> GovernanceConfigOverride {
+        GovernanceConfigOverride {
+            quorum_percentage: Some(self.quorum_percentage),
+            approval_threshold: Some(self.approval_threshold),
+            voting_period_days: Some(self.voting_period_days),
+            min_proposer_reputation: Some(self.min_proposer_reputation),
+            min_reputation_to_propose: None,
+            max_active_proposals: None,
+            min_vote_weight: None,
+            max_vote_weight: None,
+            proposal_cooldown_seconds: None,
+            type_rules: None,
         }
     }
 }
@@ -67,6 +216,8 @@ pub enum ExecutionPayload {
     RuleChange,
     /// General decision (signalling only)
     GeneralDecision,
+    /// Reprice or (de)activate one of the guild's subscription plans
+    SubscriptionPlanUpdate(SubscriptionPlanUpdateData),
 }
 
 /// Detailed payload data stored separately for complex operations
@@ -106,6 +257,14 @@ pub struct GeneralDecisionData {
     pub meta: String,
 }
 
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SubscriptionPlanUpdateData {
+    pub plan_id: u64,
+    pub new_price: i128,
+    pub active: bool,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct Proposal {
@@ -124,6 +283,8 @@ pub struct Proposal {
     pub execution_payload: ExecutionPayload,
     pub passed_at: Option<u64>,
     pub executed_at: Option<u64>,
+    /// Labels for filtering, e.g. "treasury" or "membership"
+    pub tags: Vec<String>,
 }
 
 #[contracttype]
@@ -136,6 +297,23 @@ pub struct Vote {
     pub timestamp: u64,
 }
 
+/// Aggregate proposal statistics for a guild, computed from stored proposals.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GovernanceStats {
+    pub guild_id: u64,
+    pub total_proposals: u32,
+    /// Proposals that passed, including those already executed
+    pub passed_count: u32,
+    /// Proposals that were rejected or expired
+    pub failed_count: u32,
+    pub cancelled_count: u32,
+    pub active_count: u32,
+    /// Average share of current guild members that voted per proposal (0-100),
+    /// over the most recent `MAX_TURNOUT_SAMPLE` proposals
+    pub average_turnout_percentage: u32,
+}
+
 // Events
 
 #[contracttype]
@@ -170,6 +348,23 @@ pub struct VoteUndelegatedEvent {
     pub delegator: Address,
 }
 
+/// Per-guild deposit a proposer must lock when creating a proposal
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProposalDepositConfig {
+    pub token: Address,
+    pub amount: i128,
+}
+
+/// Deposit held for a proposal until it is finalized or cancelled
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProposalDeposit {
+    pub proposer: Address,
+    pub token: Address,
+    pub amount: i128,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct ProposalFinalizedEvent {
@@ -180,6 +375,29 @@ pub struct ProposalFinalizedEvent {
     pub votes_abstain: i128,
 }
 
+/// A deposit was forfeited because its proposal missed quorum. `treasury_id`
+/// is `None` when the guild had no treasury and the funds went to the
+/// contract-held forfeit pool instead.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DepositForfeitedEvent {
+    pub proposal_id: u64,
+    pub guild_id: u64,
+    pub token: Address,
+    pub amount: i128,
+    pub treasury_id: Option<u64>,
+}
+
+/// The guild owner moved forfeited deposits out of the contract-held pool
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ForfeitedDepositsWithdrawnEvent {
+    pub guild_id: u64,
+    pub token: Address,
+    pub amount: i128,
+    pub recipient: Address,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct ProposalExecutedEvent {
@@ -205,5 +423,6 @@ pub fn role_weight(role: &Role) -> i128 {
         Role::Admin => 5,
         Role::Member => 2,
         Role::Contributor => 1,
+        Role::Guest => 0,
     }
 }
@@ -1,17 +1,28 @@
 use crate::events::emit::emit_event;
-use crate::events::topics::{ACT_DELEGATED, ACT_FINALIZED, ACT_UPDATED, ACT_VOTED, MOD_GOVERNANCE};
-use soroban_sdk::{Address, Env};
+use crate::events::topics::{
+    ACT_DELEGATED, ACT_FINALIZED, ACT_FORFEITED, ACT_UPDATED, ACT_VOTED, MOD_GOVERNANCE,
+};
+use soroban_sdk::{Address, Env, Vec};
 
+use crate::governance::proposals::refund_proposal_deposit;
 use crate::governance::storage::{
-    get_all_votes, get_config, get_delegate, get_proposal as load_proposal, remove_delegation,
-    set_delegation, store_proposal, store_vote,
+    add_forfeited_balance, get_all_votes, get_config, get_delegate, get_proposal as load_proposal,
+    get_proposal_deposit, get_vote, remove_delegation, remove_proposal_deposit, set_delegation,
+    store_proposal, store_vote,
 };
 use crate::governance::types::role_weight;
 use crate::governance::types::{
-    Proposal, ProposalFinalizedEvent, ProposalStatus, Vote, VoteCastEvent, VoteDecision,
+    DepositForfeitedEvent, GovernanceConfig, Proposal, ProposalFinalizedEvent, ProposalStatus,
+    Vote, VoteCastEvent, VoteDecision,
 };
 use crate::guild::storage as guild_storage;
+use crate::guild::types::{Member, Role};
 use crate::reputation::scoring::compute_governance_weight;
+use crate::treasury::management::credit_deposit;
+use crate::treasury::storage::{get_guild_treasury_id, get_treasury};
+
+/// Most votes a single `batch_vote` call may cast
+pub const MAX_BATCH_VOTES: u32 = 20;
 
 fn resolve_delegate(env: &Env, guild_id: u64, addr: &Address) -> Address {
     let mut current = addr.clone();
@@ -29,10 +40,27 @@ fn resolve_delegate(env: &Env, guild_id: u64, addr: &Address) -> Address {
     current
 }
 
+/// Vote weight of a member: reputation-based weight raised to the guild's
+/// floor and clamped to its cap. Read-only roles never get the floor.
+fn member_vote_weight(
+    env: &Env,
+    config: &GovernanceConfig,
+    guild_id: u64,
+    address: &Address,
+    role: &Role,
+) -> i128 {
+    let weight = compute_governance_weight(env, address, guild_id, role);
+    if role.is_read_only() {
+        return weight;
+    }
+    config.bound_vote_weight(weight)
+}
+
 fn compute_total_weight_and_tallies(env: &Env, proposal: &Proposal) -> (i128, i128, i128, i128) {
     // returns (total_votes_weight, for_weight, against_weight, abstain_weight)
     let votes_map = get_all_votes(env, proposal.id);
     let members = guild_storage::get_all_members(env, proposal.guild_id);
+    let config = get_config(env, proposal.guild_id);
 
     let mut total_votes_weight: i128 = 0;
     let mut for_weight: i128 = 0;
@@ -41,8 +69,13 @@ fn compute_total_weight_and_tallies(env: &Env, proposal: &Proposal) -> (i128, i1
 
     for member in members.iter() {
         let rep = resolve_delegate(env, proposal.guild_id, &member.address);
-        let weight =
-            compute_governance_weight(env, &member.address, proposal.guild_id, &member.role);
+        let weight = member_vote_weight(
+            env,
+            &config,
+            proposal.guild_id,
+            &member.address,
+            &member.role,
+        );
 
         let decision_opt = if rep == member.address {
             votes_map.get(member.address.clone()).map(|v| v.decision)
@@ -68,25 +101,86 @@ fn compute_total_weight_and_tallies(env: &Env, proposal: &Proposal) -> (i128, i1
     )
 }
 
-pub fn vote(env: &Env, proposal_id: u64, voter: Address, decision: VoteDecision) -> bool {
-    voter.require_auth();
-
-    let proposal = load_proposal(env, proposal_id).unwrap_or_else(|| panic!("proposal not found"));
+/// Check that `voter` may vote on `proposal_id` right now, returning the
+/// proposal and the voter's membership or the reason they can't.
+fn check_vote_eligibility(
+    env: &Env,
+    proposal_id: u64,
+    voter: &Address,
+) -> Result<(Proposal, Member), &'static str> {
+    let proposal = load_proposal(env, proposal_id).ok_or("proposal not found")?;
 
     if !matches!(proposal.status, ProposalStatus::Active) {
-        panic!("proposal not active");
+        return Err("proposal not active");
     }
 
     let now = env.ledger().timestamp();
     if now < proposal.voting_start || now > proposal.voting_end {
-        panic!("voting period closed");
+        return Err("voting period closed");
     }
 
     // must be guild member
-    let member = guild_storage::get_member(env, proposal.guild_id, &voter)
-        .unwrap_or_else(|| panic!("voter must be guild member"));
+    let member = guild_storage::get_member(env, proposal.guild_id, voter)
+        .ok_or("voter must be guild member")?;
+    if member.role.is_read_only() {
+        return Err("guests cannot vote");
+    }
+    if guild_storage::is_suspended(env, proposal.guild_id, voter) {
+        return Err("voter is suspended");
+    }
+
+    Ok((proposal, member))
+}
+
+pub fn vote(env: &Env, proposal_id: u64, voter: Address, decision: VoteDecision) -> bool {
+    voter.require_auth();
+
+    let (proposal, member) =
+        check_vote_eligibility(env, proposal_id, &voter).unwrap_or_else(|e| panic!("{}", e));
+
+    record_vote(env, &proposal, &member, voter, decision);
+
+    true
+}
+
+/// Cast several votes in one call. Proposals the voter can't vote on (closed,
+/// inactive, not a member, suspended) or has already voted on are skipped
+/// rather than aborting the batch.
+///
+/// Returns one flag per input entry, `true` where the vote was recorded.
+/// At most `MAX_BATCH_VOTES` entries are accepted.
+pub fn batch_vote(env: &Env, votes: Vec<(u64, VoteDecision)>, voter: Address) -> Vec<bool> {
+    voter.require_auth();
 
-    let weight = compute_governance_weight(env, &voter, proposal.guild_id, &member.role);
+    if votes.len() > MAX_BATCH_VOTES {
+        panic!("too many votes in batch");
+    }
+
+    let mut results = Vec::new(env);
+    for (proposal_id, decision) in votes.iter() {
+        let cast = match check_vote_eligibility(env, proposal_id, &voter) {
+            Ok((proposal, member)) if get_vote(env, proposal_id, &voter).is_none() => {
+                record_vote(env, &proposal, &member, voter.clone(), decision);
+                true
+            }
+            _ => false,
+        };
+        results.push_back(cast);
+    }
+    results
+}
+
+fn record_vote(
+    env: &Env,
+    proposal: &Proposal,
+    member: &Member,
+    voter: Address,
+    decision: VoteDecision,
+) {
+    let proposal_id = proposal.id;
+    let now = env.ledger().timestamp();
+    let config = get_config(env, proposal.guild_id);
+    let weight = member_vote_weight(env, &config, proposal.guild_id, &voter, &member.role);
 
     let vote = Vote {
         voter: voter.clone(),
@@ -104,8 +198,6 @@ pub fn vote(env: &Env, proposal_id: u64, voter: Address, decision: VoteDecision)
         decision,
     };
     emit_event(env, MOD_GOVERNANCE, ACT_VOTED, event);
-
-    true
 }
 
 pub fn delegate_vote(env: &Env, guild_id: u64, delegator: Address, delegate: Address) -> bool {
@@ -116,10 +208,18 @@ pub fn delegate_vote(env: &Env, guild_id: u64, delegator: Address, delegate: Add
     }
 
     // both must be guild members
-    let _d1 = guild_storage::get_member(env, guild_id, &delegator)
+    let d1 = guild_storage::get_member(env, guild_id, &delegator)
         .unwrap_or_else(|| panic!("delegator must be guild member"));
-    let _d2 = guild_storage::get_member(env, guild_id, &delegate)
+    let d2 = guild_storage::get_member(env, guild_id, &delegate)
         .unwrap_or_else(|| panic!("delegate must be guild member"));
+    if d1.role.is_read_only() || d2.role.is_read_only() {
+        panic!("guests cannot delegate or receive delegation");
+    }
+    if guild_storage::is_suspended(env, guild_id, &delegator)
+        || guild_storage::is_suspended(env, guild_id, &delegate)
+    {
+        panic!("suspended members cannot delegate or receive delegation");
+    }
 
     // check for cycles: walk starting from delegate
     let mut current = delegate.clone();
@@ -176,7 +276,7 @@ pub fn finalize_proposal(env: &Env, proposal_id: u64) -> ProposalStatus {
         panic!("voting period not finished");
     }
 
-    let cfg = get_config(env, proposal.guild_id);
+    let rule = get_config(env, proposal.guild_id).rule_for(&proposal.proposal_type);
 
     let members = guild_storage::get_all_members(env, proposal.guild_id);
     let mut total_possible_weight: i128 = 0;
@@ -184,7 +284,7 @@ pub fn finalize_proposal(env: &Env, proposal_id: u64) -> ProposalStatus {
         total_possible_weight += role_weight(&member.role);
     }
 
-    let quorum_threshold: i128 = (total_possible_weight * (cfg.quorum_percentage as i128)) / 100;
+    let quorum_threshold: i128 = (total_possible_weight * (rule.quorum_percentage as i128)) / 100;
 
     let (total_votes_weight, for_weight, against_weight, abstain_weight) =
         compute_total_weight_and_tallies(env, &proposal);
@@ -193,7 +293,8 @@ pub fn finalize_proposal(env: &Env, proposal_id: u64) -> ProposalStatus {
     proposal.votes_against = against_weight;
     proposal.votes_abstain = abstain_weight;
 
-    if total_votes_weight < quorum_threshold {
+    let quorum_missed = total_votes_weight < quorum_threshold;
+    if quorum_missed {
         proposal.status = ProposalStatus::Rejected;
     } else {
         let counted = for_weight + against_weight;
@@ -201,7 +302,7 @@ pub fn finalize_proposal(env: &Env, proposal_id: u64) -> ProposalStatus {
             proposal.status = ProposalStatus::Rejected;
         } else {
             let approval_pct = (for_weight * 100) / counted;
-            if approval_pct >= (cfg.approval_threshold as i128) {
+            if approval_pct >= (rule.approval_threshold as i128) {
                 proposal.status = ProposalStatus::Passed;
                 if proposal.passed_at.is_none() {
                     proposal.passed_at = Some(now);
@@ -214,6 +315,12 @@ pub fn finalize_proposal(env: &Env, proposal_id: u64) -> ProposalStatus {
 
     store_proposal(env, &proposal);
 
+    if quorum_missed {
+        forfeit_proposal_deposit(env, &proposal);
+    } else {
+        refund_proposal_deposit(env, proposal_id);
+    }
+
     let event = ProposalFinalizedEvent {
         proposal_id,
         status: proposal.status.clone(),
@@ -225,3 +332,38 @@ pub fn finalize_proposal(env: &Env, proposal_id: u64) -> ProposalStatus {
 
     proposal.status
 }
+
+/// Route the deposit of a proposal that missed quorum to the guild treasury,
+/// or to the contract-held forfeit pool when the guild has no usable treasury.
+fn forfeit_proposal_deposit(env: &Env, proposal: &Proposal) {
+    let deposit = match get_proposal_deposit(env, proposal.id) {
+        Some(d) => d,
+        None => return,
+    };
+    remove_proposal_deposit(env, proposal.id);
+
+    let treasury_id = get_guild_treasury_id(env, proposal.guild_id)
+        .filter(|id| get_treasury(env, *id).map(|t| !t.paused).unwrap_or(false));
+    match treasury_id {
+        Some(id) => {
+            credit_deposit(
+                env,
+                id,
+                env.current_contract_address(),
+                deposit.amount,
+                Some(deposit.token.clone()),
+                false,
+            );
+        }
+        None => add_forfeited_balance(env, proposal.guild_id, &deposit.token, deposit.amount),
+    }
+
+    let event = DepositForfeitedEvent {
+        proposal_id: proposal.id,
+        guild_id: proposal.guild_id,
+        token: deposit.token,
+        amount: deposit.amount,
+        treasury_id,
+    };
+    emit_event(env, MOD_GOVERNANCE, ACT_FORFEITED, event);
+}
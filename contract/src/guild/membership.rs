@@ -1,13 +1,15 @@
 ﻿use crate::events::emit::emit_event;
 use crate::events::topics::{
-    ACT_CREATED, ACT_JOINED, ACT_MEMBER_ADDED, ACT_MEMBER_REMOVED, ACT_ROLE_UPDATED, MOD_GUILD,
+    ACT_CREATED, ACT_JOINED, ACT_MEMBER_ADDED, ACT_MEMBER_REMOVED, ACT_PERMISSION_DENIED,
+    ACT_REINSTATED, ACT_ROLE_UPDATED, ACT_SUSPENDED, ACT_UPDATED, MOD_GUILD,
 };
 use crate::guild::storage;
 use crate::guild::types::{
-    Guild, GuildCreatedEvent, GuildJoinedEvent, Member, MemberAddedEvent, MemberRemovedEvent, Role,
-    RoleUpdatedEvent,
+    Guild, GuildCreatedEvent, GuildJoinedEvent, GuildMetadataLimits, GuildMetadataUpdatedEvent,
+    Member, MemberAddedEvent, MemberReinstatedEvent, MemberRemovedEvent, MemberSuspendedEvent,
+    PermissionDeniedEvent, Role, RoleUpdatedEvent,
 };
-use soroban_sdk::{Address, Env, String, Vec};
+use soroban_sdk::{Address, Env, String, Symbol, Vec};
 
 /// Create a new guild
 ///
@@ -16,8 +18,8 @@ use soroban_sdk::{Address, Env, String, Vec};
 ///
 /// # Arguments
 /// * `env` - The contract environment
-/// * `name` - The name of the guild (1â€“256 chars)
-/// * `description` - The description of the guild (max 512 chars)
+/// * `name` - The name of the guild (within the configured name bounds)
+/// * `description` - The description of the guild (within the configured maximum)
 /// * `owner` - The address of the guild owner
 ///
 /// # Returns
@@ -31,18 +33,7 @@ pub fn create_guild(
     description: String,
     owner: Address,
 ) -> Result<u64, String> {
-    if name.len() == 0 || name.len() > 256 {
-        return Err(String::from_str(
-            env,
-            "Guild name must be between 1 and 256 characters",
-        ));
-    }
-    if description.len() > 512 {
-        return Err(String::from_str(
-            env,
-            "Guild description must be at most 512 characters",
-        ));
-    }
+    validate_metadata(env, &name, &description)?;
 
     let guild_id = storage::get_next_guild_id(env);
     let timestamp = env.ledger().timestamp();
@@ -79,6 +70,100 @@ pub fn create_guild(
     Ok(guild_id)
 }
 
+/// Check a name and description against the configured length bounds
+fn validate_metadata(env: &Env, name: &String, description: &String) -> Result<(), String> {
+    let limits = storage::get_metadata_limits(env);
+    if name.len() < limits.min_name_len || name.len() > limits.max_name_len {
+        return Err(String::from_str(
+            env,
+            "Guild name length is outside the configured bounds",
+        ));
+    }
+    if description.len() > limits.max_description_len {
+        return Err(String::from_str(
+            env,
+            "Guild description exceeds the configured maximum length",
+        ));
+    }
+    Ok(())
+}
+
+/// Set the platform-wide name/description length bounds
+///
+/// Existing guilds are not revalidated; the bounds only apply to later writes.
+///
+/// # Errors
+/// Returns `Err` if the minimum name length is zero or exceeds the maximum.
+pub fn set_metadata_limits(env: &Env, limits: GuildMetadataLimits) -> Result<bool, String> {
+    if limits.min_name_len == 0 || limits.min_name_len > limits.max_name_len {
+        return Err(String::from_str(env, "Invalid guild name length bounds"));
+    }
+    storage::set_metadata_limits(env, &limits);
+    Ok(true)
+}
+
+pub fn get_metadata_limits(env: &Env) -> GuildMetadataLimits {
+    storage::get_metadata_limits(env)
+}
+
+/// Update a guild's name and description
+///
+/// # Events emitted
+/// - `(guild, updated)` â†’ `GuildMetadataUpdatedEvent`
+///
+/// # Arguments
+/// * `env`         - The contract environment
+/// * `guild_id`    - The ID of the guild
+/// * `name`        - The new guild name
+/// * `description` - The new guild description
+/// * `caller`      - The address making the request (owner or admin)
+///
+/// # Errors
+/// Returns `Err` if the guild is missing, the caller lacks permission, or the
+/// new metadata violates the configured length bounds.
+pub fn update_guild_metadata(
+    env: &Env,
+    guild_id: u64,
+    name: String,
+    description: String,
+    caller: Address,
+) -> Result<bool, String> {
+    let mut guild =
+        storage::get_guild(env, guild_id).ok_or(String::from_str(env, "Guild not found"))?;
+
+    if !enforce_permission(
+        env,
+        guild_id,
+        caller.clone(),
+        Role::Admin,
+        "update_guild_metadata",
+    ) {
+        return Err(String::from_str(
+            env,
+            "Only owner or admin can update guild metadata",
+        ));
+    }
+
+    validate_metadata(env, &name, &description)?;
+
+    guild.name = name.clone();
+    guild.description = description;
+    storage::update_guild(env, &guild);
+
+    emit_event(
+        env,
+        MOD_GUILD,
+        ACT_UPDATED,
+        GuildMetadataUpdatedEvent {
+            guild_id,
+            name,
+            updated_by: caller,
+        },
+    );
+
+    Ok(true)
+}
+
 /// Add a member to a guild
 ///
 /// # Events emitted
@@ -123,7 +208,7 @@ pub fn add_member(
                 return Err(String::from_str(env, "Only owner or admin can add admins"));
             }
         }
-        Role::Member | Role::Contributor => {
+        Role::Member | Role::Contributor | Role::Guest => {
             if !caller_member.role.has_permission(&Role::Member) {
                 return Err(String::from_str(
                     env,
@@ -160,6 +245,66 @@ pub fn add_member(
     Ok(true)
 }
 
+/// Set the role assigned by `add_member_default`
+///
+/// # Arguments
+/// * `env`      - The contract environment
+/// * `guild_id` - The ID of the guild
+/// * `role`     - The default role for new members (anything but `Owner`)
+/// * `caller`   - The address making the request (must be an owner)
+///
+/// # Errors
+/// - Guild not found
+/// - Caller is not an owner
+/// - `role` is `Owner`
+pub fn set_default_member_role(
+    env: &Env,
+    guild_id: u64,
+    role: Role,
+    caller: Address,
+) -> Result<bool, String> {
+    storage::get_guild(env, guild_id).ok_or(String::from_str(env, "Guild not found"))?;
+
+    if !enforce_permission(
+        env,
+        guild_id,
+        caller,
+        Role::Owner,
+        "set_default_member_role",
+    ) {
+        return Err(String::from_str(
+            env,
+            "Only owner can set the default member role",
+        ));
+    }
+    if role == Role::Owner {
+        return Err(String::from_str(env, "Default member role cannot be Owner"));
+    }
+
+    storage::set_default_member_role(env, guild_id, &role);
+    Ok(true)
+}
+
+pub fn get_default_member_role(env: &Env, guild_id: u64) -> Role {
+    storage::get_default_member_role(env, guild_id)
+}
+
+/// Add a member to a guild with the guild's default role
+///
+/// Same permission checks and events as `add_member`.
+///
+/// # Errors
+/// Same as `add_member`.
+pub fn add_member_default(
+    env: &Env,
+    guild_id: u64,
+    address: Address,
+    caller: Address,
+) -> Result<bool, String> {
+    let role = storage::get_default_member_role(env, guild_id);
+    add_member(env, guild_id, address, role, caller)
+}
+
 /// Remove a member from a guild
 ///
 /// # Events emitted
@@ -218,7 +363,7 @@ pub fn remove_member(
                     ));
                 }
             }
-            Role::Member | Role::Contributor => {
+            Role::Member | Role::Contributor | Role::Guest => {
                 if !caller_member.role.has_permission(&Role::Member) {
                     return Err(String::from_str(
                         env,
@@ -230,6 +375,7 @@ pub fn remove_member(
     }
 
     storage::remove_member(env, guild_id, &address);
+    storage::set_suspended(env, guild_id, &address, false);
 
     let mut updated_guild = guild;
     updated_guild.member_count = updated_guild.member_count.saturating_sub(1);
@@ -297,7 +443,7 @@ pub fn update_role(
                 ));
             }
         }
-        Role::Member | Role::Contributor => {
+        Role::Member | Role::Contributor | Role::Guest => {
             if caller_member.role != Role::Owner && caller_member.role != Role::Admin {
                 return Err(String::from_str(
                     env,
@@ -389,6 +535,152 @@ pub fn join_guild(env: &Env, guild_id: u64, caller: Address) -> Result<bool, Str
     Ok(true)
 }
 
+/// Suspend a member without removing them
+///
+/// The `Member` record (role, join date) is preserved, but a suspended
+/// member cannot vote, claim bounties, act on milestones, or use their role
+/// permissions until reinstated.
+///
+/// # Events emitted
+/// - `(guild, suspended)` → `MemberSuspendedEvent`
+///
+/// # Arguments
+/// * `env`      - The contract environment
+/// * `guild_id` - The ID of the guild
+/// * `address`  - The address of the member to suspend
+/// * `caller`   - The address making the request (owner or admin; an
+///   owner may suspend themselves)
+///
+/// # Errors
+/// - Guild or member not found
+/// - Member already suspended
+/// - Caller lacks permission over the target's role
+/// - Suspending the last active owner
+pub fn suspend_member(
+    env: &Env,
+    guild_id: u64,
+    address: Address,
+    caller: Address,
+) -> Result<bool, String> {
+    let _guild =
+        storage::get_guild(env, guild_id).ok_or(String::from_str(env, "Guild not found"))?;
+
+    let member = storage::get_member(env, guild_id, &address)
+        .ok_or(String::from_str(env, "Member not found"))?;
+
+    if storage::is_suspended(env, guild_id, &address) {
+        return Err(String::from_str(env, "Member already suspended"));
+    }
+
+    let caller_member = storage::get_member(env, guild_id, &caller)
+        .ok_or(String::from_str(env, "Caller is not a member of the guild"))?;
+    if storage::is_suspended(env, guild_id, &caller) {
+        return Err(String::from_str(env, "Caller is suspended"));
+    }
+
+    match member.role {
+        Role::Owner => {
+            if caller_member.role != Role::Owner {
+                return Err(String::from_str(env, "Only owner can suspend owners"));
+            }
+            if storage::count_active_owners(env, guild_id) <= 1 {
+                return Err(String::from_str(
+                    env,
+                    "Cannot suspend the last active owner",
+                ));
+            }
+        }
+        Role::Admin | Role::Member | Role::Contributor | Role::Guest => {
+            if !caller_member.role.has_permission(&Role::Admin) {
+                return Err(String::from_str(
+                    env,
+                    "Only owner or admin can suspend members",
+                ));
+            }
+        }
+    }
+
+    storage::set_suspended(env, guild_id, &address, true);
+
+    emit_event(
+        env,
+        MOD_GUILD,
+        ACT_SUSPENDED,
+        MemberSuspendedEvent {
+            guild_id,
+            address,
+            suspended_by: caller,
+        },
+    );
+
+    Ok(true)
+}
+
+/// Lift a member's suspension
+///
+/// # Events emitted
+/// - `(guild, reinstated)` → `MemberReinstatedEvent`
+///
+/// # Arguments
+/// * `env`      - The contract environment
+/// * `guild_id` - The ID of the guild
+/// * `address`  - The address of the suspended member
+/// * `caller`   - The address making the request (owner or admin)
+///
+/// # Errors
+/// - Guild or member not found
+/// - Member is not suspended
+/// - Caller lacks permission over the target's role
+pub fn reinstate_member(
+    env: &Env,
+    guild_id: u64,
+    address: Address,
+    caller: Address,
+) -> Result<bool, String> {
+    let _guild =
+        storage::get_guild(env, guild_id).ok_or(String::from_str(env, "Guild not found"))?;
+
+    let member = storage::get_member(env, guild_id, &address)
+        .ok_or(String::from_str(env, "Member not found"))?;
+
+    if !storage::is_suspended(env, guild_id, &address) {
+        return Err(String::from_str(env, "Member is not suspended"));
+    }
+
+    let caller_member = storage::get_member(env, guild_id, &caller)
+        .ok_or(String::from_str(env, "Caller is not a member of the guild"))?;
+    if storage::is_suspended(env, guild_id, &caller) {
+        return Err(String::from_str(env, "Caller is suspended"));
+    }
+
+    let required = if member.role == Role::Owner {
+        Role::Owner
+    } else {
+        Role::Admin
+    };
+    if !caller_member.role.has_permission(&required) {
+        return Err(String::from_str(
+            env,
+            "Insufficient permissions to reinstate member",
+        ));
+    }
+
+    storage::set_suspended(env, guild_id, &address, false);
+
+    emit_event(
+        env,
+        MOD_GUILD,
+        ACT_REINSTATED,
+        MemberReinstatedEvent {
+            guild_id,
+            address,
+            reinstated_by: caller,
+        },
+    );
+
+    Ok(true)
+}
+
 // ─── Query helpers (no events) ────────────────────────────────────────────────
 
 pub fn get_member(env: &Env, guild_id: u64, address: Address) -> Result<Member, String> {
@@ -399,14 +691,69 @@ pub fn get_all_members(env: &Env, guild_id: u64) -> Vec<Member> {
     storage::get_all_members(env, guild_id)
 }
 
+/// Number of members in the guild, owner included, read from the counter
+/// kept on the guild record rather than by loading the member list.
+pub fn get_member_count(env: &Env, guild_id: u64) -> Result<u32, String> {
+    storage::get_guild(env, guild_id)
+        .map(|guild| guild.member_count)
+        .ok_or(String::from_str(env, "Guild not found"))
+}
+
 pub fn is_member(env: &Env, guild_id: u64, address: Address) -> bool {
     storage::has_member(env, guild_id, &address)
 }
 
+pub fn is_suspended(env: &Env, guild_id: u64, address: Address) -> bool {
+    storage::is_suspended(env, guild_id, &address)
+}
+
+/// Whether the address belongs to the guild only as a read-only guest.
+pub fn is_guest(env: &Env, guild_id: u64, address: Address) -> bool {
+    storage::get_member(env, guild_id, &address)
+        .map(|member| member.role.is_read_only())
+        .unwrap_or(false)
+}
+
+/// Suspended members keep their role but cannot exercise it.
 pub fn has_permission(env: &Env, guild_id: u64, address: Address, required_role: Role) -> bool {
+    if storage::is_suspended(env, guild_id, &address) {
+        return false;
+    }
     if let Some(member) = storage::get_member(env, guild_id, &address) {
         member.role.has_permission(&required_role)
     } else {
         false
     }
 }
+
+/// `has_permission` for mutating entrypoints: a denial is also reported as a
+/// `PermissionDeniedEvent` naming `attempted_action`. Read-only queries keep
+/// using `has_permission` so they stay silent. When the caller then panics the
+/// event only survives in the diagnostic event stream of the failed call.
+///
+/// # Events emitted
+/// - `(guild, permission_denied)` â†’ `PermissionDeniedEvent`  (on denial)
+pub fn enforce_permission(
+    env: &Env,
+    guild_id: u64,
+    address: Address,
+    required_role: Role,
+    attempted_action: &str,
+) -> bool {
+    if has_permission(env, guild_id, address.clone(), required_role) {
+        return true;
+    }
+
+    emit_event(
+        env,
+        MOD_GUILD,
+        ACT_PERMISSION_DENIED,
+        PermissionDeniedEvent {
+            guild_id,
+            actor: address,
+            attempted_action: Symbol::new(env, attempted_action),
+            required_role,
+        },
+    );
+    false
+}
@@ -1,10 +1,13 @@
-﻿use crate::guild::types::{Guild, Member, Role};
+﻿use crate::guild::types::{Guild, GuildMetadataLimits, Member, Role};
 use soroban_sdk::{symbol_short, Address, Env, Map, Symbol, Vec};
 
 // Storage keys as symbols for efficient lookup
 const GUILDS_KEY: Symbol = symbol_short!("guilds");
 const MEMBERS_KEY: Symbol = symbol_short!("members");
 const GUILD_COUNTER_KEY: Symbol = symbol_short!("guild_cnt");
+const SUSPENDED_KEY: Symbol = symbol_short!("suspended");
+const META_LIMITS_KEY: Symbol = symbol_short!("g_limits");
+const DEFAULT_ROLE_KEY: Symbol = symbol_short!("g_defrole");
 
 /// Initialize storage for guilds and members
 /// This should be called during contract initialization
@@ -162,3 +165,67 @@ pub fn count_owners(env: &Env, guild_id: u64) -> u32 {
 
     count
 }
+
+/// Count owners in a guild that are not currently suspended
+pub fn count_active_owners(env: &Env, guild_id: u64) -> u32 {
+    let members = get_all_members(env, guild_id);
+    let mut count = 0u32;
+
+    for member in members.iter() {
+        if member.role == Role::Owner && !is_suspended(env, guild_id, &member.address) {
+            count += 1;
+        }
+    }
+
+    count
+}
+
+/// Mark or clear a member's suspension; the member record itself is untouched
+pub fn set_suspended(env: &Env, guild_id: u64, address: &Address, suspended: bool) {
+    let key = (SUSPENDED_KEY, guild_id, address.clone());
+    if suspended {
+        env.storage().persistent().set(&key, &true);
+    } else {
+        env.storage().persistent().remove(&key);
+    }
+}
+
+/// Check whether a member is currently suspended
+pub fn is_suspended(env: &Env, guild_id: u64, address: &Address) -> bool {
+    env.storage()
+        .persistent()
+        .get(&(SUSPENDED_KEY, guild_id, address.clone()))
+        .unwrap_or(false)
+}
+
+/// Get the name/description length bounds, falling back to the original defaults
+pub fn get_metadata_limits(env: &Env) -> GuildMetadataLimits {
+    env.storage()
+        .persistent()
+        .get(&META_LIMITS_KEY)
+        .unwrap_or(GuildMetadataLimits {
+            min_name_len: 1,
+            max_name_len: 256,
+            max_description_len: 512,
+        })
+}
+
+/// Store the name/description length bounds
+pub fn set_metadata_limits(env: &Env, limits: &GuildMetadataLimits) {
+    env.storage().persistent().set(&META_LIMITS_KEY, limits);
+}
+
+/// Get the role `add_member_default` assigns, falling back to `Role::Member`
+pub fn get_default_member_role(env: &Env, guild_id: u64) -> Role {
+    env.storage()
+        .persistent()
+        .get(&(DEFAULT_ROLE_KEY, guild_id))
+        .unwrap_or(Role::Member)
+}
+
+/// Store the role `add_member_default` assigns
+pub fn set_default_member_role(env: &Env, guild_id: u64, role: &Role) {
+    env.storage()
+        .persistent()
+        .set(&(DEFAULT_ROLE_KEY, guild_id), role);
+}
@@ -1,14 +1,17 @@
-//! Guild membership tests — join_guild
+//! Guild membership tests — join_guild and member suspension
 //!
 //! Covers authorized self-join, duplicate join rejection, join on a
-//! non-existent guild, and unauthorized join (missing signature).
+//! non-existent guild, unauthorized join (missing signature),
+//! suspending/reinstating members without removing them, and the
+//! configurable name/description length bounds.
 
 #![cfg(test)]
 
-use crate::guild::types::Role;
+use crate::governance::{ProposalType, VoteDecision};
+use crate::guild::types::{GuildMetadataLimits, Role};
 use crate::{StellarGuildsContract, StellarGuildsContractClient};
-use soroban_sdk::testutils::Address as _;
-use soroban_sdk::{Address, Env, String};
+use soroban_sdk::testutils::{Address as _, Events as _};
+use soroban_sdk::{Address, Env, String, Symbol, TryFromVal};
 
 // ─── Helpers ──────────────────────────────────────────────────────────────────
 
@@ -125,3 +128,375 @@ fn test_join_guild_unauthorized_panics() {
     // No mock_all_auths → require_auth() inside join_guild panics.
     client.join_guild(&guild_id, &joiner);
 }
+
+/// Suspension keeps the member record intact, blocks voting and bounty
+/// claims, and reinstatement restores participation.
+#[test]
+fn test_suspend_and_reinstate_member() {
+    let env = setup_env();
+    env.mock_all_auths();
+
+    let contract_id = register_and_init(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    let owner = Address::generate(&env);
+    let guild_id = create_test_guild(&client, &env, &owner);
+    let member = Address::generate(&env);
+    client.add_member(&guild_id, &member, &Role::Admin, &owner);
+
+    assert!(client.suspend_member(&guild_id, &member, &owner));
+    assert!(client.is_member_suspended(&guild_id, &member));
+
+    // Record and role are preserved, but the role cannot be exercised
+    let record = client.get_member(&guild_id, &member);
+    assert_eq!(record.role, Role::Admin);
+    assert!(client.is_member(&guild_id, &member));
+    assert!(!client.has_permission(&guild_id, &member, &Role::Member));
+
+    let proposal_id = client.create_proposal(
+        &guild_id,
+        &owner,
+        &ProposalType::GeneralDecision,
+        &String::from_str(&env, "Suspended vote"),
+        &String::from_str(&env, "suspended members cannot vote"),
+        &None,
+    );
+    assert!(client
+        .try_vote(&proposal_id, &member, &VoteDecision::For)
+        .is_err());
+
+    let bounty_id = client.create_bounty(
+        &guild_id,
+        &owner,
+        &String::from_str(&env, "Task"),
+        &String::from_str(&env, "unfunded task"),
+        &0,
+        &Address::generate(&env),
+        &(env.ledger().timestamp() + 1000),
+    );
+    assert!(client.try_claim_bounty(&bounty_id, &member).is_err());
+
+    assert!(client.reinstate_member(&guild_id, &member, &owner));
+    assert!(!client.is_member_suspended(&guild_id, &member));
+    assert!(client.has_permission(&guild_id, &member, &Role::Member));
+    assert!(client.vote(&proposal_id, &member, &VoteDecision::For));
+    assert!(client.claim_bounty(&bounty_id, &member));
+}
+
+/// The only active owner cannot be suspended, which would lock the guild;
+/// with a co-owner present the same suspension is allowed.
+#[test]
+fn test_suspend_owner_requires_another_active_owner() {
+    let env = setup_env();
+    env.mock_all_auths();
+
+    let contract_id = register_and_init(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    let owner = Address::generate(&env);
+    let guild_id = create_test_guild(&client, &env, &owner);
+
+    assert!(client
+        .try_suspend_member(&guild_id, &owner, &owner)
+        .is_err());
+    assert!(!client.is_member_suspended(&guild_id, &owner));
+
+    let co_owner = Address::generate(&env);
+    client.add_member(&guild_id, &co_owner, &Role::Owner, &owner);
+
+    assert!(client.suspend_member(&guild_id, &owner, &co_owner));
+    assert!(client.is_member_suspended(&guild_id, &owner));
+
+    // co_owner is now the last active owner
+    assert!(client
+        .try_suspend_member(&guild_id, &co_owner, &co_owner)
+        .is_err());
+}
+
+/// Admins cannot suspend owners.
+#[test]
+#[should_panic(expected = "suspend_member error")]
+fn test_admin_cannot_suspend_owner() {
+    let env = setup_env();
+    env.mock_all_auths();
+
+    let contract_id = register_and_init(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    let owner = Address::generate(&env);
+    let co_owner = Address::generate(&env);
+    let admin = Address::generate(&env);
+    let guild_id = create_test_guild(&client, &env, &owner);
+    client.add_member(&guild_id, &co_owner, &Role::Owner, &owner);
+    client.add_member(&guild_id, &admin, &Role::Admin, &owner);
+
+    client.suspend_member(&guild_id, &owner, &admin);
+}
+
+/// Length bounds are configurable and only constrain new writes.
+#[test]
+fn test_guild_metadata_limits_apply_to_new_writes() {
+    let env = setup_env();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, StellarGuildsContract);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+
+    let owner = Address::generate(&env);
+    let guild_id = create_test_guild(&client, &env, &owner);
+
+    let limits = GuildMetadataLimits {
+        min_name_len: 4,
+        max_name_len: 12,
+        max_description_len: 8,
+    };
+    assert!(client
+        .try_set_guild_metadata_limits(&limits, &owner)
+        .is_err());
+    let inverted = GuildMetadataLimits {
+        min_name_len: 20,
+        max_name_len: 12,
+        max_description_len: 8,
+    };
+    assert!(client
+        .try_set_guild_metadata_limits(&inverted, &admin)
+        .is_err());
+    assert!(client.set_guild_metadata_limits(&limits, &admin));
+    assert_eq!(client.get_guild_metadata_limits(), limits);
+
+    // the existing guild's 19-char description stays as it was
+    let stored = env.as_contract(&contract_id, || {
+        crate::guild::storage::get_guild(&env, guild_id).unwrap()
+    });
+    assert_eq!(
+        stored.description,
+        String::from_str(&env, "A guild for testing")
+    );
+
+    assert!(client
+        .try_create_guild(
+            &String::from_str(&env, "abc"),
+            &String::from_str(&env, "ok"),
+            &owner,
+        )
+        .is_err());
+    assert!(client
+        .try_update_guild_metadata(
+            &guild_id,
+            &String::from_str(&env, "Test Guild"),
+            &String::from_str(&env, "far too long"),
+            &owner,
+        )
+        .is_err());
+
+    let outsider = Address::generate(&env);
+    assert!(client
+        .try_update_guild_metadata(
+            &guild_id,
+            &String::from_str(&env, "Renamed"),
+            &String::from_str(&env, "short"),
+            &outsider,
+        )
+        .is_err());
+
+    assert!(client.update_guild_metadata(
+        &guild_id,
+        &String::from_str(&env, "Renamed"),
+        &String::from_str(&env, "short"),
+        &owner,
+    ));
+    let stored = env.as_contract(&contract_id, || {
+        crate::guild::storage::get_guild(&env, guild_id).unwrap()
+    });
+    assert_eq!(stored.name, String::from_str(&env, "Renamed"));
+    assert_eq!(stored.description, String::from_str(&env, "short"));
+}
+
+/// Guests can observe the guild but cannot take any action in it.
+#[test]
+fn test_guest_role_is_read_only() {
+    let env = setup_env();
+    env.mock_all_auths();
+
+    let contract_id = register_and_init(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    let owner = Address::generate(&env);
+    let guild_id = create_test_guild(&client, &env, &owner);
+    let contributor = Address::generate(&env);
+    let guest = Address::generate(&env);
+    client.add_member(&guild_id, &contributor, &Role::Contributor, &owner);
+    client.add_member(&guild_id, &guest, &Role::Guest, &owner);
+
+    // Guest sits below Contributor in the hierarchy
+    assert!(client.is_member(&guild_id, &guest));
+    assert!(client.has_permission(&guild_id, &guest, &Role::Guest));
+    assert!(!client.has_permission(&guild_id, &guest, &Role::Contributor));
+    assert!(client.has_permission(&guild_id, &contributor, &Role::Guest));
+    assert!(client.has_permission(&guild_id, &owner, &Role::Guest));
+
+    let outsider = Address::generate(&env);
+    assert!(client
+        .try_add_member(&guild_id, &outsider, &Role::Guest, &guest)
+        .is_err());
+    assert!(client
+        .try_create_proposal(
+            &guild_id,
+            &guest,
+            &ProposalType::GeneralDecision,
+            &String::from_str(&env, "Guest proposal"),
+            &String::from_str(&env, "guests cannot propose"),
+            &None,
+        )
+        .is_err());
+
+    let proposal_id = client.create_proposal(
+        &guild_id,
+        &owner,
+        &ProposalType::GeneralDecision,
+        &String::from_str(&env, "Member vote"),
+        &String::from_str(&env, "guests cannot vote"),
+        &None,
+    );
+    assert!(client
+        .try_vote(&proposal_id, &guest, &VoteDecision::For)
+        .is_err());
+
+    let bounty_id = client.create_bounty(
+        &guild_id,
+        &owner,
+        &String::from_str(&env, "Task"),
+        &String::from_str(&env, "unfunded task"),
+        &0,
+        &Address::generate(&env),
+        &(env.ledger().timestamp() + 1000),
+    );
+    assert!(client.try_claim_bounty(&bounty_id, &guest).is_err());
+
+    // Guests can be promoted or removed like anyone else; the last owner stays protected
+    assert!(client.update_role(&guild_id, &guest, &Role::Member, &owner));
+    assert!(client.has_permission(&guild_id, &guest, &Role::Member));
+    assert!(client.remove_member(&guild_id, &guest, &owner));
+    assert!(client.try_remove_member(&guild_id, &owner, &owner).is_err());
+}
+
+/// The cached member count tracks joins, adds and removals (owner included)
+/// and is left alone by role changes.
+#[test]
+fn test_member_count_tracks_membership_changes() {
+    let env = setup_env();
+    env.mock_all_auths();
+
+    let contract_id = register_and_init(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    let owner = Address::generate(&env);
+    let guild_id = create_test_guild(&client, &env, &owner);
+    assert_eq!(client.get_member_count(&guild_id), 1);
+
+    let joiner = Address::generate(&env);
+    let added = Address::generate(&env);
+    client.join_guild(&guild_id, &joiner);
+    client.add_member(&guild_id, &added, &Role::Contributor, &owner);
+    assert_eq!(client.get_member_count(&guild_id), 3);
+
+    client.update_role(&guild_id, &added, &Role::Admin, &owner);
+    assert_eq!(client.get_member_count(&guild_id), 3);
+
+    client.remove_member(&guild_id, &joiner, &owner);
+    assert_eq!(client.get_member_count(&guild_id), 2);
+    assert_eq!(
+        client.get_member_count(&guild_id),
+        client.get_all_members(&guild_id).len()
+    );
+}
+
+/// `add_member_default` uses the guild's configured role, which can never be Owner.
+#[test]
+fn test_add_member_default_uses_configured_role() {
+    let env = setup_env();
+    env.mock_all_auths();
+
+    let contract_id = register_and_init(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    let owner = Address::generate(&env);
+    let admin = Address::generate(&env);
+    let guild_id = create_test_guild(&client, &env, &owner);
+    client.add_member(&guild_id, &admin, &Role::Admin, &owner);
+
+    assert_eq!(client.get_default_member_role(&guild_id), Role::Member);
+    let first = Address::generate(&env);
+    client.add_member_default(&guild_id, &first, &admin);
+    assert_eq!(client.get_member(&guild_id, &first).role, Role::Member);
+
+    assert!(client
+        .try_set_default_member_role(&guild_id, &Role::Owner, &owner)
+        .is_err());
+    assert!(client
+        .try_set_default_member_role(&guild_id, &Role::Contributor, &admin)
+        .is_err());
+    client.set_default_member_role(&guild_id, &Role::Contributor, &owner);
+    assert_eq!(client.get_default_member_role(&guild_id), Role::Contributor);
+
+    let second = Address::generate(&env);
+    client.add_member_default(&guild_id, &second, &admin);
+    assert_eq!(
+        client.get_member(&guild_id, &second).role,
+        Role::Contributor
+    );
+    assert_eq!(client.get_member_count(&guild_id), 4);
+}
+
+fn count_permission_denied(env: &Env) -> u32 {
+    let action = Symbol::new(env, "permission_denied");
+    let mut count = 0;
+    for (_, topics, _) in env.events().all().iter() {
+        if topics.len() == 2
+            && Symbol::try_from_val(env, &topics.get(1).unwrap()) == Ok(action.clone())
+        {
+            count += 1;
+        }
+    }
+    count
+}
+
+/// A rejected mutating call is reported; read-only permission queries are not.
+#[test]
+fn test_denied_permission_is_audited_but_queries_are_silent() {
+    let env = setup_env();
+    env.mock_all_auths();
+
+    let contract_id = register_and_init(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    let owner = Address::generate(&env);
+    let member = Address::generate(&env);
+    let guild_id = create_test_guild(&client, &env, &owner);
+    client.add_member(&guild_id, &member, &Role::Member, &owner);
+
+    env.as_contract(&contract_id, || {
+        let result = crate::guild::membership::update_guild_metadata(
+            &env,
+            guild_id,
+            String::from_str(&env, "Hijacked"),
+            String::from_str(&env, "Not allowed"),
+            member.clone(),
+        );
+        assert!(result.is_err());
+        assert_eq!(count_permission_denied(&env), 1);
+
+        assert!(!crate::guild::membership::has_permission(
+            &env,
+            guild_id,
+            member.clone(),
+            Role::Admin
+        ));
+        assert_eq!(count_permission_denied(&env), 1);
+    });
+
+    assert!(!client.has_permission(&guild_id, &member, &Role::Owner));
+    assert_eq!(count_permission_denied(&env), 1);
+}
@@ -5,6 +5,7 @@
 /// - Admin: Can manage members and contributors
 /// - Member: Can participate in guild activities
 /// - Contributor: Limited access, read-only in most cases
+/// - Guest: Observer that can see guild internals but take no actions
 #[contracttype]
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum Role {
@@ -12,14 +13,16 @@ pub enum Role {
     Admin = 1,
     Member = 2,
     Contributor = 3,
+    Guest = 4,
 }
 
 impl Role {
     /// Check if a role has permission for a required role level
-    /// Hierarchy: Owner > Admin > Member > Contributor
+    /// Hierarchy: Owner > Admin > Member > Contributor > Guest
     pub fn has_permission(&self, required_role: &Role) -> bool {
         match (self, required_role) {
             (Role::Owner, _) => true,
+            (_, Role::Guest) => true,
             (Role::Admin, Role::Admin)
             | (Role::Admin, Role::Member)
             | (Role::Admin, Role::Contributor) => true,
@@ -28,6 +31,11 @@ impl Role {
             _ => false,
         }
     }
+
+    /// Guests may read guild state but cannot vote, propose or delegate
+    pub fn is_read_only(&self) -> bool {
+        *self == Role::Guest
+    }
 }
 
 /// Guild struct containing guild metadata
@@ -60,6 +68,20 @@ pub struct GuildConfig {
     pub member_limit: u32,
 }
 
+/// Platform-wide length bounds for guild names and descriptions
+///
+/// Only new writes are checked, so tightening a bound leaves existing guilds valid.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GuildMetadataLimits {
+    /// Minimum name length (at least 1)
+    pub min_name_len: u32,
+    /// Maximum name length
+    pub max_name_len: u32,
+    /// Maximum description length
+    pub max_description_len: u32,
+}
+
 /// Member struct representing a guild member
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -118,3 +140,40 @@ pub struct RoleUpdatedEvent {
     pub old_role: Role,
     pub new_role: Role,
 }
+
+/// Event emitted when a member is suspended
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct MemberSuspendedEvent {
+    pub guild_id: u64,
+    pub address: Address,
+    pub suspended_by: Address,
+}
+
+/// Event emitted when a suspended member is reinstated
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct MemberReinstatedEvent {
+    pub guild_id: u64,
+    pub address: Address,
+    pub reinstated_by: Address,
+}
+
+/// Event emitted when a mutating entrypoint rejects a caller for lacking a role
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct PermissionDeniedEvent {
+    pub guild_id: u64,
+    pub actor: Address,
+    pub attempted_action: soroban_sdk::Symbol,
+    pub required_role: Role,
+}
+
+/// Event emitted when a guild's name or description is changed
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct GuildMetadataUpdatedEvent {
+    pub guild_id: u64,
+    pub name: soroban_sdk::String,
+    pub updated_by: Address,
+}
@@ -0,0 +1,107 @@
+use soroban_sdk::{symbol_short, Address, Env, Map, Symbol};
+
+use crate::guild::membership::enforce_permission;
+use crate::guild::types::Role;
+use crate::integration::types::{EffectiveMemberLevel, MemberLevelConfig};
+use crate::reputation::scoring::get_decayed_profile;
+use crate::subscription::storage::get_user_subscription;
+use crate::subscription::types::{MembershipTier, SubscriptionStatus};
+
+const LEVEL_CONFIG_KEY: Symbol = symbol_short!("i_lvlcfg");
+
+/// Levels granted per tier step when a guild has not configured a formula
+pub const DEFAULT_TIER_WEIGHT: u32 = 10;
+/// Decayed reputation needed per level when a guild has not configured a formula
+pub const DEFAULT_REPUTATION_PER_LEVEL: u64 = 100;
+
+fn tier_rank(tier: &MembershipTier) -> u32 {
+    match tier {
+        MembershipTier::Basic => 1,
+        MembershipTier::Standard => 2,
+        MembershipTier::Premium => 3,
+        MembershipTier::Enterprise => 4,
+    }
+}
+
+pub fn get_member_level_config(env: &Env, guild_id: u64) -> MemberLevelConfig {
+    let configs: Map<u64, MemberLevelConfig> = env
+        .storage()
+        .persistent()
+        .get(&LEVEL_CONFIG_KEY)
+        .unwrap_or_else(|| Map::new(env));
+    configs.get(guild_id).unwrap_or(MemberLevelConfig {
+        tier_weight: DEFAULT_TIER_WEIGHT,
+        reputation_per_level: DEFAULT_REPUTATION_PER_LEVEL,
+    })
+}
+
+/// Set how a guild combines subscription tier and reputation into a level.
+/// Only the guild owner may change the formula.
+pub fn set_member_level_config(
+    env: &Env,
+    guild_id: u64,
+    caller: Address,
+    config: MemberLevelConfig,
+) -> bool {
+    caller.require_auth();
+    if !enforce_permission(
+        env,
+        guild_id,
+        caller,
+        Role::Owner,
+        "set_member_level_config",
+    ) {
+        panic!("only guild owner can configure member levels");
+    }
+    if config.reputation_per_level == 0 {
+        panic!("reputation per level must be positive");
+    }
+
+    let mut configs: Map<u64, MemberLevelConfig> = env
+        .storage()
+        .persistent()
+        .get(&LEVEL_CONFIG_KEY)
+        .unwrap_or_else(|| Map::new(env));
+    configs.set(guild_id, config);
+    env.storage().persistent().set(&LEVEL_CONFIG_KEY, &configs);
+    true
+}
+
+/// Combine the member's paid tier in the guild with their decayed
+/// reputation: `tier_rank * tier_weight + reputation / reputation_per_level`,
+/// where tiers rank Basic = 1 through Enterprise = 4. Without a current
+/// subscription the tier term is zero and the level comes from reputation
+/// alone.
+pub fn get_effective_member_level(
+    env: &Env,
+    guild_id: u64,
+    address: Address,
+) -> EffectiveMemberLevel {
+    let config = get_member_level_config(env, guild_id);
+
+    // A subscription in its grace period keeps its tier until cancelled
+    let tier = get_user_subscription(env, &address, guild_id)
+        .filter(|sub| {
+            matches!(
+                sub.status,
+                SubscriptionStatus::Active | SubscriptionStatus::GracePeriod
+            )
+        })
+        .map(|sub| sub.current_tier);
+
+    let reputation = get_decayed_profile(env, &address, guild_id)
+        .map(|p| p.decayed_score)
+        .unwrap_or(0);
+
+    let rank = tier.as_ref().map(tier_rank).unwrap_or(0);
+    let tier_level = rank.saturating_mul(config.tier_weight);
+    let reputation_level = (reputation / config.reputation_per_level).min(u32::MAX as u64) as u32;
+
+    EffectiveMemberLevel {
+        address,
+        guild_id,
+        level: tier_level.saturating_add(reputation_level),
+        tier_rank: rank,
+        reputation,
+    }
+}
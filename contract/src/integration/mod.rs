@@ -1,6 +1,9 @@
 pub mod auth;
 pub mod events;
+pub mod levels;
+pub mod overview;
 pub mod registry;
+pub mod summary;
 pub mod types;
 
 #[cfg(test)]
@@ -0,0 +1,37 @@
+use soroban_sdk::Env;
+
+use crate::bounty::storage as bounty_storage;
+use crate::governance::storage as governance_storage;
+use crate::guild::storage as guild_storage;
+use crate::integration::types::GuildOverview;
+use crate::subscription::storage as subscription_storage;
+use crate::treasury::storage as treasury_storage;
+
+/// Everything a guild page needs in one read. Every count comes from a
+/// counter or index its module keeps up to date, so the cost does not grow
+/// with the guild's history. Modules the guild doesn't use report zero.
+pub fn get_guild_overview(env: &Env, guild_id: u64) -> GuildOverview {
+    let guild = guild_storage::get_guild(env, guild_id).expect("guild not found");
+
+    let mut overview = GuildOverview {
+        guild_id,
+        member_count: guild.member_count,
+        active_proposal_count: governance_storage::get_active_proposal_count(env, guild_id),
+        open_bounty_count: bounty_storage::get_open_bounty_count(env, guild_id),
+        treasury_balance_xlm: 0,
+        active_subscriber_count: 0,
+    };
+
+    if let Some(treasury) = treasury_storage::get_guild_treasury_id(env, guild_id)
+        .and_then(|treasury_id| treasury_storage::get_treasury(env, treasury_id))
+    {
+        overview.treasury_balance_xlm = treasury.balance_xlm;
+    }
+
+    for plan_id in subscription_storage::get_guild_plans(env, guild_id).iter() {
+        overview.active_subscriber_count +=
+            subscription_storage::get_plan_subscriber_count(env, plan_id);
+    }
+
+    overview
+}
@@ -0,0 +1,119 @@
+use soroban_sdk::{Address, Env, Map};
+
+use crate::bounty::storage as bounty_storage;
+use crate::bounty::types::BountyStatus;
+use crate::integration::types::AddressSummary;
+use crate::milestone::storage as milestone_storage;
+use crate::milestone::types::{MilestoneStatus, ProjectStatus};
+use crate::payment::distribution::get_recipient_amount;
+use crate::payment::storage as payment_storage;
+use crate::payment::types::DistributionStatus;
+use crate::subscription::storage as subscription_storage;
+use crate::subscription::types::SubscriptionStatus;
+
+/// Most recent records inspected per module. There are no per-address
+/// indexes, so older activity beyond this window is not counted and the
+/// summary is flagged as truncated.
+pub const SUMMARY_SCAN_LIMIT: u32 = 50;
+
+/// Add `amount` to the running total kept for `token`
+fn add_token_amount(totals: &mut Map<Option<Address>, i128>, token: Option<Address>, amount: i128) {
+    let current = totals.get(token.clone()).unwrap_or(0);
+    totals.set(token, current + amount);
+}
+
+/// Aggregate what `address` is owed or holding across bounties, milestones,
+/// payment pools and subscriptions. Amounts are totalled per token
+/// (`None` for native XLM).
+pub fn get_address_summary(env: &Env, address: Address) -> AddressSummary {
+    let mut summary = AddressSummary {
+        address: address.clone(),
+        claimed_bounties: 0,
+        pending_bounty_rewards: Map::new(env),
+        unreleased_milestones: 0,
+        unreleased_milestone_amount: Map::new(env),
+        pending_distributions: 0,
+        pending_distribution_amount: Map::new(env),
+        active_subscriptions: 0,
+        truncated: false,
+    };
+
+    // Bounties claimed and not yet paid out
+    let (bounties, truncated) = bounty_storage::get_recent_bounties(env, SUMMARY_SCAN_LIMIT);
+    summary.truncated |= truncated;
+    for bounty in bounties.iter() {
+        if bounty.claimer.as_ref() != Some(&address) {
+            continue;
+        }
+        if matches!(
+            bounty.status,
+            BountyStatus::Claimed | BountyStatus::UnderReview
+        ) {
+            summary.claimed_bounties += 1;
+            add_token_amount(
+                &mut summary.pending_bounty_rewards,
+                bounty.token.clone(),
+                bounty.reward_amount,
+            );
+        }
+    }
+
+    // Unreleased milestone payments on active projects
+    let total_projects = milestone_storage::get_total_projects(env);
+    summary.truncated |= total_projects > SUMMARY_SCAN_LIMIT as u64;
+    let first_project = total_projects.saturating_sub(SUMMARY_SCAN_LIMIT as u64) + 1;
+    for project_id in first_project..=total_projects {
+        let project = match milestone_storage::get_project(env, project_id) {
+            Some(p) => p,
+            None => continue,
+        };
+        if project.contributor != address || project.status != ProjectStatus::Active {
+            continue;
+        }
+        for milestone_id in milestone_storage::get_project_milestone_ids(env, project_id).iter() {
+            if let Some(m) = milestone_storage::get_milestone(env, milestone_id) {
+                if !m.is_payment_released
+                    && m.status != MilestoneStatus::Expired
+                    && m.status != MilestoneStatus::Failed
+                {
+                    summary.unreleased_milestones += 1;
+                    add_token_amount(
+                        &mut summary.unreleased_milestone_amount,
+                        m.token.clone(),
+                        m.payment_amount,
+                    );
+                }
+            }
+        }
+    }
+
+    // Shares of payment pools not yet distributed
+    let total_pools = payment_storage::get_total_pools(env);
+    summary.truncated |= total_pools > SUMMARY_SCAN_LIMIT as u64;
+    let first_pool = total_pools.saturating_sub(SUMMARY_SCAN_LIMIT as u64) + 1;
+    for pool_id in first_pool..=total_pools {
+        let pool = match payment_storage::get_payment_pool(env, pool_id) {
+            Some(p) if p.status == DistributionStatus::Pending => p,
+            _ => continue,
+        };
+        if let Ok(amount) = get_recipient_amount(env, pool_id, address.clone()) {
+            summary.pending_distributions += 1;
+            add_token_amount(&mut summary.pending_distribution_amount, pool.token, amount);
+        }
+    }
+
+    // Active subscriptions, newest first
+    let active = subscription_storage::get_active_subscriptions(env);
+    summary.truncated |= active.len() > SUMMARY_SCAN_LIMIT;
+    let first_sub = active.len().saturating_sub(SUMMARY_SCAN_LIMIT);
+    for i in first_sub..active.len() {
+        let sub_id = active.get(i).unwrap();
+        if let Some(sub) = subscription_storage::get_subscription(env, sub_id) {
+            if sub.subscriber == address && sub.status == SubscriptionStatus::Active {
+                summary.active_subscriptions += 1;
+            }
+        }
+    }
+
+    summary
+}
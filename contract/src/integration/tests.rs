@@ -2,13 +2,14 @@
 mod tests {
     use crate::integration::auth;
     use crate::integration::types::{
-        ContractType, CrossContractPermission, EventFilter, EventType,
+        ContractType, CrossContractPermission, EventFilter, EventType, MemberLevelConfig,
     };
     use crate::interfaces::{
         BountyContractCall, ContractCallResponse, ContractCallResult, GuildContractCall,
         PaymentContractCall,
     };
     use crate::payment::types::DistributionRule;
+    use crate::subscription::types::{BillingCycle, MembershipTier};
     use crate::upgrade::types::Version;
     use crate::utils::errors::IntegrationErrorCode;
     use crate::{guild::types::Role, StellarGuildsContract, StellarGuildsContractClient};
@@ -546,4 +547,218 @@ mod tests {
 
         env.as_contract(&contract_id, || auth::require_admin(&env, &outsider));
     }
+
+    #[test]
+    fn address_summary_aggregates_across_modules() {
+        let (env, _admin, contract_id) = setup();
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+        let owner = Address::generate(&env);
+        let worker = Address::generate(&env);
+        let guild_id = client.create_guild(
+            &String::from_str(&env, "Summary"),
+            &String::from_str(&env, "summary"),
+            &owner,
+        );
+
+        let token = env
+            .register_stellar_asset_contract_v2(owner.clone())
+            .address();
+        soroban_sdk::token::StellarAssetClient::new(&env, &token).mint(&owner, &1_000);
+        let bounty_id = client.create_bounty(
+            &guild_id,
+            &owner,
+            &String::from_str(&env, "Task"),
+            &String::from_str(&env, "task"),
+            &100i128,
+            &token,
+            &2_000u64,
+        );
+        client.fund_bounty(&bounty_id, &owner, &100i128);
+        client.approve_bounty(&bounty_id, &owner, &worker);
+        client.claim_bounty(&bounty_id, &worker);
+
+        let mut milestones = Vec::new(&env);
+        milestones.push_back(crate::milestone::types::MilestoneInput {
+            title: String::from_str(&env, "M1"),
+            description: String::from_str(&env, ""),
+            payment_amount: 250,
+            deadline: 5_000,
+            token: None,
+        });
+        client.create_project(
+            &guild_id,
+            &worker,
+            &milestones,
+            &250i128,
+            &1u64,
+            &None,
+            &false,
+        );
+
+        let pool_id =
+            client.create_payment_pool(&400i128, &None, &DistributionRule::EqualSplit, &owner);
+        client.add_recipient(&pool_id, &worker, &1u32, &owner);
+        client.add_recipient(&pool_id, &owner, &1u32, &owner);
+        let token_pool_id = client.create_payment_pool(
+            &300i128,
+            &Some(token.clone()),
+            &DistributionRule::EqualSplit,
+            &owner,
+        );
+        client.add_recipient(&token_pool_id, &worker, &1u32, &owner);
+
+        // Amounts are kept apart per token
+        let summary = client.get_address_summary(&worker);
+        let xlm: Option<Address> = None;
+        let token = Some(token);
+        assert_eq!(summary.claimed_bounties, 1);
+        assert_eq!(summary.pending_bounty_rewards.len(), 1);
+        assert_eq!(summary.pending_bounty_rewards.get(token.clone()), Some(100));
+        assert_eq!(summary.unreleased_milestones, 1);
+        assert_eq!(
+            summary.unreleased_milestone_amount.get(xlm.clone()),
+            Some(250)
+        );
+        assert_eq!(summary.pending_distributions, 2);
+        assert_eq!(summary.pending_distribution_amount.get(xlm), Some(200));
+        assert_eq!(summary.pending_distribution_amount.get(token), Some(300));
+        assert_eq!(summary.active_subscriptions, 0);
+        assert!(!summary.truncated);
+
+        let stranger = client.get_address_summary(&Address::generate(&env));
+        assert_eq!(
+            stranger.claimed_bounties + stranger.unreleased_milestones,
+            0
+        );
+        assert!(stranger.pending_distribution_amount.is_empty());
+    }
+
+    #[test]
+    fn effective_member_level_combines_tier_and_reputation() {
+        let (env, _admin, contract_id) = setup();
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+        let owner = Address::generate(&env);
+        let member = Address::generate(&env);
+        let guild_id = client.create_guild(
+            &String::from_str(&env, "Levels"),
+            &String::from_str(&env, "levels"),
+            &owner,
+        );
+
+        // No subscription: the level comes from reputation alone
+        client.record_contribution(
+            &guild_id,
+            &member,
+            &crate::reputation::types::ContributionType::BountyCompleted,
+            &1u64,
+        );
+        let level = client.get_effective_member_level(&guild_id, &member);
+        assert_eq!(level.tier_rank, 0);
+        assert_eq!(level.reputation, 100);
+        assert_eq!(level.level, 1);
+
+        let plan_id = client.create_subscription_plan(
+            &guild_id,
+            &String::from_str(&env, "Pro"),
+            &String::from_str(&env, "pro"),
+            &MembershipTier::Premium,
+            &50i128,
+            &None,
+            &BillingCycle::Monthly,
+            &Vec::new(&env),
+            &owner,
+        );
+        client.subscribe(&plan_id, &member, &true);
+        let level = client.get_effective_member_level(&guild_id, &member);
+        assert_eq!(level.tier_rank, 3);
+        assert_eq!(level.level, 3 * 10 + 1);
+
+        let config = MemberLevelConfig {
+            tier_weight: 5,
+            reputation_per_level: 50,
+        };
+        assert!(client
+            .try_set_member_level_config(&guild_id, &member, &config)
+            .is_err());
+        client.set_member_level_config(&guild_id, &owner, &config);
+        assert_eq!(
+            client.get_effective_member_level(&guild_id, &member).level,
+            3 * 5 + 2
+        );
+
+        let stranger = client.get_effective_member_level(&guild_id, &Address::generate(&env));
+        assert_eq!(stranger.level, 0);
+    }
+
+    #[test]
+    fn guild_overview_combines_module_counts() {
+        let (env, _admin, contract_id) = setup();
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+        let owner = Address::generate(&env);
+        let member = Address::generate(&env);
+        let guild_id = client.create_guild(
+            &String::from_str(&env, "Overview"),
+            &String::from_str(&env, "overview"),
+            &owner,
+        );
+
+        // A guild using none of the optional modules reports zeros
+        let empty = client.get_guild_overview(&guild_id);
+        assert_eq!(empty.member_count, 1);
+        assert_eq!(empty.active_proposal_count, 0);
+        assert_eq!(empty.open_bounty_count, 0);
+        assert_eq!(empty.treasury_balance_xlm, 0);
+        assert_eq!(empty.active_subscriber_count, 0);
+
+        client.add_member(&guild_id, &member, &Role::Member, &owner);
+        let proposal_id = client.create_proposal(
+            &guild_id,
+            &owner,
+            &crate::governance::ProposalType::GeneralDecision,
+            &String::from_str(&env, "Proposal"),
+            &String::from_str(&env, "proposal"),
+            &None,
+        );
+        let bounty_id = client.create_bounty(
+            &guild_id,
+            &owner,
+            &String::from_str(&env, "Task"),
+            &String::from_str(&env, "task"),
+            &100i128,
+            &Address::generate(&env),
+            &2_000u64,
+        );
+
+        let treasury_id = client.initialize_treasury(&guild_id, &vec![&env, owner.clone()], &1);
+        client.deposit_treasury(&treasury_id, &owner, &500i128, &None);
+
+        let plan_id = client.create_subscription_plan(
+            &guild_id,
+            &String::from_str(&env, "Plan"),
+            &String::from_str(&env, "plan"),
+            &MembershipTier::Basic,
+            &10i128,
+            &None,
+            &BillingCycle::Monthly,
+            &Vec::new(&env),
+            &owner,
+        );
+        client.subscribe(&plan_id, &member, &true);
+
+        let overview = client.get_guild_overview(&guild_id);
+        assert_eq!(overview.member_count, 2);
+        assert_eq!(overview.active_proposal_count, 1);
+        assert_eq!(overview.open_bounty_count, 1);
+        assert_eq!(overview.treasury_balance_xlm, 500);
+        assert_eq!(overview.active_subscriber_count, 1);
+
+        // Counters follow status changes; the first treasury stays primary
+        assert!(client.cancel_proposal(&proposal_id, &owner));
+        assert!(client.cancel_bounty(&bounty_id, &owner));
+        client.initialize_treasury(&guild_id, &vec![&env, owner.clone()], &1);
+        let overview = client.get_guild_overview(&guild_id);
+        assert_eq!(overview.active_proposal_count, 0);
+        assert_eq!(overview.open_bounty_count, 0);
+        assert_eq!(overview.treasury_balance_xlm, 500);
+    }
 }
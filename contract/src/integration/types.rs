@@ -1,5 +1,5 @@
 use crate::upgrade::types::Version;
-use soroban_sdk::{contracttype, Address, String};
+use soroban_sdk::{contracttype, Address, Map, String};
 
 #[contracttype]
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
@@ -101,3 +101,66 @@ pub enum CrossContractPermission {
     RegistryWrite = 3,
     Admin = 4,
 }
+
+/// Cross-module view of what an address is owed or holding.
+/// See `integration::summary` for how far back each module is scanned.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AddressSummary {
+    pub address: Address,
+    /// Bounties claimed by the address that are not yet completed
+    pub claimed_bounties: u32,
+    /// Rewards of those bounties per token (`None` for native XLM)
+    pub pending_bounty_rewards: Map<Option<Address>, i128>,
+    /// Unpaid milestones on the address's active projects
+    pub unreleased_milestones: u32,
+    /// Payments of those milestones per token
+    pub unreleased_milestone_amount: Map<Option<Address>, i128>,
+    /// Pending payment pools the address is a recipient of
+    pub pending_distributions: u32,
+    /// The address's shares of those pools per token
+    pub pending_distribution_amount: Map<Option<Address>, i128>,
+    pub active_subscriptions: u32,
+    /// Whether any module had more records than the scan limit
+    pub truncated: bool,
+}
+
+/// Per-guild formula combining subscription tier and reputation into a level
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MemberLevelConfig {
+    /// Levels granted per tier step (Basic = 1 step, Enterprise = 4)
+    pub tier_weight: u32,
+    /// Decayed reputation points per additional level
+    pub reputation_per_level: u64,
+}
+
+/// A member's combined level, with the inputs it was derived from
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EffectiveMemberLevel {
+    pub address: Address,
+    pub guild_id: u64,
+    pub level: u32,
+    /// Rank of the member's current subscription tier in the guild
+    /// (Basic = 1 through Enterprise = 4, 0 without a subscription)
+    pub tier_rank: u32,
+    pub reputation: u64,
+}
+
+/// Headline numbers for a guild page, gathered in one call.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GuildOverview {
+    pub guild_id: u64,
+    pub member_count: u32,
+    /// Proposals in `Active` status, including ones whose voting period
+    /// has ended but which have not been finalized yet
+    pub active_proposal_count: u32,
+    /// Bounties not yet completed, cancelled or expired
+    pub open_bounty_count: u32,
+    /// XLM balance of the guild's primary treasury
+    pub treasury_balance_xlm: i128,
+    /// Uncancelled subscriptions across the guild's plans
+    pub active_subscriber_count: u32,
+}
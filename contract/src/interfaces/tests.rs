@@ -1,7 +1,7 @@
 #[cfg(test)]
 mod tests {
     use crate::bounty::types::{Bounty, BountyStatus};
-    use crate::dispute::types::{Dispute, DisputeReference, DisputeStatus};
+    use crate::dispute::types::{Dispute, DisputeReference, DisputeStatus, VoterEligibility};
     use crate::governance::types::{ExecutionPayload, Proposal, ProposalStatus, ProposalType};
     use crate::guild::types::{Member, Role};
     use crate::interfaces::{
@@ -67,12 +67,13 @@ mod tests {
                 description: String::from_str(&env, "Bounty desc"),
                 reward_amount: 100,
                 funded_amount: 100,
-                token: Address::generate(&env),
+                token: Some(Address::generate(&env)),
                 status: BountyStatus::Funded,
                 claimer: None,
                 submission_url: None,
                 created_at: 1,
                 expires_at: 2,
+                funding_treasury_id: None,
             }
         }
 
@@ -104,6 +105,7 @@ mod tests {
                 vote_count: 0,
                 resolved_at: None,
                 resolution_executed: false,
+                eligibility: VoterEligibility::GuildMembers,
             }
         }
 
@@ -128,6 +130,7 @@ mod tests {
                 execution_payload: ExecutionPayload::GeneralDecision,
                 passed_at: None,
                 executed_at: None,
+                tags: Vec::new(&env),
             }
         }
 
@@ -151,6 +154,8 @@ mod tests {
                 last_updated_at: 1,
                 version: 1,
                 is_payment_released: false,
+                token: None,
+                rejection_count: 0,
             }
         }
 
@@ -187,6 +192,9 @@ mod tests {
                 auto_renew: true,
                 cancelled_at: None,
                 cancellation_reason: None,
+                first_cycle_amount: None,
+                next_retry_at: None,
+                pending_plan_id: None,
             }
         }
 
@@ -226,10 +234,13 @@ mod tests {
                     recipient: None,
                     proposer: Address::generate(&env),
                     approvals: Vec::new(&env),
+                    required_approvals: 1,
                     status: TransactionStatus::Executed,
                     created_at: 1,
                     expires_at: 2,
                     reason: String::from_str(&env, "seed"),
+                    requires_acceptance: false,
+                    acceptance_deadline: 0,
                 }],
             )
         }
@@ -4,7 +4,8 @@ pub mod tracker;
 ///
 /// This module provides project and milestone management for long-running
 /// work with phased payments. It integrates with the treasury module using
-/// `TransactionType::MilestonePayment` for accounting.
+/// `TransactionType::MilestonePayment` for accounting, or pays out of an
+/// upfront escrow for escrow-backed projects.
 ///
 /// - `types`: Core data structures and events
 /// - `storage`: Persistent storage helpers
@@ -13,12 +14,23 @@ pub mod types;
 
 // Re-export main functions
 pub use tracker::{
-    add_milestone, approve_milestone, cancel_project, create_project, extend_milestone_deadline,
-    get_milestone_view, get_project_progress, reject_milestone, release_milestone_payment,
-    start_milestone, submit_milestone,
+    add_milestone, approve_deadline_extension, approve_milestone, batch_approve_milestones,
+    cancel_project, create_escrow_project, create_jit_project, create_project,
+    extend_milestone_deadline, get_deadline_extension_request, get_guild_projects,
+    get_max_milestones_per_project, get_milestone_progress, get_milestone_view,
+    get_milestones_by_status, get_pending_payments_for, get_project_milestones,
+    get_project_progress, get_project_view, get_queued_milestone_payments, get_rejection_policy,
+    get_start_funding_check, reject_milestone, release_milestone_payment,
+    request_deadline_extension, set_max_milestones_per_project, set_payout_address,
+    set_rejection_policy, set_start_funding_check, start_milestone, submit_milestone,
+    update_milestone_progress,
 };
 #[allow(unused_imports)]
-pub use types::{Milestone, MilestoneInput, MilestoneStatus, Project, ProjectStatus};
+pub use types::{
+    DeadlineExtensionRequest, Milestone, MilestoneApprovalOutcome, MilestoneApprovalResult,
+    MilestoneFailureAction, MilestoneInput, MilestoneProgress, MilestoneStatus, Project,
+    ProjectFunding, ProjectStatus, RejectionPolicy, StartFundingCheck,
+};
 
 // Tests are disabled pending treasury integration
 #[cfg(test)]
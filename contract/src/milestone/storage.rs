@@ -1,6 +1,9 @@
-﻿use soroban_sdk::{contracttype, Env, Vec};
+﻿use soroban_sdk::{contracttype, Address, Env, Vec};
 
-use crate::milestone::types::{Milestone, Project};
+use crate::milestone::types::{
+    DeadlineExtensionRequest, Milestone, MilestoneFailureAction, MilestoneProgress, Project,
+    RejectionPolicy, StartFundingCheck,
+};
 
 #[contracttype]
 pub enum MilestoneStorageKey {
@@ -9,6 +12,14 @@ pub enum MilestoneStorageKey {
     Project(u64),
     Milestone(u64),
     ProjectMilestones(u64), // Vec<milestone_id>
+    ExtensionRequest(u64),  // milestone_id -> pending DeadlineExtensionRequest
+    GuildProjects(u64),     // guild_id -> Vec<project_id>
+    MaxMilestonesPerProject,
+    GuildStartFundingCheck(u64),  // guild_id -> StartFundingCheck
+    GuildRejectionPolicy(u64),    // guild_id -> RejectionPolicy
+    QueuedPayments(u64),          // project_id -> Vec<milestone_id> approved but unpaid
+    ContributorProjects(Address), // contributor -> Vec<project_id>
+    MilestoneProgress(u64),       // milestone_id -> MilestoneProgress
 }
 
 #[allow(dead_code)]
@@ -50,6 +61,58 @@ pub fn get_next_milestone_id(env: &Env) -> u64 {
     current
 }
 
+/// Number of projects created so far (IDs run from 1 to this value)
+pub fn get_total_projects(env: &Env) -> u64 {
+    let next: u64 = env
+        .storage()
+        .persistent()
+        .get(&MilestoneStorageKey::NextProjectId)
+        .unwrap_or(1);
+    next.saturating_sub(1)
+}
+
+pub fn get_max_milestones(env: &Env) -> Option<u32> {
+    env.storage()
+        .persistent()
+        .get(&MilestoneStorageKey::MaxMilestonesPerProject)
+}
+
+pub fn set_max_milestones(env: &Env, max: u32) {
+    env.storage()
+        .persistent()
+        .set(&MilestoneStorageKey::MaxMilestonesPerProject, &max);
+}
+
+pub fn get_start_funding_check(env: &Env, guild_id: u64) -> StartFundingCheck {
+    env.storage()
+        .persistent()
+        .get(&MilestoneStorageKey::GuildStartFundingCheck(guild_id))
+        .unwrap_or(StartFundingCheck::Disabled)
+}
+
+pub fn set_start_funding_check(env: &Env, guild_id: u64, check: &StartFundingCheck) {
+    env.storage().persistent().set(
+        &MilestoneStorageKey::GuildStartFundingCheck(guild_id),
+        check,
+    );
+}
+
+pub fn get_rejection_policy(env: &Env, guild_id: u64) -> RejectionPolicy {
+    env.storage()
+        .persistent()
+        .get(&MilestoneStorageKey::GuildRejectionPolicy(guild_id))
+        .unwrap_or(RejectionPolicy {
+            max_rejections: 0,
+            on_failure: MilestoneFailureAction::FreeAllocation,
+        })
+}
+
+pub fn set_rejection_policy(env: &Env, guild_id: u64, policy: &RejectionPolicy) {
+    env.storage()
+        .persistent()
+        .set(&MilestoneStorageKey::GuildRejectionPolicy(guild_id), policy);
+}
+
 pub fn store_project(env: &Env, project: &Project) {
     env.storage()
         .persistent()
@@ -62,6 +125,55 @@ pub fn get_project(env: &Env, project_id: u64) -> Option<Project> {
         .get(&MilestoneStorageKey::Project(project_id))
 }
 
+/// Record a new project in its guild's index. Entries are never removed, so
+/// cancelled and completed projects stay listable.
+pub fn append_project_to_guild(env: &Env, guild_id: u64, project_id: u64) {
+    let key = MilestoneStorageKey::GuildProjects(guild_id);
+    let mut ids: Vec<u64> = env
+        .storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or(Vec::new(env));
+    ids.push_back(project_id);
+    env.storage().persistent().set(&key, &ids);
+}
+
+pub fn append_project_to_contributor(env: &Env, contributor: &Address, project_id: u64) {
+    let key = MilestoneStorageKey::ContributorProjects(contributor.clone());
+    // a contributor without an index yet is seeded by the scan, which may
+    // already include this project
+    let mut ids = get_contributor_project_ids(env, contributor);
+    if !ids.contains(project_id) {
+        ids.push_back(project_id);
+    }
+    env.storage().persistent().set(&key, &ids);
+}
+
+/// Projects created for a contributor, in creation order. Contributors whose
+/// projects predate the index are found by scanning every project.
+pub fn get_contributor_project_ids(env: &Env, contributor: &Address) -> Vec<u64> {
+    let key = MilestoneStorageKey::ContributorProjects(contributor.clone());
+    env.storage().persistent().get(&key).unwrap_or_else(|| {
+        let mut ids = Vec::new(env);
+        for id in 1..=get_total_projects(env) {
+            if let Some(project) = get_project(env, id) {
+                if project.contributor == *contributor {
+                    ids.push_back(id);
+                }
+            }
+        }
+        ids
+    })
+}
+
+pub fn get_guild_project_ids(env: &Env, guild_id: u64) -> Vec<u64> {
+    let key = MilestoneStorageKey::GuildProjects(guild_id);
+    env.storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or(Vec::new(env))
+}
+
 pub fn store_milestone(env: &Env, milestone: &Milestone) {
     env.storage()
         .persistent()
@@ -74,6 +186,25 @@ pub fn get_milestone(env: &Env, milestone_id: u64) -> Option<Milestone> {
         .get(&MilestoneStorageKey::Milestone(milestone_id))
 }
 
+pub fn get_milestone_progress(env: &Env, milestone_id: u64) -> Option<MilestoneProgress> {
+    env.storage()
+        .persistent()
+        .get(&MilestoneStorageKey::MilestoneProgress(milestone_id))
+}
+
+pub fn set_milestone_progress(env: &Env, milestone_id: u64, progress: &MilestoneProgress) {
+    env.storage().persistent().set(
+        &MilestoneStorageKey::MilestoneProgress(milestone_id),
+        progress,
+    );
+}
+
+pub fn remove_milestone_progress(env: &Env, milestone_id: u64) {
+    env.storage()
+        .persistent()
+        .remove(&MilestoneStorageKey::MilestoneProgress(milestone_id));
+}
+
 pub fn append_milestone_to_project(env: &Env, project_id: u64, milestone_id: u64) {
     let key = MilestoneStorageKey::ProjectMilestones(project_id);
     let mut ids: Vec<u64> = env
@@ -92,3 +223,51 @@ pub fn get_project_milestone_ids(env: &Env, project_id: u64) -> Vec<u64> {
         .get(&key)
         .unwrap_or(Vec::new(env))
 }
+
+/// Milestones of a project that were approved but whose payment is still
+/// waiting to be released
+pub fn get_queued_payments(env: &Env, project_id: u64) -> Vec<u64> {
+    env.storage()
+        .persistent()
+        .get(&MilestoneStorageKey::QueuedPayments(project_id))
+        .unwrap_or(Vec::new(env))
+}
+
+pub fn queue_payment(env: &Env, project_id: u64, milestone_id: u64) {
+    let mut ids = get_queued_payments(env, project_id);
+    if !ids.contains(milestone_id) {
+        ids.push_back(milestone_id);
+        env.storage()
+            .persistent()
+            .set(&MilestoneStorageKey::QueuedPayments(project_id), &ids);
+    }
+}
+
+pub fn dequeue_payment(env: &Env, project_id: u64, milestone_id: u64) {
+    let mut ids = get_queued_payments(env, project_id);
+    if let Some(index) = ids.first_index_of(milestone_id) {
+        ids.remove(index);
+        env.storage()
+            .persistent()
+            .set(&MilestoneStorageKey::QueuedPayments(project_id), &ids);
+    }
+}
+
+pub fn store_extension_request(env: &Env, request: &DeadlineExtensionRequest) {
+    env.storage().persistent().set(
+        &MilestoneStorageKey::ExtensionRequest(request.milestone_id),
+        request,
+    );
+}
+
+pub fn get_extension_request(env: &Env, milestone_id: u64) -> Option<DeadlineExtensionRequest> {
+    env.storage()
+        .persistent()
+        .get(&MilestoneStorageKey::ExtensionRequest(milestone_id))
+}
+
+pub fn remove_extension_request(env: &Env, milestone_id: u64) {
+    env.storage()
+        .persistent()
+        .remove(&MilestoneStorageKey::ExtensionRequest(milestone_id));
+}
@@ -5,8 +5,13 @@
 //!
 //! NOTE: Payment release tests are excluded as they require treasury integration.
 
+use crate::allowance::AllowanceOperation;
 use crate::guild::types::Role;
-use crate::milestone::types::{MilestoneInput, MilestoneStatus};
+use crate::milestone::storage::MilestoneStorageKey;
+use crate::milestone::types::{
+    MilestoneApprovalOutcome, MilestoneFailureAction, MilestoneInput, MilestoneStatus,
+    ProjectStatus, StartFundingCheck,
+};
 use crate::StellarGuildsContract;
 use crate::StellarGuildsContractClient;
 use soroban_sdk::testutils::{Address as _, Ledger, LedgerInfo};
@@ -102,6 +107,7 @@ fn test_create_project_success() {
         description: String::from_str(&env, "Initial development"),
         payment_amount: 100_000,
         deadline: now + 86400,
+        token: None,
     });
 
     milestones.push_back(MilestoneInput {
@@ -109,6 +115,7 @@ fn test_create_project_success() {
         description: String::from_str(&env, "Testing phase"),
         payment_amount: 50_000,
         deadline: now + 2 * 86400,
+        token: None,
     });
 
     let project_id = client.create_project(
@@ -181,6 +188,7 @@ fn test_create_project_zero_amount_fails() {
         description: String::from_str(&env, "Work"),
         payment_amount: 1000,
         deadline: now + 86400,
+        token: None,
     });
 
     client.create_project(
@@ -217,6 +225,7 @@ fn test_create_project_overallocated_fails() {
         description: String::from_str(&env, ""),
         payment_amount: 60_000,
         deadline: now + 86400,
+        token: None,
     });
 
     milestones.push_back(MilestoneInput {
@@ -224,6 +233,7 @@ fn test_create_project_overallocated_fails() {
         description: String::from_str(&env, ""),
         payment_amount: 60_000,
         deadline: now + 2 * 86400,
+        token: None,
     });
 
     // Total milestones = 120k, but budget is only 100k
@@ -260,6 +270,7 @@ fn test_create_project_past_deadline_fails() {
         description: String::from_str(&env, ""),
         payment_amount: 1000,
         deadline: 500, // Past deadline
+        token: None,
     });
 
     client.create_project(
@@ -297,6 +308,7 @@ fn test_start_milestone_success() {
         description: String::from_str(&env, ""),
         payment_amount: 1000,
         deadline: now + 86400,
+        token: None,
     });
 
     client.create_project(
@@ -342,6 +354,7 @@ fn test_start_milestone_wrong_contributor_fails() {
         description: String::from_str(&env, ""),
         payment_amount: 1000,
         deadline: now + 86400,
+        token: None,
     });
 
     client.create_project(
@@ -382,6 +395,7 @@ fn test_submit_milestone_success() {
         description: String::from_str(&env, ""),
         payment_amount: 1000,
         deadline: now + 86400,
+        token: None,
     });
 
     client.create_project(
@@ -431,6 +445,7 @@ fn test_submit_milestone_before_starting_fails() {
         description: String::from_str(&env, ""),
         payment_amount: 1000,
         deadline: now + 86400,
+        token: None,
     });
 
     client.create_project(
@@ -476,6 +491,7 @@ fn test_approve_milestone_success() {
         description: String::from_str(&env, ""),
         payment_amount: 1000,
         deadline: now + 86400,
+        token: None,
     });
 
     client.create_project(
@@ -527,6 +543,7 @@ fn test_approve_milestone_non_admin_fails() {
         description: String::from_str(&env, ""),
         payment_amount: 1000,
         deadline: now + 86400,
+        token: None,
     });
 
     client.create_project(
@@ -573,6 +590,7 @@ fn test_approve_milestone_not_submitted_fails() {
         description: String::from_str(&env, ""),
         payment_amount: 1000,
         deadline: now + 86400,
+        token: None,
     });
 
     client.create_project(
@@ -617,6 +635,7 @@ fn test_reject_milestone_success() {
         description: String::from_str(&env, ""),
         payment_amount: 1000,
         deadline: now + 86400,
+        token: None,
     });
 
     client.create_project(
@@ -667,6 +686,7 @@ fn test_resubmit_after_rejection() {
         description: String::from_str(&env, ""),
         payment_amount: 1000,
         deadline: now + 86400,
+        token: None,
     });
 
     client.create_project(
@@ -729,6 +749,7 @@ fn test_sequential_prevents_out_of_order_start() {
         description: String::from_str(&env, ""),
         payment_amount: 1000,
         deadline: now + 86400,
+        token: None,
     });
 
     milestones.push_back(MilestoneInput {
@@ -736,6 +757,7 @@ fn test_sequential_prevents_out_of_order_start() {
         description: String::from_str(&env, ""),
         payment_amount: 1000,
         deadline: now + 2 * 86400,
+        token: None,
     });
 
     client.create_project(
@@ -777,6 +799,7 @@ fn test_sequential_allows_second_after_first_approved() {
         description: String::from_str(&env, ""),
         payment_amount: 1000,
         deadline: now + 86400,
+        token: None,
     });
 
     milestones.push_back(MilestoneInput {
@@ -784,6 +807,7 @@ fn test_sequential_allows_second_after_first_approved() {
         description: String::from_str(&env, ""),
         payment_amount: 1000,
         deadline: now + 2 * 86400,
+        token: None,
     });
 
     let project_id = client.create_project(
@@ -842,6 +866,7 @@ fn test_parallel_allows_any_order() {
         description: String::from_str(&env, ""),
         payment_amount: 1000,
         deadline: now + 86400,
+        token: None,
     });
 
     milestones.push_back(MilestoneInput {
@@ -849,6 +874,7 @@ fn test_parallel_allows_any_order() {
         description: String::from_str(&env, ""),
         payment_amount: 1000,
         deadline: now + 2 * 86400,
+        token: None,
     });
 
     client.create_project(
@@ -893,6 +919,7 @@ fn test_progress_calculation() {
         description: String::from_str(&env, ""),
         payment_amount: 1000,
         deadline: now + 86400,
+        token: None,
     });
 
     milestones.push_back(MilestoneInput {
@@ -900,6 +927,7 @@ fn test_progress_calculation() {
         description: String::from_str(&env, ""),
         payment_amount: 1000,
         deadline: now + 2 * 86400,
+        token: None,
     });
 
     milestones.push_back(MilestoneInput {
@@ -907,6 +935,7 @@ fn test_progress_calculation() {
         description: String::from_str(&env, ""),
         payment_amount: 1000,
         deadline: now + 3 * 86400,
+        token: None,
     });
 
     milestones.push_back(MilestoneInput {
@@ -914,6 +943,7 @@ fn test_progress_calculation() {
         description: String::from_str(&env, ""),
         payment_amount: 1000,
         deadline: now + 4 * 86400,
+        token: None,
     });
 
     let project_id = client.create_project(
@@ -972,6 +1002,7 @@ fn test_add_milestone_to_existing_project() {
         description: String::from_str(&env, ""),
         payment_amount: 1000,
         deadline: now + 86400,
+        token: None,
     });
 
     let project_id = client.create_project(
@@ -1027,6 +1058,7 @@ fn test_add_milestone_non_admin_fails() {
         description: String::from_str(&env, ""),
         payment_amount: 1000,
         deadline: now + 86400,
+        token: None,
     });
 
     let project_id = client.create_project(
@@ -1074,6 +1106,7 @@ fn test_extend_milestone_deadline() {
         description: String::from_str(&env, ""),
         payment_amount: 1000,
         deadline: now + 86400,
+        token: None,
     });
 
     client.create_project(
@@ -1096,6 +1129,292 @@ fn test_extend_milestone_deadline() {
     assert_eq!(milestone.deadline, new_deadline);
 }
 
+// ============ Milestone Listing Tests ============
+
+#[test]
+fn test_list_project_milestones_and_filter_by_status() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+    let contributor = Address::generate(&env);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    let guild_id = setup_guild(&client, &env, &owner);
+
+    let now = env.ledger().timestamp();
+    let mut milestones: Vec<MilestoneInput> = Vec::new(&env);
+    for _ in 0..3 {
+        milestones.push_back(MilestoneInput {
+            title: String::from_str(&env, "M"),
+            description: String::from_str(&env, ""),
+            payment_amount: 100,
+            deadline: now + 86400,
+            token: None,
+        });
+    }
+
+    let project_id = client.create_project(
+        &guild_id,
+        &contributor,
+        &milestones,
+        &300i128,
+        &1u64,
+        &None,
+        &false,
+    );
+
+    client.start_milestone(&2u64, &contributor);
+    client.submit_milestone(&2u64, &String::from_str(&env, "https://proof"));
+
+    let all = client.get_project_milestones(&project_id, &0u32, &10u32);
+    assert_eq!(all.len(), 3);
+    assert_eq!(all.get(0).unwrap().order, 1);
+
+    let page = client.get_project_milestones(&project_id, &1u32, &1u32);
+    assert_eq!(page.len(), 1);
+    assert_eq!(page.get(0).unwrap().id, 2);
+
+    let submitted =
+        client.get_milestones_by_status(&project_id, &MilestoneStatus::Submitted, &0u32, &10u32);
+    assert_eq!(submitted.len(), 1);
+    assert_eq!(submitted.get(0).unwrap().id, 2);
+
+    let pending =
+        client.get_milestones_by_status(&project_id, &MilestoneStatus::Pending, &1u32, &10u32);
+    assert_eq!(pending.len(), 1);
+    assert_eq!(pending.get(0).unwrap().id, 3);
+
+    assert_eq!(
+        client
+            .get_project_milestones(&project_id, &0u32, &0u32)
+            .len(),
+        0
+    );
+}
+
+// ============ Deadline Extension Request Tests ============
+
+fn setup_single_milestone_project(
+    env: &Env,
+    client: &StellarGuildsContractClient<'_>,
+    owner: &Address,
+    contributor: &Address,
+) -> u64 {
+    let guild_id = setup_guild(client, env, owner);
+    let now = env.ledger().timestamp();
+    let mut milestones: Vec<MilestoneInput> = Vec::new(env);
+    milestones.push_back(MilestoneInput {
+        title: String::from_str(env, "M1"),
+        description: String::from_str(env, ""),
+        payment_amount: 1000,
+        deadline: now + 86400,
+        token: None,
+    });
+    client.create_project(
+        &guild_id,
+        contributor,
+        &milestones,
+        &1000i128,
+        &1u64,
+        &None,
+        &false,
+    )
+}
+
+#[test]
+fn test_request_and_approve_deadline_extension() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+    let contributor = Address::generate(&env);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+    setup_single_milestone_project(&env, &client, &owner, &contributor);
+
+    let new_deadline = 1000 + 3 * 86400;
+    assert!(client.request_deadline_extension(&1u64, &new_deadline, &contributor));
+
+    let request = client.get_deadline_extension_request(&1u64).unwrap();
+    assert_eq!(request.new_deadline, new_deadline);
+    assert_eq!(request.requested_by, contributor);
+    // Deadline untouched until an admin approves
+    assert_eq!(client.get_milestone(&1u64).deadline, 1000 + 86400);
+
+    assert!(client.approve_deadline_extension(&1u64, &owner));
+    assert_eq!(client.get_milestone(&1u64).deadline, new_deadline);
+    assert!(client.get_deadline_extension_request(&1u64).is_none());
+}
+
+#[test]
+#[should_panic(expected = "new deadline must be in the future and after current deadline")]
+fn test_request_earlier_deadline_rejected() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+    let contributor = Address::generate(&env);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+    setup_single_milestone_project(&env, &client, &owner, &contributor);
+
+    client.request_deadline_extension(&1u64, &(1000 + 3600), &contributor);
+}
+
+#[test]
+#[should_panic(expected = "only project contributor can request extension")]
+fn test_request_extension_by_non_contributor_fails() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+    let contributor = Address::generate(&env);
+    let other = Address::generate(&env);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+    setup_single_milestone_project(&env, &client, &owner, &contributor);
+
+    client.request_deadline_extension(&1u64, &(1000 + 3 * 86400), &other);
+}
+
+#[test]
+#[should_panic(expected = "new deadline must be in the future and after current deadline")]
+fn test_approve_stale_extension_request_revalidates() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+    let contributor = Address::generate(&env);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+    setup_single_milestone_project(&env, &client, &owner, &contributor);
+
+    client.request_deadline_extension(&1u64, &(1000 + 2 * 86400), &contributor);
+
+    // Request is approved only after its proposed deadline has passed
+    set_ledger_timestamp(&env, 1000 + 2 * 86400 + 1);
+    client.approve_deadline_extension(&1u64, &owner);
+}
+
+#[test]
+#[should_panic(expected = "caller must be guild admin")]
+fn test_approve_extension_non_admin_fails() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+    let contributor = Address::generate(&env);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+    setup_single_milestone_project(&env, &client, &owner, &contributor);
+
+    client.request_deadline_extension(&1u64, &(1000 + 3 * 86400), &contributor);
+    client.approve_deadline_extension(&1u64, &contributor);
+}
+
+// ============ Expiry Tests ============
+
+fn setup_submitted_funded_milestone(
+    env: &Env,
+    client: &StellarGuildsContractClient<'_>,
+    owner: &Address,
+    contributor: &Address,
+) -> u64 {
+    let guild_id = setup_guild(client, env, owner);
+    let treasury_id = create_treasury_with_funds(client, env, guild_id, owner, 5000i128);
+
+    let now = env.ledger().timestamp();
+    let mut milestones: Vec<MilestoneInput> = Vec::new(env);
+    milestones.push_back(MilestoneInput {
+        title: String::from_str(env, "M1"),
+        description: String::from_str(env, ""),
+        payment_amount: 1000,
+        deadline: now + 86400,
+        token: None,
+    });
+    let project_id = client.create_project(
+        &guild_id,
+        contributor,
+        &milestones,
+        &1000i128,
+        &treasury_id,
+        &None,
+        &false,
+    );
+
+    client.start_milestone(&1u64, contributor);
+    client.submit_milestone(&1u64, &String::from_str(env, "https://proof"));
+    project_id
+}
+
+#[test]
+fn test_milestone_approved_before_deadline_never_expires() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+    let contributor = Address::generate(&env);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+    let project_id = setup_submitted_funded_milestone(&env, &client, &owner, &contributor);
+
+    // Approved on the last second before the deadline
+    set_ledger_timestamp(&env, 1000 + 86400);
+    assert!(client.approve_milestone(&1u64, &owner));
+
+    set_ledger_timestamp(&env, 1000 + 10 * 86400);
+    let milestone = client.get_milestone(&1u64);
+    assert_eq!(milestone.status, MilestoneStatus::Approved);
+    assert!(milestone.is_payment_released);
+    assert_eq!(client.get_project_progress(&project_id), (1, 1, 100));
+
+    // An approved milestone's deadline can no longer be moved
+    let extended = client.try_extend_milestone_deadline(&1u64, &(1000 + 20 * 86400), &owner);
+    assert!(extended.is_err());
+}
+
+#[test]
+fn test_milestone_approved_after_deadline_fails() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+    let contributor = Address::generate(&env);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+    let project_id = setup_submitted_funded_milestone(&env, &client, &owner, &contributor);
+
+    set_ledger_timestamp(&env, 1000 + 86400 + 1);
+    assert!(client.try_approve_milestone(&1u64, &owner).is_err());
+    assert!(client
+        .try_reject_milestone(&1u64, &owner, &String::from_str(&env, "late"))
+        .is_err());
+    assert_eq!(client.get_project_progress(&project_id), (0, 1, 0));
+
+    // Extending the deadline revives the submission for review
+    client.extend_milestone_deadline(&1u64, &(1000 + 3 * 86400), &owner);
+    assert!(client.approve_milestone(&1u64, &owner));
+    assert_eq!(client.get_project_progress(&project_id), (1, 1, 100));
+}
+
 // ============ Project Cancellation Tests ============
 
 #[test]
@@ -1120,6 +1439,7 @@ fn test_cancel_project() {
         description: String::from_str(&env, ""),
         payment_amount: 1000,
         deadline: now + 86400,
+        token: None,
     });
 
     let project_id = client.create_project(
@@ -1160,6 +1480,7 @@ fn test_cancel_project_non_admin_fails() {
         description: String::from_str(&env, ""),
         payment_amount: 1000,
         deadline: now + 86400,
+        token: None,
     });
 
     let project_id = client.create_project(
@@ -1175,3 +1496,1101 @@ fn test_cancel_project_non_admin_fails() {
     // Non-admin tries to cancel
     client.cancel_project(&project_id, &non_admin);
 }
+
+// ============ Escrow-Backed Project Tests ============
+
+fn escrow_milestones(env: &Env, now: u64) -> Vec<MilestoneInput> {
+    let mut milestones: Vec<MilestoneInput> = Vec::new(env);
+    milestones.push_back(MilestoneInput {
+        title: String::from_str(env, "M1"),
+        description: String::from_str(env, ""),
+        payment_amount: 400,
+        deadline: now + 86400,
+        token: None,
+    });
+    milestones.push_back(MilestoneInput {
+        title: String::from_str(env, "M2"),
+        description: String::from_str(env, ""),
+        payment_amount: 300,
+        deadline: now + 2 * 86400,
+        token: None,
+    });
+    milestones
+}
+
+#[test]
+fn test_escrow_project_releases_and_refunds_on_cancel() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+    let funder = Address::generate(&env);
+    let contributor = Address::generate(&env);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+    let guild_id = setup_guild(&client, &env, &owner);
+
+    let token_admin = Address::generate(&env);
+    let token_id = env
+        .register_stellar_asset_contract_v2(token_admin)
+        .address();
+    soroban_sdk::token::StellarAssetClient::new(&env, &token_id).mint(&funder, &1000);
+    let token_client = soroban_sdk::token::TokenClient::new(&env, &token_id);
+
+    let now = env.ledger().timestamp();
+    let project_id = client.create_escrow_project(
+        &guild_id,
+        &funder,
+        &contributor,
+        &escrow_milestones(&env, now),
+        &1000i128,
+        &Some(token_id.clone()),
+        &false,
+    );
+    assert_eq!(token_client.balance(&funder), 0);
+    assert_eq!(token_client.balance(&contract_id), 1000);
+
+    client.start_milestone(&1u64, &contributor);
+    client.submit_milestone(&1u64, &String::from_str(&env, "https://github.com/pr/1"));
+    client.approve_milestone(&1u64, &owner);
+    assert_eq!(token_client.balance(&contributor), 400);
+
+    assert!(client.cancel_project(&project_id, &owner));
+    assert_eq!(token_client.balance(&funder), 600);
+    assert_eq!(token_client.balance(&contract_id), 0);
+
+    // Cancelling again moves nothing
+    assert!(client.cancel_project(&project_id, &owner));
+    assert_eq!(token_client.balance(&funder), 600);
+}
+
+#[test]
+fn test_escrow_project_refunds_unallocated_remainder_on_completion() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+    let funder = Address::generate(&env);
+    let contributor = Address::generate(&env);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+    let guild_id = setup_guild(&client, &env, &owner);
+
+    let token_admin = Address::generate(&env);
+    let token_id = env
+        .register_stellar_asset_contract_v2(token_admin)
+        .address();
+    soroban_sdk::token::StellarAssetClient::new(&env, &token_id).mint(&funder, &1000);
+    let token_client = soroban_sdk::token::TokenClient::new(&env, &token_id);
+
+    let now = env.ledger().timestamp();
+    client.create_escrow_project(
+        &guild_id,
+        &funder,
+        &contributor,
+        &escrow_milestones(&env, now),
+        &1000i128,
+        &Some(token_id.clone()),
+        &false,
+    );
+
+    for milestone_id in 1u64..=2 {
+        client.start_milestone(&milestone_id, &contributor);
+        client.submit_milestone(
+            &milestone_id,
+            &String::from_str(&env, "https://github.com/pr/1"),
+        );
+        client.approve_milestone(&milestone_id, &owner);
+    }
+
+    assert_eq!(token_client.balance(&contributor), 700);
+    assert_eq!(token_client.balance(&funder), 300);
+    assert_eq!(token_client.balance(&contract_id), 0);
+}
+
+// ============ Per-Milestone Token Tests ============
+
+#[test]
+fn test_milestones_pay_in_their_own_token() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+    let contributor = Address::generate(&env);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+    let guild_id = setup_guild(&client, &env, &owner);
+
+    let stable = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    let unfunded = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    soroban_sdk::token::StellarAssetClient::new(&env, &stable).mint(&owner, &500);
+    let stable_client = soroban_sdk::token::TokenClient::new(&env, &stable);
+
+    // treasury holds XLM and the stablecoin, but none of `unfunded`
+    let treasury_id = create_treasury_with_funds(&client, &env, guild_id, &owner, 1000);
+    client.deposit_treasury(&treasury_id, &owner, &500i128, &Some(stable.clone()));
+
+    let now = env.ledger().timestamp();
+    let mut milestones: Vec<MilestoneInput> = Vec::new(&env);
+    milestones.push_back(MilestoneInput {
+        title: String::from_str(&env, "Stable"),
+        description: String::from_str(&env, ""),
+        payment_amount: 300,
+        deadline: now + 86400,
+        token: Some(stable.clone()),
+    });
+    milestones.push_back(MilestoneInput {
+        title: String::from_str(&env, "Native"),
+        description: String::from_str(&env, ""),
+        payment_amount: 200,
+        deadline: now + 86400,
+        token: None,
+    });
+    milestones.push_back(MilestoneInput {
+        title: String::from_str(&env, "Unfunded"),
+        description: String::from_str(&env, ""),
+        payment_amount: 100,
+        deadline: now + 86400,
+        token: Some(unfunded.clone()),
+    });
+
+    // the budget covers the native milestone; the others are budgeted in
+    // their own token
+    let project_id = client.create_project(
+        &guild_id,
+        &contributor,
+        &milestones,
+        &200i128,
+        &treasury_id,
+        &None,
+        &false,
+    );
+    assert_eq!(client.get_project(&project_id).allocated_amount, 200);
+    assert_eq!(client.get_milestone(&1u64).token, Some(stable.clone()));
+    assert_eq!(client.get_milestone(&2u64).token, None);
+
+    let proof = String::from_str(&env, "https://github.com/pr/1");
+    for milestone_id in 1u64..=3 {
+        client.start_milestone(&milestone_id, &contributor);
+        client.submit_milestone(&milestone_id, &proof);
+    }
+
+    client.approve_milestone(&1u64, &owner);
+    client.approve_milestone(&2u64, &owner);
+    assert_eq!(stable_client.balance(&contributor), 300);
+    assert_eq!(
+        client.get_treasury_balance(&treasury_id, &Some(stable)),
+        200
+    );
+    assert_eq!(client.get_treasury_balance(&treasury_id, &None), 800);
+    assert_eq!(client.get_project(&project_id).released_amount, 200);
+
+    // the treasury holds none of this token, so release fails
+    assert!(client.try_approve_milestone(&3u64, &owner).is_err());
+    assert!(!client.get_milestone(&3u64).is_payment_released);
+}
+
+#[test]
+#[should_panic(expected = "escrow projects pay in the project token only")]
+fn test_escrow_project_rejects_foreign_milestone_token() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+    let funder = Address::generate(&env);
+    let contributor = Address::generate(&env);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+    let guild_id = setup_guild(&client, &env, &owner);
+
+    let now = env.ledger().timestamp();
+    let mut milestones = escrow_milestones(&env, now);
+    let mut first = milestones.get(0).unwrap();
+    first.token = Some(Address::generate(&env));
+    milestones.set(0, first);
+
+    client.create_escrow_project(
+        &guild_id,
+        &funder,
+        &contributor,
+        &milestones,
+        &1000i128,
+        &None,
+        &false,
+    );
+}
+
+#[test]
+fn test_get_project_and_guild_projects() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+    let contributor = Address::generate(&env);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    let guild_id = setup_guild(&client, &env, &owner);
+    let other_guild_id = setup_guild(&client, &env, &owner);
+    assert_eq!(client.get_guild_projects(&guild_id).len(), 0);
+
+    let now = env.ledger().timestamp();
+    let mut milestones: Vec<MilestoneInput> = Vec::new(&env);
+    milestones.push_back(MilestoneInput {
+        title: String::from_str(&env, "M1"),
+        description: String::from_str(&env, ""),
+        payment_amount: 1000,
+        deadline: now + 86400,
+        token: None,
+    });
+
+    let first = client.create_project(
+        &guild_id,
+        &contributor,
+        &milestones,
+        &1000i128,
+        &1u64,
+        &None,
+        &false,
+    );
+    let other = client.create_project(
+        &other_guild_id,
+        &contributor,
+        &milestones,
+        &1000i128,
+        &1u64,
+        &None,
+        &false,
+    );
+    let second = client.create_project(
+        &guild_id,
+        &contributor,
+        &milestones,
+        &2000i128,
+        &1u64,
+        &None,
+        &true,
+    );
+
+    let project = client.get_project(&second);
+    assert_eq!(project.guild_id, guild_id);
+    assert_eq!(project.contributor, contributor);
+    assert_eq!(project.total_amount, 2000);
+    assert!(project.is_sequential);
+    assert_eq!(project.status, ProjectStatus::Active);
+
+    // Cancelled projects stay listed with their status for history
+    client.cancel_project(&first, &owner);
+    let ids = client.get_guild_projects(&guild_id);
+    assert_eq!(ids.len(), 2);
+    assert_eq!(ids.get(0).unwrap(), first);
+    assert_eq!(ids.get(1).unwrap(), second);
+    assert_eq!(client.get_project(&first).status, ProjectStatus::Cancelled);
+
+    let other_ids = client.get_guild_projects(&other_guild_id);
+    assert_eq!(other_ids.len(), 1);
+    assert_eq!(other_ids.get(0).unwrap(), other);
+
+    assert!(client.try_get_project(&999u64).is_err());
+}
+
+#[test]
+fn test_max_milestones_per_project_enforced() {
+    let env = setup_env();
+    let platform_admin = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let contributor = Address::generate(&env);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, StellarGuildsContract);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+    client.initialize(&platform_admin);
+    let guild_id = setup_guild(&client, &env, &owner);
+    assert_eq!(client.get_max_milestones_per_project(), 50);
+
+    let now = env.ledger().timestamp();
+    let mut milestones: Vec<MilestoneInput> = Vec::new(&env);
+    for _ in 0..3 {
+        milestones.push_back(MilestoneInput {
+            title: String::from_str(&env, "M"),
+            description: String::from_str(&env, ""),
+            payment_amount: 100,
+            deadline: now + 86400,
+            token: None,
+        });
+    }
+    let existing = client.create_project(
+        &guild_id,
+        &contributor,
+        &milestones,
+        &1000i128,
+        &1u64,
+        &None,
+        &false,
+    );
+
+    assert!(client
+        .try_set_max_milestones_per_project(&2u32, &owner)
+        .is_err());
+    assert!(client
+        .try_set_max_milestones_per_project(&0u32, &platform_admin)
+        .is_err());
+    client.set_max_milestones_per_project(&2u32, &platform_admin);
+
+    // New projects over the cap are rejected
+    let over_cap = client.try_create_project(
+        &guild_id,
+        &contributor,
+        &milestones,
+        &1000i128,
+        &1u64,
+        &None,
+        &false,
+    );
+    assert!(over_cap.is_err());
+
+    // The older project stays readable but can't grow
+    assert_eq!(client.get_project_progress(&existing), (0, 3, 0));
+    let add_existing = client.try_add_milestone(
+        &existing,
+        &String::from_str(&env, "M4"),
+        &String::from_str(&env, ""),
+        &100i128,
+        &(now + 86400),
+        &owner,
+    );
+    assert!(add_existing.is_err());
+
+    milestones.pop_back();
+    let at_cap = client.create_project(
+        &guild_id,
+        &contributor,
+        &milestones,
+        &1000i128,
+        &1u64,
+        &None,
+        &false,
+    );
+    let add_at_cap = client.try_add_milestone(
+        &at_cap,
+        &String::from_str(&env, "M3"),
+        &String::from_str(&env, ""),
+        &100i128,
+        &(now + 86400),
+        &owner,
+    );
+    assert!(add_at_cap.is_err());
+}
+
+#[test]
+fn test_start_funding_check_requires_treasury_balance() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+    let contributor = Address::generate(&env);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    let guild_id = setup_guild(&client, &env, &owner);
+    let treasury_id = create_treasury_with_funds(&client, &env, guild_id, &owner, 150);
+    assert_eq!(
+        client.get_start_funding_check(&guild_id),
+        StartFundingCheck::Disabled
+    );
+
+    let now = env.ledger().timestamp();
+    let mut milestones: Vec<MilestoneInput> = Vec::new(&env);
+    for _ in 0..2 {
+        milestones.push_back(MilestoneInput {
+            title: String::from_str(&env, "M"),
+            description: String::from_str(&env, ""),
+            payment_amount: 100,
+            deadline: now + 86400,
+            token: None,
+        });
+    }
+
+    assert!(client
+        .try_set_start_funding_check(&guild_id, &StartFundingCheck::FirstMilestone, &contributor)
+        .is_err());
+
+    // 150 covers the first milestone
+    client.set_start_funding_check(&guild_id, &StartFundingCheck::FirstMilestone, &owner);
+    client.create_project(
+        &guild_id,
+        &contributor,
+        &milestones,
+        &200i128,
+        &treasury_id,
+        &None,
+        &false,
+    );
+
+    // ...but not the full 200 allocation
+    client.set_start_funding_check(&guild_id, &StartFundingCheck::FullAllocation, &owner);
+    let underfunded = client.try_create_project(
+        &guild_id,
+        &contributor,
+        &milestones,
+        &200i128,
+        &treasury_id,
+        &None,
+        &false,
+    );
+    assert!(underfunded.is_err());
+
+    // Only a guild admin may waive the check for the contributor
+    let waived_by_contributor = client.create_jit_project(
+        &guild_id,
+        &contributor,
+        &contributor,
+        &milestones,
+        &200i128,
+        &treasury_id,
+        &None,
+        &false,
+    );
+    assert_eq!(waived_by_contributor, 0);
+    assert_eq!(client.get_guild_projects(&guild_id).len(), 1);
+
+    // Just-in-time projects approved by an admin skip the check
+    client.create_jit_project(
+        &guild_id,
+        &contributor,
+        &owner,
+        &milestones,
+        &200i128,
+        &treasury_id,
+        &None,
+        &false,
+    );
+    assert_eq!(client.get_guild_projects(&guild_id).len(), 2);
+}
+
+#[test]
+fn test_rejection_limit_fails_milestone() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+    let contributor = Address::generate(&env);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    let guild_id = setup_guild(&client, &env, &owner);
+    assert_eq!(
+        client
+            .get_milestone_rejection_policy(&guild_id)
+            .max_rejections,
+        0
+    );
+    assert!(client
+        .try_set_milestone_rejection_policy(
+            &guild_id,
+            &1,
+            &MilestoneFailureAction::FreeAllocation,
+            &contributor
+        )
+        .is_err());
+    client.set_milestone_rejection_policy(
+        &guild_id,
+        &1,
+        &MilestoneFailureAction::FreeAllocation,
+        &owner,
+    );
+
+    let now = env.ledger().timestamp();
+    let mut milestones: Vec<MilestoneInput> = Vec::new(&env);
+    for _ in 0..2 {
+        milestones.push_back(MilestoneInput {
+            title: String::from_str(&env, "M"),
+            description: String::from_str(&env, ""),
+            payment_amount: 100,
+            deadline: now + 86400,
+            token: None,
+        });
+    }
+    let project_id = client.create_project(
+        &guild_id,
+        &contributor,
+        &milestones,
+        &200i128,
+        &1u64,
+        &None,
+        &false,
+    );
+    let milestone_id = client
+        .get_project_milestones(&project_id, &0, &10)
+        .get(0)
+        .unwrap()
+        .id;
+
+    let proof = String::from_str(&env, "https://github.com/pr/1");
+    let reason = String::from_str(&env, "Needs fixes");
+    client.start_milestone(&milestone_id, &contributor);
+    client.submit_milestone(&milestone_id, &proof);
+    client.reject_milestone(&milestone_id, &owner, &reason);
+
+    // The counter survives the resubmission
+    client.submit_milestone(&milestone_id, &proof);
+    assert_eq!(client.get_milestone(&milestone_id).rejection_count, 1);
+    client.reject_milestone(&milestone_id, &owner, &reason);
+
+    let milestone = client.get_milestone(&milestone_id);
+    assert_eq!(milestone.status, MilestoneStatus::Failed);
+    assert_eq!(milestone.rejection_count, 2);
+    assert_eq!(client.get_project(&project_id).allocated_amount, 100);
+    assert!(client.try_submit_milestone(&milestone_id, &proof).is_err());
+
+    // Cancelling policy ends the project on the next failure
+    client.set_milestone_rejection_policy(
+        &guild_id,
+        &0,
+        &MilestoneFailureAction::CancelProject,
+        &owner,
+    );
+    let other_id = client
+        .get_project_milestones(&project_id, &0, &10)
+        .get(1)
+        .unwrap()
+        .id;
+    client.start_milestone(&other_id, &contributor);
+    client.submit_milestone(&other_id, &proof);
+    client.reject_milestone(&other_id, &owner, &reason);
+    assert_eq!(
+        client.get_milestone(&other_id).status,
+        MilestoneStatus::Rejected
+    );
+    client.set_milestone_rejection_policy(
+        &guild_id,
+        &1,
+        &MilestoneFailureAction::CancelProject,
+        &owner,
+    );
+    client.submit_milestone(&other_id, &proof);
+    client.reject_milestone(&other_id, &owner, &reason);
+    assert_eq!(
+        client.get_project(&project_id).status,
+        ProjectStatus::Cancelled
+    );
+}
+
+#[test]
+fn test_batch_approve_milestones_isolates_payment_failures() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+    let admin = Address::generate(&env);
+    let contributor = Address::generate(&env);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    let guild_id = setup_guild(&client, &env, &owner);
+    add_admin(&client, &env, guild_id, &owner, &admin);
+
+    // Only enough funds for one of the two submitted milestones
+    let treasury_id = create_treasury_with_funds(&client, &env, guild_id, &owner, 1500i128);
+
+    let now = env.ledger().timestamp();
+    let mut milestones: Vec<MilestoneInput> = Vec::new(&env);
+    for title in ["M1", "M2", "M3"] {
+        milestones.push_back(MilestoneInput {
+            title: String::from_str(&env, title),
+            description: String::from_str(&env, ""),
+            payment_amount: 1000,
+            deadline: now + 86400,
+            token: None,
+        });
+    }
+
+    client.create_project(
+        &guild_id,
+        &contributor,
+        &milestones,
+        &3000i128,
+        &treasury_id,
+        &None,
+        &false,
+    );
+
+    let proof_url = String::from_str(&env, "https://github.com/pr/123");
+    for milestone_id in [1u64, 2u64] {
+        client.start_milestone(&milestone_id, &contributor);
+        client.submit_milestone(&milestone_id, &proof_url);
+    }
+
+    let mut ids = Vec::new(&env);
+    ids.push_back(1u64);
+    ids.push_back(2u64);
+    ids.push_back(3u64);
+    let results = client.batch_approve_milestones(&ids, &admin);
+
+    assert_eq!(results.len(), 3);
+    assert_eq!(
+        results.get(0).unwrap().outcome,
+        MilestoneApprovalOutcome::Paid
+    );
+    assert_eq!(
+        results.get(1).unwrap().outcome,
+        MilestoneApprovalOutcome::PaymentFailed
+    );
+    assert_eq!(
+        results.get(2).unwrap().outcome,
+        MilestoneApprovalOutcome::Skipped
+    );
+
+    // The failed payment does not undo either approval
+    let m1 = client.get_milestone(&1u64);
+    assert_eq!(m1.status, MilestoneStatus::Approved);
+    assert!(m1.is_payment_released);
+    let m2 = client.get_milestone(&2u64);
+    assert_eq!(m2.status, MilestoneStatus::Approved);
+    assert!(!m2.is_payment_released);
+    assert_eq!(client.get_milestone(&3u64).status, MilestoneStatus::Pending);
+
+    // Once the treasury is topped up the held payment can be released
+    client.deposit_treasury(&treasury_id, &owner, &1000i128, &None);
+    assert!(client.release_milestone_payment(&2u64));
+    assert!(client.get_milestone(&2u64).is_payment_released);
+}
+
+#[test]
+fn test_batch_approve_queues_payment_over_allowance() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+    let contributor = Address::generate(&env);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    let guild_id = setup_guild(&client, &env, &owner);
+    let treasury_id = create_treasury_with_funds(&client, &env, guild_id, &owner, 5000i128);
+
+    // The treasury holds plenty, but its owner only lets the contract pay
+    // out one milestone
+    client.approve_token_allowance(
+        &owner,
+        &contract_id,
+        &None,
+        &1500i128,
+        &0u64,
+        &AllowanceOperation::MilestonePayment,
+    );
+
+    let now = env.ledger().timestamp();
+    let mut milestones: Vec<MilestoneInput> = Vec::new(&env);
+    for title in ["M1", "M2"] {
+        milestones.push_back(MilestoneInput {
+            title: String::from_str(&env, title),
+            description: String::from_str(&env, ""),
+            payment_amount: 1000,
+            deadline: now + 86400,
+            token: None,
+        });
+    }
+    client.create_project(
+        &guild_id,
+        &contributor,
+        &milestones,
+        &2000i128,
+        &treasury_id,
+        &None,
+        &false,
+    );
+
+    let proof_url = String::from_str(&env, "https://github.com/pr/123");
+    let mut ids = Vec::new(&env);
+    for milestone_id in [1u64, 2u64] {
+        client.start_milestone(&milestone_id, &contributor);
+        client.submit_milestone(&milestone_id, &proof_url);
+        ids.push_back(milestone_id);
+    }
+
+    // The allowance shortfall holds back the second payment without
+    // reverting the batch
+    let results = client.batch_approve_milestones(&ids, &owner);
+    assert_eq!(
+        results.get(0).unwrap().outcome,
+        MilestoneApprovalOutcome::Paid
+    );
+    assert_eq!(
+        results.get(1).unwrap().outcome,
+        MilestoneApprovalOutcome::PaymentFailed
+    );
+    assert_eq!(
+        client.get_milestone(&2u64).status,
+        MilestoneStatus::Approved
+    );
+    assert!(!client.get_milestone(&2u64).is_payment_released);
+    assert_eq!(client.get_treasury_balance(&treasury_id, &None), 4000);
+}
+
+#[test]
+fn test_approval_queues_payment_while_treasury_paused() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+    let contributor = Address::generate(&env);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    let guild_id = setup_guild(&client, &env, &owner);
+    let treasury_id = create_treasury_with_funds(&client, &env, guild_id, &owner, 5000i128);
+
+    let now = env.ledger().timestamp();
+    let mut milestones: Vec<MilestoneInput> = Vec::new(&env);
+    milestones.push_back(MilestoneInput {
+        title: String::from_str(&env, "M1"),
+        description: String::from_str(&env, ""),
+        payment_amount: 1000,
+        deadline: now + 86400,
+        token: None,
+    });
+    let project_id = client.create_project(
+        &guild_id,
+        &contributor,
+        &milestones,
+        &1000i128,
+        &treasury_id,
+        &None,
+        &false,
+    );
+
+    client.start_milestone(&1u64, &contributor);
+    client.submit_milestone(&1u64, &String::from_str(&env, "https://github.com/pr/1"));
+
+    // Approval goes through during a treasury freeze; the payment waits
+    client.emergency_pause(&treasury_id, &owner, &true);
+    assert!(client.approve_milestone(&1u64, &owner));
+    let milestone = client.get_milestone(&1u64);
+    assert_eq!(milestone.status, MilestoneStatus::Approved);
+    assert!(!milestone.is_payment_released);
+    assert_eq!(client.get_queued_milestone_payments(&project_id).len(), 1);
+    assert!(client.try_release_milestone_payment(&1u64).is_err());
+
+    client.emergency_pause(&treasury_id, &owner, &false);
+    assert!(client.release_milestone_payment(&1u64));
+    assert!(client.get_milestone(&1u64).is_payment_released);
+    assert_eq!(client.get_queued_milestone_payments(&project_id).len(), 0);
+    assert_eq!(
+        client.get_project(&project_id).status,
+        ProjectStatus::Completed
+    );
+
+    // Released payments cannot be released again
+    assert!(client.try_release_milestone_payment(&1u64).is_err());
+}
+
+#[test]
+fn test_pending_payments_listed_per_contributor() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+    let funder = Address::generate(&env);
+    let contributor = Address::generate(&env);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    let guild_id = setup_guild(&client, &env, &owner);
+    let treasury_id = create_treasury_with_funds(&client, &env, guild_id, &owner, 5000i128);
+
+    let token_admin = Address::generate(&env);
+    let token_id = env
+        .register_stellar_asset_contract_v2(token_admin)
+        .address();
+    soroban_sdk::token::StellarAssetClient::new(&env, &token_id).mint(&funder, &1000);
+
+    let now = env.ledger().timestamp();
+    let mut milestones: Vec<MilestoneInput> = Vec::new(&env);
+    milestones.push_back(MilestoneInput {
+        title: String::from_str(&env, "M1"),
+        description: String::from_str(&env, ""),
+        payment_amount: 700,
+        deadline: now + 86400,
+        token: None,
+    });
+    let treasury_project = client.create_project(
+        &guild_id,
+        &contributor,
+        &milestones,
+        &700i128,
+        &treasury_id,
+        &None,
+        &false,
+    );
+    client.create_escrow_project(
+        &guild_id,
+        &funder,
+        &contributor,
+        &escrow_milestones(&env, now),
+        &1000i128,
+        &Some(token_id.clone()),
+        &false,
+    );
+    assert_eq!(
+        client.get_pending_payments_for(&contributor, &10u32).len(),
+        0
+    );
+
+    // Treasury-backed approval held by a freeze stays pending
+    client.start_milestone(&1u64, &contributor);
+    client.submit_milestone(&1u64, &String::from_str(&env, "https://github.com/pr/1"));
+    client.emergency_pause(&treasury_id, &owner, &true);
+    client.approve_milestone(&1u64, &owner);
+
+    // Escrow-backed approval pays out immediately and is not listed
+    client.start_milestone(&2u64, &contributor);
+    client.submit_milestone(&2u64, &String::from_str(&env, "https://github.com/pr/2"));
+    client.approve_milestone(&2u64, &owner);
+
+    let pending = client.get_pending_payments_for(&contributor, &10u32);
+    assert_eq!(pending.len(), 1);
+    assert_eq!(pending.get(0).unwrap(), (1u64, 700i128));
+    assert_eq!(client.get_pending_payments_for(&owner, &10u32).len(), 0);
+    assert_eq!(
+        client.get_pending_payments_for(&contributor, &0u32).len(),
+        0
+    );
+
+    // projects created before the contributor index existed are still found
+    env.as_contract(&contract_id, || {
+        let key = MilestoneStorageKey::ContributorProjects(contributor.clone());
+        env.storage().persistent().remove(&key);
+    });
+    assert_eq!(
+        client.get_pending_payments_for(&contributor, &10u32),
+        pending
+    );
+
+    client.emergency_pause(&treasury_id, &owner, &false);
+    assert!(client.release_milestone_payment(&1u64));
+    assert_eq!(
+        client.get_pending_payments_for(&contributor, &10u32).len(),
+        0
+    );
+    assert_eq!(
+        client
+            .get_queued_milestone_payments(&treasury_project)
+            .len(),
+        0
+    );
+}
+
+#[test]
+fn test_payout_address_redirects_later_payments_only() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+    let funder = Address::generate(&env);
+    let contributor = Address::generate(&env);
+    let wallet = Address::generate(&env);
+    let outsider = Address::generate(&env);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+    let guild_id = setup_guild(&client, &env, &owner);
+
+    let token_admin = Address::generate(&env);
+    let token_id = env
+        .register_stellar_asset_contract_v2(token_admin)
+        .address();
+    soroban_sdk::token::StellarAssetClient::new(&env, &token_id).mint(&funder, &1000);
+    let token_client = soroban_sdk::token::TokenClient::new(&env, &token_id);
+
+    let now = env.ledger().timestamp();
+    let project_id = client.create_escrow_project(
+        &guild_id,
+        &funder,
+        &contributor,
+        &escrow_milestones(&env, now),
+        &1000i128,
+        &Some(token_id.clone()),
+        &false,
+    );
+
+    client.start_milestone(&1u64, &contributor);
+    client.submit_milestone(&1u64, &String::from_str(&env, "https://github.com/pr/1"));
+    client.approve_milestone(&1u64, &owner);
+    assert_eq!(token_client.balance(&contributor), 400);
+
+    // A non-admin cannot approve the change
+    let wallet_opt = Some(wallet.clone());
+    let denied =
+        client.try_set_project_payout_address(&project_id, &wallet_opt, &contributor, &outsider);
+    assert!(denied.is_err());
+
+    assert!(client.set_project_payout_address(&project_id, &wallet_opt, &contributor, &owner));
+
+    // The contributor still acts on milestones, but the wallet is paid
+    client.start_milestone(&2u64, &contributor);
+    client.submit_milestone(&2u64, &String::from_str(&env, "https://github.com/pr/2"));
+    client.approve_milestone(&2u64, &owner);
+    assert_eq!(token_client.balance(&wallet), 300);
+    assert_eq!(token_client.balance(&contributor), 400);
+}
+
+#[test]
+fn test_milestone_approval_earns_reputation_scaled_by_payment_once() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+    let funder = Address::generate(&env);
+    let contributor = Address::generate(&env);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+    let guild_id = setup_guild(&client, &env, &owner);
+
+    let token_admin = Address::generate(&env);
+    let token_id = env
+        .register_stellar_asset_contract_v2(token_admin)
+        .address();
+    let whole = 10_000_000i128;
+    soroban_sdk::token::StellarAssetClient::new(&env, &token_id).mint(&funder, &(2_000 * whole));
+
+    let now = env.ledger().timestamp();
+    let mut milestones: Vec<MilestoneInput> = Vec::new(&env);
+    for (title, amount) in [("M1", 30 * whole), ("M2", 1_000 * whole)] {
+        milestones.push_back(MilestoneInput {
+            title: String::from_str(&env, title),
+            description: String::from_str(&env, ""),
+            payment_amount: amount,
+            deadline: now + 86400,
+            token: None,
+        });
+    }
+    client.create_escrow_project(
+        &guild_id,
+        &funder,
+        &contributor,
+        &milestones,
+        &(1_030 * whole),
+        &Some(token_id),
+        &false,
+    );
+
+    // Even with the guild's dedupe off, a re-approved milestone counts once
+    client.set_contribution_dedupe(&guild_id, &false, &owner);
+    let proof = String::from_str(&env, "https://github.com/pr/1");
+    client.start_milestone(&1u64, &contributor);
+    client.submit_milestone(&1u64, &proof);
+    client.reject_milestone(&1u64, &owner, &String::from_str(&env, "needs tests"));
+    assert!(client.try_get_reputation(&guild_id, &contributor).is_err());
+    client.submit_milestone(&1u64, &proof);
+    client.approve_milestone(&1u64, &owner);
+    assert_eq!(
+        client.get_reputation(&guild_id, &contributor).total_score,
+        30
+    );
+
+    env.as_contract(&contract_id, || {
+        assert!(!crate::reputation::scoring::record_milestone_contribution(
+            &env,
+            guild_id,
+            &contributor,
+            1,
+            &None,
+            30 * whole,
+        ));
+    });
+
+    // Large payments are capped
+    client.start_milestone(&2u64, &contributor);
+    client.submit_milestone(&2u64, &String::from_str(&env, "https://github.com/pr/2"));
+    client.approve_milestone(&2u64, &owner);
+    let profile = client.get_reputation(&guild_id, &contributor);
+    assert_eq!(profile.total_score, 530);
+    assert_eq!(profile.contributions_count, 2);
+}
+
+#[test]
+fn test_update_milestone_progress_moves_forward_and_resets_on_rejection() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+    let contributor = Address::generate(&env);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+    setup_single_milestone_project(&env, &client, &owner, &contributor);
+    let milestone_id = 1u64;
+    let note = Some(String::from_str(&env, "API done"));
+
+    // Not started yet
+    assert!(client
+        .try_update_milestone_progress(&milestone_id, &10, &None, &contributor)
+        .is_err());
+
+    client.start_milestone(&milestone_id, &contributor);
+    assert!(client.update_milestone_progress(&milestone_id, &40, &note, &contributor));
+    let progress = client.get_milestone_progress(&milestone_id);
+    assert_eq!(progress.percent, 40);
+    assert_eq!(progress.note, note);
+    assert_eq!(
+        client.get_milestone(&milestone_id).status,
+        MilestoneStatus::InProgress
+    );
+
+    assert!(client
+        .try_update_milestone_progress(&milestone_id, &30, &None, &contributor)
+        .is_err());
+    assert!(client
+        .try_update_milestone_progress(&milestone_id, &101, &None, &contributor)
+        .is_err());
+    assert!(client
+        .try_update_milestone_progress(&milestone_id, &50, &None, &owner)
+        .is_err());
+
+    client.submit_milestone(&milestone_id, &String::from_str(&env, "https://proof"));
+    assert!(client
+        .try_update_milestone_progress(&milestone_id, &90, &None, &contributor)
+        .is_err());
+
+    client.reject_milestone(&milestone_id, &owner, &String::from_str(&env, "redo"));
+    let progress = client.get_milestone_progress(&milestone_id);
+    assert_eq!(progress.percent, 0);
+    assert_eq!(progress.note, None);
+
+    // Rework starts from zero again
+    client.update_milestone_progress(&milestone_id, &20, &None, &contributor);
+    assert_eq!(client.get_milestone_progress(&milestone_id).percent, 20);
+}
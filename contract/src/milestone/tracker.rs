@@ -1,24 +1,69 @@
-use soroban_sdk::{Address, Env, String, Vec};
+use soroban_sdk::{panic_with_error, Address, Env, String, Vec};
 
+use crate::bounty::escrow::{lock_funds, release_funds};
 use crate::dispute::storage as dispute_storage;
 use crate::dispute::types::DisputeReference;
+use crate::events::critical::record_critical_event;
 use crate::events::emit::emit_event;
 use crate::events::topics::{
-    ACT_APPROVED, ACT_CANCELLED, ACT_COMPLETED, ACT_CREATED, ACT_REJECTED, ACT_RELEASED,
+    ACT_APPROVED, ACT_CANCELLED, ACT_COMPLETED, ACT_CREATED, ACT_DEADLINE_EXTENDED,
+    ACT_EXTENSION_REQUESTED, ACT_PAYMENT_QUEUED, ACT_PROGRESS, ACT_REJECTED, ACT_RELEASED,
     ACT_STARTED, ACT_SUBMITTED, ACT_UPDATED, MOD_MILESTONE,
 };
-use crate::guild::membership::has_permission;
+use crate::guild::membership::{enforce_permission, is_suspended};
 use crate::guild::types::Role;
 use crate::milestone::storage::{
-    append_milestone_to_project, get_milestone, get_next_milestone_id, get_next_project_id,
-    get_project, get_project_milestone_ids, store_milestone, store_project,
+    append_milestone_to_project, append_project_to_contributor, append_project_to_guild,
+    dequeue_payment, get_contributor_project_ids, get_extension_request, get_guild_project_ids,
+    get_max_milestones, get_milestone, get_milestone_progress as load_milestone_progress,
+    get_next_milestone_id, get_next_project_id, get_project, get_project_milestone_ids,
+    get_queued_payments, get_rejection_policy as load_rejection_policy,
+    get_start_funding_check as load_start_funding_check, queue_payment, remove_extension_request,
+    remove_milestone_progress, set_max_milestones, set_milestone_progress,
+    set_rejection_policy as store_rejection_policy,
+    set_start_funding_check as store_start_funding_check, store_extension_request, store_milestone,
+    store_project,
 };
 use crate::milestone::types::{
-    Milestone, MilestoneAddedEvent, MilestoneInput, MilestonePaymentReleasedEvent,
-    MilestoneRejectedEvent, MilestoneStatus, MilestoneStatusChangedEvent, MilestoneSubmittedEvent,
-    Project, ProjectCreatedEvent, ProjectStatus, ProjectStatusChangedEvent,
+    DeadlineExtensionRequest, Milestone, MilestoneAddedEvent, MilestoneApprovalOutcome,
+    MilestoneApprovalResult, MilestoneDeadlineExtendedEvent, MilestoneFailureAction,
+    MilestoneInput, MilestonePaymentQueuedEvent, MilestonePaymentReleasedEvent, MilestoneProgress,
+    MilestoneProgressUpdatedEvent, MilestoneRejectedEvent, MilestoneStatus,
+    MilestoneStatusChangedEvent, MilestoneSubmittedEvent, PayoutAddressChangedEvent, Project,
+    ProjectCreatedEvent, ProjectFunding, ProjectStatus, ProjectStatusChangedEvent, RejectionPolicy,
+    StartFundingCheck,
 };
-use crate::treasury::execute_milestone_payment;
+use crate::reputation::scoring::record_milestone_contribution;
+use crate::treasury::types::TreasuryError;
+use crate::treasury::{can_pay_milestone, get_balance, try_execute_milestone_payment};
+
+/// Upper bound on milestones returned by a single paginated query, keeping
+/// reads within the per-invocation budget for large projects.
+pub const MAX_MILESTONE_PAGE_SIZE: u32 = 50;
+/// Milestone cap per project until the contract admin configures one
+pub const DEFAULT_MAX_MILESTONES_PER_PROJECT: u32 = 50;
+
+pub fn get_max_milestones_per_project(env: &Env) -> u32 {
+    get_max_milestones(env).unwrap_or(DEFAULT_MAX_MILESTONES_PER_PROJECT)
+}
+
+/// Set the milestone cap per project (contract admin only). Projects already
+/// above a lowered cap stay readable but can't take more milestones.
+pub fn set_max_milestones_per_project(env: &Env, max: u32, caller: Address) -> bool {
+    crate::integration::auth::require_admin(env, &caller);
+
+    if max == 0 {
+        panic!("max milestones must be positive");
+    }
+    set_max_milestones(env, max);
+    true
+}
+
+fn assert_contributor_not_suspended(env: &Env, project: &Project) {
+    if is_suspended(env, project.guild_id, project.contributor.clone()) {
+        panic!("contributor is suspended");
+    }
+}
 
 fn assert_project_active(project: &Project, _env: &Env) {
     if project.status != ProjectStatus::Active {
@@ -31,15 +76,142 @@ fn assert_project_active(project: &Project, _env: &Env) {
     }
 }
 
-fn ensure_not_expired(env: &Env, milestone: &mut Milestone) {
-    let now = env.ledger().timestamp();
-    if now > milestone.deadline && milestone.status != MilestoneStatus::Approved {
-        milestone.status = MilestoneStatus::Expired;
-        store_milestone(env, milestone);
+/// Whether a milestone counts as expired. Approval and failure are terminal:
+/// the deadline passing never expires such a milestone again.
+fn is_milestone_expired(env: &Env, milestone: &Milestone) -> bool {
+    match milestone.status {
+        MilestoneStatus::Approved | MilestoneStatus::Failed => false,
+        MilestoneStatus::Expired => true,
+        _ => env.ledger().timestamp() > milestone.deadline,
+    }
+}
+
+fn ensure_not_expired(env: &Env, milestone: &Milestone) {
+    if is_milestone_expired(env, milestone) {
         panic!("milestone expired");
     }
 }
 
+/// Pay `amount` of a project's funds to `recipient`, from the treasury or
+/// the project's escrow depending on how the project is funded.
+pub fn disburse_project_payment(
+    env: &Env,
+    project: &Project,
+    token: &Option<Address>,
+    recipient: Address,
+    amount: i128,
+) {
+    try_disburse_project_payment(env, project, token, recipient, amount)
+        .unwrap_or_else(|e| panic_with_error!(env, e));
+}
+
+/// `disburse_project_payment` that returns the treasury's refusal instead of
+/// panicking; escrowed funds are always available.
+fn try_disburse_project_payment(
+    env: &Env,
+    project: &Project,
+    token: &Option<Address>,
+    recipient: Address,
+    amount: i128,
+) -> Result<(), TreasuryError> {
+    match project.funding {
+        ProjectFunding::Treasury => {
+            try_execute_milestone_payment(
+                env,
+                project.treasury_id,
+                token.clone(),
+                recipient,
+                amount,
+            )?;
+        }
+        ProjectFunding::Escrow(_) => {
+            release_funds(env, token, &recipient, amount);
+            record_critical_event(
+                env,
+                project.guild_id,
+                MOD_MILESTONE,
+                ACT_RELEASED,
+                project.id,
+            );
+        }
+    }
+    Ok(())
+}
+
+pub fn get_start_funding_check(env: &Env, guild_id: u64) -> StartFundingCheck {
+    load_start_funding_check(env, guild_id)
+}
+
+/// Set how much the treasury must hold before a treasury-backed project can
+/// be created in the guild (guild admins only).
+pub fn set_start_funding_check(
+    env: &Env,
+    guild_id: u64,
+    check: StartFundingCheck,
+    caller: Address,
+) -> bool {
+    caller.require_auth();
+
+    if !enforce_permission(
+        env,
+        guild_id,
+        caller,
+        Role::Admin,
+        "set_start_funding_check",
+    ) {
+        panic!("caller must be guild admin");
+    }
+    store_start_funding_check(env, guild_id, &check);
+    true
+}
+
+/// Panic unless the treasury covers the milestones the guild's start funding
+/// check requires, per payment token.
+fn ensure_start_funding(
+    env: &Env,
+    guild_id: u64,
+    treasury_id: u64,
+    token: &Option<Address>,
+    milestones: &Vec<MilestoneInput>,
+) {
+    let covered = match load_start_funding_check(env, guild_id) {
+        StartFundingCheck::Disabled => return,
+        StartFundingCheck::FirstMilestone => 1,
+        StartFundingCheck::FullAllocation => milestones.len(),
+    };
+    let pay_token = |m: &MilestoneInput| m.token.clone().or_else(|| token.clone());
+
+    for (i, input) in milestones.iter().take(covered as usize).enumerate() {
+        let input_token = pay_token(&input);
+        // each token is summed once, at its first milestone
+        if milestones
+            .iter()
+            .take(i)
+            .any(|m| pay_token(&m) == input_token)
+        {
+            continue;
+        }
+        let required: i128 = milestones
+            .iter()
+            .take(covered as usize)
+            .filter(|m| pay_token(m) == input_token)
+            .map(|m| m.payment_amount)
+            .sum();
+        if get_balance(env, treasury_id, input_token) < required {
+            panic!("treasury balance below project start requirement");
+        }
+    }
+}
+
+/// Return the unreleased part of an escrow-backed project to its funder.
+/// Called once, when the project leaves the Active state.
+pub fn refund_escrow_remainder(env: &Env, project: &Project) {
+    if let ProjectFunding::Escrow(funder) = &project.funding {
+        let remainder = project.total_amount - project.released_amount;
+        release_funds(env, &project.token, funder, remainder);
+    }
+}
+
 pub fn create_project(
     env: &Env,
     guild_id: u64,
@@ -49,6 +221,97 @@ pub fn create_project(
     treasury_id: u64,
     token: Option<Address>,
     is_sequential: bool,
+) -> u64 {
+    create_project_internal(
+        env,
+        guild_id,
+        contributor,
+        milestones,
+        total_amount,
+        treasury_id,
+        token,
+        is_sequential,
+        ProjectFunding::Treasury,
+        true,
+    )
+}
+
+/// Create a treasury-backed project that is funded just in time, skipping
+/// the guild's start funding check. Only a guild admin may waive the check,
+/// so `approver` must be one; returns 0 when the approver is refused.
+#[allow(clippy::too_many_arguments)]
+pub fn create_jit_project(
+    env: &Env,
+    guild_id: u64,
+    contributor: Address,
+    approver: Address,
+    milestones: Vec<MilestoneInput>,
+    total_amount: i128,
+    treasury_id: u64,
+    token: Option<Address>,
+    is_sequential: bool,
+) -> u64 {
+    approver.require_auth();
+
+    if !enforce_permission(env, guild_id, approver, Role::Admin, "create_jit_project") {
+        return 0;
+    }
+
+    create_project_internal(
+        env,
+        guild_id,
+        contributor,
+        milestones,
+        total_amount,
+        treasury_id,
+        token,
+        is_sequential,
+        ProjectFunding::Treasury,
+        false,
+    )
+}
+
+/// Create a project whose full budget is locked in escrow by `funder` up
+/// front. Milestone approvals release from the escrow instead of a treasury.
+#[allow(clippy::too_many_arguments)]
+pub fn create_escrow_project(
+    env: &Env,
+    guild_id: u64,
+    funder: Address,
+    contributor: Address,
+    milestones: Vec<MilestoneInput>,
+    total_amount: i128,
+    token: Option<Address>,
+    is_sequential: bool,
+) -> u64 {
+    funder.require_auth();
+
+    create_project_internal(
+        env,
+        guild_id,
+        contributor,
+        milestones,
+        total_amount,
+        0,
+        token,
+        is_sequential,
+        ProjectFunding::Escrow(funder),
+        false,
+    )
+}
+
+#[allow(clippy::too_many_arguments)]
+fn create_project_internal(
+    env: &Env,
+    guild_id: u64,
+    contributor: Address,
+    milestones: Vec<MilestoneInput>,
+    total_amount: i128,
+    treasury_id: u64,
+    token: Option<Address>,
+    is_sequential: bool,
+    funding: ProjectFunding,
+    check_funding: bool,
 ) -> u64 {
     contributor.require_auth();
 
@@ -58,6 +321,9 @@ pub fn create_project(
     if milestones.is_empty() {
         panic!("at least one milestone required");
     }
+    if milestones.len() > get_max_milestones_per_project(env) {
+        panic!("too many milestones for project");
+    }
 
     let now = env.ledger().timestamp();
 
@@ -76,15 +342,34 @@ pub fn create_project(
         if input.deadline <= now {
             panic!("milestone deadline must be in the future");
         }
-        allocated = allocated
-            .checked_add(input.payment_amount)
-            .expect("overflow");
+        // escrow holds a single token, so every milestone must pay in it
+        if matches!(funding, ProjectFunding::Escrow(_))
+            && input.token.is_some()
+            && input.token != token
+        {
+            panic!("escrow projects pay in the project token only");
+        }
+        // only project-token milestones share `total_amount`
+        if input.token.is_none() || input.token == token {
+            allocated = allocated
+                .checked_add(input.payment_amount)
+                .expect("overflow");
+        }
     }
 
     if allocated > total_amount {
         panic!("allocated milestone budget exceeds project total");
     }
 
+    // escrow projects lock their whole budget below, so only treasuries are checked
+    if check_funding && funding == ProjectFunding::Treasury {
+        ensure_start_funding(env, guild_id, treasury_id, &token, &milestones);
+    }
+
+    if let ProjectFunding::Escrow(funder) = &funding {
+        lock_funds(env, &token, funder, total_amount);
+    }
+
     let project_id = get_next_project_id(env);
 
     let project = Project {
@@ -99,9 +384,13 @@ pub fn create_project(
         is_sequential,
         created_at: now,
         status: ProjectStatus::Active,
+        funding,
+        payout_address: None,
     };
 
     store_project(env, &project);
+    append_project_to_guild(env, guild_id, project_id);
+    append_project_to_contributor(env, &project.contributor, project_id);
 
     // Create milestones
     let mut order: u32 = 1;
@@ -122,6 +411,8 @@ pub fn create_project(
             last_updated_at: now,
             version: 0,
             is_payment_released: false,
+            token: input.token.clone().or_else(|| token.clone()),
+            rejection_count: 0,
         };
         store_milestone(env, &milestone);
         append_milestone_to_project(env, project_id, milestone_id);
@@ -167,7 +458,7 @@ pub fn add_milestone(
     assert_project_active(&project, env);
 
     // Only guild admins can add milestones
-    if !has_permission(env, project.guild_id, caller, Role::Admin) {
+    if !enforce_permission(env, project.guild_id, caller, Role::Admin, "add_milestone") {
         panic!("caller must be guild admin");
     }
 
@@ -184,6 +475,9 @@ pub fn add_milestone(
     if description.len() > 1024 {
         panic!("milestone description too long");
     }
+    if get_project_milestone_ids(env, project_id).len() >= get_max_milestones_per_project(env) {
+        panic!("too many milestones for project");
+    }
 
     let new_allocated = project
         .allocated_amount
@@ -213,6 +507,8 @@ pub fn add_milestone(
         last_updated_at: now,
         version: 0,
         is_payment_released: false,
+        token: project.token.clone(),
+        rejection_count: 0,
     };
 
     store_milestone(env, &milestone);
@@ -237,11 +533,12 @@ pub fn start_milestone(env: &Env, milestone_id: u64, contributor: Address) -> bo
     let project = get_project(env, milestone.project_id).expect("project not found");
 
     assert_project_active(&project, env);
-    ensure_not_expired(env, &mut milestone);
+    ensure_not_expired(env, &milestone);
 
     if contributor != project.contributor {
         panic!("only project contributor can start milestone");
     }
+    assert_contributor_not_suspended(env, &project);
 
     if milestone.status != MilestoneStatus::Pending {
         panic!("milestone not pending");
@@ -253,7 +550,8 @@ pub fn start_milestone(env: &Env, milestone_id: u64, contributor: Address) -> bo
             let other = get_milestone(env, id).expect("milestone missing");
             if other.order + 1 == milestone.order {
                 if other.status != MilestoneStatus::Approved
-                    && other.status != MilestoneStatus::Expired
+                    && other.status != MilestoneStatus::Failed
+                    && !is_milestone_expired(env, &other)
                 {
                     panic!("previous milestone not completed");
                 }
@@ -277,16 +575,80 @@ pub fn start_milestone(env: &Env, milestone_id: u64, contributor: Address) -> bo
     true
 }
 
+/// Report how far along a milestone is without changing its status
+///
+/// Allowed while the milestone is in progress or being reworked after a
+/// rejection. Progress only moves forward until the next rejection resets it.
+pub fn update_milestone_progress(
+    env: &Env,
+    milestone_id: u64,
+    percent: u32,
+    note: Option<String>,
+    contributor: Address,
+) -> bool {
+    contributor.require_auth();
+
+    let mut milestone = get_milestone(env, milestone_id).expect("milestone not found");
+    let project = get_project(env, milestone.project_id).expect("project not found");
+
+    assert_project_active(&project, env);
+    ensure_not_expired(env, &milestone);
+
+    if contributor != project.contributor {
+        panic!("only project contributor can update progress");
+    }
+    assert_contributor_not_suspended(env, &project);
+
+    if milestone.status != MilestoneStatus::InProgress
+        && milestone.status != MilestoneStatus::Rejected
+    {
+        panic!("milestone not in progress or previously rejected");
+    }
+    if percent > 100 {
+        panic!("progress must be between 0 and 100");
+    }
+    let current = load_milestone_progress(env, milestone_id)
+        .map(|p| p.percent)
+        .unwrap_or(0);
+    if percent < current {
+        panic!("progress cannot move backwards");
+    }
+    if let Some(ref text) = note {
+        if text.len() > 512 {
+            panic!("progress note too long");
+        }
+    }
+
+    let progress = MilestoneProgress {
+        percent,
+        note: note.clone(),
+    };
+    set_milestone_progress(env, milestone_id, &progress);
+    milestone.last_updated_at = env.ledger().timestamp();
+    store_milestone(env, &milestone);
+
+    let event = MilestoneProgressUpdatedEvent {
+        project_id: project.id,
+        milestone_id,
+        percent,
+        note,
+    };
+    emit_event(env, MOD_MILESTONE, ACT_PROGRESS, event);
+
+    true
+}
+
 pub fn submit_milestone(env: &Env, milestone_id: u64, proof_url: String) -> bool {
     let mut milestone = get_milestone(env, milestone_id).expect("milestone not found");
     let project = get_project(env, milestone.project_id).expect("project not found");
 
     assert_project_active(&project, env);
-    ensure_not_expired(env, &mut milestone);
+    ensure_not_expired(env, &milestone);
 
     if proof_url.len() == 0 || proof_url.len() > 1024 {
         panic!("invalid proof url");
     }
+    assert_contributor_not_suspended(env, &project);
 
     if milestone.status != MilestoneStatus::InProgress
         && milestone.status != MilestoneStatus::Rejected
@@ -329,9 +691,15 @@ pub fn approve_milestone(env: &Env, milestone_id: u64, approver: Address) -> boo
     let mut project = get_project(env, milestone.project_id).expect("project not found");
 
     assert_project_active(&project, env);
-    ensure_not_expired(env, &mut milestone);
+    ensure_not_expired(env, &milestone);
 
-    if !has_permission(env, project.guild_id, approver, Role::Admin) {
+    if !enforce_permission(
+        env,
+        project.guild_id,
+        approver,
+        Role::Admin,
+        "approve_milestone",
+    ) {
         panic!("approver must be guild admin");
     }
 
@@ -339,35 +707,187 @@ pub fn approve_milestone(env: &Env, milestone_id: u64, approver: Address) -> boo
         panic!("milestone not submitted");
     }
 
+    mark_approved(env, &project, &mut milestone);
+
+    // Automatic payment release (Option B via treasury). A paused treasury
+    // must not block the approval, so the payment waits for
+    // `release_milestone_payment` instead.
+    if treasury_paused(env, &project) {
+        queue_milestone_payment(env, &project, &milestone);
+    } else {
+        let _ = release_milestone_payment_internal(env, &mut project, &mut milestone);
+    }
+
+    true
+}
+
+fn treasury_paused(env: &Env, project: &Project) -> bool {
+    project.funding == ProjectFunding::Treasury
+        && crate::treasury::storage::get_treasury(env, project.treasury_id)
+            .map(|treasury| treasury.paused)
+            .unwrap_or(false)
+}
+
+fn queue_milestone_payment(env: &Env, project: &Project, milestone: &Milestone) {
+    queue_payment(env, project.id, milestone.id);
+
+    let event = MilestonePaymentQueuedEvent {
+        project_id: project.id,
+        milestone_id: milestone.id,
+        treasury_id: project.treasury_id,
+        amount: milestone.payment_amount,
+    };
+    emit_event(env, MOD_MILESTONE, ACT_PAYMENT_QUEUED, event);
+}
+
+/// Approved milestones of a project whose payment has not been released yet
+pub fn get_queued_milestone_payments(env: &Env, project_id: u64) -> Vec<u64> {
+    get_queued_payments(env, project_id)
+}
+
+fn mark_approved(env: &Env, project: &Project, milestone: &mut Milestone) {
     let old_status = milestone.status.clone();
     milestone.status = MilestoneStatus::Approved;
     milestone.last_updated_at = env.ledger().timestamp();
-    store_milestone(env, &milestone);
+    store_milestone(env, milestone);
 
     let status_event = MilestoneStatusChangedEvent {
         project_id: project.id,
-        milestone_id,
+        milestone_id: milestone.id,
         old_status,
         new_status: milestone.status.clone(),
     };
     emit_event(env, MOD_MILESTONE, ACT_APPROVED, status_event);
 
-    // Automatic payment release (Option B via treasury)
-    let _ = release_milestone_payment_internal(env, &mut project, &mut milestone);
+    record_milestone_contribution(
+        env,
+        project.guild_id,
+        &project.contributor,
+        milestone.id,
+        &milestone.token,
+        milestone.payment_amount,
+    );
+}
 
-    true
+/// Approve every submitted milestone in `milestone_ids` and release its
+/// payment. Milestones that are not submitted are skipped. A payment that
+/// cannot be released leaves its milestone approved and reported as
+/// `PaymentFailed` without affecting the rest of the batch.
+pub fn batch_approve_milestones(
+    env: &Env,
+    milestone_ids: Vec<u64>,
+    approver: Address,
+) -> Vec<MilestoneApprovalResult> {
+    approver.require_auth();
+
+    let mut results = Vec::new(env);
+    for milestone_id in milestone_ids.iter() {
+        let outcome = approve_in_batch(env, milestone_id, &approver);
+        results.push_back(MilestoneApprovalResult {
+            milestone_id,
+            outcome,
+        });
+    }
+    results
+}
+
+fn approve_in_batch(env: &Env, milestone_id: u64, approver: &Address) -> MilestoneApprovalOutcome {
+    let mut milestone = match get_milestone(env, milestone_id) {
+        Some(milestone) => milestone,
+        None => return MilestoneApprovalOutcome::Skipped,
+    };
+    let mut project = match get_project(env, milestone.project_id) {
+        Some(project) => project,
+        None => return MilestoneApprovalOutcome::Skipped,
+    };
+
+    if !enforce_permission(
+        env,
+        project.guild_id,
+        approver.clone(),
+        Role::Admin,
+        "batch_approve_milestones",
+    ) {
+        panic!("approver must be guild admin");
+    }
+    if project.status != ProjectStatus::Active
+        || milestone.status != MilestoneStatus::Submitted
+        || is_milestone_expired(env, &milestone)
+    {
+        return MilestoneApprovalOutcome::Skipped;
+    }
+
+    mark_approved(env, &project, &mut milestone);
+
+    if !payment_releasable(env, &project, &milestone)
+        || try_release_milestone_payment(env, &mut project, &mut milestone).is_err()
+    {
+        queue_milestone_payment(env, &project, &milestone);
+        return MilestoneApprovalOutcome::PaymentFailed;
+    }
+    MilestoneApprovalOutcome::Paid
+}
+
+/// Whether an approved milestone's payment can be released right now. On
+/// sequential projects every earlier milestone must be settled first.
+fn payment_releasable(env: &Env, project: &Project, milestone: &Milestone) -> bool {
+    if dispute_storage::is_reference_locked(env, &DisputeReference::Milestone, milestone.id) {
+        return false;
+    }
+    if project.budgets_token(&milestone.token) {
+        match project
+            .released_amount
+            .checked_add(milestone.payment_amount)
+        {
+            Some(released) if released <= project.total_amount => {}
+            _ => return false,
+        }
+    }
+
+    if project.is_sequential {
+        for id in get_project_milestone_ids(env, project.id).iter() {
+            if let Some(m) = get_milestone(env, id) {
+                if m.order < milestone.order
+                    && !m.is_payment_released
+                    && m.status != MilestoneStatus::Expired
+                    && m.status != MilestoneStatus::Failed
+                {
+                    return false;
+                }
+            }
+        }
+    }
+
+    match project.funding {
+        ProjectFunding::Treasury => can_pay_milestone(
+            env,
+            project.treasury_id,
+            &milestone.token,
+            milestone.payment_amount,
+        ),
+        ProjectFunding::Escrow(_) => true,
+    }
 }
 
+/// Reject a submitted milestone. Once it has been rejected more often than the
+/// guild's rejection policy allows, it fails instead and the policy's failure
+/// action is applied to the project.
 pub fn reject_milestone(env: &Env, milestone_id: u64, approver: Address, reason: String) -> bool {
     approver.require_auth();
 
     let mut milestone = get_milestone(env, milestone_id).expect("milestone not found");
-    let project = get_project(env, milestone.project_id).expect("project not found");
+    let mut project = get_project(env, milestone.project_id).expect("project not found");
 
     assert_project_active(&project, env);
-    ensure_not_expired(env, &mut milestone);
+    ensure_not_expired(env, &milestone);
 
-    if !has_permission(env, project.guild_id, approver, Role::Admin) {
+    if !enforce_permission(
+        env,
+        project.guild_id,
+        approver,
+        Role::Admin,
+        "reject_milestone",
+    ) {
         panic!("approver must be guild admin");
     }
 
@@ -379,8 +899,17 @@ pub fn reject_milestone(env: &Env, milestone_id: u64, approver: Address, reason:
         panic!("invalid rejection reason");
     }
 
+    let policy = load_rejection_policy(env, project.guild_id);
+    milestone.rejection_count = milestone.rejection_count.saturating_add(1);
+    let failed = policy.max_rejections > 0 && milestone.rejection_count > policy.max_rejections;
+
     let old_status = milestone.status.clone();
-    milestone.status = MilestoneStatus::Rejected;
+    milestone.status = if failed {
+        MilestoneStatus::Failed
+    } else {
+        MilestoneStatus::Rejected
+    };
+    remove_milestone_progress(env, milestone_id);
     milestone.last_updated_at = env.ledger().timestamp();
     store_milestone(env, &milestone);
 
@@ -399,6 +928,44 @@ pub fn reject_milestone(env: &Env, milestone_id: u64, approver: Address, reason:
     };
     emit_event(env, MOD_MILESTONE, ACT_UPDATED, status_event);
 
+    if failed {
+        match policy.on_failure {
+            MilestoneFailureAction::FreeAllocation => {
+                if project.budgets_token(&milestone.token) {
+                    project.allocated_amount -= milestone.payment_amount;
+                    store_project(env, &project);
+                }
+            }
+            MilestoneFailureAction::CancelProject => mark_project_cancelled(env, &mut project),
+        }
+    }
+
+    true
+}
+
+pub fn get_rejection_policy(env: &Env, guild_id: u64) -> RejectionPolicy {
+    load_rejection_policy(env, guild_id)
+}
+
+/// Set how many rejections a milestone may take before it fails, and what
+/// happens to its project then (guild admins only).
+pub fn set_rejection_policy(
+    env: &Env,
+    guild_id: u64,
+    max_rejections: u32,
+    on_failure: MilestoneFailureAction,
+    caller: Address,
+) -> bool {
+    caller.require_auth();
+
+    if !enforce_permission(env, guild_id, caller, Role::Admin, "set_rejection_policy") {
+        panic!("caller must be guild admin");
+    }
+    let policy = RejectionPolicy {
+        max_rejections,
+        on_failure,
+    };
+    store_rejection_policy(env, guild_id, &policy);
     true
 }
 
@@ -422,10 +989,110 @@ pub fn get_project_progress(env: &Env, project_id: u64) -> (u32, u32, u32) {
     (completed, total, percentage)
 }
 
+pub fn get_project_view(env: &Env, project_id: u64) -> Project {
+    get_project(env, project_id).expect("project not found")
+}
+
+/// Approved milestones whose payment has not been released yet, across every
+/// project of `contributor`, as `(milestone_id, amount)` pairs. Returns at
+/// most `limit` (capped at `MAX_MILESTONE_PAGE_SIZE`) entries, oldest
+/// projects first.
+pub fn get_pending_payments_for(env: &Env, contributor: Address, limit: u32) -> Vec<(u64, i128)> {
+    let limit = limit.min(MAX_MILESTONE_PAGE_SIZE);
+    let mut pending = Vec::new(env);
+    for project_id in get_contributor_project_ids(env, &contributor).iter() {
+        for milestone_id in get_project_milestone_ids(env, project_id).iter() {
+            if pending.len() >= limit {
+                return pending;
+            }
+            if let Some(m) = get_milestone(env, milestone_id) {
+                if m.status == MilestoneStatus::Approved && !m.is_payment_released {
+                    pending.push_back((m.id, m.payment_amount));
+                }
+            }
+        }
+    }
+    pending
+}
+
+/// IDs of every project created for the guild, in creation order. Cancelled
+/// and completed projects are included; callers read their status.
+pub fn get_guild_projects(env: &Env, guild_id: u64) -> Vec<u64> {
+    get_guild_project_ids(env, guild_id)
+}
+
 pub fn get_milestone_view(env: &Env, milestone_id: u64) -> Milestone {
     get_milestone(env, milestone_id).expect("milestone not found")
 }
 
+/// Latest progress reported on a milestone; 0% without a note if none was
+pub fn get_milestone_progress(env: &Env, milestone_id: u64) -> MilestoneProgress {
+    get_milestone(env, milestone_id).expect("milestone not found");
+    load_milestone_progress(env, milestone_id).unwrap_or(MilestoneProgress {
+        percent: 0,
+        note: None,
+    })
+}
+
+pub fn get_project_milestones(
+    env: &Env,
+    project_id: u64,
+    offset: u32,
+    limit: u32,
+) -> Vec<Milestone> {
+    collect_project_milestones(env, project_id, None, offset, limit)
+}
+
+pub fn get_milestones_by_status(
+    env: &Env,
+    project_id: u64,
+    status: MilestoneStatus,
+    offset: u32,
+    limit: u32,
+) -> Vec<Milestone> {
+    collect_project_milestones(env, project_id, Some(status), offset, limit)
+}
+
+/// Walk the project's milestone index in order, skipping `offset` matches and
+/// returning at most `limit` (capped at `MAX_MILESTONE_PAGE_SIZE`) milestones.
+fn collect_project_milestones(
+    env: &Env,
+    project_id: u64,
+    status: Option<MilestoneStatus>,
+    offset: u32,
+    limit: u32,
+) -> Vec<Milestone> {
+    get_project(env, project_id).expect("project not found");
+
+    let limit = limit.min(MAX_MILESTONE_PAGE_SIZE);
+    let mut result = Vec::new(env);
+    if limit == 0 {
+        return result;
+    }
+
+    let mut skipped: u32 = 0;
+    for id in get_project_milestone_ids(env, project_id).iter() {
+        let Some(milestone) = get_milestone(env, id) else {
+            continue;
+        };
+        if let Some(ref wanted) = status {
+            if milestone.status != *wanted {
+                continue;
+            }
+        }
+        if skipped < offset {
+            skipped += 1;
+            continue;
+        }
+        result.push_back(milestone);
+        if result.len() >= limit {
+            break;
+        }
+    }
+
+    result
+}
+
 pub fn release_milestone_payment(env: &Env, milestone_id: u64) -> bool {
     let mut milestone = get_milestone(env, milestone_id).expect("milestone not found");
     let mut project = get_project(env, milestone.project_id).expect("project not found");
@@ -440,6 +1107,18 @@ fn release_milestone_payment_internal(
     project: &mut Project,
     milestone: &mut Milestone,
 ) -> bool {
+    try_release_milestone_payment(env, project, milestone)
+        .unwrap_or_else(|e| panic_with_error!(env, e));
+    true
+}
+
+/// Release an approved milestone's payment, returning the treasury's refusal
+/// instead of panicking so batch callers can queue the payment and move on.
+fn try_release_milestone_payment(
+    env: &Env,
+    project: &mut Project,
+    milestone: &mut Milestone,
+) -> Result<(), TreasuryError> {
     if dispute_storage::is_reference_locked(env, &DisputeReference::Milestone, milestone.id) {
         panic!("milestone is in active dispute");
     }
@@ -451,25 +1130,23 @@ fn release_milestone_payment_internal(
         panic!("milestone payment already released");
     }
 
-    let new_released = project
-        .released_amount
-        .checked_add(milestone.payment_amount)
-        .expect("overflow");
-    if new_released > project.total_amount {
-        panic!("project budget exceeded");
+    let mut new_released = project.released_amount;
+    if project.budgets_token(&milestone.token) {
+        new_released = new_released
+            .checked_add(milestone.payment_amount)
+            .expect("overflow");
+        if new_released > project.total_amount {
+            panic!("project budget exceeded");
+        }
     }
 
-    // Execute payment via treasury helper (Option B)
-    let token = project.token.clone();
+    let token = milestone.token.clone();
     let amount = milestone.payment_amount;
 
-    execute_milestone_payment(
-        env,
-        project.treasury_id,
-        token.clone(),
-        project.contributor.clone(),
-        amount,
-    );
+    // Paid to the project's payout wallet, which may differ from the
+    // contributor who acts on the milestones
+    let recipient = project.payee();
+    try_disburse_project_payment(env, project, &token, recipient.clone(), amount)?;
 
     project.released_amount = new_released;
     milestone.is_payment_released = true;
@@ -477,6 +1154,7 @@ fn release_milestone_payment_internal(
 
     store_project(env, project);
     store_milestone(env, milestone);
+    dequeue_payment(env, project.id, milestone.id);
 
     let event = MilestonePaymentReleasedEvent {
         project_id: project.id,
@@ -484,7 +1162,7 @@ fn release_milestone_payment_internal(
         treasury_id: project.treasury_id,
         amount,
         token,
-        recipient: project.contributor.clone(),
+        recipient,
     };
     emit_event(env, MOD_MILESTONE, ACT_RELEASED, event);
 
@@ -493,7 +1171,10 @@ fn release_milestone_payment_internal(
     let mut all_done = true;
     for id in ids.iter() {
         if let Some(m) = get_milestone(env, id) {
-            if !m.is_payment_released && m.status != MilestoneStatus::Expired {
+            if !m.is_payment_released
+                && m.status != MilestoneStatus::Expired
+                && m.status != MilestoneStatus::Failed
+            {
                 all_done = false;
                 break;
             }
@@ -504,6 +1185,7 @@ fn release_milestone_payment_internal(
         let old_status = project.status.clone();
         project.status = ProjectStatus::Completed;
         store_project(env, project);
+        refund_escrow_remainder(env, project);
 
         let pe = ProjectStatusChangedEvent {
             project_id: project.id,
@@ -513,7 +1195,7 @@ fn release_milestone_payment_internal(
         emit_event(env, MOD_MILESTONE, ACT_COMPLETED, pe);
     }
 
-    true
+    Ok(())
 }
 
 pub fn extend_milestone_deadline(
@@ -525,17 +1207,25 @@ pub fn extend_milestone_deadline(
     caller.require_auth();
 
     let mut milestone = get_milestone(env, milestone_id).expect("milestone not found");
-    let mut project = get_project(env, milestone.project_id).expect("project not found");
+    let project = get_project(env, milestone.project_id).expect("project not found");
 
-    if !has_permission(env, project.guild_id, caller, Role::Admin) {
+    if !enforce_permission(
+        env,
+        project.guild_id,
+        caller,
+        Role::Admin,
+        "extend_milestone_deadline",
+    ) {
         panic!("caller must be guild admin");
     }
+    if milestone.status == MilestoneStatus::Approved {
+        panic!("milestone already approved");
+    }
 
     let now = env.ledger().timestamp();
-    if new_deadline <= now || new_deadline <= milestone.deadline {
-        panic!("new deadline must be in the future and after current deadline");
-    }
+    validate_new_deadline(&milestone, new_deadline, now);
 
+    let old_deadline = milestone.deadline;
     milestone.deadline = new_deadline;
     milestone.last_updated_at = now;
     if milestone.status == MilestoneStatus::Expired {
@@ -543,18 +1233,85 @@ pub fn extend_milestone_deadline(
     }
     store_milestone(env, &milestone);
 
-    // project status unchanged; event could be added if needed
-    let _ = &mut project; // silence unused for now
+    // An admin extension supersedes any outstanding contributor request
+    remove_extension_request(env, milestone_id);
+
+    let event = MilestoneDeadlineExtendedEvent {
+        project_id: project.id,
+        milestone_id,
+        old_deadline,
+        new_deadline,
+    };
+    emit_event(env, MOD_MILESTONE, ACT_DEADLINE_EXTENDED, event);
+
+    true
+}
+
+fn validate_new_deadline(milestone: &Milestone, new_deadline: u64, now: u64) {
+    if new_deadline <= now || new_deadline <= milestone.deadline {
+        panic!("new deadline must be in the future and after current deadline");
+    }
+}
+
+pub fn request_deadline_extension(
+    env: &Env,
+    milestone_id: u64,
+    new_deadline: u64,
+    contributor: Address,
+) -> bool {
+    contributor.require_auth();
+
+    let milestone = get_milestone(env, milestone_id).expect("milestone not found");
+    let project = get_project(env, milestone.project_id).expect("project not found");
+
+    assert_project_active(&project, env);
+
+    if contributor != project.contributor {
+        panic!("only project contributor can request extension");
+    }
+    assert_contributor_not_suspended(env, &project);
+
+    if milestone.status == MilestoneStatus::Approved {
+        panic!("milestone already approved");
+    }
+
+    let now = env.ledger().timestamp();
+    validate_new_deadline(&milestone, new_deadline, now);
+
+    // A newer request replaces any earlier pending one
+    let request = DeadlineExtensionRequest {
+        milestone_id,
+        requested_by: contributor,
+        new_deadline,
+        requested_at: now,
+    };
+    store_extension_request(env, &request);
+
+    emit_event(env, MOD_MILESTONE, ACT_EXTENSION_REQUESTED, request);
 
     true
 }
 
+pub fn approve_deadline_extension(env: &Env, milestone_id: u64, approver: Address) -> bool {
+    let request = get_extension_request(env, milestone_id).expect("no pending extension request");
+
+    // Re-validated against the current deadline and time by the admin path
+    extend_milestone_deadline(env, milestone_id, request.new_deadline, approver)
+}
+
+pub fn get_deadline_extension_request(
+    env: &Env,
+    milestone_id: u64,
+) -> Option<DeadlineExtensionRequest> {
+    get_extension_request(env, milestone_id)
+}
+
 pub fn cancel_project(env: &Env, project_id: u64, caller: Address) -> bool {
     caller.require_auth();
 
     let mut project = get_project(env, project_id).expect("project not found");
 
-    if !has_permission(env, project.guild_id, caller, Role::Admin) {
+    if !enforce_permission(env, project.guild_id, caller, Role::Admin, "cancel_project") {
         panic!("caller must be guild admin");
     }
 
@@ -562,9 +1319,67 @@ pub fn cancel_project(env: &Env, project_id: u64, caller: Address) -> bool {
         return true;
     }
 
+    mark_project_cancelled(env, &mut project);
+
+    true
+}
+
+/// Route a project's future milestone payments to `payout_address` (or back
+/// to the contributor with `None`). The contributor requests the change and
+/// a guild admin must co-sign it; payments already released are unaffected.
+pub fn set_payout_address(
+    env: &Env,
+    project_id: u64,
+    payout_address: Option<Address>,
+    contributor: Address,
+    admin: Address,
+) -> bool {
+    contributor.require_auth();
+    admin.require_auth();
+
+    let mut project = get_project(env, project_id).expect("project not found");
+    if project.contributor != contributor {
+        panic!("only the project contributor can change the payout address");
+    }
+    if !enforce_permission(
+        env,
+        project.guild_id,
+        admin.clone(),
+        Role::Admin,
+        "set_payout_address",
+    ) {
+        panic!("payout address change must be approved by a guild admin");
+    }
+    if project.status != ProjectStatus::Active {
+        panic!("project is not active");
+    }
+
+    let old_payee = project.payee();
+    project.payout_address = payout_address;
+    store_project(env, &project);
+
+    let event = PayoutAddressChangedEvent {
+        project_id,
+        old_payee,
+        new_payee: project.payee(),
+        approved_by: admin,
+    };
+    emit_event(env, MOD_MILESTONE, ACT_UPDATED, event);
+
+    true
+}
+
+/// Cancel a project that is not already cancelled, returning any escrow
+/// remainder of an active project.
+fn mark_project_cancelled(env: &Env, project: &mut Project) {
     let old_status = project.status.clone();
     project.status = ProjectStatus::Cancelled;
-    store_project(env, &project);
+    store_project(env, project);
+
+    // A completed project has already returned its escrow remainder
+    if old_status == ProjectStatus::Active {
+        refund_escrow_remainder(env, project);
+    }
 
     let event = ProjectStatusChangedEvent {
         project_id: project.id,
@@ -572,6 +1387,4 @@ pub fn cancel_project(env: &Env, project_id: u64, caller: Address) -> bool {
         new_status: ProjectStatus::Cancelled,
     };
     emit_event(env, MOD_MILESTONE, ACT_CANCELLED, event);
-
-    true
 }
@@ -19,6 +19,73 @@ pub enum MilestoneStatus {
     Approved,
     Rejected,
     Expired,
+    /// Rejected more often than the guild's rejection policy allows; terminal
+    Failed,
+}
+
+/// Source of a project's milestone payments, fixed at creation
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ProjectFunding {
+    /// Paid out of `treasury_id` as each milestone is released
+    Treasury,
+    /// `total_amount` locked upfront by the given funder; whatever is not
+    /// released goes back to them when the project ends
+    Escrow(Address),
+}
+
+/// How much a guild's treasury must hold before a treasury-backed project
+/// can be created
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum StartFundingCheck {
+    /// No balance check (default)
+    Disabled,
+    /// Enough to pay the first milestone
+    FirstMilestone,
+    /// Enough to pay every milestone of the project
+    FullAllocation,
+}
+
+/// What happens to the project when one of its milestones fails
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum MilestoneFailureAction {
+    /// Return the milestone's payment to the project's unallocated budget
+    FreeAllocation,
+    /// Cancel the whole project
+    CancelProject,
+}
+
+/// Guild-wide limit on milestone rejections
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RejectionPolicy {
+    /// Rejections a milestone may receive before the next one fails it
+    /// (0 = unlimited)
+    pub max_rejections: u32,
+    pub on_failure: MilestoneFailureAction,
+}
+
+/// What a batch approval did with one milestone
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum MilestoneApprovalOutcome {
+    /// Approved and its payment released
+    Paid,
+    /// Approved, but the payment could not be released yet; it can be
+    /// retried with `release_milestone_payment`
+    PaymentFailed,
+    /// Left untouched: missing, expired, not submitted or project inactive
+    Skipped,
+}
+
+/// Per-milestone result of a batch approval
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MilestoneApprovalResult {
+    pub milestone_id: u64,
+    pub outcome: MilestoneApprovalOutcome,
 }
 
 /// Project configuration and aggregate accounting
@@ -36,6 +103,25 @@ pub struct Project {
     pub is_sequential: bool,
     pub created_at: u64,
     pub status: ProjectStatus,
+    pub funding: ProjectFunding,
+    /// Wallet milestone payments go to; the contributor when unset
+    pub payout_address: Option<Address>,
+}
+
+impl Project {
+    /// Address that receives this project's milestone payments
+    pub fn payee(&self) -> Address {
+        self.payout_address
+            .clone()
+            .unwrap_or_else(|| self.contributor.clone())
+    }
+
+    /// Whether a payment in `token` draws on this project's budget. The
+    /// amounts above are in the project token; a milestone paying in another
+    /// token is budgeted by its own payment amount alone.
+    pub fn budgets_token(&self, token: &Option<Address>) -> bool {
+        self.token == *token
+    }
 }
 
 /// Milestone metadata and state
@@ -56,6 +142,20 @@ pub struct Milestone {
     pub last_updated_at: u64,
     pub version: u32,
     pub is_payment_released: bool,
+    /// Token this milestone pays in (the project token unless overridden)
+    pub token: Option<Address>,
+    /// Times the milestone has been rejected; kept across resubmissions
+    pub rejection_count: u32,
+}
+
+/// Progress a contributor reported on a milestone before submitting it
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MilestoneProgress {
+    /// Completion from 0 to 100; reset when the milestone is rejected
+    pub percent: u32,
+    /// Note attached to the latest progress update
+    pub note: Option<String>,
 }
 
 /// Input used when creating a project with multiple milestones
@@ -66,6 +166,18 @@ pub struct MilestoneInput {
     pub description: String,
     pub payment_amount: i128,
     pub deadline: u64,
+    /// Pay this milestone in a different token; `None` uses the project token
+    pub token: Option<Address>,
+}
+
+/// Contributor-initiated request to push back a milestone deadline
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DeadlineExtensionRequest {
+    pub milestone_id: u64,
+    pub requested_by: Address,
+    pub new_deadline: u64,
+    pub requested_at: u64,
 }
 
 // Events
@@ -90,6 +202,15 @@ pub struct ProjectStatusChangedEvent {
     pub new_status: ProjectStatus,
 }
 
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PayoutAddressChangedEvent {
+    pub project_id: u64,
+    pub old_payee: Address,
+    pub new_payee: Address,
+    pub approved_by: Address,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct MilestoneAddedEvent {
@@ -118,6 +239,15 @@ pub struct MilestoneSubmittedEvent {
     pub version: u32,
 }
 
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MilestoneProgressUpdatedEvent {
+    pub project_id: u64,
+    pub milestone_id: u64,
+    pub percent: u32,
+    pub note: Option<String>,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct MilestoneRejectedEvent {
@@ -136,3 +266,22 @@ pub struct MilestonePaymentReleasedEvent {
     pub token: Option<Address>,
     pub recipient: Address,
 }
+
+/// An approved milestone's payment was held back for later release
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MilestonePaymentQueuedEvent {
+    pub project_id: u64,
+    pub milestone_id: u64,
+    pub treasury_id: u64,
+    pub amount: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MilestoneDeadlineExtendedEvent {
+    pub project_id: u64,
+    pub milestone_id: u64,
+    pub old_deadline: u64,
+    pub new_deadline: u64,
+}
@@ -1,6 +1,11 @@
-﻿use crate::multisig::storage::{get_account, get_policy, store_policy};
+﻿use crate::multisig::storage::{
+    clear_pending_signer_change_cooldown, get_account, get_policy, get_signer_change_cooldown,
+    store_max_timeout_extension, store_pending_signer_change_cooldown, store_policy,
+    store_signer_change_cooldown,
+};
 use crate::multisig::types::{
-    OperationPolicy, OperationType, DEFAULT_TIMEOUT, TIMEOUT_24H, TIMEOUT_48H,
+    OperationPolicy, OperationType, DEFAULT_TIMEOUT, ERR_COOLDOWN_TOO_LONG, ERR_EXTENSION_TOO_LONG,
+    MAX_SIGNER_CHANGE_COOLDOWN, TIMEOUT_24H, TIMEOUT_48H,
 };
 use soroban_sdk::{Address, Env};
 
@@ -77,3 +82,60 @@ pub fn ms_reset_operation_policy(
     store_policy(env, account_id, operation_type, &default_policy);
     Ok(())
 }
+
+/// Cap how far past its original window an operation on this account can be
+/// extended via `ms_emergency_extend_timeout`. Applies to operations proposed
+/// after the call; the default cap equals the original window.
+pub fn ms_set_max_timeout_extension(
+    env: &Env,
+    account_id: u64,
+    max_extension_seconds: u64,
+    caller: Address,
+) -> Result<(), u32> {
+    caller.require_auth();
+    let account = get_account(env, account_id).ok_or(1u32)?;
+    if account.owner != caller {
+        return Err(2u32);
+    }
+    if max_extension_seconds > TIMEOUT_48H {
+        return Err(ERR_EXTENSION_TOO_LONG);
+    }
+    store_max_timeout_extension(env, account_id, max_extension_seconds);
+    Ok(())
+}
+
+/// Require at least `cooldown_seconds` between changes to the account's
+/// signer set (add, remove, rotate). Zero disables the cooldown; emergency
+/// rotations backed by an executed `EmergencyAction` are always exempt.
+///
+/// A raise applies at once. A decrease only applies once the current
+/// cooldown has elapsed, so a leaked owner key cannot drop it and churn the
+/// signer set straight away.
+pub fn ms_set_signer_change_cooldown(
+    env: &Env,
+    account_id: u64,
+    cooldown_seconds: u64,
+    caller: Address,
+) -> Result<(), u32> {
+    caller.require_auth();
+    let account = get_account(env, account_id).ok_or(1u32)?;
+    if account.owner != caller {
+        return Err(2u32);
+    }
+    if cooldown_seconds > MAX_SIGNER_CHANGE_COOLDOWN {
+        return Err(ERR_COOLDOWN_TOO_LONG);
+    }
+
+    let current = get_signer_change_cooldown(env, account_id);
+    if cooldown_seconds < current {
+        // Pin the value in force (it may be a decrease that just came due)
+        // until the new one takes over
+        store_signer_change_cooldown(env, account_id, current);
+        let effective_at = env.ledger().timestamp().saturating_add(current);
+        store_pending_signer_change_cooldown(env, account_id, cooldown_seconds, effective_at);
+    } else {
+        store_signer_change_cooldown(env, account_id, cooldown_seconds);
+        clear_pending_signer_change_cooldown(env, account_id);
+    }
+    Ok(())
+}
@@ -1,7 +1,51 @@
-﻿use crate::multisig::storage::{get_account, next_account_id, store_account};
-use crate::multisig::types::{AccountStatus, MultiSigAccount};
+﻿use crate::multisig::signing::ms_require_executed_operation;
+use crate::multisig::storage::{
+    get_account, get_last_signer_change, get_operation, get_signer_change_cooldown,
+    is_operation_consumed, mark_operation_consumed, next_account_id, store_account,
+    store_last_signer_change,
+};
+use crate::multisig::types::{AccountStatus, MultiSigAccount, OperationType, MAX_SIGNERS};
 use soroban_sdk::{Address, Env, Vec};
 
+/// Reject a signer-set change made within the account's cooldown of the
+/// previous one.
+fn ensure_signer_change_allowed(env: &Env, account_id: u64) -> Result<(), u32> {
+    let cooldown = get_signer_change_cooldown(env, account_id);
+    if cooldown == 0 {
+        return Ok(());
+    }
+    if let Some(last) = get_last_signer_change(env, account_id) {
+        if env.ledger().timestamp() < last.saturating_add(cooldown) {
+            return Err(12u32);
+        }
+    }
+    Ok(())
+}
+
+fn record_signer_change(env: &Env, account_id: u64) {
+    store_last_signer_change(env, account_id, env.ledger().timestamp());
+}
+
+fn replace_signer(
+    env: &Env,
+    account: &mut MultiSigAccount,
+    old_signer: Address,
+    new_signer: Address,
+) -> Result<(), u32> {
+    if account.signers.contains(&new_signer) {
+        return Err(1u32);
+    }
+    let idx = account.signers.first_index_of(&old_signer).ok_or(4u32)?;
+    account.signers.set(idx, new_signer);
+    if account.owner == old_signer {
+        account.owner = account.signers.get(idx).unwrap();
+    }
+    account.nonce += 1;
+    store_account(env, account.id, account);
+    record_signer_change(env, account.id);
+    Ok(())
+}
+
 pub fn ms_register_account(
     env: &Env,
     owner: Address,
@@ -14,6 +58,9 @@ pub fn ms_register_account(
     if !signers.contains(&owner) {
         signers.push_back(owner.clone());
     }
+    if signers.len() > MAX_SIGNERS {
+        return Err(11u32);
+    }
     let min_safe_threshold = (signers.len() / 2) + 1;
     if threshold < min_safe_threshold || threshold > signers.len() {
         return Err(1u32);
@@ -65,8 +112,13 @@ pub fn ms_add_signer(
         return Err(3u32);
     }
     if !account.signers.contains(&new_signer) {
+        if account.signers.len() >= MAX_SIGNERS {
+            return Err(11u32);
+        }
+        ensure_signer_change_allowed(env, account_id)?;
         account.signers.push_back(new_signer);
         store_account(env, account_id, &account);
+        record_signer_change(env, account_id);
     }
     Ok(())
 }
@@ -84,6 +136,7 @@ pub fn ms_remove_signer(
         return Err(3u32);
     }
     if let Some(idx) = account.signers.first_index_of(&signer) {
+        ensure_signer_change_allowed(env, account_id)?;
         account.signers.remove(idx);
         if account.signers.is_empty() {
             return Err(1u32);
@@ -95,6 +148,7 @@ pub fn ms_remove_signer(
         account.threshold = new_threshold;
         account.nonce += 1;
         store_account(env, account_id, &account);
+        record_signer_change(env, account_id);
     }
     Ok(())
 }
@@ -111,19 +165,35 @@ pub fn ms_rotate_signer(
     if account.owner != caller {
         return Err(3u32);
     }
-    if account.signers.contains(&new_signer) {
-        return Err(1u32);
+    ensure_signer_change_allowed(env, account_id)?;
+    replace_signer(env, &mut account, old_signer, new_signer)
+}
+
+/// Rotate a signer outside the change cooldown for incident response. Each
+/// rotation consumes an executed `EmergencyAction` operation on the account.
+pub fn ms_emergency_rotate_signer(
+    env: &Env,
+    account_id: u64,
+    emergency_op_id: u64,
+    old_signer: Address,
+    new_signer: Address,
+    caller: Address,
+) -> Result<(), u32> {
+    caller.require_auth();
+    let mut account = get_account(env, account_id).ok_or(2u32)?;
+    if account.owner != caller {
+        return Err(3u32);
     }
-    if let Some(idx) = account.signers.first_index_of(&old_signer) {
-        account.signers.set(idx, new_signer);
-        if account.owner == old_signer {
-            account.owner = account.signers.get(idx).unwrap();
-        }
-        account.nonce += 1;
-        store_account(env, account_id, &account);
-        return Ok(());
+    ms_require_executed_operation(env, emergency_op_id, OperationType::EmergencyAction)?;
+    let operation = get_operation(env, emergency_op_id).ok_or(3u32)?;
+    if operation.account_id != account_id {
+        return Err(9u32);
+    }
+    if is_operation_consumed(env, emergency_op_id) {
+        return Err(13u32);
     }
-    Err(4u32)
+    mark_operation_consumed(env, emergency_op_id);
+    replace_signer(env, &mut account, old_signer, new_signer)
 }
 
 pub fn ms_update_threshold(
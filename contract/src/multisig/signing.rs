@@ -1,12 +1,52 @@
-﻿use crate::multisig::policy::ms_get_operation_policy;
+﻿use crate::events::emit::emit_event;
+use crate::events::topics::{ACT_SIG_REQUESTED, ACT_THRESHOLD_REACHED, MOD_MULTISIG};
+use crate::multisig::policy::ms_get_operation_policy;
 use crate::multisig::storage::{
-    get_account, get_operation, next_operation_id, store_account, store_operation, DataKey,
+    get_account, get_max_timeout_extension, get_operation, is_operation_consumed,
+    mark_operation_consumed, next_operation_id, store_account, store_operation, DataKey,
 };
 use crate::multisig::types::{
-    AccountStatus, MultiSigOperation, OperationStatus, OperationType, TIMEOUT_24H, TIMEOUT_48H,
+    AccountStatus, MultiSigAccount, MultiSigOperation, OperationPolicy, OperationStatus,
+    OperationType, SignatureRequestedEvent, SignatureThresholdReachedEvent, TIMEOUT_24H,
+    TIMEOUT_48H,
 };
 use soroban_sdk::{Address, Env, String, Vec};
 
+fn resolve_required_signatures(policy: &OperationPolicy, account: &MultiSigAccount) -> u32 {
+    if policy.require_all_signers {
+        account.signers.len()
+    } else if policy.min_signatures > 0 {
+        policy.min_signatures
+    } else {
+        account.threshold
+    }
+}
+
+/// Whether an operation holds enough signatures, including the owner's when
+/// the policy demands it, to be executed
+fn has_required_signatures(
+    operation: &MultiSigOperation,
+    account: &MultiSigAccount,
+    policy: &OperationPolicy,
+) -> bool {
+    operation.signatures.len() >= operation.required_signatures
+        && (!policy.require_owner_signature || operation.signatures.contains(&account.owner))
+}
+
+fn emit_threshold_reached(env: &Env, operation: &MultiSigOperation) {
+    emit_event(
+        env,
+        MOD_MULTISIG,
+        ACT_THRESHOLD_REACHED,
+        SignatureThresholdReachedEvent {
+            op_id: operation.id,
+            account_id: operation.account_id,
+            signatures: operation.signatures.len(),
+            required_signatures: operation.required_signatures,
+        },
+    );
+}
+
 pub fn ms_propose_operation(
     env: &Env,
     account_id: u64,
@@ -25,6 +65,8 @@ pub fn ms_propose_operation(
     let mut signatures = Vec::new(env);
     signatures.push_back(proposer.clone());
     let timeout = policy.timeout_seconds.clamp(TIMEOUT_24H, TIMEOUT_48H);
+    // By default an operation may be extended to at most twice its original window
+    let max_extension = get_max_timeout_extension(env, account_id).unwrap_or(timeout);
     let nonce = account.nonce;
     account.nonce += 1;
     store_account(env, account.id, &account);
@@ -35,12 +77,39 @@ pub fn ms_propose_operation(
         description,
         proposer,
         signatures,
+        required_signatures: resolve_required_signatures(&policy, &account),
         nonce,
         created_at: current_time,
         expires_at: current_time + timeout,
+        max_expires_at: current_time + timeout + max_extension,
         status: OperationStatus::Pending,
     };
     store_operation(env, op_id, &operation);
+
+    // Requests go out once, to the signer set at proposal time; later signer
+    // or policy changes do not re-announce the operation
+    let mut requested_signers = Vec::new(env);
+    for signer in account.signers.iter() {
+        if !operation.signatures.contains(&signer) {
+            requested_signers.push_back(signer);
+        }
+    }
+    emit_event(
+        env,
+        MOD_MULTISIG,
+        ACT_SIG_REQUESTED,
+        SignatureRequestedEvent {
+            op_id,
+            account_id,
+            op_type: operation.op_type.clone(),
+            requested_signers,
+            required_signatures: operation.required_signatures,
+            expires_at: operation.expires_at,
+        },
+    );
+    if has_required_signatures(&operation, &account, &policy) {
+        emit_threshold_reached(env, &operation);
+    }
     Ok(op_id)
 }
 
@@ -59,9 +128,14 @@ pub fn ms_sign_operation(env: &Env, op_id: u64, signer: Address) -> Result<u32,
     if !account.signers.contains(&signer) || operation.signatures.contains(&signer) {
         return Err(6u32);
     }
+    let policy = ms_get_operation_policy(env, account.id, operation.op_type.clone());
+    let was_executable = has_required_signatures(&operation, &account, &policy);
     operation.signatures.push_back(signer);
     let sig_count = operation.signatures.len();
     store_operation(env, op_id, &operation);
+    if !was_executable && has_required_signatures(&operation, &account, &policy) {
+        emit_threshold_reached(env, &operation);
+    }
     Ok(sig_count)
 }
 
@@ -78,14 +152,7 @@ pub fn ms_execute_operation(env: &Env, op_id: u64, executor: Address) -> Result<
         return Err(5u32);
     }
     let policy = ms_get_operation_policy(env, account.id, operation.op_type.clone());
-    let required_sigs = if policy.require_all_signers {
-        account.signers.len()
-    } else if policy.min_signatures > 0 {
-        policy.min_signatures
-    } else {
-        account.threshold
-    };
-    if operation.signatures.len() < required_sigs {
+    if operation.signatures.len() < operation.required_signatures {
         return Err(7u32);
     }
     if policy.require_owner_signature && !operation.signatures.contains(&account.owner) {
@@ -133,6 +200,42 @@ pub fn ms_emergency_expire_operation(env: &Env, op_id: u64, owner: Address) -> R
     Ok(())
 }
 
+/// Cancel every pending operation on an account in one call (owner-only
+/// panic button). Operations whose window already lapsed are marked expired;
+/// terminal operations are left untouched. Returns how many were closed.
+pub fn ms_cancel_all_pending_operations(
+    env: &Env,
+    account_id: u64,
+    owner: Address,
+) -> Result<u32, u32> {
+    owner.require_auth();
+    let account = get_account(env, account_id).ok_or(1u32)?;
+    if account.owner != owner {
+        return Err(2u32);
+    }
+    let now = env.ledger().timestamp();
+    let max_id: u64 = env
+        .storage()
+        .instance()
+        .get(&DataKey::OperationCounter)
+        .unwrap_or(0);
+    let mut closed = 0u32;
+    for op_id in 1..=max_id {
+        if let Some(mut op) = get_operation(env, op_id) {
+            if op.account_id == account_id && op.status == OperationStatus::Pending {
+                op.status = if now > op.expires_at {
+                    OperationStatus::Expired
+                } else {
+                    OperationStatus::Cancelled
+                };
+                store_operation(env, op_id, &op);
+                closed += 1;
+            }
+        }
+    }
+    Ok(closed)
+}
+
 pub fn ms_emergency_extend_timeout(
     env: &Env,
     op_id: u64,
@@ -146,7 +249,12 @@ pub fn ms_emergency_extend_timeout(
         return Err(2u32);
     }
     let timeout = new_timeout_seconds.clamp(TIMEOUT_24H, TIMEOUT_48H);
-    op.expires_at = env.ledger().timestamp() + timeout;
+    let new_expires_at = env.ledger().timestamp() + timeout;
+    // Shortening is always allowed; lengthening is capped by the proposal-time bound
+    if new_expires_at > op.expires_at && new_expires_at > op.max_expires_at {
+        return Err(10u32);
+    }
+    op.expires_at = new_expires_at;
     store_operation(env, op_id, &op);
     Ok(())
 }
@@ -170,6 +278,28 @@ pub fn ms_require_executed_operation(
     Ok(())
 }
 
+/// Consume an executed operation as the authorization for one follow-up
+/// action by `caller`, who must own or sign for the operation's account.
+/// An operation can gate a single action only; reuse returns error 13.
+pub fn ms_consume_executed_operation(
+    env: &Env,
+    op_id: u64,
+    expected_type: OperationType,
+    caller: &Address,
+) -> Result<MultiSigAccount, u32> {
+    ms_require_executed_operation(env, op_id, expected_type)?;
+    let op = get_operation(env, op_id).ok_or(3u32)?;
+    let account = get_account(env, op.account_id).ok_or(2u32)?;
+    if account.owner != *caller && !account.signers.contains(caller) {
+        return Err(3u32);
+    }
+    if is_operation_consumed(env, op_id) {
+        return Err(13u32);
+    }
+    mark_operation_consumed(env, op_id);
+    Ok(account)
+}
+
 pub fn ms_get_pending_operations(env: &Env, account_id: u64) -> Vec<MultiSigOperation> {
     let now = env.ledger().timestamp();
     let max_id: u64 = env
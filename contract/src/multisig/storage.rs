@@ -8,6 +8,11 @@ pub enum DataKey {
     OperationPolicy(u64, OperationType),
     AccountCounter,
     OperationCounter,
+    MaxTimeoutExtension(u64),
+    SignerChangeCooldown(u64),
+    PendingSignerChangeCooldown(u64),
+    LastSignerChange(u64),
+    OperationConsumed(u64),
 }
 
 pub fn next_account_id(env: &Env) -> u64 {
@@ -71,3 +76,82 @@ pub fn get_policy(env: &Env, account_id: u64, op_type: OperationType) -> Option<
         .persistent()
         .get(&DataKey::OperationPolicy(account_id, op_type))
 }
+
+pub fn store_max_timeout_extension(env: &Env, account_id: u64, max_extension: u64) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::MaxTimeoutExtension(account_id), &max_extension);
+}
+
+pub fn get_max_timeout_extension(env: &Env, account_id: u64) -> Option<u64> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::MaxTimeoutExtension(account_id))
+}
+
+pub fn store_signer_change_cooldown(env: &Env, account_id: u64, cooldown: u64) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::SignerChangeCooldown(account_id), &cooldown);
+}
+
+/// Queue a lowered cooldown that replaces the current one at `effective_at`.
+pub fn store_pending_signer_change_cooldown(
+    env: &Env,
+    account_id: u64,
+    cooldown: u64,
+    effective_at: u64,
+) {
+    env.storage().persistent().set(
+        &DataKey::PendingSignerChangeCooldown(account_id),
+        &(cooldown, effective_at),
+    );
+}
+
+pub fn clear_pending_signer_change_cooldown(env: &Env, account_id: u64) {
+    env.storage()
+        .persistent()
+        .remove(&DataKey::PendingSignerChangeCooldown(account_id));
+}
+
+/// The cooldown in force now: a queued decrease once it is due, otherwise
+/// the stored value.
+pub fn get_signer_change_cooldown(env: &Env, account_id: u64) -> u64 {
+    let pending: Option<(u64, u64)> = env
+        .storage()
+        .persistent()
+        .get(&DataKey::PendingSignerChangeCooldown(account_id));
+    if let Some((cooldown, effective_at)) = pending {
+        if env.ledger().timestamp() >= effective_at {
+            return cooldown;
+        }
+    }
+    env.storage()
+        .persistent()
+        .get(&DataKey::SignerChangeCooldown(account_id))
+        .unwrap_or(0)
+}
+
+pub fn store_last_signer_change(env: &Env, account_id: u64, timestamp: u64) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::LastSignerChange(account_id), &timestamp);
+}
+
+pub fn get_last_signer_change(env: &Env, account_id: u64) -> Option<u64> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::LastSignerChange(account_id))
+}
+
+pub fn mark_operation_consumed(env: &Env, op_id: u64) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::OperationConsumed(op_id), &true);
+}
+
+pub fn is_operation_consumed(env: &Env, op_id: u64) -> bool {
+    env.storage()
+        .persistent()
+        .has(&DataKey::OperationConsumed(op_id))
+}
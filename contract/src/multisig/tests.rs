@@ -1,10 +1,13 @@
 ﻿#[cfg(test)]
 mod tests {
     use crate::governance::{ProposalType, VoteDecision};
-    use crate::multisig::types::{OperationStatus, OperationType, TIMEOUT_24H, TIMEOUT_48H};
+    use crate::multisig::types::{
+        OperationStatus, OperationType, MAX_SIGNERS, MAX_SIGNER_CHANGE_COOLDOWN, TIMEOUT_24H,
+        TIMEOUT_48H,
+    };
     use crate::{StellarGuildsContract, StellarGuildsContractClient};
-    use soroban_sdk::testutils::{Address as _, Ledger as _, LedgerInfo};
-    use soroban_sdk::{Address, Env, String, Vec};
+    use soroban_sdk::testutils::{Address as _, Events as _, Ledger as _, LedgerInfo};
+    use soroban_sdk::{Address, Env, String, Symbol, TryFromVal, Vec};
 
     fn setup_env() -> (Env, Address, Address, Address) {
         let env = Env::default();
@@ -225,6 +228,58 @@ mod tests {
         );
         // Tx id 1 is the deposit; withdrawal proposal is the next tx.
         assert_eq!(tx_id, 2);
+
+        // An executed operation gates a single withdrawal
+        let reuse = client.try_ms_propose_treasury_withdrawal(
+            &op_id, &treasury_id, &owner, &signer1, &100i128, &None, &reason,
+        );
+        assert!(reuse.is_err());
+    }
+
+    #[test]
+    fn test_treasury_withdrawal_gate_rejects_cross_account_operation() {
+        let (env, owner, signer1, signer2) = setup_env();
+        env.mock_all_auths();
+        let client = init_client(&env);
+
+        let guild_name = String::from_str(&env, "Treasury Guild");
+        let guild_desc = String::from_str(&env, "Guild for treasury integration");
+        let guild_id = client.create_guild(&guild_name, &guild_desc, &owner);
+        let mut treasury_signers = Vec::new(&env);
+        treasury_signers.push_back(owner.clone());
+        treasury_signers.push_back(signer1.clone());
+        treasury_signers.push_back(signer2.clone());
+        let treasury_id = client.initialize_treasury(&guild_id, &treasury_signers, &2u32);
+        client.deposit_treasury(&treasury_id, &owner, &1_000i128, &None);
+
+        // An unrelated multisig account executes its own withdrawal operation
+        let outsider = Address::generate(&env);
+        let outsider_s1 = Address::generate(&env);
+        let outsider_s2 = Address::generate(&env);
+        let outsider_account =
+            register_ms_account(&env, &client, &outsider, &outsider_s1, &outsider_s2);
+        let op_desc = String::from_str(&env, "Outsider withdrawal");
+        let op_id = client.ms_propose_operation(
+            &outsider_account,
+            &OperationType::TreasuryWithdrawal,
+            &op_desc,
+            &outsider,
+        );
+        client.ms_sign_operation(&op_id, &outsider_s1);
+        assert!(client.ms_execute_operation(&op_id, &outsider_s2));
+
+        // A treasury signer can't borrow the outsider's operation
+        let reason = String::from_str(&env, "borrowed approval");
+        let borrowed = client.try_ms_propose_treasury_withdrawal(
+            &op_id, &treasury_id, &owner, &signer1, &100i128, &None, &reason,
+        );
+        assert!(borrowed.is_err());
+
+        // Nor can the outsider use it against a treasury it doesn't sign for
+        let foreign = client.try_ms_propose_treasury_withdrawal(
+            &op_id, &treasury_id, &outsider, &outsider, &100i128, &None, &reason,
+        );
+        assert!(foreign.is_err());
     }
 
     #[test]
@@ -245,6 +300,7 @@ mod tests {
             &ProposalType::GeneralDecision,
             &title,
             &description,
+            &None,
         );
         client.vote(&proposal_id, &owner, &VoteDecision::For);
         // End voting period.
@@ -263,6 +319,54 @@ mod tests {
         assert!(client.ms_execute_governance_proposal(&op_id, &proposal_id, &owner));
     }
 
+    #[test]
+    fn test_governance_gate_rejects_cross_guild_operation() {
+        let (env, owner, _, _) = setup_env();
+        env.mock_all_auths();
+        let client = init_client(&env);
+
+        let guild_name = String::from_str(&env, "Gov Guild");
+        let guild_desc = String::from_str(&env, "Guild for governance integration");
+        let guild_id = client.create_guild(&guild_name, &guild_desc, &owner);
+        let title = String::from_str(&env, "General decision");
+        let description = String::from_str(&env, "Should we proceed?");
+        let proposal_id = client.create_proposal(
+            &guild_id,
+            &owner,
+            &ProposalType::GeneralDecision,
+            &title,
+            &description,
+            &None,
+        );
+        client.vote(&proposal_id, &owner, &VoteDecision::For);
+        set_timestamp(&env, env.ledger().timestamp() + 8 * 24 * 60 * 60);
+
+        // An outsider's own account executes a governance operation
+        let outsider = Address::generate(&env);
+        let outsider_s1 = Address::generate(&env);
+        let outsider_s2 = Address::generate(&env);
+        let outsider_account =
+            register_ms_account(&env, &client, &outsider, &outsider_s1, &outsider_s2);
+        let op_desc = String::from_str(&env, "Outsider governance approval");
+        let op_id = client.ms_propose_operation(
+            &outsider_account,
+            &OperationType::GovernanceUpdate,
+            &op_desc,
+            &outsider,
+        );
+        client.ms_sign_operation(&op_id, &outsider_s1);
+        assert!(client.ms_execute_operation(&op_id, &outsider_s2));
+
+        // It can't gate execution of another guild's proposal
+        assert!(client
+            .try_ms_execute_governance_proposal(&op_id, &proposal_id, &outsider)
+            .is_err());
+        assert_eq!(
+            client.get_proposal(&proposal_id).status,
+            crate::governance::ProposalStatus::Active
+        );
+    }
+
     #[test]
     fn test_account_management_controls() {
         let (env, owner, signer1, signer2) = setup_env();
@@ -295,6 +399,55 @@ mod tests {
         assert!(!account.signers.contains(&replacement));
     }
 
+    #[test]
+    fn test_threshold_update_keeps_snapshot_for_pending_operation() {
+        let (env, owner, signer1, signer2) = setup_env();
+        env.mock_all_auths();
+        let client = init_client(&env);
+        let account_id = register_ms_account(&env, &client, &owner, &signer1, &signer2);
+
+        client.ms_set_policy(
+            &account_id,
+            &OperationType::GuildConfigChange,
+            &2u32,
+            &false,
+            &TIMEOUT_24H,
+            &false,
+            &owner,
+        );
+
+        let op_id = client.ms_propose_operation(
+            &account_id,
+            &OperationType::GuildConfigChange,
+            &String::from_str(&env, "config"),
+            &owner,
+        );
+        assert!(client.ms_update_threshold(&account_id, &3, &owner));
+        client.ms_set_policy(
+            &account_id,
+            &OperationType::GuildConfigChange,
+            &3u32,
+            &false,
+            &TIMEOUT_24H,
+            &false,
+            &owner,
+        );
+        assert_eq!(client.ms_get_operation(&op_id).required_signatures, 2);
+
+        // Two signatures still satisfy the operation proposed under threshold 2
+        client.ms_sign_operation(&op_id, &signer1);
+        assert!(client.ms_execute_operation(&op_id, &owner));
+
+        let new_op = client.ms_propose_operation(
+            &account_id,
+            &OperationType::GuildConfigChange,
+            &String::from_str(&env, "config"),
+            &owner,
+        );
+        client.ms_sign_operation(&new_op, &signer1);
+        assert!(client.try_ms_execute_operation(&new_op, &owner).is_err());
+    }
+
     #[test]
     fn test_cancel_expire_and_pending_operation_queries() {
         let (env, owner, signer1, signer2) = setup_env();
@@ -401,4 +554,293 @@ mod tests {
             &owner,
         );
     }
+
+    #[test]
+    fn test_emergency_extend_timeout_bounded_to_twice_window() {
+        let (env, owner, signer1, signer2) = setup_env();
+        env.mock_all_auths();
+        let client = init_client(&env);
+        let account_id = register_ms_account(&env, &client, &owner, &signer1, &signer2);
+        let start = env.ledger().timestamp();
+
+        let op_id = client.ms_propose_operation(
+            &account_id,
+            &OperationType::TreasuryWithdrawal,
+            &String::from_str(&env, "slow withdrawal"),
+            &owner,
+        );
+        let op = client.ms_get_operation(&op_id);
+        assert_eq!(op.max_expires_at, start + 2 * TIMEOUT_48H);
+
+        set_timestamp(&env, start + TIMEOUT_48H - 3_600);
+        assert!(client.ms_emergency_extend_timeout(&op_id, &TIMEOUT_48H, &owner));
+        assert_eq!(client.ms_get_operation(&op_id).expires_at, start + 2 * TIMEOUT_48H - 3_600);
+
+        set_timestamp(&env, start + 2 * TIMEOUT_48H - 7_200);
+        assert!(client.try_ms_emergency_extend_timeout(&op_id, &TIMEOUT_48H, &owner).is_err());
+        assert_eq!(client.ms_get_operation(&op_id).expires_at, start + 2 * TIMEOUT_48H - 3_600);
+    }
+
+    #[test]
+    fn test_emergency_shorten_timeout_always_allowed() {
+        let (env, owner, signer1, signer2) = setup_env();
+        env.mock_all_auths();
+        let client = init_client(&env);
+        let account_id = register_ms_account(&env, &client, &owner, &signer1, &signer2);
+        let start = env.ledger().timestamp();
+
+        assert!(client.ms_set_max_timeout_extension(&account_id, &0, &owner));
+        let op_id = client.ms_propose_operation(
+            &account_id,
+            &OperationType::EmergencyAction,
+            &String::from_str(&env, "no extensions"),
+            &owner,
+        );
+        assert_eq!(client.ms_get_operation(&op_id).max_expires_at, start + TIMEOUT_48H);
+
+        assert!(client.ms_emergency_extend_timeout(&op_id, &TIMEOUT_24H, &owner));
+        assert_eq!(client.ms_get_operation(&op_id).expires_at, start + TIMEOUT_24H);
+
+        set_timestamp(&env, start + 3_600);
+        assert!(client.try_ms_emergency_extend_timeout(&op_id, &TIMEOUT_48H, &owner).is_err());
+        assert!(client.ms_emergency_extend_timeout(&op_id, &TIMEOUT_24H, &owner));
+        assert_eq!(client.ms_get_operation(&op_id).expires_at, start + 3_600 + TIMEOUT_24H);
+    }
+
+    #[test]
+    #[should_panic(expected = "ms_set_max_timeout_extension error")]
+    fn test_set_max_timeout_extension_requires_owner() {
+        let (env, owner, signer1, signer2) = setup_env();
+        env.mock_all_auths();
+        let client = init_client(&env);
+        let account_id = register_ms_account(&env, &client, &owner, &signer1, &signer2);
+
+        client.ms_set_max_timeout_extension(&account_id, &TIMEOUT_24H, &signer1);
+    }
+
+    #[test]
+    #[should_panic(expected = "ms_set_max_timeout_extension error: 16")]
+    fn test_set_max_timeout_extension_rejects_cap_above_48h() {
+        let (env, owner, signer1, signer2) = setup_env();
+        env.mock_all_auths();
+        let client = init_client(&env);
+        let account_id = register_ms_account(&env, &client, &owner, &signer1, &signer2);
+
+        client.ms_set_max_timeout_extension(&account_id, &(TIMEOUT_48H + 1), &owner);
+    }
+
+    #[test]
+    fn test_signer_cap_enforced_but_rotation_allowed() {
+        let (env, owner, signer1, signer2) = setup_env();
+        env.mock_all_auths();
+        let client = init_client(&env);
+        let account_id = register_ms_account(&env, &client, &owner, &signer1, &signer2);
+
+        while client.ms_get_account(&account_id).signers.len() < MAX_SIGNERS {
+            client.ms_add_signer(&account_id, &Address::generate(&env), &owner);
+        }
+        let extra = Address::generate(&env);
+        assert!(client.try_ms_add_signer(&account_id, &extra, &owner).is_err());
+
+        // Rotation keeps the count unchanged, so it still works at the cap
+        assert!(client.ms_rotate_signer(&account_id, &signer1, &extra, &owner));
+        let account = client.ms_get_account(&account_id);
+        assert_eq!(account.signers.len(), MAX_SIGNERS);
+        assert!(account.signers.contains(&extra));
+    }
+
+    #[test]
+    #[should_panic(expected = "ms_register_account error")]
+    fn test_register_above_signer_cap_panics() {
+        let (env, owner, _, _) = setup_env();
+        env.mock_all_auths();
+        let client = init_client(&env);
+        let mut signers = Vec::new(&env);
+        for _ in 0..MAX_SIGNERS {
+            signers.push_back(Address::generate(&env));
+        }
+
+        // The owner is appended on top, taking the set past the cap
+        client.ms_register_account(&owner, &signers, &(MAX_SIGNERS / 2 + 1), &None, &TIMEOUT_24H);
+    }
+
+    #[test]
+    fn test_signer_change_cooldown_blocks_rapid_churn() {
+        let (env, owner, signer1, signer2) = setup_env();
+        env.mock_all_auths();
+        let client = init_client(&env);
+        set_timestamp(&env, 10_000);
+        let account_id = register_ms_account(&env, &client, &owner, &signer1, &signer2);
+        let by_signer =
+            client.try_ms_set_signer_change_cooldown(&account_id, &TIMEOUT_24H, &signer1);
+        assert!(by_signer.is_err());
+        assert!(client.ms_set_signer_change_cooldown(&account_id, &TIMEOUT_24H, &owner));
+
+        let first = Address::generate(&env);
+        assert!(client.ms_rotate_signer(&account_id, &signer1, &first, &owner));
+
+        // Every kind of signer-set change waits out the cooldown
+        let second = Address::generate(&env);
+        assert!(client.try_ms_rotate_signer(&account_id, &first, &second, &owner).is_err());
+        assert!(client.try_ms_add_signer(&account_id, &second, &owner).is_err());
+        assert!(client.try_ms_remove_signer(&account_id, &first, &owner, &2u32).is_err());
+
+        set_timestamp(&env, 10_000 + TIMEOUT_24H);
+        assert!(client.ms_add_signer(&account_id, &second, &owner));
+        assert!(client.try_ms_remove_signer(&account_id, &second, &owner, &2u32).is_err());
+    }
+
+    #[test]
+    fn test_signer_change_cooldown_decrease_waits_out_current() {
+        let (env, owner, signer1, signer2) = setup_env();
+        env.mock_all_auths();
+        let client = init_client(&env);
+        set_timestamp(&env, 10_000);
+        let account_id = register_ms_account(&env, &client, &owner, &signer1, &signer2);
+        client.ms_set_signer_change_cooldown(&account_id, &TIMEOUT_48H, &owner);
+        let first = Address::generate(&env);
+        client.ms_rotate_signer(&account_id, &signer1, &first, &owner);
+
+        let too_long = MAX_SIGNER_CHANGE_COOLDOWN + 1;
+        assert!(client
+            .try_ms_set_signer_change_cooldown(&account_id, &too_long, &owner)
+            .is_err());
+
+        // Dropping the cooldown does not unlock an immediate change
+        assert!(client.ms_set_signer_change_cooldown(&account_id, &0, &owner));
+        let second = Address::generate(&env);
+        assert!(client.try_ms_add_signer(&account_id, &second, &owner).is_err());
+
+        // The 48h cooldown still governs until it has run from the decrease
+        set_timestamp(&env, 10_000 + TIMEOUT_48H);
+        assert!(client.ms_add_signer(&account_id, &second, &owner));
+        assert!(client.ms_remove_signer(&account_id, &second, &owner, &2u32));
+
+        // Raising it again applies at once
+        assert!(client.ms_set_signer_change_cooldown(&account_id, &TIMEOUT_24H, &owner));
+        assert!(client.try_ms_add_signer(&account_id, &second, &owner).is_err());
+    }
+
+    #[test]
+    fn test_emergency_rotation_exempt_from_cooldown() {
+        let (env, owner, signer1, signer2) = setup_env();
+        env.mock_all_auths();
+        let client = init_client(&env);
+        set_timestamp(&env, 10_000);
+        let account_id = register_ms_account(&env, &client, &owner, &signer1, &signer2);
+        client.ms_set_signer_change_cooldown(&account_id, &TIMEOUT_48H, &owner);
+
+        let first = Address::generate(&env);
+        client.ms_rotate_signer(&account_id, &signer1, &first, &owner);
+
+        let op_id = client.ms_propose_operation(
+            &account_id,
+            &OperationType::EmergencyAction,
+            &String::from_str(&env, "compromised key"),
+            &owner,
+        );
+        let replacement = Address::generate(&env);
+        // The operation must be executed before it authorises a rotation
+        assert!(client
+            .try_ms_emergency_rotate_signer(&account_id, &op_id, &first, &replacement, &owner)
+            .is_err());
+        client.ms_sign_operation(&op_id, &signer2);
+        client.ms_execute_operation(&op_id, &owner);
+
+        let rotated =
+            client.ms_emergency_rotate_signer(&account_id, &op_id, &first, &replacement, &owner);
+        assert!(rotated);
+        let account = client.ms_get_account(&account_id);
+        assert!(account.signers.contains(&replacement));
+        assert!(!account.signers.contains(&first));
+
+        // Each emergency operation authorises a single rotation
+        let another = Address::generate(&env);
+        assert!(client
+            .try_ms_emergency_rotate_signer(&account_id, &op_id, &signer2, &another, &owner)
+            .is_err());
+    }
+
+    #[test]
+    fn test_cancel_all_pending_skips_terminal_operations() {
+        let (env, owner, signer1, signer2) = setup_env();
+        env.mock_all_auths();
+        let client = init_client(&env);
+        let account_id = register_ms_account(&env, &client, &owner, &signer1, &signer2);
+
+        // Safe with nothing pending
+        assert_eq!(client.ms_cancel_all_pending(&account_id, &owner), 0);
+
+        let desc = String::from_str(&env, "incident");
+        let op_a = client.ms_propose_operation(
+            &account_id,
+            &OperationType::EmergencyAction,
+            &desc,
+            &owner,
+        );
+        let op_b = client.ms_propose_operation(
+            &account_id,
+            &OperationType::EmergencyAction,
+            &desc,
+            &owner,
+        );
+        let op_c = client.ms_propose_operation(
+            &account_id,
+            &OperationType::EmergencyAction,
+            &desc,
+            &owner,
+        );
+        client.ms_sign_operation(&op_c, &signer1);
+        client.ms_execute_operation(&op_c, &owner);
+
+        assert!(client.try_ms_cancel_all_pending(&account_id, &signer1).is_err());
+        assert_eq!(client.ms_cancel_all_pending(&account_id, &owner), 2);
+        assert_eq!(client.ms_get_operation(&op_a).status, OperationStatus::Cancelled);
+        assert_eq!(client.ms_get_operation(&op_b).status, OperationStatus::Cancelled);
+        assert_eq!(client.ms_get_operation(&op_c).status, OperationStatus::Executed);
+        assert_eq!(client.ms_cancel_all_pending(&account_id, &owner), 0);
+    }
+
+    fn count_events(env: &Env, action: &str) -> u32 {
+        let action = Symbol::new(env, action);
+        let mut count = 0;
+        for (_, topics, _) in env.events().all().iter() {
+            if topics.len() == 2 && Symbol::try_from_val(env, &topics.get(1).unwrap()) == Ok(action.clone())
+            {
+                count += 1;
+            }
+        }
+        count
+    }
+
+    #[test]
+    fn test_signature_request_and_threshold_events() {
+        let (env, owner, signer1, signer2) = setup_env();
+        env.mock_all_auths();
+        let client = init_client(&env);
+        let account_id = register_ms_account(&env, &client, &owner, &signer1, &signer2);
+        let withdrawal = OperationType::TreasuryWithdrawal;
+        client.ms_set_policy(&account_id, &withdrawal, &2u32, &false, &0u64, &false, &owner);
+
+        let op_id = client.ms_propose_operation(
+            &account_id,
+            &withdrawal,
+            &String::from_str(&env, "Pay contractor"),
+            &signer1,
+        );
+        assert_eq!(count_events(&env, "sig_requested"), 1);
+        assert_eq!(count_events(&env, "threshold_met"), 0);
+
+        // A policy change mid-flight does not re-announce the operation
+        client.ms_set_policy(&account_id, &withdrawal, &3u32, &false, &0u64, &false, &owner);
+        assert_eq!(count_events(&env, "sig_requested"), 1);
+
+        client.ms_sign_operation(&op_id, &signer2);
+        assert_eq!(count_events(&env, "threshold_met"), 1);
+
+        // Further signatures on an executable operation stay quiet
+        client.ms_sign_operation(&op_id, &owner);
+        assert_eq!(count_events(&env, "threshold_met"), 1);
+        assert_eq!(count_events(&env, "sig_requested"), 1);
+    }
 }
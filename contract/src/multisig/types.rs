@@ -3,6 +3,19 @@
 pub const TIMEOUT_24H: u64 = 86_400;
 pub const TIMEOUT_48H: u64 = 172_800;
 pub const DEFAULT_TIMEOUT: u64 = TIMEOUT_48H;
+/// Upper bound on signers per account so signer iteration on sign/execute
+/// stays within the invocation budget. Exceeding it returns error code 11.
+pub const MAX_SIGNERS: u32 = 20;
+/// Longest cooldown an owner may impose between signer-set changes, so an
+/// account can never lock itself out of routine key management for long.
+pub const MAX_SIGNER_CHANGE_COOLDOWN: u64 = 7 * TIMEOUT_24H;
+/// Error code for a gating operation whose account has no authority over the
+/// treasury or guild it is used against
+pub const ERR_OPERATION_OUT_OF_SCOPE: u32 = 14;
+/// Error code for a signer-change cooldown above `MAX_SIGNER_CHANGE_COOLDOWN`
+pub const ERR_COOLDOWN_TOO_LONG: u32 = 15;
+/// Error code for a timeout-extension cap above `TIMEOUT_48H`
+pub const ERR_EXTENSION_TOO_LONG: u32 = 16;
 
 #[contracttype]
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -49,12 +62,41 @@ pub struct MultiSigOperation {
     pub description: String,
     pub proposer: Address,
     pub signatures: Vec<Address>,
+    /// Signatures required, resolved from the policy and account threshold at
+    /// proposal time so later changes only apply to new operations
+    pub required_signatures: u32,
     pub nonce: u64,
     pub created_at: u64,
     pub expires_at: u64,
+    /// Latest expiry an emergency extension may push this operation to,
+    /// fixed at proposal time
+    pub max_expires_at: u64,
     pub status: OperationStatus,
 }
 
+/// Emitted once when an operation is proposed, listing the signers whose
+/// signature is still wanted so off-chain notifiers can ping them
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct SignatureRequestedEvent {
+    pub op_id: u64,
+    pub account_id: u64,
+    pub op_type: OperationType,
+    pub requested_signers: Vec<Address>,
+    pub required_signatures: u32,
+    pub expires_at: u64,
+}
+
+/// Emitted once when an operation gathers the signatures it needs to execute
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct SignatureThresholdReachedEvent {
+    pub op_id: u64,
+    pub account_id: u64,
+    pub signatures: u32,
+    pub required_signatures: u32,
+}
+
 #[contracttype]
 #[derive(Clone)]
 pub struct OperationPolicy {
@@ -1,15 +1,21 @@
 use crate::events::emit::emit_event;
 use crate::events::topics::{
-    ACT_CANCELLED, ACT_CREATED, ACT_DISTRIBUTED, ACT_FAILED, ACT_RECIPIENT_ADDED, MOD_PAYMENT,
+    ACT_CANCELLED, ACT_CLAIMED, ACT_CREATED, ACT_DISTRIBUTED, ACT_FAILED, ACT_RECIPIENT_ADDED,
+    ACT_RECLAIMED, MOD_PAYMENT,
 };
 use crate::payment::storage::{
-    add_recipient_to_pool, clear_pool_recipients, get_next_pool_id, get_payment_pool,
-    get_pool_recipients, recipient_exists_in_pool, store_payment_pool, update_pool_status,
+    add_recipient_to_pool, clear_pool_recipients, get_claimable_amount, get_next_pool_id,
+    get_payment_pool, get_pool_by_idempotency_key, get_pool_escrow, get_pool_expiry,
+    get_pool_recipients, is_recipient_paid, mark_recipient_paid, recipient_exists_in_pool,
+    set_claimable_amount, set_pool_escrow, set_pool_expiry, set_pool_idempotency_key,
+    store_payment_pool, update_pool_status,
 };
 use crate::payment::types::{
     DistributionExecutedEvent, DistributionFailedEvent, DistributionRule, DistributionStatus,
-    PaymentPool, PaymentPoolCreatedEvent, PoolCancelledEvent, Recipient, RecipientAddedEvent,
+    FailedPayoutSettledEvent, PaymentPool, PaymentPoolCreatedEvent, PayoutOutcome,
+    PoolCancelledEvent, PoolReclaimedEvent, Recipient, RecipientAddedEvent, RecipientPayout,
 };
+use crate::utils::math::distribute_with_remainder;
 use soroban_sdk::{contracterror, Address, Env, String, Vec};
 
 /// Error types for payment distribution operations
@@ -28,6 +34,33 @@ pub enum PaymentError {
     TransferFailed = 9,
     ArithmeticOverflow = 10,
     InvalidAmount = 11,
+    IdempotencyKeyConflict = 12,
+    NothingToClaim = 13,
+    PoolNotExpired = 14,
+    InvalidExpiry = 15,
+    UnsupportedToken = 16,
+}
+
+/// Name of a payment error, used as the panic message by the contract entrypoints
+pub fn payment_error_msg(e: PaymentError) -> &'static str {
+    match e {
+        PaymentError::PoolNotFound => "PoolNotFound",
+        PaymentError::PoolNotPending => "PoolNotPending",
+        PaymentError::Unauthorized => "Unauthorized",
+        PaymentError::InvalidShare => "InvalidShare",
+        PaymentError::DuplicateRecipient => "DuplicateRecipient",
+        PaymentError::SharesNot100Percent => "SharesNot100Percent",
+        PaymentError::NoRecipients => "NoRecipients",
+        PaymentError::InsufficientBalance => "InsufficientBalance",
+        PaymentError::TransferFailed => "TransferFailed",
+        PaymentError::ArithmeticOverflow => "ArithmeticOverflow",
+        PaymentError::InvalidAmount => "InvalidAmount",
+        PaymentError::IdempotencyKeyConflict => "IdempotencyKeyConflict",
+        PaymentError::NothingToClaim => "NothingToClaim",
+        PaymentError::PoolNotExpired => "PoolNotExpired",
+        PaymentError::InvalidExpiry => "InvalidExpiry",
+        PaymentError::UnsupportedToken => "UnsupportedToken",
+    }
 }
 
 /// Minimum share amount to avoid dust issues
@@ -53,6 +86,61 @@ pub fn create_payment_pool(
     token: Option<Address>,
     rule: DistributionRule,
     creator: Address,
+) -> Result<u64, PaymentError> {
+    create_pool(env, amount, token, rule, creator)
+}
+
+/// Create a payment pool funded up front from the creator, which anyone can
+/// reclaim for its creator once `expires_at` has passed without the pool
+/// being executed or cancelled
+///
+/// # Arguments
+/// * `env`        - The contract environment
+/// * `amount`     - Total amount to distribute (must be > 0)
+/// * `token`      - Token contract address; native XLM can't be escrowed
+/// * `rule`       - Distribution rule type
+/// * `creator`    - Address creating the pool, which pays in `amount`
+/// * `expires_at` - Timestamp after which the pool can be reclaimed
+///
+/// # Errors
+/// `InvalidAmount`, `InvalidExpiry` if `expires_at` is not in the future,
+/// `UnsupportedToken` for native XLM, `TransferFailed`
+pub fn create_payment_pool_with_expiry(
+    env: &Env,
+    amount: i128,
+    token: Option<Address>,
+    rule: DistributionRule,
+    creator: Address,
+    expires_at: u64,
+) -> Result<u64, PaymentError> {
+    creator.require_auth();
+    if expires_at <= env.ledger().timestamp() {
+        return Err(PaymentError::InvalidExpiry);
+    }
+    let token_addr = token.clone().ok_or(PaymentError::UnsupportedToken)?;
+    if amount <= 0 {
+        return Err(PaymentError::InvalidAmount);
+    }
+
+    if soroban_sdk::token::Client::new(env, &token_addr)
+        .try_transfer(&creator, &env.current_contract_address(), &amount)
+        .is_err()
+    {
+        return Err(PaymentError::TransferFailed);
+    }
+
+    let pool_id = create_pool(env, amount, token, rule, creator)?;
+    set_pool_expiry(env, pool_id, expires_at);
+    set_pool_escrow(env, pool_id, amount);
+    Ok(pool_id)
+}
+
+fn create_pool(
+    env: &Env,
+    amount: i128,
+    token: Option<Address>,
+    rule: DistributionRule,
+    creator: Address,
 ) -> Result<u64, PaymentError> {
     if amount <= 0 {
         return Err(PaymentError::InvalidAmount);
@@ -87,6 +175,46 @@ pub fn create_payment_pool(
     Ok(pool_id)
 }
 
+/// Create a payment pool, or return the pool `creator` already created with
+/// the same `idempotency_key`, so a retried transaction can't mint a duplicate
+///
+/// # Arguments
+/// * `env`             - The contract environment
+/// * `amount`          - Total amount to distribute (must be > 0)
+/// * `token`           - Token contract address (None for native XLM)
+/// * `rule`            - Distribution rule type
+/// * `creator`         - Address creating the pool
+/// * `idempotency_key` - Client-supplied key, unique per creator
+///
+/// # Returns
+/// The ID of the new or previously created pool
+///
+/// # Errors
+/// `InvalidAmount`, `IdempotencyKeyConflict` if the key was already used
+/// with a different amount, token or rule
+pub fn create_payment_pool_idempotent(
+    env: &Env,
+    amount: i128,
+    token: Option<Address>,
+    rule: DistributionRule,
+    creator: Address,
+    idempotency_key: String,
+) -> Result<u64, PaymentError> {
+    creator.require_auth();
+
+    if let Some(pool_id) = get_pool_by_idempotency_key(env, &creator, &idempotency_key) {
+        let pool = get_payment_pool(env, pool_id).ok_or(PaymentError::PoolNotFound)?;
+        if pool.total_amount != amount || pool.token != token || pool.rule != rule {
+            return Err(PaymentError::IdempotencyKeyConflict);
+        }
+        return Ok(pool_id);
+    }
+
+    let pool_id = create_payment_pool(env, amount, token, rule, creator.clone())?;
+    set_pool_idempotency_key(env, &creator, &idempotency_key, pool_id);
+    Ok(pool_id)
+}
+
 /// Add a recipient to a payment pool
 ///
 /// # Events emitted
@@ -182,42 +310,38 @@ pub fn validate_distribution(env: &Env, pool_id: u64) -> Result<bool, PaymentErr
     Ok(true)
 }
 
-/// Calculate the amount a single recipient should receive.
-fn calculate_recipient_amount(
+/// Calculate every recipient's amount, in recipient order.
+///
+/// Shares are split with `distribute_with_remainder`, so the amounts always
+/// add up to the full pool. For `Percentage` pools whose shares do not yet
+/// reach 100, the unallocated percentage is held back rather than spread.
+fn calculate_recipient_amounts(
+    env: &Env,
     pool: &PaymentPool,
-    recipient: &Recipient,
-    total_recipients: u32,
-    total_weight: Option<u32>,
-) -> Result<i128, PaymentError> {
-    match pool.rule {
-        DistributionRule::Percentage => {
-            let amount = (pool.total_amount as i128)
-                .checked_mul(recipient.share as i128)
-                .ok_or(PaymentError::ArithmeticOverflow)?
-                .checked_div(100)
-                .ok_or(PaymentError::ArithmeticOverflow)?;
-            Ok(amount)
-        }
-        DistributionRule::EqualSplit => {
-            let amount = pool
-                .total_amount
-                .checked_div(total_recipients as i128)
-                .ok_or(PaymentError::ArithmeticOverflow)?;
-            Ok(amount)
-        }
-        DistributionRule::Weighted => {
-            if let Some(total_w) = total_weight {
-                let amount = (pool.total_amount as i128)
-                    .checked_mul(recipient.share as i128)
-                    .ok_or(PaymentError::ArithmeticOverflow)?
-                    .checked_div(total_w as i128)
-                    .ok_or(PaymentError::ArithmeticOverflow)?;
-                Ok(amount)
-            } else {
-                Err(PaymentError::ArithmeticOverflow)
-            }
+    recipients: &Vec<Recipient>,
+) -> Result<Vec<i128>, PaymentError> {
+    let mut weights: Vec<u32> = Vec::new(env);
+    for recipient in recipients.iter() {
+        let weight = match pool.rule {
+            DistributionRule::EqualSplit => 1,
+            DistributionRule::Percentage | DistributionRule::Weighted => recipient.share,
+        };
+        weights.push_back(weight);
+    }
+
+    if pool.rule == DistributionRule::Percentage {
+        let allocated: u32 = weights.iter().sum();
+        if allocated < 100 {
+            weights.push_back(100 - allocated);
         }
     }
+
+    let mut amounts = distribute_with_remainder(env, pool.total_amount, &weights)
+        .ok_or(PaymentError::InvalidAmount)?;
+    while amounts.len() > recipients.len() {
+        amounts.pop_back();
+    }
+    Ok(amounts)
 }
 
 /// Execute the distribution for a payment pool.
@@ -251,13 +375,8 @@ pub fn execute_distribution(
     validate_distribution(env, pool_id)?;
 
     let recipients = get_pool_recipients(env, pool_id);
-    let total_recipients = recipients.len() as u32;
-
-    let total_weight = if pool.rule == DistributionRule::Weighted {
-        Some(recipients.iter().map(|r| r.share).sum())
-    } else {
-        None
-    };
+    let total_recipients = recipients.len();
+    let amounts = calculate_recipient_amounts(env, &pool, &recipients)?;
 
     // Check contract balance
     let contract_balance = if let Some(token_addr) = &pool.token {
@@ -283,10 +402,8 @@ pub fn execute_distribution(
 
     let mut total_distributed = 0i128;
 
-    for recipient in recipients.iter() {
-        let amount = calculate_recipient_amount(&pool, &recipient, total_recipients, total_weight)?;
-
-        if amount < MIN_SHARE_AMOUNT {
+    for (recipient, amount) in recipients.iter().zip(amounts.iter()) {
+        if amount < MIN_SHARE_AMOUNT || is_recipient_paid(env, pool_id, &recipient.address) {
             continue;
         }
 
@@ -295,6 +412,7 @@ pub fn execute_distribution(
             token_client.transfer(&env.current_contract_address(), &recipient.address, &amount);
         }
         // TODO: native XLM transfer
+        mark_recipient_paid(env, pool_id, &recipient.address);
 
         total_distributed = total_distributed
             .checked_add(amount)
@@ -318,6 +436,190 @@ pub fn execute_distribution(
     Ok(true)
 }
 
+/// Execute a distribution, holding the share of any recipient whose transfer fails.
+///
+/// Successful transfers go through; a failed transfer leaves that recipient's
+/// amount in the contract as a claimable balance, which the recipient can later
+/// claim or the creator can reclaim. Recipients already paid from this pool are
+/// never paid again.
+///
+/// # Events emitted
+/// - `(payment, distributed)` â†’ `DistributionExecutedEvent`   (on success)
+/// - `(payment, failed)`      â†’ `DistributionFailedEvent`     (on insufficient balance)
+///
+/// # Returns
+/// One `RecipientPayout` per recipient describing what happened to its share.
+pub fn execute_distribution_partial(
+    env: &Env,
+    pool_id: u64,
+    caller: Address,
+) -> Result<Vec<RecipientPayout>, PaymentError> {
+    caller.require_auth();
+    let mut pool = get_payment_pool(env, pool_id).ok_or(PaymentError::PoolNotFound)?;
+
+    if pool.created_by != caller {
+        return Err(PaymentError::Unauthorized);
+    }
+    if pool.status != DistributionStatus::Pending {
+        return Err(PaymentError::PoolNotPending);
+    }
+
+    validate_distribution(env, pool_id)?;
+
+    let recipients = get_pool_recipients(env, pool_id);
+    let total_recipients = recipients.len();
+    let amounts = calculate_recipient_amounts(env, &pool, &recipients)?;
+
+    let contract_balance = if let Some(token_addr) = &pool.token {
+        let token_client = soroban_sdk::token::Client::new(env, token_addr);
+        token_client.balance(&env.current_contract_address())
+    } else {
+        i128::MAX // TODO: implement native XLM balance check
+    };
+
+    if contract_balance < pool.total_amount {
+        update_pool_status(env, pool_id, DistributionStatus::Failed);
+        emit_event(
+            env,
+            MOD_PAYMENT,
+            ACT_FAILED,
+            DistributionFailedEvent {
+                pool_id,
+                reason: String::from_str(env, "Insufficient contract balance"),
+            },
+        );
+        return Err(PaymentError::InsufficientBalance);
+    }
+
+    let mut results = Vec::new(env);
+    let mut total_distributed = 0i128;
+
+    for (recipient, amount) in recipients.iter().zip(amounts.iter()) {
+        let outcome = if amount < MIN_SHARE_AMOUNT {
+            PayoutOutcome::Skipped
+        } else if is_recipient_paid(env, pool_id, &recipient.address) {
+            PayoutOutcome::AlreadyPaid
+        } else {
+            let transferred = match &pool.token {
+                Some(token_addr) => soroban_sdk::token::Client::new(env, token_addr)
+                    .try_transfer(&env.current_contract_address(), &recipient.address, &amount)
+                    .is_ok(),
+                // TODO: native XLM transfer
+                None => true,
+            };
+            if transferred {
+                mark_recipient_paid(env, pool_id, &recipient.address);
+                total_distributed = total_distributed
+                    .checked_add(amount)
+                    .ok_or(PaymentError::ArithmeticOverflow)?;
+                PayoutOutcome::Paid
+            } else {
+                set_claimable_amount(env, pool_id, &recipient.address, amount);
+                PayoutOutcome::Claimable
+            }
+        };
+
+        results.push_back(RecipientPayout {
+            recipient: recipient.address.clone(),
+            amount,
+            outcome,
+        });
+    }
+
+    pool.status = DistributionStatus::Executed;
+    store_payment_pool(env, &pool);
+
+    emit_event(
+        env,
+        MOD_PAYMENT,
+        ACT_DISTRIBUTED,
+        DistributionExecutedEvent {
+            pool_id,
+            total_recipients,
+            total_distributed,
+        },
+    );
+
+    Ok(results)
+}
+
+/// Get the amount held for a recipient whose transfer failed during distribution.
+pub fn get_claimable_payout(env: &Env, pool_id: u64, recipient: Address) -> i128 {
+    get_claimable_amount(env, pool_id, &recipient)
+}
+
+/// Retry a held payout, sending it to the recipient.
+///
+/// # Events emitted
+/// - `(payment, claimed)` â†’ `FailedPayoutSettledEvent`
+pub fn claim_failed_payout(
+    env: &Env,
+    pool_id: u64,
+    recipient: Address,
+) -> Result<i128, PaymentError> {
+    recipient.require_auth();
+    let pool = get_payment_pool(env, pool_id).ok_or(PaymentError::PoolNotFound)?;
+    let amount = settle_failed_payout(env, &pool, &recipient, &recipient)?;
+    mark_recipient_paid(env, pool_id, &recipient);
+    Ok(amount)
+}
+
+/// Return a held payout to the pool creator instead of the recipient.
+///
+/// # Events emitted
+/// - `(payment, claimed)` â†’ `FailedPayoutSettledEvent`
+pub fn refund_failed_payout(
+    env: &Env,
+    pool_id: u64,
+    recipient: Address,
+    caller: Address,
+) -> Result<i128, PaymentError> {
+    caller.require_auth();
+    let pool = get_payment_pool(env, pool_id).ok_or(PaymentError::PoolNotFound)?;
+    if pool.created_by != caller {
+        return Err(PaymentError::Unauthorized);
+    }
+    settle_failed_payout(env, &pool, &recipient, &caller)
+}
+
+fn settle_failed_payout(
+    env: &Env,
+    pool: &PaymentPool,
+    recipient: &Address,
+    pay_to: &Address,
+) -> Result<i128, PaymentError> {
+    let amount = get_claimable_amount(env, pool.id, recipient);
+    if amount <= 0 {
+        return Err(PaymentError::NothingToClaim);
+    }
+
+    if let Some(token_addr) = &pool.token {
+        let token_client = soroban_sdk::token::Client::new(env, token_addr);
+        if token_client
+            .try_transfer(&env.current_contract_address(), pay_to, &amount)
+            .is_err()
+        {
+            return Err(PaymentError::TransferFailed);
+        }
+    }
+    // TODO: native XLM transfer
+    set_claimable_amount(env, pool.id, recipient, 0);
+
+    emit_event(
+        env,
+        MOD_PAYMENT,
+        ACT_CLAIMED,
+        FailedPayoutSettledEvent {
+            pool_id: pool.id,
+            recipient: recipient.clone(),
+            paid_to: pay_to.clone(),
+            amount,
+        },
+    );
+
+    Ok(amount)
+}
+
 /// Get the calculated amount a specific recipient would receive.
 pub fn get_recipient_amount(
     env: &Env,
@@ -327,22 +629,17 @@ pub fn get_recipient_amount(
     let pool = get_payment_pool(env, pool_id).ok_or(PaymentError::PoolNotFound)?;
     let recipients = get_pool_recipients(env, pool_id);
 
-    let recipient = recipients
+    let index = recipients
         .iter()
-        .find(|r| r.address == address)
+        .position(|r| r.address == address)
         .ok_or(PaymentError::PoolNotFound)?;
 
-    let total_recipients = recipients.len() as u32;
-    let total_weight = if pool.rule == DistributionRule::Weighted {
-        Some(recipients.iter().map(|r| r.share).sum())
-    } else {
-        None
-    };
-
-    calculate_recipient_amount(&pool, &recipient, total_recipients, total_weight)
+    let amounts = calculate_recipient_amounts(env, &pool, &recipients)?;
+    Ok(amounts.get_unchecked(index as u32))
 }
 
-/// Cancel a pending payment pool and clear its recipients.
+/// Cancel a pending payment pool and clear its recipients. Funds escrowed
+/// at creation go back to the creator.
 ///
 /// # Events emitted
 /// - `(payment, cancelled)` â†’ `PoolCancelledEvent`
@@ -356,6 +653,7 @@ pub fn cancel_distribution(env: &Env, pool_id: u64, caller: Address) -> Result<b
         return Err(PaymentError::PoolNotPending);
     }
 
+    refund_escrow(env, &pool)?;
     update_pool_status(env, pool_id, DistributionStatus::Cancelled);
     clear_pool_recipients(env, pool_id);
 
@@ -372,6 +670,71 @@ pub fn cancel_distribution(env: &Env, pool_id: u64, caller: Address) -> Result<b
     Ok(true)
 }
 
+/// Refund the escrow of an expired, unexecuted pool to its creator and cancel
+/// it. Anyone may trigger this once the expiry has passed.
+///
+/// Only what the creator paid in at creation is refunded, less shares already
+/// paid out and balances still held for recipients whose transfer failed.
+///
+/// # Events emitted
+/// - `(payment, reclaimed)` â†’ `PoolReclaimedEvent`
+///
+/// # Returns
+/// The amount refunded to the creator
+///
+/// # Errors
+/// `PoolNotFound`, `PoolNotPending`, `PoolNotExpired` if the pool has no
+/// expiry or it has not passed, `TransferFailed`
+pub fn reclaim_expired_pool(env: &Env, pool_id: u64) -> Result<i128, PaymentError> {
+    let mut pool = get_payment_pool(env, pool_id).ok_or(PaymentError::PoolNotFound)?;
+
+    if pool.status != DistributionStatus::Pending {
+        return Err(PaymentError::PoolNotPending);
+    }
+    match get_pool_expiry(env, pool_id) {
+        Some(expires_at) if env.ledger().timestamp() > expires_at => {}
+        _ => return Err(PaymentError::PoolNotExpired),
+    }
+
+    let refund = refund_escrow(env, &pool)?;
+
+    pool.status = DistributionStatus::Cancelled;
+    store_payment_pool(env, &pool);
+    clear_pool_recipients(env, pool_id);
+
+    emit_event(
+        env,
+        MOD_PAYMENT,
+        ACT_RECLAIMED,
+        PoolReclaimedEvent {
+            pool_id,
+            refunded_to: pool.created_by,
+            amount: refund,
+        },
+    );
+
+    Ok(refund)
+}
+
+/// Send a pending pool's escrow back to its creator. Nothing has been paid
+/// out of a pending pool, so the whole escrow is returned.
+fn refund_escrow(env: &Env, pool: &PaymentPool) -> Result<i128, PaymentError> {
+    let refund = get_pool_escrow(env, pool.id);
+    let token_addr = match &pool.token {
+        Some(token_addr) if refund > 0 => token_addr,
+        _ => return Ok(0),
+    };
+
+    if soroban_sdk::token::Client::new(env, token_addr)
+        .try_transfer(&env.current_contract_address(), &pool.created_by, &refund)
+        .is_err()
+    {
+        return Err(PaymentError::TransferFailed);
+    }
+
+    Ok(refund)
+}
+
 /// Get the current status of a payment pool.
 pub fn get_pool_status(env: &Env, pool_id: u64) -> Result<DistributionStatus, PaymentError> {
     let pool = get_payment_pool(env, pool_id).ok_or(PaymentError::PoolNotFound)?;
@@ -17,6 +17,7 @@ pub mod storage;
 ///
 /// # Key Features
 /// - Atomic distribution execution
+/// - Partial-failure mode that holds failed shares as claimable balances
 /// - Support for native XLM and custom tokens
 /// - Dust amount protection
 /// - Comprehensive validation
@@ -25,11 +26,13 @@ pub mod types;
 
 // Re-export main functions for convenience
 pub use distribution::{
-    add_recipient, batch_distribute, cancel_distribution, create_payment_pool,
-    execute_distribution, get_pool_status, get_recipient_amount, validate_distribution,
+    add_recipient, batch_distribute, cancel_distribution, claim_failed_payout, create_payment_pool,
+    create_payment_pool_idempotent, create_payment_pool_with_expiry, execute_distribution,
+    execute_distribution_partial, get_claimable_payout, get_pool_status, get_recipient_amount,
+    payment_error_msg, reclaim_expired_pool, refund_failed_payout, validate_distribution,
 };
 // pub use storage::initialize_payment_storage;
-pub use types::{DistributionRule, DistributionStatus};
+pub use types::{DistributionRule, DistributionStatus, RecipientPayout};
 
 #[cfg(test)]
 mod tests;
@@ -1,5 +1,5 @@
 ﻿use crate::payment::types::{DistributionStatus, PaymentPool, Recipient};
-use soroban_sdk::{contracttype, Address, Env, Vec};
+use soroban_sdk::{contracttype, Address, Env, String, Vec};
 
 /// Storage key for the next pool ID counter
 #[contracttype]
@@ -7,6 +7,11 @@ pub enum PaymentStorageKey {
     NextPoolId,
     Pool(u64),
     Recipients(u64),
+    IdempotencyKey(Address, String), // (creator, key) -> pool_id
+    Paid(u64, Address),              // (pool_id, recipient) -> true once paid
+    Claimable(u64, Address),         // (pool_id, recipient) -> held amount
+    PoolExpiry(u64),                 // pool_id -> time after which it can be reclaimed
+    PoolEscrow(u64),                 // pool_id -> amount pulled from the creator
 }
 
 /// Initialize payment distribution storage
@@ -60,6 +65,33 @@ pub fn pool_exists(env: &Env, pool_id: u64) -> bool {
         .has(&PaymentStorageKey::Pool(pool_id))
 }
 
+/// Time after which an unexecuted pool can be reclaimed (never if None)
+pub fn get_pool_expiry(env: &Env, pool_id: u64) -> Option<u64> {
+    env.storage()
+        .persistent()
+        .get(&PaymentStorageKey::PoolExpiry(pool_id))
+}
+
+pub fn set_pool_expiry(env: &Env, pool_id: u64, expires_at: u64) {
+    env.storage()
+        .persistent()
+        .set(&PaymentStorageKey::PoolExpiry(pool_id), &expires_at);
+}
+
+/// Amount pulled from the creator into the contract at creation (0 if none)
+pub fn get_pool_escrow(env: &Env, pool_id: u64) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&PaymentStorageKey::PoolEscrow(pool_id))
+        .unwrap_or(0)
+}
+
+pub fn set_pool_escrow(env: &Env, pool_id: u64, amount: i128) {
+    env.storage()
+        .persistent()
+        .set(&PaymentStorageKey::PoolEscrow(pool_id), &amount);
+}
+
 /// Update pool status
 pub fn update_pool_status(env: &Env, pool_id: u64, status: DistributionStatus) {
     if let Some(mut pool) = get_payment_pool(env, pool_id) {
@@ -68,6 +100,24 @@ pub fn update_pool_status(env: &Env, pool_id: u64, status: DistributionStatus) {
     }
 }
 
+/// Get the pool a creator already made with an idempotency key
+pub fn get_pool_by_idempotency_key(env: &Env, creator: &Address, key: &String) -> Option<u64> {
+    env.storage()
+        .persistent()
+        .get(&PaymentStorageKey::IdempotencyKey(
+            creator.clone(),
+            key.clone(),
+        ))
+}
+
+/// Remember which pool a creator's idempotency key produced
+pub fn set_pool_idempotency_key(env: &Env, creator: &Address, key: &String, pool_id: u64) {
+    env.storage().persistent().set(
+        &PaymentStorageKey::IdempotencyKey(creator.clone(), key.clone()),
+        &pool_id,
+    );
+}
+
 /// Add a recipient to a pool
 pub fn add_recipient_to_pool(env: &Env, pool_id: u64, recipient: &Recipient) {
     let key = PaymentStorageKey::Recipients(pool_id);
@@ -107,7 +157,6 @@ pub fn clear_pool_recipients(env: &Env, pool_id: u64) {
 }
 
 /// Get total number of pools created
-#[allow(dead_code)]
 pub fn get_total_pools(env: &Env) -> u64 {
     let next_id: u64 = env
         .storage()
@@ -116,3 +165,36 @@ pub fn get_total_pools(env: &Env) -> u64 {
         .unwrap_or(1);
     next_id.saturating_sub(1)
 }
+
+/// Check whether a recipient has already been paid from a pool
+pub fn is_recipient_paid(env: &Env, pool_id: u64, address: &Address) -> bool {
+    env.storage()
+        .persistent()
+        .get(&PaymentStorageKey::Paid(pool_id, address.clone()))
+        .unwrap_or(false)
+}
+
+/// Record that a recipient has been paid from a pool
+pub fn mark_recipient_paid(env: &Env, pool_id: u64, address: &Address) {
+    env.storage()
+        .persistent()
+        .set(&PaymentStorageKey::Paid(pool_id, address.clone()), &true);
+}
+
+/// Get the amount held for a recipient whose transfer failed
+pub fn get_claimable_amount(env: &Env, pool_id: u64, address: &Address) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&PaymentStorageKey::Claimable(pool_id, address.clone()))
+        .unwrap_or(0)
+}
+
+/// Set or clear the amount held for a recipient whose transfer failed
+pub fn set_claimable_amount(env: &Env, pool_id: u64, address: &Address, amount: i128) {
+    let key = PaymentStorageKey::Claimable(pool_id, address.clone());
+    if amount > 0 {
+        env.storage().persistent().set(&key, &amount);
+    } else {
+        env.storage().persistent().remove(&key);
+    }
+}
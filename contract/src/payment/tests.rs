@@ -5,11 +5,11 @@
 
 use super::*;
 use crate::payment::storage;
-use crate::payment::types::{PaymentPool, Recipient};
+use crate::payment::types::{PaymentPool, PayoutOutcome, Recipient};
 use crate::StellarGuildsContract;
 use crate::StellarGuildsContractClient;
-use soroban_sdk::testutils::Address as _;
-use soroban_sdk::{token, Address, Env, Vec};
+use soroban_sdk::testutils::{Address as _, IssuerFlags, Ledger};
+use soroban_sdk::{token, Address, Env, String, Vec};
 
 // ============ Test Helpers ============
 
@@ -216,8 +216,8 @@ fn test_equal_split_distribution_success() {
     let amount2 = client.get_recipient_amount(&pool_id, &recipient2);
     let amount3 = client.get_recipient_amount(&pool_id, &recipient3);
 
-    // Each should get 1000 / 3 = 333 (integer division)
-    assert_eq!(amount1, 333);
+    // 1000 / 3 = 333 each, with the leftover unit going to the first recipient
+    assert_eq!(amount1, 334);
     assert_eq!(amount2, 333);
     assert_eq!(amount3, 333);
 
@@ -230,7 +230,7 @@ fn test_equal_split_distribution_success() {
     let balance2 = get_token_balance(&env, &token_addr, &recipient2);
     let balance3 = get_token_balance(&env, &token_addr, &recipient3);
 
-    assert_eq!(balance1, 333);
+    assert_eq!(balance1, 334);
     assert_eq!(balance2, 333);
     assert_eq!(balance3, 333);
 }
@@ -960,3 +960,237 @@ fn test_full_payment_lifecycle() {
     assert_eq!(balance2, 3000); // 30%
     assert_eq!(balance3, 2000); // 20%
 }
+
+#[test]
+fn test_create_pool_with_idempotency_key_returns_existing_pool() {
+    let env = setup_env();
+    let creator = Address::generate(&env);
+    let other_creator = Address::generate(&env);
+    let token = Some(create_mock_token(&env, &creator));
+
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    let key = String::from_str(&env, "payout-2024-06");
+    let pool_id = client.create_payment_pool_with_key(
+        &1000i128, &token, &DistributionRule::EqualSplit, &creator, &key,
+    );
+
+    // A retried call returns the original pool instead of creating another
+    let retried = client.create_payment_pool_with_key(
+        &1000i128, &token, &DistributionRule::EqualSplit, &creator, &key,
+    );
+    assert_eq!(retried, pool_id);
+    env.as_contract(&contract_id, || {
+        assert_eq!(storage::get_total_pools(&env), 1);
+    });
+
+    // Keys are scoped per creator
+    let other_pool = client.create_payment_pool_with_key(
+        &1000i128, &token, &DistributionRule::EqualSplit, &other_creator, &key,
+    );
+    assert_ne!(other_pool, pool_id);
+
+    // Reusing the key with different parameters is rejected
+    let conflict = client.try_create_payment_pool_with_key(
+        &2000i128, &token, &DistributionRule::EqualSplit, &creator, &key,
+    );
+    assert!(conflict.is_err());
+    let conflict = client.try_create_payment_pool_with_key(
+        &1000i128, &token, &DistributionRule::Weighted, &creator, &key,
+    );
+    assert!(conflict.is_err());
+}
+
+// ============ Partial Failure Distribution Tests ============
+
+#[test]
+fn test_partial_distribution_holds_failed_transfers() {
+    let env = setup_env();
+    let creator = Address::generate(&env);
+    let good = Address::generate(&env);
+    let frozen = Address::generate(&env);
+    let asset = env.register_stellar_asset_contract_v2(creator.clone());
+    asset.issuer().set_flag(IssuerFlags::RevocableFlag);
+    let token_addr = asset.address();
+    let token = Some(token_addr.clone());
+
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+    mint_tokens(&env, &token_addr, &contract_id, 2000);
+
+    // Transfers to a deauthorized holder revert
+    let sac = token::StellarAssetClient::new(&env, &token_addr);
+    sac.set_authorized(&frozen, &false);
+
+    let pool_id =
+        client.create_payment_pool(&1000i128, &token, &DistributionRule::Percentage, &creator);
+    client.add_recipient(&pool_id, &good, &70u32, &creator);
+    client.add_recipient(&pool_id, &frozen, &30u32, &creator);
+
+    let results = client.execute_distribution_partial(&pool_id, &creator);
+    assert_eq!(results.len(), 2);
+    assert_eq!(results.get(0).unwrap().outcome, PayoutOutcome::Paid);
+    assert_eq!(results.get(1).unwrap().outcome, PayoutOutcome::Claimable);
+    assert_eq!(results.get(1).unwrap().amount, 300);
+    assert_eq!(get_token_balance(&env, &token_addr, &good), 700);
+    assert_eq!(client.get_claimable_payout(&pool_id, &frozen), 300);
+    assert_eq!(client.get_pool_status(&pool_id), DistributionStatus::Executed);
+
+    // Running again cannot pay anyone twice
+    assert!(client.try_execute_distribution_partial(&pool_id, &creator).is_err());
+    assert!(client.try_execute_distribution(&pool_id, &creator).is_err());
+    assert_eq!(get_token_balance(&env, &token_addr, &good), 700);
+    env.as_contract(&contract_id, || {
+        assert!(storage::is_recipient_paid(&env, pool_id, &good));
+        assert!(!storage::is_recipient_paid(&env, pool_id, &frozen));
+    });
+
+    // The claim keeps failing until the recipient is reauthorized
+    assert!(client.try_claim_failed_payout(&pool_id, &frozen).is_err());
+    sac.set_authorized(&frozen, &true);
+    assert_eq!(client.claim_failed_payout(&pool_id, &frozen), 300);
+    assert_eq!(get_token_balance(&env, &token_addr, &frozen), 300);
+    assert_eq!(client.get_claimable_payout(&pool_id, &frozen), 0);
+    assert!(client.try_claim_failed_payout(&pool_id, &frozen).is_err());
+
+    // A held share can instead be refunded to the creator
+    let other = Address::generate(&env);
+    sac.set_authorized(&other, &false);
+    let refund_pool =
+        client.create_payment_pool(&1000i128, &token, &DistributionRule::EqualSplit, &creator);
+    client.add_recipient(&refund_pool, &good, &1u32, &creator);
+    client.add_recipient(&refund_pool, &other, &1u32, &creator);
+    client.execute_distribution_partial(&refund_pool, &creator);
+    assert!(client.try_refund_failed_payout(&refund_pool, &other, &good).is_err());
+    assert_eq!(client.refund_failed_payout(&refund_pool, &other, &creator), 500);
+    assert_eq!(get_token_balance(&env, &token_addr, &creator), 500);
+    assert_eq!(client.get_claimable_payout(&refund_pool, &other), 0);
+}
+
+#[test]
+fn test_reclaim_expired_pool_refunds_creator() {
+    let env = setup_env();
+    let creator = Address::generate(&env);
+    let recipient1 = Address::generate(&env);
+    let token_addr = create_mock_token(&env, &creator);
+    let token = Some(token_addr.clone());
+
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    // Funds the contract already holds for other purposes
+    mint_tokens(&env, &token_addr, &contract_id, 5000);
+    mint_tokens(&env, &token_addr, &creator, 1000);
+
+    let expires_at = env.ledger().timestamp() + 100;
+    let pool_id = client.create_payment_pool_with_expiry(
+        &1000i128,
+        &token,
+        &DistributionRule::Percentage,
+        &creator,
+        &expires_at,
+    );
+    client.add_recipient(&pool_id, &recipient1, &100u32, &creator);
+    assert_eq!(get_token_balance(&env, &token_addr, &creator), 0);
+    assert_eq!(get_token_balance(&env, &token_addr, &contract_id), 6000);
+
+    // Not reclaimable before the expiry
+    assert!(client.try_reclaim_expired_pool(&pool_id).is_err());
+
+    env.ledger().with_mut(|l| l.timestamp = expires_at + 1);
+    assert_eq!(client.reclaim_expired_pool(&pool_id), 1000);
+    assert_eq!(get_token_balance(&env, &token_addr, &creator), 1000);
+    assert_eq!(get_token_balance(&env, &token_addr, &contract_id), 5000);
+    assert_eq!(client.get_pool_status(&pool_id), DistributionStatus::Cancelled);
+
+    // A reclaimed pool can't be reclaimed or executed again
+    assert!(client.try_reclaim_expired_pool(&pool_id).is_err());
+    assert!(client.try_execute_distribution(&pool_id, &creator).is_err());
+}
+
+#[test]
+#[should_panic(expected = "PoolNotPending")]
+fn test_reclaim_executed_pool_fails() {
+    let env = setup_env();
+    let creator = Address::generate(&env);
+    let recipient1 = Address::generate(&env);
+    let token_addr = create_mock_token(&env, &creator);
+    let token = Some(token_addr.clone());
+
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    mint_tokens(&env, &token_addr, &creator, 1000);
+
+    let expires_at = env.ledger().timestamp() + 100;
+    let pool_id = client.create_payment_pool_with_expiry(
+        &1000i128,
+        &token,
+        &DistributionRule::Percentage,
+        &creator,
+        &expires_at,
+    );
+    client.add_recipient(&pool_id, &recipient1, &100u32, &creator);
+    client.execute_distribution(&pool_id, &creator);
+
+    env.ledger().with_mut(|l| l.timestamp = expires_at + 1);
+    client.reclaim_expired_pool(&pool_id);
+}
+
+#[test]
+fn test_expiring_pool_requires_creator_funds() {
+    let env = setup_env();
+    let creator = Address::generate(&env);
+    let token_addr = create_mock_token(&env, &creator);
+
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    // The contract's own balance can't back a pool the creator never paid for
+    mint_tokens(&env, &token_addr, &contract_id, 5000);
+    let expires_at = env.ledger().timestamp() + 1;
+    assert!(client
+        .try_create_payment_pool_with_expiry(
+            &1000i128,
+            &Some(token_addr.clone()),
+            &DistributionRule::EqualSplit,
+            &creator,
+            &expires_at,
+        )
+        .is_err());
+
+    // Native XLM can't be escrowed
+    assert!(client
+        .try_create_payment_pool_with_expiry(
+            &1000i128,
+            &None,
+            &DistributionRule::EqualSplit,
+            &creator,
+            &expires_at,
+        )
+        .is_err());
+
+    // Cancelling an escrowed pool returns the escrow
+    mint_tokens(&env, &token_addr, &creator, 1000);
+    let pool_id = client.create_payment_pool_with_expiry(
+        &1000i128,
+        &Some(token_addr.clone()),
+        &DistributionRule::EqualSplit,
+        &creator,
+        &expires_at,
+    );
+    client.cancel_distribution(&pool_id, &creator);
+    assert_eq!(get_token_balance(&env, &token_addr, &creator), 1000);
+    assert_eq!(get_token_balance(&env, &token_addr, &contract_id), 5000);
+}
@@ -46,6 +46,29 @@ pub struct PaymentPool {
     pub created_at: u64,
 }
 
+/// Outcome of a single recipient's transfer in a partial-failure distribution
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum PayoutOutcome {
+    /// Tokens were transferred to the recipient
+    Paid,
+    /// The transfer failed; the amount is held as a claimable balance
+    Claimable,
+    /// The recipient had already been paid from this pool
+    AlreadyPaid,
+    /// The share was below the dust threshold and nothing was sent
+    Skipped,
+}
+
+/// Per-recipient result returned by a partial-failure distribution
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RecipientPayout {
+    pub recipient: Address,
+    pub amount: i128,
+    pub outcome: PayoutOutcome,
+}
+
 /// A recipient in a payment distribution
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -95,3 +118,21 @@ pub struct PoolCancelledEvent {
     pub pool_id: u64,
     pub cancelled_by: Address,
 }
+
+/// Event emitted when an expired pool's remaining funds are refunded to its creator
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PoolReclaimedEvent {
+    pub pool_id: u64,
+    pub refunded_to: Address,
+    pub amount: i128,
+}
+
+/// Event emitted when a held payout is claimed by its recipient or refunded to the creator
+#[contracttype]
+pub struct FailedPayoutSettledEvent {
+    pub pool_id: u64,
+    pub recipient: Address,
+    pub paid_to: Address,
+    pub amount: i128,
+}
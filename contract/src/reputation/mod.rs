@@ -3,12 +3,17 @@ pub mod storage;
 pub mod types;
 
 pub use scoring::{
-    compute_governance_weight, get_decayed_profile, get_global_reputation, record_contribution,
+    compute_governance_weight, export_reputation_snapshot, get_decayed_profile,
+    get_global_reputation, get_reputation_timeline, is_contribution_dedupe_enabled,
+    record_contribution, set_contribution_dedupe, verify_reputation_snapshot,
 };
 
-pub use storage::{get_badges, get_contributions};
+pub use storage::{get_badges, get_contributions, get_guild_total_reputation};
 
-pub use types::{Badge, BadgeType, ContributionRecord, ContributionType, ReputationProfile};
+pub use types::{
+    Badge, BadgeType, ContributionRecord, ContributionType, ReputationProfile,
+    ReputationTimelineEntry,
+};
 
 #[cfg(test)]
 mod tests;
@@ -1,32 +1,139 @@
 use crate::events::emit::emit_event;
-use crate::events::topics::{ACT_BADGE_EARNED, ACT_UPDATED, MOD_REPUTATION};
-use soroban_sdk::{Address, Env, String};
+use crate::events::topics::{
+    ACT_BADGE_EARNED, ACT_EXPORTED, ACT_PENALIZED, ACT_UPDATED, MOD_REPUTATION,
+};
+use soroban_sdk::{Address, Env, String, Vec};
 
 use crate::guild::types::Role;
 use crate::reputation::storage::{
-    count_contributions_by_type, get_badges, get_next_badge_id, get_next_contribution_id,
-    get_profile, has_badge_type, store_badge, store_contribution, store_profile,
+    count_contributions_by_type, get_badges, get_contributions, get_last_export, get_next_badge_id,
+    get_next_contribution_id, get_profile, has_badge_type, is_dedupe_enabled,
+    is_reference_recorded, mark_reference_recorded, set_dedupe_enabled, set_last_export,
+    store_badge, store_contribution, store_profile,
 };
 use crate::reputation::types::{
     points_for_contribution, Badge, BadgeAwardedEvent, BadgeType, ContributionRecord,
-    ContributionType, ReputationProfile, ReputationUpdatedEvent, DECAY_DENOMINATOR,
-    DECAY_NUMERATOR, DECAY_PERIOD_SECS,
+    ContributionType, ReputationExportedEvent, ReputationPenalizedEvent, ReputationProfile,
+    ReputationTimelineEntry, ReputationUpdatedEvent, DECAY_DENOMINATOR, DECAY_NUMERATOR,
+    DECAY_PERIOD_SECS, PENALTY_DISPUTE_LOST_BASE, PENALTY_DISPUTE_LOST_MAX, POINTS_MILESTONE_MAX,
+    POINTS_MILESTONE_MIN,
 };
 
+use crate::analytics::computations::{resolve_token_decimals, NATIVE_DECIMALS};
+
 use crate::governance::types::role_weight;
 
 // â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€ Core Scoring â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€
 
 /// Record a contribution and update the user's reputation profile.
 /// Awards badges if thresholds are met.
+///
+/// Unless the guild disabled deduplication, a reference is credited once per
+/// contribution type; repeats are ignored and return `false`. Penalties
+/// can only be applied through `apply_penalty` and are refused here.
 pub fn record_contribution(
     env: &Env,
     guild_id: u64,
     contributor: &Address,
     contribution_type: ContributionType,
     reference_id: u64,
-) {
+) -> bool {
     let points = points_for_contribution(&contribution_type);
+    record_weighted_contribution(
+        env,
+        guild_id,
+        contributor,
+        contribution_type,
+        reference_id,
+        points,
+    )
+}
+
+/// `record_contribution` with an explicit point value, under the same
+/// penalty and deduplication guards
+fn record_weighted_contribution(
+    env: &Env,
+    guild_id: u64,
+    contributor: &Address,
+    contribution_type: ContributionType,
+    reference_id: u64,
+    points: u32,
+) -> bool {
+    if contribution_type == ContributionType::Penalty {
+        return false;
+    }
+    if is_dedupe_enabled(env, guild_id) {
+        if is_reference_recorded(env, guild_id, contributor, &contribution_type, reference_id) {
+            return false;
+        }
+        mark_reference_recorded(env, guild_id, contributor, &contribution_type, reference_id);
+    }
+
+    apply_contribution(
+        env,
+        guild_id,
+        contributor,
+        contribution_type,
+        reference_id,
+        points,
+    );
+    true
+}
+
+/// Credit the contributor of an approved milestone with points scaled by its
+/// payment. Each milestone is credited at most once, whatever the guild's
+/// dedupe setting, so re-approval after a rejection earns nothing more.
+pub fn record_milestone_contribution(
+    env: &Env,
+    guild_id: u64,
+    contributor: &Address,
+    milestone_id: u64,
+    token: &Option<Address>,
+    payment_amount: i128,
+) -> bool {
+    let contribution_type = ContributionType::MilestoneApproved;
+    if is_reference_recorded(env, guild_id, contributor, &contribution_type, milestone_id) {
+        return false;
+    }
+
+    let points = milestone_points(env, token, payment_amount);
+    if !record_weighted_contribution(
+        env,
+        guild_id,
+        contributor,
+        contribution_type.clone(),
+        milestone_id,
+        points,
+    ) {
+        return false;
+    }
+    mark_reference_recorded(env, guild_id, contributor, &contribution_type, milestone_id);
+    true
+}
+
+/// Points for a milestone paying `payment_amount` of `token`, clamped to the
+/// milestone bounds
+pub fn milestone_points(env: &Env, token: &Option<Address>, payment_amount: i128) -> u32 {
+    whole_tokens(env, token, payment_amount)
+        .clamp(POINTS_MILESTONE_MIN as i128, POINTS_MILESTONE_MAX as i128) as u32
+}
+
+/// Whole tokens in `amount` of `token`. Tokens whose decimals cannot be
+/// resolved are treated like the native asset.
+fn whole_tokens(env: &Env, token: &Option<Address>, amount: i128) -> i128 {
+    let decimals = resolve_token_decimals(env, token).unwrap_or(NATIVE_DECIMALS);
+    amount.max(0) / 10i128.pow(decimals)
+}
+
+/// Store a contribution record worth `points` and update the profile and badges
+fn apply_contribution(
+    env: &Env,
+    guild_id: u64,
+    contributor: &Address,
+    contribution_type: ContributionType,
+    reference_id: u64,
+    points: u32,
+) {
     let now = env.ledger().timestamp();
 
     // Store the contribution record
@@ -76,6 +183,85 @@ pub fn record_contribution(
     check_and_award_badges(env, guild_id, contributor, &profile);
 }
 
+/// Deduct up to `points` from a member's reputation, never going below zero.
+/// Members without a profile have nothing to lose. Returns the points removed.
+///
+/// The penalty is kept in the contribution history as a `Penalty` record so
+/// `get_reputation_timeline` replays it.
+pub fn apply_penalty(
+    env: &Env,
+    guild_id: u64,
+    address: &Address,
+    points: u64,
+    reference_id: u64,
+) -> u64 {
+    let mut profile = match get_profile(env, address, guild_id) {
+        Some(profile) => profile,
+        None => return 0,
+    };
+    let now = env.ledger().timestamp();
+    let points = points.min(u32::MAX as u64);
+    store_contribution(
+        env,
+        &ContributionRecord {
+            id: get_next_contribution_id(env),
+            guild_id,
+            contributor: address.clone(),
+            contribution_type: ContributionType::Penalty,
+            points: points as u32,
+            timestamp: now,
+            reference_id,
+        },
+    );
+    apply_decay_to_profile(&mut profile, now);
+
+    let deducted = points.min(profile.total_score);
+    profile.total_score -= deducted;
+    profile.decayed_score = profile.decayed_score.saturating_sub(points);
+    store_profile(env, &profile);
+
+    let event = ReputationPenalizedEvent {
+        guild_id,
+        address: address.clone(),
+        points_deducted: deducted,
+        new_total_score: profile.total_score,
+        reference_id,
+    };
+    emit_event(env, MOD_REPUTATION, ACT_PENALIZED, event);
+
+    deducted
+}
+
+/// Penalty for losing a dispute over `stakes` worth of `token`
+pub fn dispute_loss_penalty(env: &Env, token: &Option<Address>, stakes: i128) -> u64 {
+    let stake_points = whole_tokens(env, token, stakes).min(u64::MAX as i128) as u64;
+    PENALTY_DISPUTE_LOST_BASE
+        .saturating_add(stake_points)
+        .min(PENALTY_DISPUTE_LOST_MAX)
+}
+
+/// Turn contribution reference deduplication on or off for a guild
+/// (guild admins only).
+pub fn set_contribution_dedupe(env: &Env, guild_id: u64, enabled: bool, caller: Address) -> bool {
+    caller.require_auth();
+    if !crate::guild::membership::enforce_permission(
+        env,
+        guild_id,
+        caller,
+        Role::Admin,
+        "set_contribution_dedupe",
+    ) {
+        panic!("caller must be guild admin");
+    }
+    set_dedupe_enabled(env, guild_id, enabled);
+    true
+}
+
+/// Whether contribution references are deduplicated in the guild.
+pub fn is_contribution_dedupe_enabled(env: &Env, guild_id: u64) -> bool {
+    is_dedupe_enabled(env, guild_id)
+}
+
 // â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€ Decay â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€
 
 /// Apply time-based decay to a profile's decayed_score.
@@ -116,6 +302,104 @@ pub fn get_decayed_profile(
     Some(profile)
 }
 
+// â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€ Export â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€
+
+/// Export a user's guild reputation for other contracts.
+///
+/// Returns `(score, timestamp)`: `score` is the decayed score as of this call
+/// (0 without a profile) and `timestamp` is the ledger time it was taken,
+/// which consumers compare against their own clock to reject stale values.
+/// The pair is committed as the user's latest export and published as an
+/// event, so it can later be checked with `verify_reputation_snapshot`.
+/// Only the user can replace their latest export.
+pub fn export_reputation_snapshot(env: &Env, guild_id: u64, address: &Address) -> (u64, u64) {
+    address.require_auth();
+
+    let score = get_decayed_profile(env, address, guild_id)
+        .map(|p| p.decayed_score)
+        .unwrap_or(0);
+    let timestamp = env.ledger().timestamp();
+    let snapshot = (score, timestamp);
+    set_last_export(env, guild_id, address, &snapshot);
+
+    emit_event(
+        env,
+        MOD_REPUTATION,
+        ACT_EXPORTED,
+        ReputationExportedEvent {
+            guild_id,
+            address: address.clone(),
+            score,
+            timestamp,
+        },
+    );
+
+    snapshot
+}
+
+/// Whether `(score, timestamp)` is the user's latest export in the guild.
+pub fn verify_reputation_snapshot(
+    env: &Env,
+    guild_id: u64,
+    address: &Address,
+    score: u64,
+    timestamp: u64,
+) -> bool {
+    get_last_export(env, guild_id, address) == Some((score, timestamp))
+}
+
+// â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€ Timeline â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€
+
+/// Replay a user's contributions and penalties in a guild and return the last
+/// `limit` entries (oldest first), each with the running raw and decayed score.
+/// The full history is replayed so decay between entries matches the stored
+/// profile.
+pub fn get_reputation_timeline(
+    env: &Env,
+    guild_id: u64,
+    address: &Address,
+    limit: u32,
+) -> Vec<ReputationTimelineEntry> {
+    let records = get_contributions(env, address, guild_id, u32::MAX);
+    let len = records.len();
+    let start = len.saturating_sub(limit);
+
+    let mut result = Vec::new(env);
+    let mut running: Option<ReputationProfile> = None;
+    for (i, record) in records.iter().enumerate() {
+        let profile = running.get_or_insert(ReputationProfile {
+            address: address.clone(),
+            guild_id,
+            total_score: 0,
+            decayed_score: 0,
+            contributions_count: 0,
+            last_activity: record.timestamp,
+            last_decay_applied: record.timestamp,
+        });
+        apply_decay_to_profile(profile, record.timestamp);
+        let points = record.points as u64;
+        if record.contribution_type == ContributionType::Penalty {
+            // Same arithmetic as `apply_penalty`
+            profile.total_score -= points.min(profile.total_score);
+            profile.decayed_score = profile.decayed_score.saturating_sub(points);
+        } else {
+            profile.total_score += points;
+            profile.decayed_score += points;
+            profile.contributions_count += 1;
+            profile.last_activity = record.timestamp;
+        }
+
+        if i as u32 >= start {
+            result.push_back(ReputationTimelineEntry {
+                cumulative_score: profile.total_score,
+                decayed_score: profile.decayed_score,
+                record,
+            });
+        }
+    }
+    result
+}
+
 // â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€ Governance Weight â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€
 
 /// Compute governance weight: role_weight + integer_sqrt(decayed_score).
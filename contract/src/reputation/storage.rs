@@ -1,6 +1,6 @@
 ﻿use soroban_sdk::{symbol_short, Address, Env, Map, Symbol, Vec};
 
-use crate::reputation::types::{Badge, ContributionRecord, ReputationProfile};
+use crate::reputation::types::{Badge, ContributionRecord, ContributionType, ReputationProfile};
 
 const PROFILES_KEY: Symbol = symbol_short!("r_prof");
 const CONTRIBS_KEY: Symbol = symbol_short!("r_cont");
@@ -9,16 +9,43 @@ const BADGES_KEY: Symbol = symbol_short!("r_badge");
 const BADGE_IDX: Symbol = symbol_short!("r_bidx");
 const CONTRIB_CNT: Symbol = symbol_short!("r_ccnt");
 const BADGE_CNT: Symbol = symbol_short!("r_bcnt");
+const GUILD_TOTAL_KEY: Symbol = symbol_short!("r_gtot");
+const REF_SEEN_KEY: Symbol = symbol_short!("r_seen");
+const DEDUPE_OFF_KEY: Symbol = symbol_short!("r_ddoff");
+const EXPORT_KEY: Symbol = symbol_short!("r_export");
 
 // â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€ Reputation Profiles â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€
 
 /// Store or update a reputation profile keyed by (address, guild_id).
+///
+/// Keeps the guild's aggregate reputation in step with the profile's raw score.
 pub fn store_profile(env: &Env, profile: &ReputationProfile) {
     let storage = env.storage().persistent();
     let mut profiles: Map<(Address, u64), ReputationProfile> =
         storage.get(&PROFILES_KEY).unwrap_or_else(|| Map::new(env));
-    profiles.set((profile.address.clone(), profile.guild_id), profile.clone());
+    let key = (profile.address.clone(), profile.guild_id);
+    let previous = profiles
+        .get(key.clone())
+        .map(|p| p.total_score)
+        .unwrap_or(0);
+    profiles.set(key, profile.clone());
     storage.set(&PROFILES_KEY, &profiles);
+
+    if previous != profile.total_score {
+        let total = get_guild_total_reputation(env, profile.guild_id);
+        let updated = total
+            .saturating_sub(previous)
+            .saturating_add(profile.total_score);
+        storage.set(&(GUILD_TOTAL_KEY, profile.guild_id), &updated);
+    }
+}
+
+/// Sum of every member's raw reputation score in a guild.
+pub fn get_guild_total_reputation(env: &Env, guild_id: u64) -> u64 {
+    env.storage()
+        .persistent()
+        .get(&(GUILD_TOTAL_KEY, guild_id))
+        .unwrap_or(0)
 }
 
 /// Get a reputation profile for (address, guild_id). Returns None if not found.
@@ -75,6 +102,71 @@ pub fn store_contribution(env: &Env, record: &ContributionRecord) {
     storage.set(&CONTRIB_IDX, &index);
 }
 
+/// Whether a contribution of this type over `reference_id` has already been
+/// credited to the contributor in the guild.
+pub fn is_reference_recorded(
+    env: &Env,
+    guild_id: u64,
+    contributor: &Address,
+    contribution_type: &ContributionType,
+    reference_id: u64,
+) -> bool {
+    let key = (
+        REF_SEEN_KEY,
+        guild_id,
+        contributor.clone(),
+        contribution_type.clone(),
+        reference_id,
+    );
+    env.storage().persistent().has(&key)
+}
+
+/// Mark a contribution reference as credited.
+pub fn mark_reference_recorded(
+    env: &Env,
+    guild_id: u64,
+    contributor: &Address,
+    contribution_type: &ContributionType,
+    reference_id: u64,
+) {
+    let key = (
+        REF_SEEN_KEY,
+        guild_id,
+        contributor.clone(),
+        contribution_type.clone(),
+        reference_id,
+    );
+    env.storage().persistent().set(&key, &true);
+}
+
+/// Whether the guild only credits each contribution reference once
+/// (enabled unless the guild turned it off).
+pub fn is_dedupe_enabled(env: &Env, guild_id: u64) -> bool {
+    !env.storage()
+        .persistent()
+        .get(&(DEDUPE_OFF_KEY, guild_id))
+        .unwrap_or(false)
+}
+
+pub fn set_dedupe_enabled(env: &Env, guild_id: u64, enabled: bool) {
+    env.storage()
+        .persistent()
+        .set(&(DEDUPE_OFF_KEY, guild_id), &!enabled);
+}
+
+/// Latest exported `(score, timestamp)` for a user in a guild.
+pub fn get_last_export(env: &Env, guild_id: u64, address: &Address) -> Option<(u64, u64)> {
+    env.storage()
+        .persistent()
+        .get(&(EXPORT_KEY, guild_id, address.clone()))
+}
+
+pub fn set_last_export(env: &Env, guild_id: u64, address: &Address, snapshot: &(u64, u64)) {
+    env.storage()
+        .persistent()
+        .set(&(EXPORT_KEY, guild_id, address.clone()), snapshot);
+}
+
 /// Get contribution records for a user in a guild, most recent first, limited.
 pub fn get_contributions(
     env: &Env,
@@ -112,7 +204,7 @@ pub fn count_contributions_by_type(
     env: &Env,
     address: &Address,
     guild_id: u64,
-    contribution_type: &crate::reputation::types::ContributionType,
+    contribution_type: &ContributionType,
 ) -> u32 {
     let storage = env.storage().persistent();
 
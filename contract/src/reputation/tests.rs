@@ -309,4 +309,221 @@ mod tests {
         let global = client.get_reputation_global(&member);
         assert_eq!(global, 0);
     }
+
+    #[test]
+    fn test_reputation_timeline_running_score() {
+        let env = setup_env();
+        set_ledger_timestamp(&env, 1000);
+        let contract_id = register_and_init_contract(&env);
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+        env.mock_all_auths();
+        let owner = Address::generate(&env);
+        let guild_id = setup_guild(&client, &env, &owner);
+        let contributor = Address::generate(&env);
+        client.add_member(&guild_id, &contributor, &Role::Contributor, &owner);
+
+        client.record_contribution(
+            &guild_id,
+            &contributor,
+            &ContributionType::BountyCompleted,
+            &1u64,
+        );
+        set_ledger_timestamp(&env, 1000 + 604_800);
+        client.record_contribution(&guild_id, &contributor, &ContributionType::VoteCast, &2u64);
+        client.record_contribution(
+            &guild_id,
+            &contributor,
+            &ContributionType::ProposalCreated,
+            &3u64,
+        );
+
+        let timeline = client.get_reputation_timeline(&guild_id, &contributor, &10);
+        assert_eq!(timeline.len(), 3);
+        assert_eq!(timeline.get(0).unwrap().cumulative_score, 100);
+        assert_eq!(timeline.get(0).unwrap().decayed_score, 100);
+        // One decay period elapsed before the vote: 100 -> 99, then +5
+        assert_eq!(timeline.get(1).unwrap().cumulative_score, 105);
+        assert_eq!(timeline.get(1).unwrap().decayed_score, 104);
+        assert_eq!(timeline.get(2).unwrap().cumulative_score, 125);
+        assert_eq!(timeline.get(2).unwrap().decayed_score, 124);
+
+        // Final entry agrees with the stored profile
+        let profile = client.get_reputation(&guild_id, &contributor);
+        assert_eq!(profile.total_score, 125);
+        assert_eq!(profile.decayed_score, 124);
+
+        let recent = client.get_reputation_timeline(&guild_id, &contributor, &2);
+        assert_eq!(recent.len(), 2);
+        assert_eq!(recent.get(0).unwrap().record.reference_id, 2);
+        assert_eq!(recent.get(1).unwrap().cumulative_score, 125);
+    }
+
+    #[test]
+    fn test_reputation_timeline_replays_penalties() {
+        let env = setup_env();
+        set_ledger_timestamp(&env, 1000);
+        let contract_id = register_and_init_contract(&env);
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+        env.mock_all_auths();
+        let owner = Address::generate(&env);
+        let guild_id = setup_guild(&client, &env, &owner);
+        let contributor = Address::generate(&env);
+        client.add_member(&guild_id, &contributor, &Role::Contributor, &owner);
+
+        client.record_contribution(
+            &guild_id,
+            &contributor,
+            &ContributionType::BountyCompleted,
+            &1u64,
+        );
+        set_ledger_timestamp(&env, 1000 + 604_800);
+        let deducted = env.as_contract(&contract_id, || {
+            crate::reputation::scoring::apply_penalty(&env, guild_id, &contributor, 30, 9)
+        });
+        assert_eq!(deducted, 30);
+        client.record_contribution(&guild_id, &contributor, &ContributionType::VoteCast, &2u64);
+
+        // Penalties cannot be self-recorded
+        client.record_contribution(&guild_id, &contributor, &ContributionType::Penalty, &3u64);
+
+        let timeline = client.get_reputation_timeline(&guild_id, &contributor, &10);
+        assert_eq!(timeline.len(), 3);
+        let penalty = timeline.get(1).unwrap();
+        assert_eq!(penalty.record.contribution_type, ContributionType::Penalty);
+        assert_eq!(penalty.record.reference_id, 9);
+        // 100 decays to 99 before the 30 point deduction
+        assert_eq!(penalty.cumulative_score, 70);
+        assert_eq!(penalty.decayed_score, 69);
+        assert_eq!(timeline.get(2).unwrap().cumulative_score, 75);
+        assert_eq!(timeline.get(2).unwrap().decayed_score, 74);
+
+        let profile = client.get_reputation(&guild_id, &contributor);
+        assert_eq!(profile.total_score, 75);
+        assert_eq!(profile.decayed_score, 74);
+        assert_eq!(profile.contributions_count, 2);
+    }
+
+    #[test]
+    fn test_contribution_reference_credited_once() {
+        let env = setup_env();
+        set_ledger_timestamp(&env, 1000);
+        let contract_id = register_and_init_contract(&env);
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+        env.mock_all_auths();
+        let owner = Address::generate(&env);
+        let guild_id = setup_guild(&client, &env, &owner);
+        let contributor = Address::generate(&env);
+        client.add_member(&guild_id, &contributor, &Role::Contributor, &owner);
+        assert!(client.get_contribution_dedupe(&guild_id));
+
+        let bounty = ContributionType::BountyCompleted;
+        client.record_contribution(&guild_id, &contributor, &bounty, &7u64);
+        client.record_contribution(&guild_id, &contributor, &bounty, &7u64);
+        // Another contribution type over the same reference still counts once
+        let review = ContributionType::MilestoneApproved;
+        client.record_contribution(&guild_id, &contributor, &review, &7u64);
+        client.record_contribution(&guild_id, &contributor, &review, &7u64);
+
+        let profile = client.get_reputation(&guild_id, &contributor);
+        assert_eq!(profile.total_score, 150);
+        assert_eq!(profile.contributions_count, 2);
+        assert_eq!(
+            client
+                .get_reputation_contributions(&guild_id, &contributor, &10)
+                .len(),
+            2
+        );
+
+        // Guilds that turn deduplication off credit every call
+        client.set_contribution_dedupe(&guild_id, &false, &owner);
+        client.record_contribution(&guild_id, &contributor, &bounty, &7u64);
+        assert_eq!(
+            client.get_reputation(&guild_id, &contributor).total_score,
+            250
+        );
+
+        let outsider = Address::generate(&env);
+        assert!(client
+            .try_set_contribution_dedupe(&guild_id, &true, &outsider)
+            .is_err());
+    }
+
+    #[test]
+    fn test_export_reputation_snapshot_uses_decayed_score() {
+        let env = setup_env();
+        set_ledger_timestamp(&env, 1000);
+        let contract_id = register_and_init_contract(&env);
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+        env.mock_all_auths();
+        let owner = Address::generate(&env);
+        let guild_id = setup_guild(&client, &env, &owner);
+        let contributor = Address::generate(&env);
+        client.add_member(&guild_id, &contributor, &Role::Contributor, &owner);
+
+        // No profile yet: exported as zero
+        assert_eq!(
+            client.export_reputation_snapshot(&guild_id, &contributor),
+            (0, 1000)
+        );
+
+        client.record_contribution(
+            &guild_id,
+            &contributor,
+            &ContributionType::BountyCompleted,
+            &1u64,
+        );
+        set_ledger_timestamp(&env, 1000 + 604_800);
+        let snapshot = client.export_reputation_snapshot(&guild_id, &contributor);
+        assert_eq!(env.auths()[0].0, contributor);
+        assert_eq!(snapshot, (99, 1000 + 604_800));
+        assert!(client.verify_reputation_snapshot(&guild_id, &contributor, &99, &snapshot.1));
+        assert!(!client.verify_reputation_snapshot(&guild_id, &contributor, &100, &snapshot.1));
+        assert!(!client.verify_reputation_snapshot(&guild_id, &owner, &99, &snapshot.1));
+
+        // A newer export supersedes the committed one
+        set_ledger_timestamp(&env, 1000 + 2 * 604_800);
+        assert_eq!(
+            client.export_reputation_snapshot(&guild_id, &contributor),
+            (98, 1000 + 2 * 604_800)
+        );
+        assert!(!client.verify_reputation_snapshot(&guild_id, &contributor, &99, &snapshot.1));
+    }
+
+    mod six_decimal_token {
+        use soroban_sdk::{contract, contractimpl, Env};
+
+        #[contract]
+        pub struct SixDecimalToken;
+
+        #[contractimpl]
+        impl SixDecimalToken {
+            pub fn decimals(_env: Env) -> u32 {
+                6
+            }
+        }
+    }
+
+    #[test]
+    fn test_payment_scaled_points_follow_token_decimals() {
+        use crate::reputation::scoring::{dispute_loss_penalty, milestone_points};
+
+        let env = setup_env();
+        let contract_id = register_and_init_contract(&env);
+        let usdc = env.register_contract(None, six_decimal_token::SixDecimalToken);
+
+        env.as_contract(&contract_id, || {
+            // 40 whole tokens either way, whatever the token's scale
+            assert_eq!(milestone_points(&env, &None, 400_000_000), 40);
+            assert_eq!(milestone_points(&env, &Some(usdc.clone()), 40_000_000), 40);
+            assert_eq!(dispute_loss_penalty(&env, &None, 400_000_000), 65);
+            assert_eq!(
+                dispute_loss_penalty(&env, &Some(usdc.clone()), 40_000_000),
+                65
+            );
+        });
+    }
 }
@@ -11,6 +11,9 @@ pub enum ContributionType {
     ProposalCreated,
     VoteCast,
     DisputeResolved,
+    /// Reputation taken away (e.g. for losing a dispute); `points` is the
+    /// deduction applied. Only recorded by the contract itself.
+    Penalty,
 }
 
 // â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€ Scoring Constants â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€
@@ -22,6 +25,15 @@ pub const POINTS_PROPOSAL_CREATED: u32 = 20;
 pub const POINTS_VOTE_CAST: u32 = 5;
 pub const POINTS_DISPUTE_RESOLVED: u32 = 30;
 
+/// Approved milestones earn one point per whole token paid, within these bounds
+pub const POINTS_MILESTONE_MIN: u32 = 10;
+pub const POINTS_MILESTONE_MAX: u32 = 500;
+
+/// Dispute loss penalty: base points plus one point per whole token at stake,
+/// capped
+pub const PENALTY_DISPUTE_LOST_BASE: u64 = 25;
+pub const PENALTY_DISPUTE_LOST_MAX: u64 = 200;
+
 /// Decay: 1% per period, applied lazily
 pub const DECAY_PERIOD_SECS: u64 = 604_800; // 1 week
 /// Decay numerator / denominator => 99/100 = keep 99% per period
@@ -62,6 +74,19 @@ pub struct ReputationProfile {
     pub last_decay_applied: u64,
 }
 
+/// A contribution or penalty paired with the user's score right after it was
+/// recorded
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReputationTimelineEntry {
+    pub record: ContributionRecord,
+    /// Raw accumulated score including this record
+    pub cumulative_score: u64,
+    /// Decayed score including this record, computed the same way
+    /// as `get_decayed_profile`
+    pub decayed_score: u64,
+}
+
 // â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€ Badge System â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€
 
 /// Types of badges that can be earned
@@ -104,6 +129,29 @@ pub struct ReputationUpdatedEvent {
     pub contribution_type: ContributionType,
 }
 
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReputationPenalizedEvent {
+    pub guild_id: u64,
+    pub address: Address,
+    /// Points actually removed; less than requested at the zero floor
+    pub points_deducted: u64,
+    pub new_total_score: u64,
+    /// Dispute (or other record) that caused the penalty
+    pub reference_id: u64,
+}
+
+/// Published by `export_reputation_snapshot` so off-chain consumers can
+/// pick up the same `(score, timestamp)` pair the caller received
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReputationExportedEvent {
+    pub guild_id: u64,
+    pub address: Address,
+    pub score: u64,
+    pub timestamp: u64,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct BadgeAwardedEvent {
@@ -121,5 +169,6 @@ pub fn points_for_contribution(ct: &ContributionType) -> u32 {
         ContributionType::ProposalCreated => POINTS_PROPOSAL_CREATED,
         ContributionType::VoteCast => POINTS_VOTE_CAST,
         ContributionType::DisputeResolved => POINTS_DISPUTE_RESOLVED,
+        ContributionType::Penalty => 0,
     }
 }
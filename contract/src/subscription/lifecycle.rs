@@ -1,23 +1,35 @@
 use crate::events::emit::emit_event;
 use crate::events::topics::{
-    ACT_CANCELLED, ACT_CREATED, ACT_FAILED, ACT_GRACE_STARTED, ACT_PAUSED, ACT_PAYMENT_FAILED,
-    ACT_PAYMENT_PROCESSED, ACT_PAYMENT_RETRIED, ACT_PLAN_CREATED, ACT_RECORDED, ACT_RESUMED,
-    ACT_TIER_CHANGED, MOD_SUBSCRIPTION,
+    ACT_CANCELLED, ACT_CREATED, ACT_FAILED, ACT_FEE_COLLECTED, ACT_GRACE_STARTED, ACT_PAUSED,
+    ACT_PAYMENT_FAILED, ACT_PAYMENT_PROCESSED, ACT_PAYMENT_RETRIED, ACT_PLAN_CREATED, ACT_RECORDED,
+    ACT_RESUMED, ACT_TIER_CHANGED, ACT_UPDATED, MOD_SUBSCRIPTION,
 };
 use crate::subscription::storage::{
-    add_active_subscription, add_guild_revenue, add_plan_to_guild, get_next_plan_id,
-    get_next_revenue_record_id, get_next_subscription_id, get_plan, get_retry_config,
-    get_subscription, get_user_subscription, remove_active_subscription, store_plan,
-    store_revenue_record, store_subscription, store_user_subscription,
+    add_active_subscription, add_guild_revenue, add_plan_to_guild, get_all_plans, get_guild_plans,
+    get_next_plan_id, get_next_revenue_record_id, get_next_subscription_id, get_plan,
+    get_plan_proration_basis, get_plan_retry_config, get_plan_subscriber_count,
+    get_platform_fee_config, get_proration_rounding, get_retry_backoff_schedule, get_retry_config,
+    get_subscription, get_subscription_retry_config, get_user_subscription,
+    remove_active_subscription, set_plan_proration_basis, set_plan_retry_config,
+    set_platform_fee_config, set_proration_rounding, set_retry_backoff_schedule, set_retry_config,
+    set_subscription_retry_config, store_plan, store_revenue_record, store_subscription,
+    store_user_subscription, update_plan_subscriber_count,
 };
 use crate::subscription::types::{
-    GracePeriodStartedEvent, MembershipTier, PaymentProcessedEvent, PlanCreatedEvent,
-    ProrationResult, RetryConfig, RevenueRecord, RevenueRecordedEvent, Subscription,
-    SubscriptionCancelledEvent, SubscriptionChange, SubscriptionCreatedEvent, SubscriptionError,
-    SubscriptionPlan, SubscriptionStatus, TierChangedEvent,
+    CancellationScheduledEvent, EffectiveRetryConfig, GracePeriodStartedEvent, MembershipTier,
+    PaymentProcessedEvent, PlanCreatedEvent, PlanRetryConfigUpdatedEvent,
+    PlatformFeeCollectedEvent, PlatformFeeConfig, ProrationBasis, ProrationResult,
+    ProrationRounding, RetryConfig, RetryConfigSource, RetryScheduleUpdatedEvent, RevenueRecord,
+    RevenueRecordedEvent, Subscription, SubscriptionCancelledEvent, SubscriptionChange,
+    SubscriptionCreatedEvent, SubscriptionError, SubscriptionPlan,
+    SubscriptionRetryConfigUpdatedEvent, SubscriptionStatus, TierChangeScheduledEvent,
+    TierChangedEvent,
 };
 use soroban_sdk::{token, Address, Env, String, Vec};
 
+/// Upper bound on plans returned by a single `list_all_plans` call
+pub const MAX_PLAN_PAGE_SIZE: u32 = 100;
+
 /// Create a new subscription plan
 ///
 /// # Arguments
@@ -66,6 +78,9 @@ pub fn create_plan(
         benefits,
         created_by: created_by.clone(),
         created_at: env.ledger().timestamp(),
+        cancel_at_period_end: false,
+        billing_anchor: None,
+        max_subscribers: None,
     };
 
     store_plan(env, &plan);
@@ -89,6 +104,160 @@ pub fn create_plan(
     Ok(plan_id)
 }
 
+/// Configure whether cancelling a subscription to a plan takes effect
+/// immediately or at the end of the current billing period
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `plan_id` - ID of the plan
+/// * `cancel_at_period_end` - Whether cancellations wait for the period end
+/// * `caller` - Address making the request (must be the plan creator)
+///
+/// # Returns
+/// true if successful
+pub fn set_plan_cancellation_policy(
+    env: &Env,
+    plan_id: u64,
+    cancel_at_period_end: bool,
+    caller: Address,
+) -> Result<bool, SubscriptionError> {
+    let mut plan = get_plan(env, plan_id).ok_or(SubscriptionError::PlanNotFound)?;
+
+    if plan.created_by != caller {
+        return Err(SubscriptionError::Unauthorized);
+    }
+
+    plan.cancel_at_period_end = cancel_at_period_end;
+    store_plan(env, &plan);
+
+    emit_event(env, MOD_SUBSCRIPTION, ACT_UPDATED, plan);
+
+    Ok(true)
+}
+
+/// Anchor a plan's billing to a fixed cycle boundary so all of its
+/// subscribers bill together
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `plan_id` - ID of the plan
+/// * `anchor` - Timestamp of any cycle boundary, at most one cycle ahead
+///   (None restores per-subscriber billing)
+/// * `caller` - Address making the request (must be the plan creator)
+///
+/// # Returns
+/// true if successful
+pub fn set_plan_billing_anchor(
+    env: &Env,
+    plan_id: u64,
+    anchor: Option<u64>,
+    caller: Address,
+) -> Result<bool, SubscriptionError> {
+    let mut plan = get_plan(env, plan_id).ok_or(SubscriptionError::PlanNotFound)?;
+
+    if plan.created_by != caller {
+        return Err(SubscriptionError::Unauthorized);
+    }
+
+    // A boundary further out would make the prorated first charge exceed
+    // the plan price
+    if let Some(anchor) = anchor {
+        let latest = env
+            .ledger()
+            .timestamp()
+            .saturating_add(plan.billing_cycle.duration_seconds());
+        if anchor > latest {
+            return Err(SubscriptionError::InvalidBillingAnchor);
+        }
+    }
+
+    plan.billing_anchor = anchor;
+    store_plan(env, &plan);
+
+    emit_event(env, MOD_SUBSCRIPTION, ACT_UPDATED, plan);
+
+    Ok(true)
+}
+
+/// Cap how many subscriptions a plan holds at once
+///
+/// Lowering the cap below the current count keeps existing subscribers;
+/// only new subscriptions are refused until enough of them cancel.
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `plan_id` - ID of the plan
+/// * `max_subscribers` - The cap (None removes it)
+/// * `caller` - Address making the request (must be the plan creator)
+///
+/// # Returns
+/// true if successful
+pub fn set_plan_max_subscribers(
+    env: &Env,
+    plan_id: u64,
+    max_subscribers: Option<u32>,
+    caller: Address,
+) -> Result<bool, SubscriptionError> {
+    let mut plan = get_plan(env, plan_id).ok_or(SubscriptionError::PlanNotFound)?;
+
+    if plan.created_by != caller {
+        return Err(SubscriptionError::Unauthorized);
+    }
+
+    plan.max_subscribers = max_subscribers;
+    store_plan(env, &plan);
+
+    emit_event(env, MOD_SUBSCRIPTION, ACT_UPDATED, plan);
+
+    Ok(true)
+}
+
+/// Reprice and (de)activate a guild plan on behalf of the guild's governance
+///
+/// Used when a `SubscriptionPlanUpdate` proposal executes. Existing
+/// subscribers are charged the new price from their next billing cycle.
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `guild_id` - Guild whose proposal passed (must own the plan)
+/// * `plan_id` - ID of the plan
+/// * `new_price` - New price amount
+/// * `active` - Whether the plan accepts new subscriptions
+///
+/// # Returns
+/// true if successful
+pub fn apply_governed_plan_update(
+    env: &Env,
+    guild_id: u64,
+    plan_id: u64,
+    new_price: i128,
+    active: bool,
+) -> Result<bool, SubscriptionError> {
+    let mut plan = get_plan(env, plan_id).ok_or(SubscriptionError::PlanNotFound)?;
+
+    if plan.guild_id != guild_id {
+        return Err(SubscriptionError::Unauthorized);
+    }
+    if new_price <= 0 {
+        return Err(SubscriptionError::InvalidPrice);
+    }
+
+    plan.price = new_price;
+    plan.is_active = active;
+    store_plan(env, &plan);
+
+    emit_event(env, MOD_SUBSCRIPTION, ACT_UPDATED, plan);
+
+    Ok(true)
+}
+
+/// Whether a plan has no free slot for another subscription
+fn plan_is_full(env: &Env, plan: &SubscriptionPlan) -> bool {
+    plan.max_subscribers
+        .map(|max| get_plan_subscriber_count(env, plan.id) >= max)
+        .unwrap_or(false)
+}
+
 /// Subscribe to a plan
 ///
 /// # Arguments
@@ -120,10 +289,32 @@ pub fn subscribe(
         }
     }
 
+    // The check and the slot update below run in the same invocation, and
+    // invocations never interleave, so two subscribers can't both take the
+    // last slot
+    if plan_is_full(env, &plan) {
+        return Err(SubscriptionError::PlanFull);
+    }
+
     let subscription_id = get_next_subscription_id(env);
     let now = env.ledger().timestamp();
     let cycle_duration = plan.billing_cycle.duration_seconds();
 
+    // Anchored plans bill on the shared boundary; the partial cycle up to it
+    // is charged pro rata
+    let (next_billing_at, first_cycle_amount) = match plan.billing_anchor {
+        Some(anchor) => {
+            let boundary = plan.billing_cycle.next_boundary(anchor, now);
+            let amount = plan
+                .price
+                .checked_mul((boundary - now) as i128)
+                .ok_or(SubscriptionError::ArithmeticOverflow)?
+                / cycle_duration as i128;
+            (boundary, Some(amount))
+        }
+        None => (now + cycle_duration, None),
+    };
+
     let subscription = Subscription {
         id: subscription_id,
         plan_id,
@@ -132,7 +323,7 @@ pub fn subscribe(
         current_tier: plan.tier.clone(),
         started_at: now,
         ends_at: None,
-        next_billing_at: now + cycle_duration,
+        next_billing_at,
         last_payment_at: None,
         last_payment_amount: None,
         failed_payment_count: 0,
@@ -140,8 +331,12 @@ pub fn subscribe(
         auto_renew,
         cancelled_at: None,
         cancellation_reason: None,
+        first_cycle_amount,
+        next_retry_at: None,
+        pending_plan_id: None,
     };
 
+    update_plan_subscriber_count(env, plan_id, true);
     store_subscription(env, &subscription);
     store_user_subscription(env, &subscriber, plan.guild_id, subscription_id);
     add_active_subscription(env, subscription_id);
@@ -176,8 +371,6 @@ pub fn process_payment(
     let mut subscription =
         get_subscription(env, subscription_id).ok_or(SubscriptionError::SubscriptionNotFound)?;
 
-    let plan = get_plan(env, subscription.plan_id).ok_or(SubscriptionError::PlanNotFound)?;
-
     // Only process active or grace period subscriptions
     if subscription.status != SubscriptionStatus::Active
         && subscription.status != SubscriptionStatus::GracePeriod
@@ -185,7 +378,23 @@ pub fn process_payment(
         return Err(SubscriptionError::InvalidState);
     }
 
-    let payment_result = execute_payment(env, &subscription.subscriber, plan.price, &plan.token);
+    // Never bill past a scheduled cancellation
+    if let Some(ends_at) = subscription.ends_at {
+        if env.ledger().timestamp() >= ends_at {
+            return Err(SubscriptionError::InvalidState);
+        }
+    }
+
+    // A deferred tier change takes effect once the current cycle is over;
+    // a payment taken before then is still for the current plan
+    if env.ledger().timestamp() >= subscription.next_billing_at {
+        apply_scheduled_tier_change(env, &mut subscription);
+    }
+
+    let plan = get_plan(env, subscription.plan_id).ok_or(SubscriptionError::PlanNotFound)?;
+
+    let amount = subscription.first_cycle_amount.unwrap_or(plan.price);
+    let payment_result = execute_payment(env, &subscription.subscriber, amount, &plan.token);
 
     let now = env.ledger().timestamp();
 
@@ -193,24 +402,31 @@ pub fn process_payment(
         Ok(()) => {
             // Payment successful
             subscription.last_payment_at = Some(now);
-            subscription.last_payment_amount = Some(plan.price);
+            subscription.last_payment_amount = Some(amount);
+            subscription.first_cycle_amount = None;
             subscription.failed_payment_count = 0;
             subscription.grace_period_ends_at = None;
+            subscription.next_retry_at = None;
             subscription.status = SubscriptionStatus::Active;
 
             // Calculate next billing date
-            let cycle_duration = plan.billing_cycle.duration_seconds();
-            subscription.next_billing_at = now + cycle_duration;
+            subscription.next_billing_at = match plan.billing_anchor {
+                Some(anchor) => plan.billing_cycle.next_boundary(anchor, now),
+                None => now + plan.billing_cycle.duration_seconds(),
+            };
 
             store_subscription(env, &subscription);
 
+            // Route the platform fee (if any) before crediting the guild
+            let net_amount = collect_platform_fee(env, subscription_id, amount, &plan.token)?;
+
             // Record revenue
             record_revenue(
                 env,
                 plan.guild_id,
                 subscription_id,
                 subscription.subscriber.clone(),
-                plan.price,
+                net_amount,
                 plan.token.clone(),
                 plan.billing_cycle.clone(),
                 retry_attempt > 0,
@@ -220,7 +436,7 @@ pub fn process_payment(
             // Emit success event
             let event = PaymentProcessedEvent {
                 subscription_id,
-                amount: plan.price,
+                amount,
                 success: true,
                 retry_attempt,
             };
@@ -237,7 +453,7 @@ pub fn process_payment(
             // Payment failed
             subscription.failed_payment_count += 1;
 
-            let retry_config = get_retry_config(env);
+            let retry_config = get_effective_retry_config(env, subscription_id).config;
 
             // Check if max retries exceeded
             if subscription.failed_payment_count >= retry_config.max_retries {
@@ -247,11 +463,17 @@ pub fn process_payment(
                 subscription.cancellation_reason =
                     Some(String::from_str(env, "Max payment retries exceeded"));
                 remove_active_subscription(env, subscription_id);
+                update_plan_subscriber_count(env, subscription.plan_id, false);
             } else {
                 // Enter or continue grace period
                 subscription.status = SubscriptionStatus::GracePeriod;
                 let grace_end = now + retry_config.grace_period_seconds;
                 subscription.grace_period_ends_at = Some(grace_end);
+                subscription.next_retry_at = Some(now.saturating_add(backoff_delay(
+                    env,
+                    &retry_config,
+                    subscription.failed_payment_count,
+                )));
 
                 // Emit grace period event
                 let event = GracePeriodStartedEvent {
@@ -267,7 +489,7 @@ pub fn process_payment(
             // Emit failure event
             let event = PaymentProcessedEvent {
                 subscription_id,
-                amount: plan.price,
+                amount,
                 success: false,
                 retry_attempt,
             };
@@ -300,6 +522,305 @@ fn execute_payment(
     Ok(())
 }
 
+/// Transfer the configured platform fee to the fee collector
+///
+/// Returns the amount remaining for the guild after the fee. When no fee is
+/// configured (or it rounds down to zero) the full amount is returned and no
+/// transfer is made.
+fn collect_platform_fee(
+    env: &Env,
+    subscription_id: u64,
+    amount: i128,
+    token: &Option<Address>,
+) -> Result<i128, SubscriptionError> {
+    let config = match get_platform_fee_config(env) {
+        Some(config) if config.fee_bps > 0 => config,
+        _ => return Ok(amount),
+    };
+
+    let fee_amount = amount
+        .checked_mul(config.fee_bps as i128)
+        .ok_or(SubscriptionError::ArithmeticOverflow)?
+        / 10_000;
+    if fee_amount == 0 {
+        return Ok(amount);
+    }
+
+    if let Some(token_addr) = token {
+        let token_client = token::Client::new(env, token_addr);
+        token_client.transfer(
+            &env.current_contract_address(),
+            &config.collector,
+            &fee_amount,
+        );
+    }
+
+    let net_amount = amount - fee_amount;
+
+    let event = PlatformFeeCollectedEvent {
+        subscription_id,
+        collector: config.collector,
+        fee_amount,
+        net_amount,
+    };
+    emit_event(env, MOD_SUBSCRIPTION, ACT_FEE_COLLECTED, event);
+
+    Ok(net_amount)
+}
+
+/// Set the platform fee taken from every successful subscription charge
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `fee_bps` - Fee in basis points (0 disables the fee, max 10_000)
+/// * `collector` - Address receiving the fee
+///
+/// # Returns
+/// true if successful
+pub fn set_platform_fee(
+    env: &Env,
+    fee_bps: u32,
+    collector: Address,
+) -> Result<bool, SubscriptionError> {
+    if fee_bps > 10_000 {
+        return Err(SubscriptionError::InvalidFeeConfig);
+    }
+
+    let config = PlatformFeeConfig { fee_bps, collector };
+    set_platform_fee_config(env, &config);
+
+    emit_event(env, MOD_SUBSCRIPTION, ACT_UPDATED, config);
+
+    Ok(true)
+}
+
+/// Set the rounding mode used when prorating tier changes
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `mode` - Floor, ceil or nearest rounding
+///
+/// # Returns
+/// true if successful
+pub fn set_proration_rounding_mode(
+    env: &Env,
+    mode: ProrationRounding,
+) -> Result<bool, SubscriptionError> {
+    set_proration_rounding(env, &mode);
+
+    emit_event(env, MOD_SUBSCRIPTION, ACT_UPDATED, mode);
+
+    Ok(true)
+}
+
+/// Set the delays between payment retry attempts
+///
+/// The n-th entry is the wait before retry n; when there are more retries than
+/// entries the last delay repeats. An empty schedule restores the exponential
+/// backoff derived from `RetryConfig`.
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `schedule` - Delays in seconds, each greater than zero
+///
+/// # Returns
+/// true if successful
+pub fn set_retry_schedule(env: &Env, schedule: Vec<u64>) -> Result<bool, SubscriptionError> {
+    if schedule.iter().any(|delay| delay == 0) {
+        return Err(SubscriptionError::InvalidRetrySchedule);
+    }
+
+    set_retry_backoff_schedule(env, &schedule);
+
+    let event = RetryScheduleUpdatedEvent { schedule };
+    emit_event(env, MOD_SUBSCRIPTION, ACT_UPDATED, event);
+
+    Ok(true)
+}
+
+/// Check that a retry configuration allows at least one attempt and backs off
+fn validate_retry_config(config: &RetryConfig) -> Result<(), SubscriptionError> {
+    if config.max_retries == 0 || config.backoff_multiplier == 0 {
+        return Err(SubscriptionError::InvalidRetryConfig);
+    }
+    Ok(())
+}
+
+/// Set the global payment retry configuration
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `config` - Retry limits, backoff and grace period
+///
+/// # Returns
+/// true if successful
+pub fn update_retry_config(env: &Env, config: RetryConfig) -> Result<bool, SubscriptionError> {
+    validate_retry_config(&config)?;
+
+    set_retry_config(env, &config);
+
+    emit_event(env, MOD_SUBSCRIPTION, ACT_UPDATED, config);
+
+    Ok(true)
+}
+
+/// Choose whether tier changes away from a plan prorate by whole days or
+/// by exact seconds remaining in the cycle
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `plan_id` - ID of the plan
+/// * `basis` - Daily or seconds-based proration
+/// * `caller` - Address making the request (must be the plan creator)
+///
+/// # Returns
+/// true if successful
+pub fn update_plan_proration_basis(
+    env: &Env,
+    plan_id: u64,
+    basis: ProrationBasis,
+    caller: Address,
+) -> Result<bool, SubscriptionError> {
+    let plan = get_plan(env, plan_id).ok_or(SubscriptionError::PlanNotFound)?;
+
+    if plan.created_by != caller {
+        return Err(SubscriptionError::Unauthorized);
+    }
+
+    set_plan_proration_basis(env, plan_id, &basis);
+
+    emit_event(env, MOD_SUBSCRIPTION, ACT_UPDATED, (plan_id, basis));
+
+    Ok(true)
+}
+
+/// Override the retry configuration for every subscription on a plan
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `plan_id` - ID of the plan
+/// * `config` - The override (None falls back to the global configuration)
+/// * `caller` - Address making the request (must be the plan creator)
+///
+/// # Returns
+/// true if successful
+pub fn update_plan_retry_config(
+    env: &Env,
+    plan_id: u64,
+    config: Option<RetryConfig>,
+    caller: Address,
+) -> Result<bool, SubscriptionError> {
+    let plan = get_plan(env, plan_id).ok_or(SubscriptionError::PlanNotFound)?;
+
+    if plan.created_by != caller {
+        return Err(SubscriptionError::Unauthorized);
+    }
+    if let Some(config) = &config {
+        validate_retry_config(config)?;
+    }
+
+    set_plan_retry_config(env, plan_id, &config);
+
+    let retry_config = match config {
+        Some(config) => EffectiveRetryConfig {
+            config,
+            source: RetryConfigSource::Plan,
+        },
+        None => EffectiveRetryConfig {
+            config: get_retry_config(env),
+            source: RetryConfigSource::Global,
+        },
+    };
+    let event = PlanRetryConfigUpdatedEvent {
+        plan_id,
+        retry_config,
+    };
+    emit_event(env, MOD_SUBSCRIPTION, ACT_UPDATED, event);
+
+    Ok(true)
+}
+
+/// Override the retry configuration for a single subscription
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `subscription_id` - ID of the subscription
+/// * `config` - The override (None falls back to the plan or global configuration)
+/// * `caller` - Address making the request (must be the plan creator)
+///
+/// # Returns
+/// true if successful
+pub fn update_subscription_retry_config(
+    env: &Env,
+    subscription_id: u64,
+    config: Option<RetryConfig>,
+    caller: Address,
+) -> Result<bool, SubscriptionError> {
+    let subscription =
+        get_subscription(env, subscription_id).ok_or(SubscriptionError::SubscriptionNotFound)?;
+    let plan = get_plan(env, subscription.plan_id).ok_or(SubscriptionError::PlanNotFound)?;
+
+    if plan.created_by != caller {
+        return Err(SubscriptionError::Unauthorized);
+    }
+    if let Some(config) = &config {
+        validate_retry_config(config)?;
+    }
+
+    set_subscription_retry_config(env, subscription_id, &config);
+
+    let event = SubscriptionRetryConfigUpdatedEvent {
+        subscription_id,
+        retry_config: get_effective_retry_config(env, subscription_id),
+    };
+    emit_event(env, MOD_SUBSCRIPTION, ACT_UPDATED, event);
+
+    Ok(true)
+}
+
+/// The retry configuration that applies to a subscription
+///
+/// A subscription override wins over its plan's override, which wins over
+/// the global configuration. An unknown subscription gets the global
+/// configuration with `RetryConfigSource::Global`.
+pub fn get_effective_retry_config(env: &Env, subscription_id: u64) -> EffectiveRetryConfig {
+    if let Some(config) = get_subscription_retry_config(env, subscription_id) {
+        return EffectiveRetryConfig {
+            config,
+            source: RetryConfigSource::Subscription,
+        };
+    }
+    if let Some(config) = get_subscription(env, subscription_id)
+        .and_then(|subscription| get_plan_retry_config(env, subscription.plan_id))
+    {
+        return EffectiveRetryConfig {
+            config,
+            source: RetryConfigSource::Plan,
+        };
+    }
+    EffectiveRetryConfig {
+        config: get_retry_config(env),
+        source: RetryConfigSource::Global,
+    }
+}
+
+/// Delay before a retry attempt (1-based): the backoff schedule when one is
+/// set, otherwise exponential backoff from `config`
+pub fn backoff_delay(env: &Env, config: &RetryConfig, attempt: u32) -> u64 {
+    let index = attempt.saturating_sub(1);
+    let schedule = get_retry_backoff_schedule(env);
+    if !schedule.is_empty() {
+        return schedule
+            .get(index.min(schedule.len() - 1))
+            .unwrap_or_default();
+    }
+
+    (config.backoff_multiplier as u64)
+        .checked_pow(index)
+        .and_then(|factor| config.initial_delay_seconds.checked_mul(factor))
+        .unwrap_or(u64::MAX)
+}
+
 /// Record revenue from a successful payment
 fn record_revenue(
     env: &Env,
@@ -384,7 +905,8 @@ pub fn pause_subscription(
     Ok(true)
 }
 
-/// Resume a paused subscription
+/// Resume a paused subscription, or withdraw a cancellation that is
+/// scheduled for the end of the current period
 ///
 /// # Arguments
 /// * `env` - The contract environment
@@ -406,6 +928,17 @@ pub fn resume_subscription(
         return Err(SubscriptionError::Unauthorized);
     }
 
+    // Un-cancel: benefits were never interrupted, so only the end date is cleared
+    if subscription.status == SubscriptionStatus::Active && subscription.ends_at.is_some() {
+        subscription.ends_at = None;
+        subscription.cancellation_reason = None;
+        store_subscription(env, &subscription);
+
+        emit_event(env, MOD_SUBSCRIPTION, ACT_RESUMED, subscription.clone());
+
+        return Ok(true);
+    }
+
     if subscription.status != SubscriptionStatus::Paused {
         return Err(SubscriptionError::InvalidState);
     }
@@ -455,12 +988,35 @@ pub fn cancel_subscription(
         return Err(SubscriptionError::AlreadyCancelled);
     }
 
+    let plan = get_plan(env, subscription.plan_id).ok_or(SubscriptionError::PlanNotFound)?;
+
+    // Notice period: stay Active until the paid period runs out, then
+    // `process_due_subscriptions` completes the cancellation
+    if plan.cancel_at_period_end && subscription.status == SubscriptionStatus::Active {
+        if subscription.ends_at.is_some() {
+            return Err(SubscriptionError::AlreadyCancelled);
+        }
+        subscription.ends_at = Some(subscription.next_billing_at);
+        subscription.cancellation_reason = reason.clone();
+        store_subscription(env, &subscription);
+
+        let event = CancellationScheduledEvent {
+            subscription_id,
+            cancelled_by: caller,
+            reason,
+            effective_at: subscription.next_billing_at,
+        };
+        emit_event(env, MOD_SUBSCRIPTION, ACT_UPDATED, event);
+        return Ok(true);
+    }
+
     let now = env.ledger().timestamp();
     subscription.status = SubscriptionStatus::Cancelled;
     subscription.cancelled_at = Some(now);
     subscription.cancellation_reason = reason.clone();
     subscription.auto_renew = false;
 
+    update_plan_subscriber_count(env, subscription.plan_id, false);
     store_subscription(env, &subscription);
     remove_active_subscription(env, subscription_id);
 
@@ -517,47 +1073,75 @@ pub fn change_tier(
     if !is_upgrade && !is_downgrade {
         return Err(SubscriptionError::InvalidTierChange);
     }
+    if plan_is_full(env, &new_plan) {
+        return Err(SubscriptionError::PlanFull);
+    }
+
+    // A deferred change leaves the current plan in force until the next
+    // cycle is billed, when `process_payment` switches it over
+    if !change.effective_immediately {
+        subscription.pending_plan_id = Some(change.new_plan_id);
+        store_subscription(env, &subscription);
+
+        let event = TierChangeScheduledEvent {
+            subscription_id,
+            new_plan_id: Some(change.new_plan_id),
+            effective_at: subscription.next_billing_at,
+        };
+        emit_event(env, MOD_SUBSCRIPTION, ACT_UPDATED, event);
+        return Ok(None);
+    }
 
     let old_tier = subscription.current_tier.clone();
+    update_plan_subscriber_count(env, subscription.plan_id, false);
+    update_plan_subscriber_count(env, change.new_plan_id, true);
     subscription.current_tier = new_plan.tier.clone();
     subscription.plan_id = change.new_plan_id;
+    subscription.pending_plan_id = None;
 
-    let proration = if change.effective_immediately {
-        // Calculate proration
-        let now = env.ledger().timestamp();
-        let cycle_duration = current_plan.billing_cycle.duration_seconds();
-        let time_remaining = subscription.next_billing_at.saturating_sub(now);
-
-        if time_remaining > 0 && cycle_duration > 0 {
-            let days_remaining = time_remaining / (24 * 60 * 60);
-            let total_days = cycle_duration / (24 * 60 * 60);
-
-            // Calculate prorated amounts
-            let current_plan_daily_rate = current_plan.price / total_days as i128;
-            let new_plan_daily_rate = new_plan.price / total_days as i128;
-
-            let remaining_value = current_plan_daily_rate * days_remaining as i128;
-            let new_plan_value = new_plan_daily_rate * days_remaining as i128;
-
-            let proration_amount = if is_upgrade {
-                // Charge difference for upgrade
-                new_plan_value.saturating_sub(remaining_value)
-            } else {
-                // Credit difference for downgrade
-                remaining_value.saturating_sub(new_plan_value)
-            };
-
-            Some(ProrationResult {
-                amount: proration_amount,
-                is_charge: is_upgrade,
-                days_remaining,
-                total_days,
-            })
+    // Calculate proration
+    let now = env.ledger().timestamp();
+    let cycle_duration = current_plan.billing_cycle.duration_seconds();
+    let time_remaining = subscription.next_billing_at.saturating_sub(now);
+
+    let proration = if time_remaining > 0 && cycle_duration > 0 {
+        let day = 24 * 60 * 60;
+        let total_days = cycle_duration / day;
+        // A partially used day still counts as remaining, so a same-day
+        // change prorates over the whole cycle
+        let days_remaining = time_remaining.div_ceil(day).min(total_days);
+
+        let price_difference = if is_upgrade {
+            // Charge difference for upgrade
+            new_plan.price - current_plan.price
         } else {
-            None
+            // Credit difference for downgrade
+            current_plan.price - new_plan.price
+        };
+
+        // The outgoing plan's basis decides the unit; the reported days stay
+        // whole days either way
+        let (units_remaining, total_units) = match get_plan_proration_basis(env, current_plan.id) {
+            ProrationBasis::Daily => (days_remaining, total_days),
+            ProrationBasis::Seconds => (time_remaining.min(cycle_duration), cycle_duration),
+        };
+        if total_units == 0 {
+            return Err(SubscriptionError::InvalidBillingCycle);
         }
+
+        // Multiply before dividing so only the final step rounds
+        let scaled = price_difference
+            .checked_mul(units_remaining as i128)
+            .ok_or(SubscriptionError::ArithmeticOverflow)?;
+        let proration_amount = get_proration_rounding(env).divide(scaled, total_units as i128);
+
+        Some(ProrationResult {
+            amount: proration_amount,
+            is_charge: is_upgrade,
+            days_remaining,
+            total_days,
+        })
     } else {
-        // Change effective at next billing cycle - no proration
         None
     };
 
@@ -575,6 +1159,70 @@ pub fn change_tier(
     Ok(proration)
 }
 
+/// Withdraw a tier change scheduled for the next billing cycle
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `subscription_id` - ID of the subscription
+/// * `caller` - Address making the request (must be the subscriber)
+///
+/// # Returns
+/// true if a scheduled change was withdrawn
+pub fn cancel_scheduled_tier_change(
+    env: &Env,
+    subscription_id: u64,
+    caller: Address,
+) -> Result<bool, SubscriptionError> {
+    let mut subscription =
+        get_subscription(env, subscription_id).ok_or(SubscriptionError::SubscriptionNotFound)?;
+
+    if subscription.subscriber != caller {
+        return Err(SubscriptionError::Unauthorized);
+    }
+    if subscription.pending_plan_id.is_none() {
+        return Err(SubscriptionError::NoScheduledTierChange);
+    }
+
+    subscription.pending_plan_id = None;
+    store_subscription(env, &subscription);
+
+    let event = TierChangeScheduledEvent {
+        subscription_id,
+        new_plan_id: None,
+        effective_at: subscription.next_billing_at,
+    };
+    emit_event(env, MOD_SUBSCRIPTION, ACT_UPDATED, event);
+
+    Ok(true)
+}
+
+/// Switch a subscription to its scheduled plan, if any. A scheduled plan
+/// that no longer exists, or filled up since the change was scheduled, is
+/// dropped and the current plan stays in force.
+fn apply_scheduled_tier_change(env: &Env, subscription: &mut Subscription) {
+    let Some(plan_id) = subscription.pending_plan_id.take() else {
+        return;
+    };
+    let Some(new_plan) = get_plan(env, plan_id) else {
+        return;
+    };
+    if plan_is_full(env, &new_plan) {
+        return;
+    }
+    update_plan_subscriber_count(env, subscription.plan_id, false);
+    update_plan_subscriber_count(env, plan_id, true);
+
+    let event = TierChangedEvent {
+        subscription_id: subscription.id,
+        old_tier: subscription.current_tier.clone(),
+        new_tier: new_plan.tier.clone(),
+        proration_amount: 0,
+    };
+    subscription.current_tier = new_plan.tier;
+    subscription.plan_id = plan_id;
+    emit_event(env, MOD_SUBSCRIPTION, ACT_TIER_CHANGED, event);
+}
+
 /// Retry a failed payment
 ///
 /// # Arguments
@@ -590,6 +1238,11 @@ pub fn retry_payment(env: &Env, subscription_id: u64) -> Result<bool, Subscripti
     if subscription.status != SubscriptionStatus::GracePeriod {
         return Err(SubscriptionError::NotInGracePeriod);
     }
+    if let Some(next_retry_at) = subscription.next_retry_at {
+        if env.ledger().timestamp() < next_retry_at {
+            return Err(SubscriptionError::RetryNotDue);
+        }
+    }
 
     let retry_attempt = subscription.failed_payment_count;
     process_payment(env, subscription_id, retry_attempt)
@@ -616,8 +1269,31 @@ pub fn process_due_subscriptions(env: &Env, limit: u32) -> u32 {
         }
 
         if let Some(subscription) = get_subscription(env, sub_id) {
+            let cancellation_due = subscription
+                .ends_at
+                .map(|ends_at| now >= ends_at)
+                .unwrap_or(false);
+
+            // Complete cancellations scheduled for the end of the period
+            if subscription.status == SubscriptionStatus::Active && cancellation_due {
+                let mut sub = subscription;
+                sub.status = SubscriptionStatus::Cancelled;
+                sub.cancelled_at = Some(now);
+                sub.auto_renew = false;
+                update_plan_subscriber_count(env, sub.plan_id, false);
+                store_subscription(env, &sub);
+                remove_active_subscription(env, sub_id);
+
+                let event = SubscriptionCancelledEvent {
+                    subscription_id: sub_id,
+                    cancelled_by: sub.subscriber.clone(),
+                    reason: sub.cancellation_reason.clone(),
+                };
+                emit_event(env, MOD_SUBSCRIPTION, ACT_CANCELLED, event);
+                processed += 1;
+            }
             // Check if subscription is due for payment
-            if subscription.status == SubscriptionStatus::Active
+            else if subscription.status == SubscriptionStatus::Active
                 && subscription.next_billing_at <= now
                 && subscription.auto_renew
             {
@@ -635,6 +1311,7 @@ pub fn process_due_subscriptions(env: &Env, limit: u32) -> u32 {
                         sub.cancelled_at = Some(now);
                         sub.cancellation_reason =
                             Some(String::from_str(env, "Grace period expired"));
+                        update_plan_subscriber_count(env, sub.plan_id, false);
                         store_subscription(env, &sub);
                         remove_active_subscription(env, sub_id);
 
@@ -646,6 +1323,15 @@ pub fn process_due_subscriptions(env: &Env, limit: u32) -> u32 {
                         };
                         emit_event(env, MOD_SUBSCRIPTION, ACT_CANCELLED, event);
                         processed += 1;
+                    } else if subscription.auto_renew
+                        && subscription
+                            .next_retry_at
+                            .map(|at| now >= at)
+                            .unwrap_or(true)
+                    {
+                        // Retry once the backoff delay for this attempt has elapsed
+                        let _ = process_payment(env, sub_id, subscription.failed_payment_count);
+                        processed += 1;
                     }
                 }
             }
@@ -667,6 +1353,36 @@ pub fn get_subscription_status(env: &Env, subscription_id: u64) -> Option<Subscr
     get_subscription(env, subscription_id)
 }
 
+/// List the plans offered by a guild, including deactivated ones
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `guild_id` - Guild ID
+///
+/// # Returns
+/// Plans in creation order; inactive plans have `is_active = false`
+pub fn list_guild_plans(env: &Env, guild_id: u64) -> Vec<SubscriptionPlan> {
+    let mut plans = Vec::new(env);
+    for plan_id in get_guild_plans(env, guild_id).iter() {
+        if let Some(plan) = get_plan(env, plan_id) {
+            plans.push_back(plan);
+        }
+    }
+    plans
+}
+
+/// List plans across the platform, guild-specific and platform-wide
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `limit` - Most plans to return, capped at `MAX_PLAN_PAGE_SIZE`
+///
+/// # Returns
+/// Plans in creation order; inactive plans have `is_active = false`
+pub fn list_all_plans(env: &Env, limit: u32) -> Vec<SubscriptionPlan> {
+    get_all_plans(env, limit.min(MAX_PLAN_PAGE_SIZE))
+}
+
 /// Check if a subscription is active and valid
 ///
 /// # Arguments
@@ -15,6 +15,7 @@ pub mod types;
 /// # Key Features
 /// - **Tiered Membership**: Basic, Standard, Premium, and Enterprise tiers with different benefits
 /// - **Flexible Billing Cycles**: Weekly, Monthly, Quarterly, and Annual billing options
+/// - **Anchored Billing**: Plans can bill every subscriber on a shared cycle boundary
 /// - **Automatic Payment Processing**: Built-in retry logic with configurable parameters
 /// - **Grace Periods**: Automatic handling of failed payments with grace periods
 /// - **Proration Support**: Automatic calculations for mid-cycle tier changes
@@ -27,7 +28,8 @@ pub mod types;
 /// 3. **Payment Processing**: Automatic payment processing at billing cycle intervals
 /// 4. **Retry Logic**: Failed payments trigger retry attempts with grace periods
 /// 5. **Tier Changes**: Support for upgrades/downgrades with proration
-/// 6. **Cancellation**: Subscribers can cancel at any time
+/// 6. **Cancellation**: Subscribers can cancel at any time, immediately or at
+///    period end when the plan requires a notice period
 ///
 /// # Edge Cases Handled
 /// - Payment failures with configurable retry logic
@@ -37,25 +39,32 @@ pub mod types;
 /// - Auto-renewal management
 // Re-export main types
 pub use types::{
-    BillingCycle, GracePeriodStartedEvent, MembershipTier, PaymentProcessedEvent, PlanCreatedEvent,
-    ProrationResult, RetryConfig, RevenueRecord, RevenueRecordedEvent, Subscription,
-    SubscriptionCancelledEvent, SubscriptionChange, SubscriptionCreatedEvent, SubscriptionError,
-    SubscriptionPlan, SubscriptionStatus, TierChangedEvent,
+    BillingCycle, EffectiveRetryConfig, GracePeriodStartedEvent, MembershipTier,
+    PaymentProcessedEvent, PlanCreatedEvent, PlatformFeeConfig, ProrationBasis, ProrationResult,
+    ProrationRounding, RetryConfig, RetryConfigSource, RevenueRecord, RevenueRecordedEvent,
+    Subscription, SubscriptionCancelledEvent, SubscriptionChange, SubscriptionCreatedEvent,
+    SubscriptionError, SubscriptionPlan, SubscriptionStatus, TierChangedEvent,
 };
 
 // Re-export storage functions
 pub use storage::{
     add_guild_revenue, get_all_plans, get_guild_plans, get_guild_revenue_records, get_plan,
-    get_retry_config, get_revenue_record, get_subscription, get_subscriptions_by_plan,
-    get_user_subscription, initialize_subscription_storage, set_retry_config, store_plan,
-    store_subscription,
+    get_plan_proration_basis, get_plan_subscriber_count, get_platform_fee_config,
+    get_proration_rounding, get_retry_backoff_schedule, get_retry_config, get_revenue_record,
+    get_subscription, get_subscriptions_by_plan, get_user_subscription,
+    initialize_subscription_storage, set_retry_config, store_plan, store_subscription,
 };
 
 // Re-export lifecycle functions
 pub use lifecycle::{
-    cancel_subscription, change_tier, create_plan, days_until_billing, get_subscription_status,
-    is_subscription_active, pause_subscription, process_due_subscriptions, process_payment,
-    resume_subscription, retry_payment, subscribe,
+    cancel_scheduled_tier_change, cancel_subscription, change_tier, create_plan,
+    days_until_billing, get_effective_retry_config, get_subscription_status,
+    is_subscription_active, list_all_plans, list_guild_plans, pause_subscription,
+    process_due_subscriptions, process_payment, resume_subscription, retry_payment,
+    set_plan_billing_anchor, set_plan_cancellation_policy, set_plan_max_subscribers,
+    set_platform_fee, set_proration_rounding_mode, set_retry_schedule, subscribe,
+    update_plan_proration_basis, update_plan_retry_config, update_retry_config,
+    update_subscription_retry_config,
 };
 
 #[cfg(test)]
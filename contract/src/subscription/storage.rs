@@ -1,5 +1,6 @@
 ﻿use crate::subscription::types::{
-    BillingCycle, MembershipTier, RetryConfig, RevenueRecord, Subscription, SubscriptionPlan,
+    BillingCycle, MembershipTier, PlatformFeeConfig, ProrationBasis, ProrationRounding,
+    RetryConfig, RevenueRecord, Subscription, SubscriptionPlan, SubscriptionStatus,
 };
 use soroban_sdk::{contracttype, Address, Env, Map, Vec};
 
@@ -28,6 +29,20 @@ pub enum SubscriptionStorageKey {
     GuildRevenue(u64, u64),
     /// Retry configuration
     RetryConfig,
+    /// Platform fee configuration
+    PlatformFeeConfig,
+    /// Rounding mode for tier-change proration
+    ProrationRounding,
+    /// Delays between payment retry attempts
+    RetryBackoffSchedule,
+    /// Subscriptions currently holding a slot on a plan: plan_id -> u32
+    PlanSubscriberCount(u64),
+    /// Retry configuration override for a plan's subscriptions
+    PlanRetryConfig(u64),
+    /// Retry configuration override for a single subscription
+    SubscriptionRetryConfig(u64),
+    /// Unit a plan's tier changes are prorated in: plan_id -> ProrationBasis
+    PlanProrationBasis(u64),
 }
 
 /// Initialize subscription storage
@@ -280,6 +295,103 @@ pub fn set_retry_config(env: &Env, config: &RetryConfig) {
         .set(&SubscriptionStorageKey::RetryConfig, config);
 }
 
+/// Get a plan's retry configuration override
+pub fn get_plan_retry_config(env: &Env, plan_id: u64) -> Option<RetryConfig> {
+    env.storage()
+        .persistent()
+        .get(&SubscriptionStorageKey::PlanRetryConfig(plan_id))
+}
+
+/// Set or clear a plan's retry configuration override
+pub fn set_plan_retry_config(env: &Env, plan_id: u64, config: &Option<RetryConfig>) {
+    let key = SubscriptionStorageKey::PlanRetryConfig(plan_id);
+    match config {
+        Some(config) => env.storage().persistent().set(&key, config),
+        None => env.storage().persistent().remove(&key),
+    }
+}
+
+/// Get a subscription's retry configuration override
+pub fn get_subscription_retry_config(env: &Env, subscription_id: u64) -> Option<RetryConfig> {
+    env.storage()
+        .persistent()
+        .get(&SubscriptionStorageKey::SubscriptionRetryConfig(
+            subscription_id,
+        ))
+}
+
+/// Set or clear a subscription's retry configuration override
+pub fn set_subscription_retry_config(
+    env: &Env,
+    subscription_id: u64,
+    config: &Option<RetryConfig>,
+) {
+    let key = SubscriptionStorageKey::SubscriptionRetryConfig(subscription_id);
+    match config {
+        Some(config) => env.storage().persistent().set(&key, config),
+        None => env.storage().persistent().remove(&key),
+    }
+}
+
+/// Get platform fee configuration (None when no fee is configured)
+pub fn get_platform_fee_config(env: &Env) -> Option<PlatformFeeConfig> {
+    env.storage()
+        .persistent()
+        .get(&SubscriptionStorageKey::PlatformFeeConfig)
+}
+
+/// Update platform fee configuration
+pub fn set_platform_fee_config(env: &Env, config: &PlatformFeeConfig) {
+    env.storage()
+        .persistent()
+        .set(&SubscriptionStorageKey::PlatformFeeConfig, config);
+}
+
+/// Get the proration rounding mode (defaults to `Floor`)
+pub fn get_proration_rounding(env: &Env) -> ProrationRounding {
+    env.storage()
+        .persistent()
+        .get(&SubscriptionStorageKey::ProrationRounding)
+        .unwrap_or(ProrationRounding::Floor)
+}
+
+/// Update the proration rounding mode
+pub fn set_proration_rounding(env: &Env, mode: &ProrationRounding) {
+    env.storage()
+        .persistent()
+        .set(&SubscriptionStorageKey::ProrationRounding, mode);
+}
+
+/// Get a plan's proration basis (defaults to `Daily`)
+pub fn get_plan_proration_basis(env: &Env, plan_id: u64) -> ProrationBasis {
+    env.storage()
+        .persistent()
+        .get(&SubscriptionStorageKey::PlanProrationBasis(plan_id))
+        .unwrap_or(ProrationBasis::Daily)
+}
+
+/// Update a plan's proration basis
+pub fn set_plan_proration_basis(env: &Env, plan_id: u64, basis: &ProrationBasis) {
+    env.storage()
+        .persistent()
+        .set(&SubscriptionStorageKey::PlanProrationBasis(plan_id), basis);
+}
+
+/// Get the retry backoff schedule (empty when the exponential default applies)
+pub fn get_retry_backoff_schedule(env: &Env) -> Vec<u64> {
+    env.storage()
+        .persistent()
+        .get(&SubscriptionStorageKey::RetryBackoffSchedule)
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+/// Update the retry backoff schedule
+pub fn set_retry_backoff_schedule(env: &Env, schedule: &Vec<u64>) {
+    env.storage()
+        .persistent()
+        .set(&SubscriptionStorageKey::RetryBackoffSchedule, schedule);
+}
+
 /// Get all plans (for platform-wide queries)
 pub fn get_all_plans(env: &Env, limit: u32) -> Vec<SubscriptionPlan> {
     let next_id = env
@@ -329,3 +441,33 @@ pub fn get_subscriptions_by_plan(env: &Env, plan_id: u64, limit: u32) -> Vec<Sub
 
     subscriptions
 }
+
+/// Number of uncancelled subscriptions on a plan. Plans whose subscriptions
+/// predate the stored count are scanned.
+pub fn get_plan_subscriber_count(env: &Env, plan_id: u64) -> u32 {
+    env.storage()
+        .persistent()
+        .get(&SubscriptionStorageKey::PlanSubscriberCount(plan_id))
+        .unwrap_or_else(|| {
+            get_subscriptions_by_plan(env, plan_id, u32::MAX)
+                .iter()
+                .filter(|s| s.status != SubscriptionStatus::Cancelled)
+                .count() as u32
+        })
+}
+
+/// Adjust a plan's subscriber count by one slot taken or freed. Call before
+/// storing the subscription's new state, so a plan with no stored count yet
+/// is scanned as it stood.
+pub fn update_plan_subscriber_count(env: &Env, plan_id: u64, taken: bool) {
+    let count = get_plan_subscriber_count(env, plan_id);
+    let count = if taken {
+        count + 1
+    } else {
+        count.saturating_sub(1)
+    };
+    env.storage().persistent().set(
+        &SubscriptionStorageKey::PlanSubscriberCount(plan_id),
+        &count,
+    );
+}
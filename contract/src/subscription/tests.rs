@@ -1,11 +1,11 @@
-﻿use crate::subscription::storage;
-use crate::subscription::types::{
-    BillingCycle, MembershipTier, RetryConfig, RevenueRecord, Subscription, SubscriptionPlan,
-    SubscriptionStatus,
+﻿use crate::subscription::types::{
+    BillingCycle, MembershipTier, ProrationBasis, ProrationRounding, RetryConfig,
+    RetryConfigSource, RevenueRecord, Subscription, SubscriptionPlan, SubscriptionStatus,
 };
+use crate::subscription::{lifecycle, storage};
 use crate::{StellarGuildsContract, StellarGuildsContractClient};
 use soroban_sdk::testutils::{Address as _, Ledger, LedgerInfo};
-use soroban_sdk::{Address, Env, String, Vec};
+use soroban_sdk::{token, Address, Env, String, Vec};
 
 fn setup_env() -> Env {
     let env = Env::default();
@@ -673,6 +673,9 @@ fn test_subscription_storage_indexes_and_revenue_queries() {
             benefits: benefits.clone(),
             created_by: creator.clone(),
             created_at: 1,
+            cancel_at_period_end: false,
+            billing_anchor: None,
+            max_subscribers: None,
         };
         let plan_2 = SubscriptionPlan {
             id: plan_id_2,
@@ -687,6 +690,9 @@ fn test_subscription_storage_indexes_and_revenue_queries() {
             benefits,
             created_by: creator.clone(),
             created_at: 2,
+            cancel_at_period_end: false,
+            billing_anchor: None,
+            max_subscribers: None,
         };
 
         storage::store_plan(&env, &plan_1);
@@ -715,6 +721,9 @@ fn test_subscription_storage_indexes_and_revenue_queries() {
             auto_renew: true,
             cancelled_at: None,
             cancellation_reason: empty_reason.clone(),
+            first_cycle_amount: None,
+            next_retry_at: None,
+            pending_plan_id: None,
         };
         storage::store_subscription(&env, &subscription);
         storage::store_user_subscription(&env, &subscriber, 77, subscription_id);
@@ -821,3 +830,849 @@ fn test_payment_processing_and_grace_period_cleanup() {
     );
     assert!(!client.is_subscription_active(&subscription_id));
 }
+
+fn create_token_plan(
+    env: &Env,
+    client: &StellarGuildsContractClient,
+    creator: &Address,
+    guild_id: u64,
+    price: i128,
+    token: &Address,
+) -> u64 {
+    client.create_subscription_plan(
+        &guild_id,
+        &String::from_str(env, "Token Plan"),
+        &String::from_str(env, "Token plan description"),
+        &MembershipTier::Standard,
+        &price,
+        &Some(token.clone()),
+        &BillingCycle::Monthly,
+        &Vec::new(env),
+        creator,
+    )
+}
+
+#[test]
+fn test_platform_fee_routed_to_collector() {
+    let env = setup_env();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let contract_id = env.register_contract(None, StellarGuildsContract);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+    client.initialize(&admin);
+    let creator = Address::generate(&env);
+    let subscriber = Address::generate(&env);
+    let collector = Address::generate(&env);
+
+    let token_addr = env
+        .register_stellar_asset_contract_v2(creator.clone())
+        .address();
+    token::StellarAssetClient::new(&env, &token_addr).mint(&subscriber, &10_000);
+
+    // 2.5% platform fee
+    client.set_subscription_platform_fee(&250, &collector, &admin);
+    let config = client.get_subscription_platform_fee().unwrap();
+    assert_eq!(config.fee_bps, 250);
+    assert_eq!(config.collector, collector);
+
+    set_ledger_timestamp(&env, 5_000_000);
+    let plan_id = create_token_plan(&env, &client, &creator, 9, 1_000, &token_addr);
+    let subscription_id = client.subscribe(&plan_id, &subscriber, &true);
+    assert!(client.process_subscription_payment(&subscription_id));
+
+    let token_client = token::TokenClient::new(&env, &token_addr);
+    assert_eq!(token_client.balance(&collector), 25);
+    assert_eq!(token_client.balance(&contract_id), 975);
+
+    let period_start = 5_000_000 - BillingCycle::Monthly.duration_seconds();
+    env.as_contract(&contract_id, || {
+        let records = storage::get_guild_revenue_records(&env, 9, period_start);
+        assert_eq!(records.len(), 1);
+        assert_eq!(records.get(0).unwrap().amount, 975);
+    });
+}
+
+#[test]
+fn test_zero_platform_fee_preserves_full_revenue() {
+    let env = setup_env();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let contract_id = env.register_contract(None, StellarGuildsContract);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+    client.initialize(&admin);
+    let creator = Address::generate(&env);
+    let subscriber = Address::generate(&env);
+    let collector = Address::generate(&env);
+
+    let token_addr = env
+        .register_stellar_asset_contract_v2(creator.clone())
+        .address();
+    token::StellarAssetClient::new(&env, &token_addr).mint(&subscriber, &10_000);
+
+    client.set_subscription_platform_fee(&0, &collector, &admin);
+
+    set_ledger_timestamp(&env, 5_000_000);
+    let plan_id = create_token_plan(&env, &client, &creator, 9, 1_000, &token_addr);
+    let subscription_id = client.subscribe(&plan_id, &subscriber, &true);
+    assert!(client.process_subscription_payment(&subscription_id));
+
+    let token_client = token::TokenClient::new(&env, &token_addr);
+    assert_eq!(token_client.balance(&collector), 0);
+    assert_eq!(token_client.balance(&contract_id), 1_000);
+
+    let period_start = 5_000_000 - BillingCycle::Monthly.duration_seconds();
+    env.as_contract(&contract_id, || {
+        let records = storage::get_guild_revenue_records(&env, 9, period_start);
+        assert_eq!(records.get(0).unwrap().amount, 1_000);
+    });
+}
+
+#[test]
+#[should_panic(expected = "set_platform_fee error")]
+fn test_platform_fee_above_100_percent_rejected() {
+    let env = setup_env();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let contract_id = env.register_contract(None, StellarGuildsContract);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+    client.initialize(&admin);
+
+    client.set_subscription_platform_fee(&10_001, &Address::generate(&env), &admin);
+}
+
+#[test]
+fn test_cancel_at_period_end_keeps_benefits_until_cycle_end() {
+    let env = setup_env();
+    set_ledger_timestamp(&env, 1_000);
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+    let creator = Address::generate(&env);
+    let subscriber = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    let plan_id = create_test_plan(
+        &env,
+        &client,
+        &creator,
+        1,
+        MembershipTier::Standard,
+        1000,
+        BillingCycle::Monthly,
+    );
+    assert!(client.set_plan_cancellation_policy(&plan_id, &true, &creator));
+
+    let subscription_id = client.subscribe(&plan_id, &subscriber, &true);
+    let next_billing_at = client.get_subscription(&subscription_id).next_billing_at;
+
+    let reason = Some(String::from_str(&env, "Moving on"));
+    assert!(client.cancel_subscription(&subscription_id, &subscriber, &reason));
+
+    let subscription = client.get_subscription(&subscription_id);
+    assert_eq!(subscription.status, SubscriptionStatus::Active);
+    assert_eq!(subscription.ends_at, Some(next_billing_at));
+    assert!(client.is_subscription_active(&subscription_id));
+
+    // A second cancel while one is pending is rejected
+    let result = client.try_cancel_subscription(&subscription_id, &subscriber, &None);
+    assert!(result.is_err());
+
+    set_ledger_timestamp(&env, next_billing_at);
+    assert_eq!(client.process_due_subscriptions(&10), 1);
+
+    let subscription = client.get_subscription(&subscription_id);
+    assert_eq!(subscription.status, SubscriptionStatus::Cancelled);
+    assert_eq!(subscription.cancelled_at, Some(next_billing_at));
+    assert!(subscription.last_payment_at.is_none());
+}
+
+#[test]
+fn test_resume_withdraws_scheduled_cancellation() {
+    let env = setup_env();
+    set_ledger_timestamp(&env, 1_000);
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+    let creator = Address::generate(&env);
+    let subscriber = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    let plan_id = create_test_plan(
+        &env,
+        &client,
+        &creator,
+        1,
+        MembershipTier::Standard,
+        1000,
+        BillingCycle::Monthly,
+    );
+    assert!(client.set_plan_cancellation_policy(&plan_id, &true, &creator));
+
+    let subscription_id = client.subscribe(&plan_id, &subscriber, &true);
+    let next_billing_at = client.get_subscription(&subscription_id).next_billing_at;
+    assert!(client.cancel_subscription(&subscription_id, &subscriber, &None));
+
+    assert!(client.resume_subscription(&subscription_id, &subscriber));
+    let subscription = client.get_subscription(&subscription_id);
+    assert_eq!(subscription.status, SubscriptionStatus::Active);
+    assert_eq!(subscription.ends_at, None);
+    assert_eq!(subscription.next_billing_at, next_billing_at);
+
+    // Renews normally at period end instead of cancelling
+    set_ledger_timestamp(&env, next_billing_at);
+    assert_eq!(client.process_due_subscriptions(&10), 1);
+    let subscription = client.get_subscription(&subscription_id);
+    assert_eq!(subscription.status, SubscriptionStatus::Active);
+    assert_eq!(subscription.last_payment_at, Some(next_billing_at));
+}
+
+#[test]
+#[should_panic(expected = "set_plan_cancellation_policy error")]
+fn test_set_plan_cancellation_policy_requires_creator() {
+    let env = setup_env();
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+    let creator = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    let plan_id = create_test_plan(
+        &env,
+        &client,
+        &creator,
+        1,
+        MembershipTier::Standard,
+        1000,
+        BillingCycle::Monthly,
+    );
+    client.set_plan_cancellation_policy(&plan_id, &true, &Address::generate(&env));
+}
+
+#[test]
+fn test_plan_browsing_entrypoints() {
+    let env = setup_env();
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+    let creator = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    let basic = create_test_plan(
+        &env,
+        &client,
+        &creator,
+        7,
+        MembershipTier::Basic,
+        100,
+        BillingCycle::Monthly,
+    );
+    let premium = create_test_plan(
+        &env,
+        &client,
+        &creator,
+        7,
+        MembershipTier::Premium,
+        500,
+        BillingCycle::Monthly,
+    );
+    let platform = create_test_plan(
+        &env,
+        &client,
+        &creator,
+        0,
+        MembershipTier::Standard,
+        200,
+        BillingCycle::Annually,
+    );
+
+    // Deactivated plans remain visible
+    env.as_contract(&contract_id, || {
+        let mut plan = storage::get_plan(&env, premium).unwrap();
+        plan.is_active = false;
+        storage::store_plan(&env, &plan);
+    });
+
+    let plan = client.get_subscription_plan(&basic);
+    assert_eq!(plan.tier, MembershipTier::Basic);
+    assert_eq!(plan.price, 100);
+
+    let guild_plans = client.get_guild_plans(&7);
+    assert_eq!(guild_plans.len(), 2);
+    assert_eq!(guild_plans.get(0).unwrap().id, basic);
+    assert_eq!(guild_plans.get(1).unwrap().id, premium);
+    assert!(!guild_plans.get(1).unwrap().is_active);
+
+    let all_plans = client.get_all_plans(&10);
+    assert_eq!(all_plans.len(), 3);
+    assert_eq!(all_plans.get(2).unwrap().id, platform);
+    assert_eq!(client.get_all_plans(&2).len(), 2);
+    assert!(client.get_guild_plans(&99).is_empty());
+}
+
+#[test]
+#[should_panic(expected = "plan not found")]
+fn test_get_subscription_plan_not_found() {
+    let env = setup_env();
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    client.get_subscription_plan(&42);
+}
+
+#[test]
+fn test_anchored_plan_bills_subscribers_together_with_prorated_first_cycle() {
+    let env = setup_env();
+    env.mock_all_auths();
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+    let creator = Address::generate(&env);
+    let early = Address::generate(&env);
+    let late = Address::generate(&env);
+
+    let token_addr = env
+        .register_stellar_asset_contract_v2(creator.clone())
+        .address();
+    let asset = token::StellarAssetClient::new(&env, &token_addr);
+    asset.mint(&early, &10_000);
+    asset.mint(&late, &10_000);
+    let token_client = token::TokenClient::new(&env, &token_addr);
+
+    let day = 24 * 60 * 60;
+    let anchor = 1_000_000u64;
+    let boundary = anchor + 30 * day;
+
+    set_ledger_timestamp(&env, anchor + 10 * day);
+    let plan_id = create_token_plan(&env, &client, &creator, 9, 3_000, &token_addr);
+    assert!(client.set_plan_billing_anchor(&plan_id, &Some(anchor), &creator));
+
+    let early_sub = client.subscribe(&plan_id, &early, &true);
+    set_ledger_timestamp(&env, anchor + 25 * day);
+    let late_sub = client.subscribe(&plan_id, &late, &true);
+
+    // Both subscribers bill on the same boundary
+    assert_eq!(
+        client.get_subscription(&early_sub).next_billing_at,
+        boundary
+    );
+    assert_eq!(client.get_subscription(&late_sub).next_billing_at, boundary);
+
+    // First charge covers only the partial cycle up to the boundary
+    assert!(client.process_subscription_payment(&early_sub));
+    assert!(client.process_subscription_payment(&late_sub));
+    assert_eq!(token_client.balance(&early), 10_000 - 2_000);
+    assert_eq!(token_client.balance(&late), 10_000 - 500);
+    assert_eq!(client.get_subscription(&late_sub).next_billing_at, boundary);
+
+    // Renewals charge the full price and stay on the anchor
+    set_ledger_timestamp(&env, boundary + 5);
+    assert_eq!(client.process_due_subscriptions(&10), 2);
+    assert_eq!(token_client.balance(&early), 10_000 - 5_000);
+    assert_eq!(token_client.balance(&late), 10_000 - 3_500);
+    let renewed = client.get_subscription(&late_sub);
+    assert_eq!(renewed.next_billing_at, boundary + 30 * day);
+    assert_eq!(renewed.last_payment_amount, Some(3_000));
+
+    // Only the plan creator may anchor billing
+    let other = Address::generate(&env);
+    assert!(client
+        .try_set_plan_billing_anchor(&plan_id, &None, &other)
+        .is_err());
+
+    // An anchor more than one cycle out would overcharge the first cycle
+    let now = boundary + 5;
+    assert!(client
+        .try_set_plan_billing_anchor(&plan_id, &Some(now + 30 * day + 1), &creator)
+        .is_err());
+    assert!(client.set_plan_billing_anchor(&plan_id, &Some(now + 30 * day), &creator));
+    let full_cycle_sub = client.subscribe(&plan_id, &Address::generate(&env), &false);
+    assert_eq!(
+        client.get_subscription(&full_cycle_sub).next_billing_at,
+        now + 30 * day
+    );
+}
+
+#[test]
+fn test_tier_change_proration_rounding() {
+    let env = setup_env();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let contract_id = env.register_contract(None, StellarGuildsContract);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+    client.initialize(&admin);
+    let creator = Address::generate(&env);
+    let day = 24 * 60 * 60;
+
+    let basic = create_test_plan(
+        &env,
+        &client,
+        &creator,
+        1,
+        MembershipTier::Basic,
+        100,
+        BillingCycle::Monthly,
+    );
+    let premium = create_test_plan(
+        &env,
+        &client,
+        &creator,
+        1,
+        MembershipTier::Premium,
+        300,
+        BillingCycle::Monthly,
+    );
+
+    set_ledger_timestamp(&env, 1_000_000);
+    let (alice, bob, carol) = (
+        Address::generate(&env),
+        Address::generate(&env),
+        Address::generate(&env),
+    );
+    let same_day = client.subscribe(&basic, &alice, &true);
+    let floored = client.subscribe(&basic, &bob, &true);
+    let ceiled = client.subscribe(&basic, &carol, &true);
+
+    // Same-day change charges the full price difference
+    set_ledger_timestamp(&env, 1_000_000 + 3_600);
+    let proration = client.change_subscription_tier(&same_day, &premium, &true, &alice);
+    assert_eq!(proration, 200);
+
+    // 20 of 30 days remain: 200 * 20 / 30 = 133.33
+    set_ledger_timestamp(&env, 1_000_000 + 10 * day);
+    assert_eq!(client.get_proration_rounding(), ProrationRounding::Floor);
+    let proration = client.change_subscription_tier(&floored, &premium, &true, &bob);
+    assert_eq!(proration, 133);
+
+    client.set_proration_rounding(&ProrationRounding::Ceil, &admin);
+    let proration = client.change_subscription_tier(&ceiled, &premium, &true, &carol);
+    assert_eq!(proration, 134);
+}
+
+#[test]
+fn test_retry_backoff_schedule_spaces_out_retries() {
+    let env = setup_env();
+    env.mock_all_auths();
+    // past one billing cycle so revenue periods start after zero
+    set_ledger_timestamp(&env, 5_000_000);
+
+    let contract_id = env.register_contract(None, StellarGuildsContract);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+
+    // Without a schedule the retry config's exponential backoff applies
+    let retry_delay = |attempt: u32| {
+        env.as_contract(&contract_id, || {
+            lifecycle::backoff_delay(&env, &storage::get_retry_config(&env), attempt)
+        })
+    };
+    assert_eq!(retry_delay(1), 86_400);
+    assert_eq!(retry_delay(3), 4 * 86_400);
+
+    let mut zero = Vec::new(&env);
+    zero.push_back(0u64);
+    assert!(client
+        .try_set_retry_backoff_schedule(&zero, &admin)
+        .is_err());
+
+    let mut schedule = Vec::new(&env);
+    schedule.push_back(100u64);
+    schedule.push_back(500u64);
+    assert!(client.set_retry_backoff_schedule(&schedule, &admin));
+    assert_eq!(client.get_retry_backoff_schedule(), schedule);
+
+    // The last delay repeats for attempts beyond the schedule
+    assert_eq!(retry_delay(1), 100);
+    assert_eq!(retry_delay(2), 500);
+    assert_eq!(retry_delay(5), 500);
+
+    let creator = Address::generate(&env);
+    let subscriber = Address::generate(&env);
+    let plan_id = create_test_plan(
+        &env,
+        &client,
+        &creator,
+        42,
+        MembershipTier::Standard,
+        1000,
+        BillingCycle::Monthly,
+    );
+    let subscription_id = client.subscribe(&plan_id, &subscriber, &true);
+
+    env.as_contract(&contract_id, || {
+        let mut grace = storage::get_subscription(&env, subscription_id).unwrap();
+        grace.status = SubscriptionStatus::GracePeriod;
+        grace.grace_period_ends_at = Some(5_100_000);
+        grace.failed_payment_count = 1;
+        grace.next_retry_at = Some(5_000_100);
+        storage::store_subscription(&env, &grace);
+    });
+
+    // Neither path retries before the delay has elapsed
+    assert!(client
+        .try_retry_subscription_payment(&subscription_id)
+        .is_err());
+    assert_eq!(client.process_due_subscriptions(&10), 0);
+
+    set_ledger_timestamp(&env, 5_000_100);
+    assert_eq!(client.process_due_subscriptions(&10), 1);
+    let recovered = client.get_subscription(&subscription_id);
+    assert_eq!(recovered.status, SubscriptionStatus::Active);
+    assert_eq!(recovered.failed_payment_count, 0);
+    assert_eq!(recovered.next_retry_at, None);
+}
+
+#[test]
+fn test_scheduled_tier_change_waits_for_billing_and_can_be_cancelled() {
+    let env = setup_env();
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+    let creator = Address::generate(&env);
+    let subscriber = Address::generate(&env);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let premium_plan_id = create_test_plan(
+        &env,
+        &client,
+        &creator,
+        1,
+        MembershipTier::Premium,
+        2000,
+        BillingCycle::Monthly,
+    );
+    let basic_plan_id = create_test_plan(
+        &env,
+        &client,
+        &creator,
+        1,
+        MembershipTier::Basic,
+        500,
+        BillingCycle::Monthly,
+    );
+    let subscription_id = client.subscribe(&premium_plan_id, &subscriber, &true);
+
+    // A deferred downgrade leaves the current plan in force
+    let proration =
+        client.change_subscription_tier(&subscription_id, &basic_plan_id, &false, &subscriber);
+    assert_eq!(proration, 0);
+    let subscription = client.get_subscription(&subscription_id);
+    assert_eq!(subscription.plan_id, premium_plan_id);
+    assert_eq!(subscription.current_tier, MembershipTier::Premium);
+    assert_eq!(subscription.pending_plan_id, Some(basic_plan_id));
+
+    let stranger = Address::generate(&env);
+    assert!(client
+        .try_cancel_scheduled_tier_change(&subscription_id, &stranger)
+        .is_err());
+    assert!(client.cancel_scheduled_tier_change(&subscription_id, &subscriber));
+    assert_eq!(
+        client.get_subscription(&subscription_id).pending_plan_id,
+        None
+    );
+    assert!(client
+        .try_cancel_scheduled_tier_change(&subscription_id, &subscriber)
+        .is_err());
+
+    // Rescheduled, the change lands when the next cycle is billed
+    client.change_subscription_tier(&subscription_id, &basic_plan_id, &false, &subscriber);
+    let next_billing_at = client.get_subscription(&subscription_id).next_billing_at;
+    set_ledger_timestamp(&env, next_billing_at);
+    assert_eq!(client.process_due_subscriptions(&10u32), 1);
+
+    let subscription = client.get_subscription(&subscription_id);
+    assert_eq!(subscription.plan_id, basic_plan_id);
+    assert_eq!(subscription.current_tier, MembershipTier::Basic);
+    assert_eq!(subscription.pending_plan_id, None);
+    assert_eq!(subscription.last_payment_amount, Some(500));
+}
+
+#[test]
+fn test_deferred_downgrade_bills_old_price_until_cycle_ends() {
+    let env = setup_env();
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+    let creator = Address::generate(&env);
+    let subscriber = Address::generate(&env);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let premium_plan_id = create_test_plan(
+        &env,
+        &client,
+        &creator,
+        1,
+        MembershipTier::Premium,
+        2000,
+        BillingCycle::Monthly,
+    );
+    let basic_plan_id = create_test_plan(
+        &env,
+        &client,
+        &creator,
+        1,
+        MembershipTier::Basic,
+        500,
+        BillingCycle::Monthly,
+    );
+    let subscription_id = client.subscribe(&premium_plan_id, &subscriber, &true);
+    client.change_subscription_tier(&subscription_id, &basic_plan_id, &false, &subscriber);
+
+    // A payment taken within the current cycle is still at the premium price
+    let first_boundary = client.get_subscription(&subscription_id).next_billing_at;
+    set_ledger_timestamp(&env, first_boundary - 1);
+    assert!(client.process_subscription_payment(&subscription_id));
+    let subscription = client.get_subscription(&subscription_id);
+    assert_eq!(subscription.last_payment_amount, Some(2000));
+    assert_eq!(subscription.plan_id, premium_plan_id);
+    assert_eq!(subscription.pending_plan_id, Some(basic_plan_id));
+
+    // The next cycle is billed at the downgraded price
+    set_ledger_timestamp(&env, subscription.next_billing_at);
+    assert!(client.process_subscription_payment(&subscription_id));
+    let subscription = client.get_subscription(&subscription_id);
+    assert_eq!(subscription.last_payment_amount, Some(500));
+    assert_eq!(subscription.plan_id, basic_plan_id);
+    assert_eq!(subscription.current_tier, MembershipTier::Basic);
+    assert_eq!(subscription.pending_plan_id, None);
+
+    // An immediate upgrade still applies at once, with proration
+    let proration =
+        client.change_subscription_tier(&subscription_id, &premium_plan_id, &true, &subscriber);
+    assert!(proration > 0);
+    assert_eq!(
+        client.get_subscription(&subscription_id).plan_id,
+        premium_plan_id
+    );
+}
+
+#[test]
+fn test_plan_subscriber_cap() {
+    let env = setup_env();
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+    let creator = Address::generate(&env);
+    let first = Address::generate(&env);
+    let second = Address::generate(&env);
+    let third = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    let founding = create_test_plan(
+        &env,
+        &client,
+        &creator,
+        1,
+        MembershipTier::Premium,
+        1000,
+        BillingCycle::Monthly,
+    );
+    let basic = create_test_plan(
+        &env,
+        &client,
+        &creator,
+        1,
+        MembershipTier::Basic,
+        100,
+        BillingCycle::Monthly,
+    );
+    assert!(client.set_plan_max_subscribers(&founding, &Some(2), &creator));
+    assert!(client
+        .try_set_plan_max_subscribers(&founding, &Some(5), &first)
+        .is_err());
+
+    let first_sub = client.subscribe(&founding, &first, &true);
+    client.subscribe(&founding, &second, &true);
+    assert_eq!(client.get_plan_subscriber_count(&founding), 2);
+
+    // Full: neither a new subscription nor an upgrade can take a slot
+    assert!(client.try_subscribe(&founding, &third, &true).is_err());
+    let third_sub = client.subscribe(&basic, &third, &true);
+    assert!(client
+        .try_change_subscription_tier(&third_sub, &founding, &true, &third)
+        .is_err());
+
+    // Cancelling frees the slot
+    client.cancel_subscription(&first_sub, &first, &None);
+    assert_eq!(client.get_plan_subscriber_count(&founding), 1);
+    client.change_subscription_tier(&third_sub, &founding, &true, &third);
+    assert_eq!(client.get_plan_subscriber_count(&founding), 2);
+    assert_eq!(client.get_plan_subscriber_count(&basic), 0);
+}
+
+#[test]
+fn test_plan_subscriber_count_missing_for_existing_subscriptions() {
+    let env = setup_env();
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+    let creator = Address::generate(&env);
+    let first = Address::generate(&env);
+    let second = Address::generate(&env);
+    let third = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    let plan_id = create_test_plan(
+        &env,
+        &client,
+        &creator,
+        1,
+        MembershipTier::Premium,
+        1000,
+        BillingCycle::Monthly,
+    );
+    let first_sub = client.subscribe(&plan_id, &first, &true);
+    client.subscribe(&plan_id, &second, &true);
+
+    // subscriptions taken before the count was kept
+    env.as_contract(&contract_id, || {
+        env.storage()
+            .persistent()
+            .remove(&storage::SubscriptionStorageKey::PlanSubscriberCount(plan_id));
+    });
+    assert_eq!(client.get_plan_subscriber_count(&plan_id), 2);
+
+    assert!(client.set_plan_max_subscribers(&plan_id, &Some(2), &creator));
+    assert!(client.try_subscribe(&plan_id, &third, &true).is_err());
+
+    client.cancel_subscription(&first_sub, &first, &None);
+    assert_eq!(client.get_plan_subscriber_count(&plan_id), 1);
+    client.subscribe(&plan_id, &third, &true);
+    assert_eq!(client.get_plan_subscriber_count(&plan_id), 2);
+}
+
+#[test]
+fn test_effective_retry_config_precedence() {
+    let env = setup_env();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, StellarGuildsContract);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+    let creator = Address::generate(&env);
+    let subscriber = Address::generate(&env);
+
+    // An unknown subscription reports the global config, not an error
+    let unknown = client.get_effective_retry_config(&99);
+    assert_eq!(unknown.config, RetryConfig::default());
+    assert_eq!(unknown.source, RetryConfigSource::Global);
+
+    let global = RetryConfig {
+        max_retries: 5,
+        initial_delay_seconds: 3_600,
+        backoff_multiplier: 3,
+        grace_period_seconds: 86_400,
+    };
+    assert!(client.try_set_retry_config(&global, &creator).is_err());
+    assert!(client
+        .try_set_retry_config(
+            &RetryConfig {
+                max_retries: 0,
+                ..global.clone()
+            },
+            &admin
+        )
+        .is_err());
+    client.set_retry_config(&global, &admin);
+    assert_eq!(client.get_retry_config(), global);
+
+    let plan_id = create_test_plan(
+        &env,
+        &client,
+        &creator,
+        1,
+        MembershipTier::Standard,
+        1000,
+        BillingCycle::Monthly,
+    );
+    let subscription_id = client.subscribe(&plan_id, &subscriber, &true);
+    assert_eq!(
+        client.get_effective_retry_config(&subscription_id).config,
+        global
+    );
+
+    let plan_config = RetryConfig {
+        max_retries: 2,
+        ..global.clone()
+    };
+    assert!(client
+        .try_set_plan_retry_config(&plan_id, &Some(plan_config.clone()), &subscriber)
+        .is_err());
+    client.set_plan_retry_config(&plan_id, &Some(plan_config.clone()), &creator);
+    let effective = client.get_effective_retry_config(&subscription_id);
+    assert_eq!(effective.config, plan_config);
+    assert_eq!(effective.source, RetryConfigSource::Plan);
+
+    let own_config = RetryConfig {
+        max_retries: 1,
+        ..global.clone()
+    };
+    client.set_subscription_retry_config(&subscription_id, &Some(own_config.clone()), &creator);
+    let effective = client.get_effective_retry_config(&subscription_id);
+    assert_eq!(effective.config, own_config);
+    assert_eq!(effective.source, RetryConfigSource::Subscription);
+
+    // Clearing an override falls back one level
+    client.set_subscription_retry_config(&subscription_id, &None, &creator);
+    assert_eq!(
+        client.get_effective_retry_config(&subscription_id).source,
+        RetryConfigSource::Plan
+    );
+}
+
+#[test]
+fn test_tier_change_seconds_proration_basis() {
+    let env = setup_env();
+    env.mock_all_auths();
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+    let creator = Address::generate(&env);
+    let hour = 60 * 60;
+
+    let basic = create_test_plan(
+        &env,
+        &client,
+        &creator,
+        1,
+        MembershipTier::Basic,
+        100,
+        BillingCycle::Weekly,
+    );
+    let premium = create_test_plan(
+        &env,
+        &client,
+        &creator,
+        1,
+        MembershipTier::Premium,
+        800,
+        BillingCycle::Weekly,
+    );
+    assert_eq!(
+        client.get_plan_proration_basis(&basic),
+        ProrationBasis::Daily
+    );
+    assert!(client
+        .try_set_plan_proration_basis(&basic, &ProrationBasis::Seconds, &Address::generate(&env))
+        .is_err());
+
+    set_ledger_timestamp(&env, 1_000_000);
+    let (alice, bob) = (Address::generate(&env), Address::generate(&env));
+    let daily = client.subscribe(&basic, &alice, &true);
+    let seconds = client.subscribe(&basic, &bob, &true);
+
+    // 12 hours of the week remain: a whole day by the daily basis, 700 / 7
+    set_ledger_timestamp(&env, 1_000_000 + 7 * 24 * hour - 12 * hour);
+    let proration = client.change_subscription_tier(&daily, &premium, &true, &alice);
+    assert_eq!(proration, 100);
+
+    // Exactly 700 * 43_200 / 604_800 by the seconds basis
+    client.set_plan_proration_basis(&basic, &ProrationBasis::Seconds, &creator);
+    assert_eq!(
+        client.get_plan_proration_basis(&basic),
+        ProrationBasis::Seconds
+    );
+    let proration = client.change_subscription_tier(&seconds, &premium, &true, &bob);
+    assert_eq!(proration, 50);
+}
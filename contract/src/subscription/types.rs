@@ -24,6 +24,17 @@ impl BillingCycle {
             BillingCycle::Annually => 365 * 24 * 60 * 60, // 31,536,000 seconds
         }
     }
+
+    /// Get the first cycle boundary aligned to `anchor` that falls strictly
+    /// after `now`
+    pub fn next_boundary(&self, anchor: u64, now: u64) -> u64 {
+        if now < anchor {
+            return anchor;
+        }
+        let duration = self.duration_seconds();
+        let elapsed_cycles = (now - anchor) / duration;
+        anchor + (elapsed_cycles + 1) * duration
+    }
 }
 
 /// Status of a subscription
@@ -84,6 +95,13 @@ pub struct SubscriptionPlan {
     pub created_by: Address,
     /// Creation timestamp
     pub created_at: u64,
+    /// Whether cancellation waits until the end of the paid billing period
+    pub cancel_at_period_end: bool,
+    /// Cycle boundary all subscribers bill on (None bills from each
+    /// subscription's own start)
+    pub billing_anchor: Option<u64>,
+    /// Most subscriptions the plan holds at once (None = unlimited)
+    pub max_subscribers: Option<u32>,
 }
 
 /// A user subscription instance
@@ -102,7 +120,8 @@ pub struct Subscription {
     pub current_tier: MembershipTier,
     /// Start timestamp
     pub started_at: u64,
-    /// End timestamp (for fixed-term subscriptions)
+    /// End timestamp (for fixed-term subscriptions or a cancellation
+    /// scheduled for the end of the current period)
     pub ends_at: Option<u64>,
     /// Next billing timestamp
     pub next_billing_at: u64,
@@ -120,6 +139,14 @@ pub struct Subscription {
     pub cancelled_at: Option<u64>,
     /// Cancellation reason
     pub cancellation_reason: Option<String>,
+    /// Prorated charge for a partial first cycle on an anchored plan,
+    /// consumed by the first successful payment
+    pub first_cycle_amount: Option<i128>,
+    /// Earliest time the next payment retry may run while in grace period
+    pub next_retry_at: Option<u64>,
+    /// Plan switched to at the next billing cycle, if a deferred tier change
+    /// is scheduled
+    pub pending_plan_id: Option<u64>,
 }
 
 /// Payment retry configuration
@@ -147,6 +174,36 @@ impl Default for RetryConfig {
     }
 }
 
+/// Where an effective retry configuration came from
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RetryConfigSource {
+    /// No override applies; the global configuration is used
+    Global = 0,
+    /// The subscription's plan overrides the global configuration
+    Plan = 1,
+    /// The subscription carries its own override
+    Subscription = 2,
+}
+
+/// Retry configuration that applies to a subscription, and its origin
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EffectiveRetryConfig {
+    pub config: RetryConfig,
+    pub source: RetryConfigSource,
+}
+
+/// Platform fee configuration applied to every successful subscription charge
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PlatformFeeConfig {
+    /// Fee in basis points (1/100 of a percent, max 10_000)
+    pub fee_bps: u32,
+    /// Address receiving the platform fee
+    pub collector: Address,
+}
+
 /// Revenue tracking record
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -175,6 +232,41 @@ pub struct RevenueRecord {
     pub retry_attempt: u32,
 }
 
+/// Rounding applied to prorated tier-change amounts
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ProrationRounding {
+    /// Round toward negative infinity
+    Floor,
+    /// Round toward positive infinity
+    Ceil,
+    /// Round to the nearest unit, halves rounding up
+    Nearest,
+}
+
+impl ProrationRounding {
+    /// Divide `numerator` by a positive `denominator` using this rounding mode
+    pub fn divide(&self, numerator: i128, denominator: i128) -> i128 {
+        let quotient = numerator.div_euclid(denominator);
+        let remainder = numerator.rem_euclid(denominator);
+        match self {
+            ProrationRounding::Floor => quotient,
+            ProrationRounding::Ceil => quotient + i128::from(remainder > 0),
+            ProrationRounding::Nearest => quotient + i128::from(remainder * 2 >= denominator),
+        }
+    }
+}
+
+/// Unit a plan's tier-change proration is measured in
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ProrationBasis {
+    /// Whole days, a partially used day counting as remaining
+    Daily,
+    /// Exact seconds remaining in the cycle
+    Seconds,
+}
+
 /// Proration calculation result
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -238,6 +330,20 @@ pub enum SubscriptionError {
     InvalidState = 15,
     /// Revenue record not found
     RevenueRecordNotFound = 16,
+    /// Invalid platform fee configuration
+    InvalidFeeConfig = 17,
+    /// The backoff delay before the next retry has not elapsed
+    RetryNotDue = 18,
+    /// Retry backoff schedule contains a zero delay
+    InvalidRetrySchedule = 19,
+    /// No deferred tier change is scheduled
+    NoScheduledTierChange = 20,
+    /// The plan has reached its subscriber cap
+    PlanFull = 21,
+    /// Retry configuration allows no attempts or has a zero backoff multiplier
+    InvalidRetryConfig = 22,
+    /// Billing anchor lies more than one billing cycle in the future
+    InvalidBillingAnchor = 23,
 }
 
 /// Event emitted when a subscription plan is created
@@ -291,6 +397,17 @@ pub struct SubscriptionCancelledEvent {
     pub reason: Option<String>,
 }
 
+/// Event emitted when a cancellation is scheduled for the end of the
+/// current billing period
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CancellationScheduledEvent {
+    pub subscription_id: u64,
+    pub cancelled_by: Address,
+    pub reason: Option<String>,
+    pub effective_at: u64,
+}
+
 /// Event emitted when a subscription tier is changed
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -301,6 +418,43 @@ pub struct TierChangedEvent {
     pub proration_amount: i128,
 }
 
+/// Event emitted when a deferred tier change is scheduled or withdrawn
+/// (`new_plan_id` is `None` once withdrawn)
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TierChangeScheduledEvent {
+    pub subscription_id: u64,
+    pub new_plan_id: Option<u64>,
+    pub effective_at: u64,
+}
+
+/// Event emitted when the payment retry delays are replaced
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RetryScheduleUpdatedEvent {
+    /// Delays in seconds; empty restores exponential backoff
+    pub schedule: Vec<u64>,
+}
+
+/// Event emitted when a plan's retry configuration override changes
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PlanRetryConfigUpdatedEvent {
+    pub plan_id: u64,
+    /// Configuration now applied to the plan: its override, or the global
+    /// configuration once the override is cleared
+    pub retry_config: EffectiveRetryConfig,
+}
+
+/// Event emitted when a subscription's retry configuration override changes
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SubscriptionRetryConfigUpdatedEvent {
+    pub subscription_id: u64,
+    /// Configuration now applied to the subscription and its origin
+    pub retry_config: EffectiveRetryConfig,
+}
+
 /// Event emitted when revenue is recorded
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -311,3 +465,13 @@ pub struct RevenueRecordedEvent {
     pub amount: i128,
     pub paid_at: u64,
 }
+
+/// Event emitted when a platform fee is collected from a subscription payment
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PlatformFeeCollectedEvent {
+    pub subscription_id: u64,
+    pub collector: Address,
+    pub fee_amount: i128,
+    pub net_amount: i128,
+}
@@ -1,25 +1,33 @@
+use crate::events::critical::record_critical_event;
 use crate::events::emit::emit_event;
 use crate::events::topics::{
-    ACT_APPROVED, ACT_CREATED, ACT_EXECUTED, ACT_FUNDED, ACT_GRANTED, ACT_PAUSED, ACT_PROPOSED,
-    ACT_RESUMED, ACT_UPDATED, MOD_TREASURY,
+    ACT_ACCEPTED, ACT_APPROVED, ACT_BATCH_PROPOSED, ACT_CREATED, ACT_EXECUTED, ACT_FUNDED,
+    ACT_GRANTED, ACT_PAUSED, ACT_PROPOSED, ACT_RECLAIMED, ACT_RESUMED, ACT_UPDATED, MOD_TREASURY,
 };
-use soroban_sdk::{token::Client as TokenClient, Address, Env, String, Vec};
+use soroban_sdk::{panic_with_error, token::Client as TokenClient, Address, Env, String, Vec};
 
 use crate::analytics::storage::store_snapshot;
 use crate::analytics::types::TreasurySnapshot;
 
 use crate::treasury::multisig::{
-    add_approval, assert_signer, ensure_is_signer, expire_if_needed, required_approvals_for_tx,
-    validate_threshold, TX_EXPIRY_SECONDS,
+    add_approval, approvals_remaining, assert_signer, can_sign, compute_required_approvals,
+    ensure_is_signer, expire_if_needed, required_approvals_for_tx, validate_threshold,
+    ACCEPTANCE_WINDOW_SECONDS,
 };
 use crate::treasury::storage::{
-    get_allowance, get_budget, get_next_treasury_id, get_next_tx_id, get_treasury,
-    get_treasury_transactions, store_allowance, store_budget, store_transaction, store_treasury,
+    get_allowance, get_batch_recipients, get_budget, get_budget_categories, get_next_treasury_id,
+    get_next_tx_id, get_pause_policy, get_token_allowlist, get_treasury, get_treasury_count,
+    get_treasury_transactions, get_tx_expiry, is_guild_role_linked, record_budget_allocation,
+    record_budget_spend, set_batch_recipients, set_guild_role_linked, set_token_allowlist,
+    set_tx_expiry, store_allowance, store_budget, store_pause_policy, store_transaction,
+    store_treasury, MAX_ALLOWLISTED_TOKENS,
 };
 use crate::treasury::types::{
-    Allowance, Budget, DepositEvent, EmergencyPauseEvent, Transaction, TransactionApprovedEvent,
+    Allowance, BatchWithdrawalProposedEvent, Budget, DepositEvent, EmergencyPauseEvent,
+    PausePolicy, TokenAllowlistUpdatedEvent, Transaction, TransactionApprovedEvent,
     TransactionExecutedEvent, TransactionStatus, TransactionType, Treasury, TreasuryError,
-    TreasuryInitializedEvent, WithdrawalProposedEvent,
+    TreasuryInitializedEvent, TreasurySummary, TreasuryThresholdUpdatedEvent,
+    WithdrawalAcceptedEvent, WithdrawalProposedEvent, WithdrawalReclaimedEvent,
 };
 
 pub fn initialize_treasury(
@@ -79,8 +87,59 @@ pub fn deposit(
     depositor: Address,
     amount: i128,
     token: Option<Address>,
+) -> bool {
+    deposit_with_source(env, treasury_id, depositor, amount, token, None)
+}
+
+/// Deposit funds attributed to a `source` (e.g. "subscription revenue",
+/// "grant"), recorded as the deposit transaction's reason. `None` keeps the
+/// generic "deposit" reason.
+pub fn deposit_with_source(
+    env: &Env,
+    treasury_id: u64,
+    depositor: Address,
+    amount: i128,
+    token: Option<Address>,
+    source: Option<String>,
 ) -> bool {
     depositor.require_auth();
+    if let Some(ref s) = source {
+        if s.is_empty() {
+            panic!("deposit source cannot be empty");
+        }
+    }
+    if let Some(ref token_addr) = token {
+        let allowlist = get_token_allowlist(env, treasury_id);
+        if !allowlist.is_empty() && !allowlist.contains(token_addr) {
+            panic!("token is not on the treasury allowlist");
+        }
+    }
+    credit_deposit_from(env, treasury_id, depositor, amount, token, true, source)
+}
+
+/// Credit a deposit to a treasury. With `pull_funds` unset the tokens are
+/// already held by the contract (e.g. forfeited proposal deposits) and only
+/// the treasury accounting is updated.
+pub(crate) fn credit_deposit(
+    env: &Env,
+    treasury_id: u64,
+    depositor: Address,
+    amount: i128,
+    token: Option<Address>,
+    pull_funds: bool,
+) -> bool {
+    credit_deposit_from(env, treasury_id, depositor, amount, token, pull_funds, None)
+}
+
+fn credit_deposit_from(
+    env: &Env,
+    treasury_id: u64,
+    depositor: Address,
+    amount: i128,
+    token: Option<Address>,
+    pull_funds: bool,
+    source: Option<String>,
+) -> bool {
     if amount <= 0 {
         panic!("amount must be positive");
     }
@@ -92,8 +151,10 @@ pub fn deposit(
 
     match token {
         Some(ref token_addr) => {
-            let client = TokenClient::new(env, token_addr);
-            client.transfer(&depositor, &env.current_contract_address(), &amount);
+            if pull_funds {
+                let client = TokenClient::new(env, token_addr);
+                client.transfer(&depositor, &env.current_contract_address(), &amount);
+            }
 
             let mut balances = treasury.token_balances.clone();
             let current = balances.get(token_addr.clone()).unwrap_or(0i128);
@@ -120,10 +181,17 @@ pub fn deposit(
         recipient: Some(env.current_contract_address()),
         proposer: depositor.clone(),
         approvals: Vec::new(env),
+        required_approvals: compute_required_approvals(
+            &treasury,
+            &TransactionType::Deposit,
+            amount,
+        ),
         status: TransactionStatus::Executed,
         created_at: now,
         expires_at: now,
-        reason: String::from_str(env, "deposit"),
+        reason: source.unwrap_or_else(|| String::from_str(env, "deposit")),
+        requires_acceptance: false,
+        acceptance_deadline: 0,
     };
     store_transaction(env, &tx);
 
@@ -149,6 +217,204 @@ pub fn propose_withdrawal(
     amount: i128,
     token: Option<Address>,
     reason: String,
+) -> u64 {
+    propose_withdrawal_internal(
+        env,
+        treasury_id,
+        proposer,
+        recipient,
+        amount,
+        token,
+        reason,
+        TransactionType::Withdrawal,
+        false,
+    )
+}
+
+/// Propose, approve and execute a withdrawal in one call. Only available when
+/// the treasury's approval threshold is 1, so the signer's own approval is the
+/// only one any transaction could require.
+pub fn withdraw_single_sig(
+    env: &Env,
+    treasury_id: u64,
+    recipient: Address,
+    amount: i128,
+    token: Option<Address>,
+    reason: String,
+    signer: Address,
+) -> u64 {
+    let treasury = get_treasury(env, treasury_id).expect("treasury not found");
+    if treasury.approval_threshold > 1 {
+        panic!("treasury requires multiple approvals");
+    }
+
+    let tx_id = propose_withdrawal_internal(
+        env,
+        treasury_id,
+        signer.clone(),
+        recipient,
+        amount,
+        token,
+        reason,
+        TransactionType::Withdrawal,
+        false,
+    );
+
+    // The proposer's approval is recorded at proposal time and meets the threshold
+    let mut tx = crate::treasury::storage::get_transaction(env, tx_id).expect("tx not found");
+    tx.status = TransactionStatus::Approved;
+    store_transaction(env, &tx);
+
+    let event = TransactionApprovedEvent {
+        treasury_id,
+        tx_id,
+        approver: signer.clone(),
+    };
+    emit_event(env, MOD_TREASURY, ACT_APPROVED, event);
+
+    execute_approved_transaction(env, tx_id, signer);
+    tx_id
+}
+
+/// Propose a withdrawal whose funds are held on execution until the recipient
+/// accepts them via `accept_withdrawal`. Unaccepted funds can be reclaimed
+/// after `ACCEPTANCE_WINDOW_SECONDS`.
+pub fn propose_claimable_withdrawal(
+    env: &Env,
+    treasury_id: u64,
+    proposer: Address,
+    recipient: Address,
+    amount: i128,
+    token: Option<Address>,
+    reason: String,
+) -> u64 {
+    propose_withdrawal_internal(
+        env,
+        treasury_id,
+        proposer,
+        recipient,
+        amount,
+        token,
+        reason,
+        TransactionType::Withdrawal,
+        true,
+    )
+}
+
+/// Propose moving treasury funds into bounty escrow. The transaction goes
+/// through the usual approval threshold and is executed by
+/// `bounty::create_and_fund_bounty_from_treasury`, never directly.
+pub fn propose_bounty_funding(
+    env: &Env,
+    treasury_id: u64,
+    proposer: Address,
+    amount: i128,
+    token: Address,
+    reason: String,
+) -> u64 {
+    propose_withdrawal_internal(
+        env,
+        treasury_id,
+        proposer,
+        env.current_contract_address(),
+        amount,
+        Some(token),
+        reason,
+        TransactionType::BountyFunding,
+        false,
+    )
+}
+
+/// Most payees a single batch withdrawal may carry
+pub const MAX_BATCH_RECIPIENTS: u32 = 50;
+
+/// Propose one withdrawal paying several recipients, e.g. a payroll run.
+/// It needs approvals like a single withdrawal of the batch total, and on
+/// execution either every recipient is paid or none is.
+pub fn propose_batch_withdrawal(
+    env: &Env,
+    treasury_id: u64,
+    recipients: Vec<(Address, i128)>,
+    token: Option<Address>,
+    reason: String,
+    proposer: Address,
+) -> u64 {
+    if recipients.is_empty() {
+        panic!("batch must have at least one recipient");
+    }
+    if recipients.len() > MAX_BATCH_RECIPIENTS {
+        panic!("too many batch recipients");
+    }
+    let mut total: i128 = 0;
+    for (_, amount) in recipients.iter() {
+        if amount <= 0 {
+            panic!("amount must be positive");
+        }
+        total = total.checked_add(amount).expect("batch total overflow");
+    }
+
+    let treasury = get_treasury(env, treasury_id).expect("treasury not found");
+    if treasury.paused {
+        panic!("treasury is paused");
+    }
+
+    assert_signer(env, &treasury, &proposer);
+
+    let tx_id = get_next_tx_id(env);
+    let now = env.ledger().timestamp();
+    let mut approvals = Vec::new(env);
+    approvals.push_back(proposer.clone());
+
+    let tx_type = TransactionType::BatchWithdrawal;
+    let tx = Transaction {
+        id: tx_id,
+        treasury_id,
+        required_approvals: compute_required_approvals(&treasury, &tx_type, total),
+        tx_type,
+        amount: total,
+        token: token.clone(),
+        recipient: None,
+        proposer: proposer.clone(),
+        approvals,
+        status: TransactionStatus::Pending,
+        created_at: now,
+        expires_at: now + get_tx_expiry(env, treasury_id),
+        reason,
+        requires_acceptance: false,
+        acceptance_deadline: 0,
+    };
+    store_transaction(env, &tx);
+    set_batch_recipients(env, tx_id, &recipients);
+
+    let event = BatchWithdrawalProposedEvent {
+        treasury_id,
+        tx_id,
+        proposer,
+        recipients,
+        total,
+        token,
+    };
+    emit_event(env, MOD_TREASURY, ACT_BATCH_PROPOSED, event);
+
+    tx_id
+}
+
+/// Payees of a batch withdrawal (empty for any other transaction)
+pub fn get_batch_withdrawal_recipients(env: &Env, tx_id: u64) -> Vec<(Address, i128)> {
+    get_batch_recipients(env, tx_id)
+}
+
+#[allow(clippy::too_many_arguments)]
+fn propose_withdrawal_internal(
+    env: &Env,
+    treasury_id: u64,
+    proposer: Address,
+    recipient: Address,
+    amount: i128,
+    token: Option<Address>,
+    reason: String,
+    tx_type: TransactionType,
+    requires_acceptance: bool,
 ) -> u64 {
     if amount <= 0 {
         panic!("amount must be positive");
@@ -169,16 +435,19 @@ pub fn propose_withdrawal(
     let tx = Transaction {
         id: tx_id,
         treasury_id,
-        tx_type: TransactionType::Withdrawal,
+        tx_type: tx_type.clone(),
         amount,
         token: token.clone(),
         recipient: Some(recipient.clone()),
         proposer: proposer.clone(),
         approvals,
+        required_approvals: compute_required_approvals(&treasury, &tx_type, amount),
         status: TransactionStatus::Pending,
         created_at: now,
-        expires_at: now + TX_EXPIRY_SECONDS,
+        expires_at: now + get_tx_expiry(env, treasury_id),
         reason,
+        requires_acceptance,
+        acceptance_deadline: 0,
     };
     store_transaction(env, &tx);
 
@@ -195,6 +464,37 @@ pub fn propose_withdrawal(
     tx_id
 }
 
+/// Whether `execute_transaction` would accept the transaction right now:
+/// it has reached its approval threshold and has not expired. Bounty funding
+/// executes through bounty creation and is never directly executable.
+pub fn tx_can_execute(env: &Env, tx_id: u64) -> bool {
+    let mut tx = crate::treasury::storage::get_transaction(env, tx_id).expect("tx not found");
+    expire_if_needed(&mut tx, env.ledger().timestamp());
+    let treasury = get_treasury(env, tx.treasury_id).expect("treasury not found");
+    !blocked_by_pause(&treasury, &tx)
+        && tx.status == TransactionStatus::Approved
+        && !matches!(
+            tx.tx_type,
+            TransactionType::BountyFunding | TransactionType::Deposit
+        )
+}
+
+/// Approvals the transaction still needs, applying lazy expiry and the
+/// threshold fixed at proposal time (including the high-value rule).
+/// Returns `TX_APPROVALS_DEAD` for transactions that can never execute.
+pub fn tx_approvals_remaining(env: &Env, tx_id: u64) -> u32 {
+    let tx = crate::treasury::storage::get_transaction(env, tx_id).expect("tx not found");
+    approvals_remaining(&tx, env.ledger().timestamp())
+}
+
+/// A single transaction as it stands now: a pending or approved transaction
+/// past its expiry reads as `Expired` even before anything sweeps it.
+pub fn get_transaction(env: &Env, tx_id: u64) -> Transaction {
+    let mut tx = crate::treasury::storage::get_transaction(env, tx_id).expect("tx not found");
+    expire_if_needed(&mut tx, env.ledger().timestamp());
+    tx
+}
+
 pub fn approve_transaction(env: &Env, tx_id: u64, approver: Address) -> bool {
     approver.require_auth();
 
@@ -205,15 +505,19 @@ pub fn approve_transaction(env: &Env, tx_id: u64, approver: Address) -> bool {
     expire_if_needed(&mut tx, now);
     if matches!(
         tx.status,
-        TransactionStatus::Rejected | TransactionStatus::Executed | TransactionStatus::Expired
+        TransactionStatus::Rejected
+            | TransactionStatus::Executed
+            | TransactionStatus::Expired
+            | TransactionStatus::AwaitingAcceptance
+            | TransactionStatus::Reclaimed
     ) {
         panic!("transaction not approvable");
     }
 
-    ensure_is_signer(&treasury, &approver);
+    ensure_is_signer(env, &treasury, &approver);
     add_approval(&mut tx, &approver);
 
-    let required = required_approvals_for_tx(&treasury, &tx);
+    let required = required_approvals_for_tx(&tx);
     if (tx.approvals.len() as u32) >= required {
         tx.status = TransactionStatus::Approved;
     }
@@ -249,11 +553,7 @@ fn enforce_budget(
         period_start: now,
     });
 
-    if budget.period_seconds > 0 && now >= budget.period_start.saturating_add(budget.period_seconds)
-    {
-        budget.period_start = now;
-        budget.spent_amount = 0;
-    }
+    budget.ensure_period_current(env);
 
     if budget.allocated_amount > 0 && budget.spent_amount + amount > budget.allocated_amount {
         return Err(TreasuryError::BudgetExceeded);
@@ -261,6 +561,53 @@ fn enforce_budget(
 
     budget.spent_amount += amount;
     store_budget(env, &budget);
+    record_budget_spend(env, treasury_id, category, amount);
+    Ok(())
+}
+
+/// Give back `amount` charged to `category` at `spent_at` by `enforce_budget`.
+/// The budget's `spent_amount` only drops if the charge fell in its current
+/// period; the spend log always gets an offsetting entry.
+fn refund_budget(env: &Env, treasury_id: u64, category: &String, amount: i128, spent_at: u64) {
+    if amount <= 0 {
+        return;
+    }
+    if let Some(mut budget) = get_budget(env, treasury_id, category) {
+        budget.ensure_period_current(env);
+        if spent_at >= budget.period_start {
+            budget.spent_amount = (budget.spent_amount - amount).max(0);
+        }
+        store_budget(env, &budget);
+    }
+    record_budget_spend(env, treasury_id, category, -amount);
+}
+
+/// Deduct `amount` from the treasury's recorded balance of `token`.
+fn debit_balance(
+    treasury: &mut Treasury,
+    token: &Option<Address>,
+    amount: i128,
+) -> Result<(), TreasuryError> {
+    match token {
+        Some(token_addr) => {
+            let current = treasury
+                .token_balances
+                .get(token_addr.clone())
+                .unwrap_or(0i128);
+            if current < amount {
+                return Err(TreasuryError::InsufficientTokenBalance);
+            }
+            treasury
+                .token_balances
+                .set(token_addr.clone(), current - amount);
+        }
+        None => {
+            if treasury.balance_xlm < amount {
+                return Err(TreasuryError::InsufficientXlmBalance);
+            }
+            treasury.balance_xlm -= amount;
+        }
+    }
     Ok(())
 }
 
@@ -288,19 +635,75 @@ fn enforce_allowance(
 
     if let Some(treasury) = get_treasury(env, treasury_id) {
         let result = crate::allowance::spend(env, admin, &treasury.owner, token, amount, op_type);
-        match result {
-            Ok(_) => return Ok(()),
-            Err(crate::allowance::AllowanceError::NotFound) => return Ok(()),
-            Err(_) => return Err(TreasuryError::AllowanceExceeded),
+        return token_allowance_result(result);
+    }
+
+    Ok(())
+}
+
+/// Read-only counterpart of `enforce_allowance`: whether it would succeed.
+fn check_allowance(
+    env: &Env,
+    treasury_id: u64,
+    admin: &Address,
+    token: &Option<Address>,
+    amount: i128,
+    op_type: &crate::allowance::AllowanceOperation,
+) -> Result<(), TreasuryError> {
+    if amount <= 0 {
+        return Ok(());
+    }
+
+    if let Some(mut allowance) = get_allowance(env, treasury_id, admin, token) {
+        allowance.ensure_period_current(env);
+        if allowance.remaining_amount < amount {
+            return Err(TreasuryError::AllowanceExceeded);
         }
+        return Ok(());
+    }
+
+    if let Some(treasury) = get_treasury(env, treasury_id) {
+        let result =
+            crate::allowance::check_spend(env, admin, &treasury.owner, token, amount, op_type);
+        return token_allowance_result(result);
     }
 
     Ok(())
 }
 
+/// Map a token-allowance outcome to the treasury's view: a missing token
+/// allowance places no limit.
+fn token_allowance_result<T>(
+    result: Result<T, crate::allowance::AllowanceError>,
+) -> Result<(), TreasuryError> {
+    match result {
+        Ok(_) | Err(crate::allowance::AllowanceError::NotFound) => Ok(()),
+        Err(crate::allowance::AllowanceError::OperationNotPermitted) => {
+            Err(TreasuryError::AllowanceOperationNotPermitted)
+        }
+        Err(_) => Err(TreasuryError::AllowanceExceeded),
+    }
+}
+
 pub fn execute_transaction(env: &Env, tx_id: u64, executor: Address) -> bool {
     executor.require_auth();
 
+    let tx = crate::treasury::storage::get_transaction(env, tx_id).expect("tx not found");
+    if tx.tx_type == TransactionType::BountyFunding {
+        panic!("bounty funding executes through bounty creation");
+    }
+
+    execute_approved_transaction(env, tx_id, executor);
+    true
+}
+
+/// Execute an approved transaction on behalf of `executor`, who must be a
+/// signer and must already have authorized the call.
+pub(crate) fn execute_approved_transaction(
+    env: &Env,
+    tx_id: u64,
+    executor: Address,
+) -> Transaction {
     let mut tx = crate::treasury::storage::get_transaction(env, tx_id).expect("tx not found");
     let mut treasury = get_treasury(env, tx.treasury_id).expect("treasury not found");
 
@@ -308,17 +711,20 @@ pub fn execute_transaction(env: &Env, tx_id: u64, executor: Address) -> bool {
     expire_if_needed(&mut tx, now);
     if matches!(
         tx.status,
-        TransactionStatus::Rejected | TransactionStatus::Executed | TransactionStatus::Expired
+        TransactionStatus::Rejected
+            | TransactionStatus::Executed
+            | TransactionStatus::Expired
+            | TransactionStatus::AwaitingAcceptance
+            | TransactionStatus::Reclaimed
     ) {
         panic!("transaction not executable");
     }
 
-    // when paused, only already-approved transactions may be executed
-    if treasury.paused && !matches!(tx.status, TransactionStatus::Approved) {
+    if blocked_by_pause(&treasury, &tx) {
         panic!("treasury is paused");
     }
 
-    ensure_is_signer(&treasury, &executor);
+    ensure_is_signer(env, &treasury, &executor);
 
     if !matches!(tx.status, TransactionStatus::Approved) {
         panic!("transaction must be approved");
@@ -338,13 +744,10 @@ pub fn execute_transaction(env: &Env, tx_id: u64, executor: Address) -> bool {
                 _ => String::from_str(env, "other"),
             };
 
-            // Convert Result to panic with expected error message
-            // This creates a proper contract error (all panics in Soroban become contract errors)
-            // while maintaining the expected error message for test compatibility
-            enforce_budget(env, tx.treasury_id, &category, tx.amount).unwrap_or_else(|e| match e {
-                TreasuryError::BudgetExceeded => panic!("budget exceeded"),
-                TreasuryError::AllowanceExceeded => panic!("allowance exceeded"),
-            });
+            // Failures surface as typed `TreasuryError` contract errors so
+            // clients can tell budget, allowance and balance shortfalls apart
+            enforce_budget(env, tx.treasury_id, &category, tx.amount)
+                .unwrap_or_else(|e| panic_with_error!(env, e));
 
             let op_type = match tx.tx_type {
                 TransactionType::Withdrawal => crate::allowance::AllowanceOperation::Withdrawal,
@@ -365,30 +768,51 @@ pub fn execute_transaction(env: &Env, tx_id: u64, executor: Address) -> bool {
                 tx.amount,
                 &op_type,
             )
-            .unwrap_or_else(|e| match e {
-                TreasuryError::BudgetExceeded => panic!("budget exceeded"),
-                TreasuryError::AllowanceExceeded => panic!("allowance exceeded"),
-            });
-
-            match tx.token {
-                Some(ref token_addr) => {
-                    let client = TokenClient::new(env, token_addr);
-
-                    let mut balances = treasury.token_balances.clone();
-                    let current = balances.get(token_addr.clone()).unwrap_or(0i128);
-                    if current < tx.amount {
-                        panic!("insufficient treasury balance");
-                    }
-                    balances.set(token_addr.clone(), current - tx.amount);
-                    treasury.token_balances = balances;
+            .unwrap_or_else(|e| panic_with_error!(env, e));
+
+            debit_balance(&mut treasury, &tx.token, tx.amount)
+                .unwrap_or_else(|e| panic_with_error!(env, e));
+
+            // funds awaiting acceptance stay in the contract until claimed, as
+            // do funds moved into bounty escrow
+            let leaves_contract =
+                !tx.requires_acceptance && recipient != env.current_contract_address();
+            if let (Some(token_addr), true) = (&tx.token, leaves_contract) {
+                let client = TokenClient::new(env, token_addr);
+                client.transfer(&env.current_contract_address(), &recipient, &tx.amount);
+            }
 
-                    client.transfer(&env.current_contract_address(), &recipient, &tx.amount);
-                }
-                None => {
-                    if treasury.balance_xlm < tx.amount {
-                        panic!("insufficient XLM balance");
-                    }
-                    treasury.balance_xlm -= tx.amount;
+            if !tx.requires_acceptance {
+                treasury.total_withdrawals += tx.amount;
+            }
+            store_treasury(env, &treasury);
+        }
+        TransactionType::BatchWithdrawal => {
+            // Every check runs against the batch total before any payee is
+            // paid, so a shortfall reverts the whole batch
+            enforce_budget(
+                env,
+                tx.treasury_id,
+                &String::from_str(env, "withdrawal"),
+                tx.amount,
+            )
+            .unwrap_or_else(|e| panic_with_error!(env, e));
+            enforce_allowance(
+                env,
+                tx.treasury_id,
+                &executor,
+                &tx.token,
+                tx.amount,
+                &crate::allowance::AllowanceOperation::Withdrawal,
+            )
+            .unwrap_or_else(|e| panic_with_error!(env, e));
+            debit_balance(&mut treasury, &tx.token, tx.amount)
+                .unwrap_or_else(|e| panic_with_error!(env, e));
+
+            if let Some(token_addr) = &tx.token {
+                let client = TokenClient::new(env, token_addr);
+                for (recipient, amount) in get_batch_recipients(env, tx_id).iter() {
+                    client.transfer(&env.current_contract_address(), &recipient, &amount);
                 }
             }
 
@@ -401,9 +825,21 @@ pub fn execute_transaction(env: &Env, tx_id: u64, executor: Address) -> bool {
         TransactionType::AllowanceGrant => {
             // state-only; execution path not used in this simplified version
         }
+        TransactionType::EmergencyPause | TransactionType::EmergencyUnpause => {
+            set_paused(
+                env,
+                &mut treasury,
+                tx.tx_type == TransactionType::EmergencyPause,
+            );
+        }
     }
 
-    tx.status = TransactionStatus::Executed;
+    if tx.requires_acceptance {
+        tx.status = TransactionStatus::AwaitingAcceptance;
+        tx.acceptance_deadline = now.saturating_add(ACCEPTANCE_WINDOW_SECONDS);
+    } else {
+        tx.status = TransactionStatus::Executed;
+    }
     store_transaction(env, &tx);
 
     // Record analytics snapshot after execution
@@ -415,65 +851,136 @@ pub fn execute_transaction(env: &Env, tx_id: u64, executor: Address) -> bool {
         tx_id,
     };
     emit_event(env, MOD_TREASURY, ACT_EXECUTED, event);
+    record_critical_event(env, treasury.guild_id, MOD_TREASURY, ACT_EXECUTED, tx_id);
+
+    tx
+}
+
+/// Release a withdrawal held for acceptance to its designated recipient.
+pub fn accept_withdrawal(env: &Env, tx_id: u64, recipient: Address) -> bool {
+    recipient.require_auth();
+
+    let mut tx = crate::treasury::storage::get_transaction(env, tx_id).expect("tx not found");
+    if !matches!(tx.status, TransactionStatus::AwaitingAcceptance) {
+        panic!("transaction not awaiting acceptance");
+    }
+    if tx.recipient.as_ref() != Some(&recipient) {
+        panic!("only the recipient can accept");
+    }
+    if env.ledger().timestamp() > tx.acceptance_deadline {
+        panic!("acceptance window elapsed");
+    }
+
+    let mut treasury = get_treasury(env, tx.treasury_id).expect("treasury not found");
+    if let Some(ref token_addr) = tx.token {
+        let client = TokenClient::new(env, token_addr);
+        client.transfer(&env.current_contract_address(), &recipient, &tx.amount);
+    }
+    treasury.total_withdrawals += tx.amount;
+    store_treasury(env, &treasury);
+
+    tx.status = TransactionStatus::Executed;
+    store_transaction(env, &tx);
+
+    let event = WithdrawalAcceptedEvent {
+        treasury_id: tx.treasury_id,
+        tx_id,
+        recipient,
+        amount: tx.amount,
+    };
+    emit_event(env, MOD_TREASURY, ACT_ACCEPTED, event);
+
+    true
+}
+
+/// Return the funds of an unaccepted withdrawal to the treasury once the
+/// acceptance window has elapsed. Callable by any treasury signer.
+pub fn reclaim_withdrawal(env: &Env, tx_id: u64, caller: Address) -> bool {
+    caller.require_auth();
+
+    let mut tx = crate::treasury::storage::get_transaction(env, tx_id).expect("tx not found");
+    let mut treasury = get_treasury(env, tx.treasury_id).expect("treasury not found");
+    ensure_is_signer(env, &treasury, &caller);
+
+    if !matches!(tx.status, TransactionStatus::AwaitingAcceptance) {
+        panic!("transaction not awaiting acceptance");
+    }
+    if env.ledger().timestamp() <= tx.acceptance_deadline {
+        panic!("acceptance window still open");
+    }
+
+    match tx.token {
+        Some(ref token_addr) => {
+            let mut balances = treasury.token_balances.clone();
+            let current = balances.get(token_addr.clone()).unwrap_or(0i128);
+            balances.set(token_addr.clone(), current + tx.amount);
+            treasury.token_balances = balances;
+        }
+        None => {
+            treasury.balance_xlm += tx.amount;
+        }
+    }
+    store_treasury(env, &treasury);
+    record_snapshot(env, &treasury);
+
+    // The withdrawal was charged to the budget when executed, which is when
+    // its acceptance window opened
+    let spent_at = tx
+        .acceptance_deadline
+        .saturating_sub(ACCEPTANCE_WINDOW_SECONDS);
+    refund_budget(
+        env,
+        tx.treasury_id,
+        &String::from_str(env, "withdrawal"),
+        tx.amount,
+        spent_at,
+    );
+
+    tx.status = TransactionStatus::Reclaimed;
+    store_transaction(env, &tx);
+
+    let event = WithdrawalReclaimedEvent {
+        treasury_id: tx.treasury_id,
+        tx_id,
+        amount: tx.amount,
+    };
+    emit_event(env, MOD_TREASURY, ACT_RECLAIMED, event);
 
     true
 }
 
-pub fn execute_milestone_payment(
+/// Pay a milestone out of the treasury. A payment the treasury cannot make
+/// (paused, short of funds, over budget or allowance) is returned as an
+/// error rather than panicking, and nothing is written unless it goes through.
+pub fn try_execute_milestone_payment(
     env: &Env,
     treasury_id: u64,
     token: Option<Address>,
     recipient: Address,
     amount: i128,
-) -> bool {
+) -> Result<(), TreasuryError> {
     if amount <= 0 {
         panic!("amount must be positive");
     }
 
     let mut treasury = get_treasury(env, treasury_id).expect("treasury not found");
-    if treasury.paused {
-        panic!("treasury is paused");
-    }
+    check_milestone_payment(env, &treasury, &token, amount)?;
 
     // Budget enforcement under the "milestone" category
     let category = String::from_str(env, "milestone");
-    enforce_budget(env, treasury_id, &category, amount).unwrap_or_else(|e| match e {
-        TreasuryError::BudgetExceeded => panic!("budget exceeded"),
-        TreasuryError::AllowanceExceeded => panic!("allowance exceeded"),
-    });
+    enforce_budget(env, treasury_id, &category, amount)?;
 
     // Allowance enforcement (if any) keyed by current contract address;
     // if no allowance exists this is a no-op.
     let executor = env.current_contract_address();
     let op_type = crate::allowance::AllowanceOperation::MilestonePayment;
-    enforce_allowance(env, treasury_id, &executor, &token, amount, &op_type).unwrap_or_else(|e| {
-        match e {
-            TreasuryError::BudgetExceeded => panic!("budget exceeded"),
-            TreasuryError::AllowanceExceeded => panic!("allowance exceeded"),
-        }
-    });
+    enforce_allowance(env, treasury_id, &executor, &token, amount, &op_type)?;
 
     // Move funds from treasury to recipient
-    match token {
-        Some(ref token_addr) => {
-            let client = TokenClient::new(env, token_addr);
-
-            let mut balances = treasury.token_balances.clone();
-            let current = balances.get(token_addr.clone()).unwrap_or(0i128);
-            if current < amount {
-                panic!("insufficient treasury balance");
-            }
-            balances.set(token_addr.clone(), current - amount);
-            treasury.token_balances = balances;
-
-            client.transfer(&env.current_contract_address(), &recipient, &amount);
-        }
-        None => {
-            if treasury.balance_xlm < amount {
-                panic!("insufficient XLM balance");
-            }
-            treasury.balance_xlm -= amount;
-        }
+    debit_balance(&mut treasury, &token, amount)?;
+    if let Some(ref token_addr) = token {
+        let client = TokenClient::new(env, token_addr);
+        client.transfer(&env.current_contract_address(), &recipient, &amount);
     }
 
     treasury.total_withdrawals += amount;
@@ -491,17 +998,77 @@ pub fn execute_milestone_payment(
         recipient: Some(recipient),
         proposer: executor,
         approvals: Vec::new(env),
+        required_approvals: compute_required_approvals(
+            &treasury,
+            &TransactionType::MilestonePayment,
+            amount,
+        ),
         status: TransactionStatus::Executed,
         created_at: now,
         expires_at: now,
         reason: String::from_str(env, "milestone_payment"),
+        requires_acceptance: false,
+        acceptance_deadline: 0,
     };
     store_transaction(env, &tx);
 
     let event = TransactionExecutedEvent { treasury_id, tx_id };
     emit_event(env, MOD_TREASURY, ACT_EXECUTED, event);
 
-    true
+    Ok(())
+}
+
+/// Whether `try_execute_milestone_payment` would currently succeed for `amount`:
+/// the treasury is unpaused, holds enough of `token`, and both the
+/// "milestone" budget and any allowance have room.
+pub fn can_pay_milestone(
+    env: &Env,
+    treasury_id: u64,
+    token: &Option<Address>,
+    amount: i128,
+) -> bool {
+    if amount <= 0 {
+        return false;
+    }
+    match get_treasury(env, treasury_id) {
+        Some(treasury) => check_milestone_payment(env, &treasury, token, amount).is_ok(),
+        None => false,
+    }
+}
+
+/// Every check `try_execute_milestone_payment` makes before moving funds,
+/// without writing anything.
+fn check_milestone_payment(
+    env: &Env,
+    treasury: &Treasury,
+    token: &Option<Address>,
+    amount: i128,
+) -> Result<(), TreasuryError> {
+    if treasury.paused {
+        return Err(TreasuryError::TreasuryPaused);
+    }
+    let available = match token {
+        Some(token_addr) => treasury.token_balances.get(token_addr.clone()).unwrap_or(0),
+        None => treasury.balance_xlm,
+    };
+    if available < amount {
+        return Err(match token {
+            Some(_) => TreasuryError::InsufficientTokenBalance,
+            None => TreasuryError::InsufficientXlmBalance,
+        });
+    }
+
+    let category = String::from_str(env, "milestone");
+    if let Some(mut budget) = get_budget(env, treasury.id, &category) {
+        budget.ensure_period_current(env);
+        if budget.allocated_amount > 0 && budget.spent_amount + amount > budget.allocated_amount {
+            return Err(TreasuryError::BudgetExceeded);
+        }
+    }
+
+    let executor = env.current_contract_address();
+    let op_type = crate::allowance::AllowanceOperation::MilestonePayment;
+    check_allowance(env, treasury.id, &executor, token, amount, &op_type)
 }
 
 pub fn set_budget(
@@ -536,6 +1103,7 @@ pub fn set_budget(
 
     budget.allocated_amount = amount;
     store_budget(env, &budget);
+    record_budget_allocation(env, treasury_id, &category, amount);
 
     let event = crate::treasury::types::BudgetUpdatedEvent {
         treasury_id,
@@ -559,6 +1127,46 @@ pub fn get_balance(env: &Env, treasury_id: u64, token: Option<Address>) -> i128
     }
 }
 
+/// Upper bound on summaries returned by a single `get_treasuries_paginated` call
+pub const MAX_TREASURY_PAGE_SIZE: u32 = 50;
+
+/// List treasuries by ID starting at `start`, returning at most `limit`
+/// (capped at `MAX_TREASURY_PAGE_SIZE`) summaries.
+pub fn get_treasuries_paginated(env: &Env, start: u64, limit: u32) -> Vec<TreasurySummary> {
+    let limit = limit.min(MAX_TREASURY_PAGE_SIZE);
+    let mut result = Vec::new(env);
+    let count = get_treasury_count(env);
+    let mut id = start.max(1);
+    while id <= count && result.len() < limit {
+        if let Some(treasury) = get_treasury(env, id) {
+            result.push_back(TreasurySummary {
+                id: treasury.id,
+                guild_id: treasury.guild_id,
+                balance_xlm: treasury.balance_xlm,
+                total_deposits: treasury.total_deposits,
+                total_withdrawals: treasury.total_withdrawals,
+                signer_count: treasury.signers.len(),
+                paused: treasury.paused,
+            });
+        }
+        id += 1;
+    }
+    result
+}
+
+pub fn get_signers(env: &Env, treasury_id: u64) -> Vec<Address> {
+    get_treasury(env, treasury_id)
+        .expect("treasury not found")
+        .signers
+}
+
+pub fn is_signer(env: &Env, treasury_id: u64, address: &Address) -> bool {
+    match get_treasury(env, treasury_id) {
+        Some(treasury) => can_sign(env, &treasury, address),
+        None => false,
+    }
+}
+
 pub fn get_transaction_history(env: &Env, treasury_id: u64, limit: u32) -> Vec<Transaction> {
     let all = get_treasury_transactions(env, treasury_id);
     let len = all.len();
@@ -577,6 +1185,17 @@ pub fn get_transaction_history(env: &Env, treasury_id: u64, limit: u32) -> Vec<T
     result
 }
 
+/// Deposits into a treasury attributed to `source`, oldest first.
+pub fn get_deposits_by_source(env: &Env, treasury_id: u64, source: String) -> Vec<Transaction> {
+    let mut result = Vec::new(env);
+    for tx in get_treasury_transactions(env, treasury_id).iter() {
+        if tx.tx_type == TransactionType::Deposit && tx.reason == source {
+            result.push_back(tx);
+        }
+    }
+    result
+}
+
 pub fn grant_allowance(
     env: &Env,
     treasury_id: u64,
@@ -627,21 +1246,314 @@ pub fn grant_allowance(
     true
 }
 
+/// Change the approval and high-value thresholds of a treasury (owner only).
+/// Transactions already proposed keep the approval count they were created with.
+pub fn update_thresholds(
+    env: &Env,
+    treasury_id: u64,
+    caller: Address,
+    approval_threshold: u32,
+    high_value_threshold: i128,
+) -> bool {
+    caller.require_auth();
+
+    let mut treasury = get_treasury(env, treasury_id).expect("treasury not found");
+    if caller != treasury.owner {
+        panic!("only treasury owner can update thresholds");
+    }
+    validate_threshold(treasury.signers.len(), approval_threshold);
+    if high_value_threshold <= 0 {
+        panic!("high value threshold must be positive");
+    }
+
+    treasury.approval_threshold = approval_threshold;
+    treasury.high_value_threshold = high_value_threshold;
+    store_treasury(env, &treasury);
+
+    let event = TreasuryThresholdUpdatedEvent {
+        treasury_id,
+        approval_threshold,
+        high_value_threshold,
+    };
+    emit_event(env, MOD_TREASURY, ACT_UPDATED, event);
+
+    true
+}
+
+/// Pause or unpause a treasury with a single signer. Directions that the
+/// treasury's pause policy puts behind a quorum must go through
+/// `propose_pause_change` instead.
 pub fn emergency_pause(env: &Env, treasury_id: u64, signer: Address, paused: bool) -> bool {
     let mut treasury = get_treasury(env, treasury_id).expect("treasury not found");
     assert_signer(env, &treasury, &signer);
 
+    if requires_pause_quorum(&get_pause_policy(env, treasury_id), paused) {
+        panic!("pause change requires signer quorum");
+    }
+
+    set_paused(env, &mut treasury, paused);
+
+    true
+}
+
+/// Propose a pause or unpause as a treasury transaction needing the
+/// approval threshold. Proposing is allowed while the treasury is paused.
+pub fn propose_pause_change(env: &Env, treasury_id: u64, proposer: Address, paused: bool) -> u64 {
+    let treasury = get_treasury(env, treasury_id).expect("treasury not found");
+    assert_signer(env, &treasury, &proposer);
+
+    if treasury.paused == paused {
+        panic!("treasury already in requested pause state");
+    }
+
+    let (tx_type, reason) = if paused {
+        (TransactionType::EmergencyPause, "emergency pause")
+    } else {
+        (TransactionType::EmergencyUnpause, "emergency unpause")
+    };
+    let required_approvals = compute_required_approvals(&treasury, &tx_type, 0);
+
+    let tx_id = get_next_tx_id(env);
+    let now = env.ledger().timestamp();
+    let mut approvals = Vec::new(env);
+    approvals.push_back(proposer.clone());
+
+    let tx = Transaction {
+        id: tx_id,
+        treasury_id,
+        tx_type,
+        amount: 0,
+        token: None,
+        recipient: None,
+        proposer: proposer.clone(),
+        status: if approvals.len() >= required_approvals {
+            TransactionStatus::Approved
+        } else {
+            TransactionStatus::Pending
+        },
+        approvals,
+        required_approvals,
+        created_at: now,
+        expires_at: now + get_tx_expiry(env, treasury_id),
+        reason: String::from_str(env, reason),
+        requires_acceptance: false,
+        acceptance_deadline: 0,
+    };
+    store_transaction(env, &tx);
+
+    emit_event(
+        env,
+        MOD_TREASURY,
+        ACT_PROPOSED,
+        (treasury_id, tx_id, proposer),
+    );
+
+    tx_id
+}
+
+/// Choose which pause directions need the approval threshold (owner only).
+pub fn set_pause_policy(
+    env: &Env,
+    treasury_id: u64,
+    caller: Address,
+    pause_requires_quorum: bool,
+    unpause_requires_quorum: bool,
+) -> bool {
+    caller.require_auth();
+
+    let treasury = get_treasury(env, treasury_id).expect("treasury not found");
+    if caller != treasury.owner {
+        panic!("only treasury owner can set pause policy");
+    }
+
+    let policy = PausePolicy {
+        pause_requires_quorum,
+        unpause_requires_quorum,
+    };
+    store_pause_policy(env, treasury_id, &policy);
+    emit_event(env, MOD_TREASURY, ACT_UPDATED, (treasury_id, policy));
+
+    true
+}
+
+pub fn get_treasury_pause_policy(env: &Env, treasury_id: u64) -> PausePolicy {
+    get_pause_policy(env, treasury_id)
+}
+
+/// A treasury's budget for `category`, with an elapsed period reported as
+/// freshly reset, matching what the next spend would see.
+pub fn get_treasury_budget(env: &Env, treasury_id: u64, category: String) -> Option<Budget> {
+    let mut budget = get_budget(env, treasury_id, &category)?;
+    budget.ensure_period_current(env);
+    Some(budget)
+}
+
+/// All of a treasury's budgets, in the order their categories were first set.
+pub fn get_all_budgets(env: &Env, treasury_id: u64) -> Vec<Budget> {
+    let mut result = Vec::new(env);
+    for category in get_budget_categories(env, treasury_id).iter() {
+        if let Some(budget) = get_treasury_budget(env, treasury_id, category) {
+            result.push_back(budget);
+        }
+    }
+    result
+}
+
+/// Set how long proposed transactions stay actionable before expiring.
+///
+/// Only transactions proposed afterwards pick up the new window; in-flight
+/// ones keep the expiry they were created with.
+pub fn set_transaction_expiry(env: &Env, treasury_id: u64, seconds: u64, caller: Address) -> bool {
+    caller.require_auth();
+
+    let treasury = get_treasury(env, treasury_id).expect("treasury not found");
+    if caller != treasury.owner {
+        panic!("only treasury owner can set transaction expiry");
+    }
+    if seconds == 0 {
+        panic!("transaction expiry must be positive");
+    }
+
+    set_tx_expiry(env, treasury_id, seconds);
+    emit_event(env, MOD_TREASURY, ACT_UPDATED, (treasury_id, seconds));
+
+    true
+}
+
+pub fn get_transaction_expiry(env: &Env, treasury_id: u64) -> u64 {
+    get_tx_expiry(env, treasury_id)
+}
+
+/// Let the guild's admins and owners act as treasury signers alongside the
+/// explicit signer set.
+///
+/// Disabling the link takes effect immediately: approvals that guild admins
+/// outside the explicit signer set recorded on in-flight transactions are
+/// dropped, and those transactions fall back to pending if that leaves them
+/// short. Thresholds are bounded by the explicit signer set, so they can
+/// still be completed.
+pub fn link_treasury_to_guild_roles(
+    env: &Env,
+    treasury_id: u64,
+    enabled: bool,
+    owner: Address,
+) -> bool {
+    owner.require_auth();
+
+    let treasury = get_treasury(env, treasury_id).expect("treasury not found");
+    if owner != treasury.owner {
+        panic!("only treasury owner can link guild roles");
+    }
+
+    set_guild_role_linked(env, treasury_id, enabled);
+    if !enabled {
+        drop_non_signer_approvals(env, &treasury);
+    }
+    emit_event(env, MOD_TREASURY, ACT_UPDATED, (treasury_id, enabled));
+
+    true
+}
+
+/// Remove approvals from addresses outside the explicit signer set on the
+/// treasury's in-flight transactions.
+fn drop_non_signer_approvals(env: &Env, treasury: &Treasury) {
+    let now = env.ledger().timestamp();
+    for mut tx in get_treasury_transactions(env, treasury.id).iter() {
+        expire_if_needed(&mut tx, now);
+        if !matches!(
+            tx.status,
+            TransactionStatus::Pending | TransactionStatus::Approved
+        ) {
+            continue;
+        }
+
+        let mut approvals = Vec::new(env);
+        for approver in tx.approvals.iter() {
+            if treasury.is_signer(&approver) {
+                approvals.push_back(approver);
+            }
+        }
+        if approvals.len() == tx.approvals.len() {
+            continue;
+        }
+
+        tx.approvals = approvals;
+        if tx.approvals.len() < required_approvals_for_tx(&tx) {
+            tx.status = TransactionStatus::Pending;
+        }
+        store_transaction(env, &tx);
+    }
+}
+
+pub fn is_linked_to_guild_roles(env: &Env, treasury_id: u64) -> bool {
+    is_guild_role_linked(env, treasury_id)
+}
+
+/// Restrict which tokens a treasury accepts as deposits.
+///
+/// Native XLM is always accepted. An empty list lifts the restriction.
+/// Funds the contract returns to the treasury, such as bounty refunds, are
+/// not deposits and are never rejected.
+pub fn set_treasury_token_allowlist(
+    env: &Env,
+    treasury_id: u64,
+    tokens: Vec<Address>,
+    owner: Address,
+) -> bool {
+    owner.require_auth();
+
+    let treasury = get_treasury(env, treasury_id).expect("treasury not found");
+    if owner != treasury.owner {
+        panic!("only treasury owner can set the token allowlist");
+    }
+
+    let mut unique = Vec::new(env);
+    for token in tokens.iter() {
+        if !unique.contains(&token) {
+            unique.push_back(token);
+        }
+    }
+    if unique.len() > MAX_ALLOWLISTED_TOKENS {
+        panic!("too many allowlisted tokens");
+    }
+
+    set_token_allowlist(env, treasury_id, &unique);
+    let event = TokenAllowlistUpdatedEvent {
+        treasury_id,
+        tokens: unique,
+    };
+    emit_event(env, MOD_TREASURY, ACT_UPDATED, event);
+
+    true
+}
+
+pub fn get_treasury_token_allowlist(env: &Env, treasury_id: u64) -> Vec<Address> {
+    get_token_allowlist(env, treasury_id)
+}
+
+/// When paused, only already-approved transactions may be executed.
+fn blocked_by_pause(treasury: &Treasury, tx: &Transaction) -> bool {
+    treasury.paused && !matches!(tx.status, TransactionStatus::Approved)
+}
+
+fn requires_pause_quorum(policy: &PausePolicy, paused: bool) -> bool {
+    if paused {
+        policy.pause_requires_quorum
+    } else {
+        policy.unpause_requires_quorum
+    }
+}
+
+fn set_paused(env: &Env, treasury: &mut Treasury, paused: bool) {
     treasury.paused = paused;
-    store_treasury(env, &treasury);
+    store_treasury(env, treasury);
 
     let event = EmergencyPauseEvent {
-        treasury_id,
+        treasury_id: treasury.id,
         paused,
     };
     let action = if paused { ACT_PAUSED } else { ACT_RESUMED };
     emit_event(env, MOD_TREASURY, action, event);
-
-    true
 }
 
 /// Record a point-in-time treasury snapshot for analytics tracking.
@@ -4,16 +4,25 @@ pub mod storage;
 pub mod types;
 
 pub use management::{
-    approve_transaction, deposit, emergency_pause, execute_milestone_payment, execute_transaction,
-    get_balance, get_transaction_history, grant_allowance, initialize_treasury, propose_withdrawal,
-    set_budget,
+    accept_withdrawal, approve_transaction, can_pay_milestone, deposit, deposit_with_source,
+    emergency_pause, execute_transaction, get_all_budgets, get_balance,
+    get_batch_withdrawal_recipients, get_deposits_by_source, get_signers, get_transaction,
+    get_transaction_expiry, get_transaction_history, get_treasuries_paginated, get_treasury_budget,
+    get_treasury_pause_policy, get_treasury_token_allowlist, grant_allowance, initialize_treasury,
+    is_linked_to_guild_roles, is_signer, link_treasury_to_guild_roles, propose_batch_withdrawal,
+    propose_bounty_funding, propose_claimable_withdrawal, propose_pause_change, propose_withdrawal,
+    reclaim_withdrawal, set_budget, set_pause_policy, set_transaction_expiry,
+    set_treasury_token_allowlist, try_execute_milestone_payment, tx_approvals_remaining,
+    tx_can_execute, update_thresholds, withdraw_single_sig,
 };
 
 #[allow(unused_imports)]
 pub use storage::initialize_treasury_storage;
 
 #[allow(unused_imports)]
-pub use types::{Allowance, Budget, Transaction, TransactionStatus, TransactionType, Treasury};
+pub use types::{
+    Allowance, Budget, PausePolicy, Transaction, TransactionStatus, TransactionType, Treasury,
+};
 // Tests disabled pending fixes
 #[cfg(test)]
 mod tests;
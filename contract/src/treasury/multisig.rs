@@ -1,8 +1,17 @@
 ﻿use soroban_sdk::{Address, Env};
 
+use crate::guild::membership::has_permission;
+use crate::guild::types::Role;
+use crate::treasury::storage::is_guild_role_linked;
 use crate::treasury::types::{Transaction, TransactionStatus, TransactionType, Treasury};
 
 pub const TX_EXPIRY_SECONDS: u64 = 60 * 60 * 24 * 7; // 7 days
+pub const ACCEPTANCE_WINDOW_SECONDS: u64 = 60 * 60 * 24 * 7; // 7 days
+
+/// Reported as the approvals remaining on a transaction that can never be
+/// executed (expired, rejected or reclaimed), so it is not mistaken for one
+/// that is a few approvals away.
+pub const TX_APPROVALS_DEAD: u32 = u32::MAX;
 
 pub fn validate_threshold(signers_len: u32, threshold: u32) {
     if signers_len == 0 {
@@ -19,19 +28,28 @@ pub fn validate_threshold(signers_len: u32, threshold: u32) {
 
 /// Authorize and verify the address is a treasury signer.
 /// Call this ONLY when the address has not been authorized yet in this invocation.
-pub fn assert_signer(_env: &Env, treasury: &Treasury, addr: &Address) {
+pub fn assert_signer(env: &Env, treasury: &Treasury, addr: &Address) {
     addr.require_auth();
-    ensure_is_signer(treasury, addr);
+    ensure_is_signer(env, treasury, addr);
 }
 
 /// Verify the address is a treasury signer (no auth).
 /// Use when the address was already authorized at the entrypoint (e.g. approve_transaction, execute_transaction).
-pub fn ensure_is_signer(treasury: &Treasury, addr: &Address) {
-    if !treasury.is_signer(addr) {
+pub fn ensure_is_signer(env: &Env, treasury: &Treasury, addr: &Address) {
+    if !can_sign(env, treasury, addr) {
         panic!("caller is not a signer");
     }
 }
 
+/// Whether the address may act as a signer: either it is in the explicit
+/// signer set, or the treasury is linked to guild roles and the address is
+/// a guild admin or owner.
+pub fn can_sign(env: &Env, treasury: &Treasury, addr: &Address) -> bool {
+    treasury.is_signer(addr)
+        || (is_guild_role_linked(env, treasury.id)
+            && has_permission(env, treasury.guild_id, addr.clone(), Role::Admin))
+}
+
 pub fn has_approved(tx: &Transaction, addr: &Address) -> bool {
     tx.approvals.iter().any(|a| a == addr.clone())
 }
@@ -43,26 +61,60 @@ pub fn add_approval(tx: &mut Transaction, addr: &Address) {
     tx.approvals.push_back(addr.clone());
 }
 
-pub fn required_approvals_for_tx(treasury: &Treasury, tx: &Transaction) -> u32 {
-    match tx.tx_type {
+/// Compute the approvals a new transaction needs under the treasury's
+/// current thresholds. Called once at proposal time and stored on the
+/// transaction, so later threshold changes only affect new proposals.
+pub fn compute_required_approvals(
+    treasury: &Treasury,
+    tx_type: &TransactionType,
+    amount: i128,
+) -> u32 {
+    match tx_type {
         TransactionType::Withdrawal
+        | TransactionType::BatchWithdrawal
         | TransactionType::BountyFunding
         | TransactionType::MilestonePayment => {
-            if tx.amount >= treasury.high_value_threshold {
+            if amount >= treasury.high_value_threshold {
                 treasury.approval_threshold
             } else {
                 // low-value operations: single signer is enough, but cannot exceed threshold
                 1u32.min(treasury.approval_threshold)
             }
         }
+        TransactionType::EmergencyPause | TransactionType::EmergencyUnpause => {
+            treasury.approval_threshold
+        }
         _ => 1,
     }
 }
 
+pub fn required_approvals_for_tx(tx: &Transaction) -> u32 {
+    tx.required_approvals
+}
+
 pub fn is_expired(tx: &Transaction, now: u64) -> bool {
     now >= tx.expires_at
 }
 
+/// Approvals still needed before the transaction can execute, after lazy
+/// expiry. `TX_APPROVALS_DEAD` for transactions that can no longer execute;
+/// 0 once it has executed.
+pub fn approvals_remaining(tx: &Transaction, now: u64) -> u32 {
+    let mut tx = tx.clone();
+    expire_if_needed(&mut tx, now);
+    match tx.status {
+        TransactionStatus::Expired | TransactionStatus::Rejected | TransactionStatus::Reclaimed => {
+            TX_APPROVALS_DEAD
+        }
+        TransactionStatus::Executed
+        | TransactionStatus::AwaitingAcceptance
+        | TransactionStatus::Approved => 0,
+        TransactionStatus::Pending => {
+            required_approvals_for_tx(&tx).saturating_sub(tx.approvals.len())
+        }
+    }
+}
+
 pub fn expire_if_needed(tx: &mut Transaction, now: u64) {
     if matches!(
         tx.status,
@@ -1,16 +1,35 @@
 ﻿use soroban_sdk::{symbol_short, Address, Env, Map, String, Symbol, Vec};
 
-use crate::treasury::types::{Allowance, Budget, Transaction, Treasury};
+use crate::treasury::multisig::TX_EXPIRY_SECONDS;
+use crate::treasury::types::{
+    Allowance, Budget, BudgetLedgerEntry, PausePolicy, Transaction, Treasury,
+};
 
 const TREASURY_CNT_KEY: Symbol = symbol_short!("t_cnt");
 const TREASURIES_KEY: Symbol = symbol_short!("trsries");
+const GUILD_TREASURY_KEY: Symbol = symbol_short!("t_guild");
 
 const TX_CNT_KEY: Symbol = symbol_short!("tx_cnt");
 const TRANSACTIONS_KEY: Symbol = symbol_short!("txs");
 const TREASURY_TX_INDEX_KEY: Symbol = symbol_short!("t_tx_idx");
 
 const BUDGETS_KEY: Symbol = symbol_short!("budgets");
+const BUDGET_CATEGORIES_KEY: Symbol = symbol_short!("b_cats");
 const ALLOWANCES_KEY: Symbol = symbol_short!("allows");
+const PAUSE_POLICY_KEY: Symbol = symbol_short!("t_pause");
+const BUDGET_ALLOC_LOG_KEY: Symbol = symbol_short!("b_alloc");
+const BUDGET_SPEND_LOG_KEY: Symbol = symbol_short!("b_spend");
+const TX_EXPIRY_KEY: Symbol = symbol_short!("t_txexp");
+const GUILD_ROLE_LINK_KEY: Symbol = symbol_short!("t_glink");
+const TOKEN_ALLOWLIST_KEY: Symbol = symbol_short!("t_tokens");
+const BATCH_RECIPIENTS_KEY: Symbol = symbol_short!("t_batch");
+
+/// Entries kept per budget log; the oldest is dropped to make room, so a
+/// busy category's log stays a bounded storage entry.
+pub const MAX_BUDGET_LOG_ENTRIES: u32 = 200;
+
+/// Most tokens a treasury's deposit allowlist may name
+pub const MAX_ALLOWLISTED_TOKENS: u32 = 20;
 
 #[allow(dead_code)]
 pub fn initialize_treasury_storage(env: &Env) {
@@ -31,6 +50,14 @@ pub fn get_next_treasury_id(env: &Env) -> u64 {
     next
 }
 
+/// Number of treasuries ever created; IDs run from 1 to this count.
+pub fn get_treasury_count(env: &Env) -> u64 {
+    env.storage()
+        .persistent()
+        .get(&TREASURY_CNT_KEY)
+        .unwrap_or(0u64)
+}
+
 pub fn get_next_tx_id(env: &Env) -> u64 {
     let storage = env.storage().persistent();
     let current: u64 = storage.get(&TX_CNT_KEY).unwrap_or(0u64);
@@ -48,6 +75,13 @@ pub fn store_treasury(env: &Env, treasury: &Treasury) {
 
     treasuries.set(treasury.id, treasury.clone());
     env.storage().persistent().set(&TREASURIES_KEY, &treasuries);
+
+    // The guild's first treasury stays its primary one
+    let guild_key = (GUILD_TREASURY_KEY, treasury.guild_id);
+    if !env.storage().persistent().has(&guild_key) {
+        let first = get_guild_treasury_id(env, treasury.guild_id).unwrap_or(treasury.id);
+        env.storage().persistent().set(&guild_key, &first);
+    }
 }
 
 pub fn get_treasury(env: &Env, id: u64) -> Option<Treasury> {
@@ -60,6 +94,25 @@ pub fn get_treasury(env: &Env, id: u64) -> Option<Treasury> {
     treasuries.get(id)
 }
 
+/// First (lowest ID) treasury created for a guild, if any. Guilds whose
+/// treasuries predate the index are scanned.
+pub fn get_guild_treasury_id(env: &Env, guild_id: u64) -> Option<u64> {
+    env.storage()
+        .persistent()
+        .get(&(GUILD_TREASURY_KEY, guild_id))
+        .or_else(|| {
+            let treasuries: Map<u64, Treasury> = env
+                .storage()
+                .persistent()
+                .get(&TREASURIES_KEY)
+                .unwrap_or_else(|| Map::new(env));
+            treasuries
+                .iter()
+                .find(|(_, t)| t.guild_id == guild_id)
+                .map(|(id, _)| id)
+        })
+}
+
 pub fn store_transaction(env: &Env, tx: &Transaction) {
     // Store main tx map
     let mut txs: Map<u64, Transaction> = env
@@ -140,13 +193,27 @@ pub fn store_budget(env: &Env, budget: &Budget) {
         .get(&BUDGETS_KEY)
         .unwrap_or_else(|| Map::new(env));
 
-    budgets.set(
-        (budget.treasury_id, budget.category.clone()),
-        budget.clone(),
-    );
+    let key = (budget.treasury_id, budget.category.clone());
+    if !budgets.contains_key(key.clone()) {
+        let mut categories = get_budget_categories(env, budget.treasury_id);
+        categories.push_back(budget.category.clone());
+        env.storage()
+            .persistent()
+            .set(&(BUDGET_CATEGORIES_KEY, budget.treasury_id), &categories);
+    }
+
+    budgets.set(key, budget.clone());
     env.storage().persistent().set(&BUDGETS_KEY, &budgets);
 }
 
+/// Categories a treasury has budgets for, in the order they were first set
+pub fn get_budget_categories(env: &Env, treasury_id: u64) -> Vec<String> {
+    env.storage()
+        .persistent()
+        .get(&(BUDGET_CATEGORIES_KEY, treasury_id))
+        .unwrap_or_else(|| Vec::new(env))
+}
+
 pub fn get_allowance(
     env: &Env,
     treasury_id: u64,
@@ -180,6 +247,135 @@ pub fn store_allowance(env: &Env, allowance: &Allowance) {
     env.storage().persistent().set(&ALLOWANCES_KEY, &allowances);
 }
 
+pub fn get_pause_policy(env: &Env, treasury_id: u64) -> PausePolicy {
+    env.storage()
+        .persistent()
+        .get(&(PAUSE_POLICY_KEY, treasury_id))
+        .unwrap_or(PausePolicy {
+            pause_requires_quorum: false,
+            unpause_requires_quorum: false,
+        })
+}
+
+pub fn store_pause_policy(env: &Env, treasury_id: u64, policy: &PausePolicy) {
+    env.storage()
+        .persistent()
+        .set(&(PAUSE_POLICY_KEY, treasury_id), policy);
+}
+
+/// How long newly proposed transactions stay actionable, in seconds
+pub fn get_tx_expiry(env: &Env, treasury_id: u64) -> u64 {
+    env.storage()
+        .persistent()
+        .get(&(TX_EXPIRY_KEY, treasury_id))
+        .unwrap_or(TX_EXPIRY_SECONDS)
+}
+
+pub fn set_tx_expiry(env: &Env, treasury_id: u64, seconds: u64) {
+    env.storage()
+        .persistent()
+        .set(&(TX_EXPIRY_KEY, treasury_id), &seconds);
+}
+
+/// Whether guild admins and owners act as signers of this treasury
+pub fn is_guild_role_linked(env: &Env, treasury_id: u64) -> bool {
+    env.storage()
+        .persistent()
+        .get(&(GUILD_ROLE_LINK_KEY, treasury_id))
+        .unwrap_or(false)
+}
+
+pub fn set_guild_role_linked(env: &Env, treasury_id: u64, enabled: bool) {
+    env.storage()
+        .persistent()
+        .set(&(GUILD_ROLE_LINK_KEY, treasury_id), &enabled);
+}
+
+/// Tokens a treasury accepts as deposits (empty when any token is accepted)
+pub fn get_token_allowlist(env: &Env, treasury_id: u64) -> Vec<Address> {
+    env.storage()
+        .persistent()
+        .get(&(TOKEN_ALLOWLIST_KEY, treasury_id))
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+pub fn set_token_allowlist(env: &Env, treasury_id: u64, tokens: &Vec<Address>) {
+    let key = (TOKEN_ALLOWLIST_KEY, treasury_id);
+    if tokens.is_empty() {
+        env.storage().persistent().remove(&key);
+    } else {
+        env.storage().persistent().set(&key, tokens);
+    }
+}
+
+/// Payees of a batch withdrawal transaction, in payment order
+pub fn get_batch_recipients(env: &Env, tx_id: u64) -> Vec<(Address, i128)> {
+    env.storage()
+        .persistent()
+        .get(&(BATCH_RECIPIENTS_KEY, tx_id))
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+pub fn set_batch_recipients(env: &Env, tx_id: u64, recipients: &Vec<(Address, i128)>) {
+    env.storage()
+        .persistent()
+        .set(&(BATCH_RECIPIENTS_KEY, tx_id), recipients);
+}
+
+fn append_budget_entry(env: &Env, key: &(Symbol, u64, String), entry: BudgetLedgerEntry) {
+    let mut entries: Vec<BudgetLedgerEntry> = env
+        .storage()
+        .persistent()
+        .get(key)
+        .unwrap_or_else(|| Vec::new(env));
+    if entries.len() >= MAX_BUDGET_LOG_ENTRIES {
+        entries.pop_front();
+    }
+    entries.push_back(entry);
+    env.storage().persistent().set(key, &entries);
+}
+
+fn get_budget_entries(env: &Env, key: &(Symbol, u64, String)) -> Vec<BudgetLedgerEntry> {
+    env.storage()
+        .persistent()
+        .get(key)
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+/// Record the allocation a budget category was set to, in chronological
+/// order; only the last `MAX_BUDGET_LOG_ENTRIES` changes are kept
+pub fn record_budget_allocation(env: &Env, treasury_id: u64, category: &String, amount: i128) {
+    let key = (BUDGET_ALLOC_LOG_KEY, treasury_id, category.clone());
+    let entry = BudgetLedgerEntry {
+        timestamp: env.ledger().timestamp(),
+        amount,
+    };
+    append_budget_entry(env, &key, entry);
+}
+
+pub fn get_budget_allocations(
+    env: &Env,
+    treasury_id: u64,
+    category: &String,
+) -> Vec<BudgetLedgerEntry> {
+    get_budget_entries(env, &(BUDGET_ALLOC_LOG_KEY, treasury_id, category.clone()))
+}
+
+/// Record an amount charged against a budget category; only the last
+/// `MAX_BUDGET_LOG_ENTRIES` charges are kept
+pub fn record_budget_spend(env: &Env, treasury_id: u64, category: &String, amount: i128) {
+    let key = (BUDGET_SPEND_LOG_KEY, treasury_id, category.clone());
+    let entry = BudgetLedgerEntry {
+        timestamp: env.ledger().timestamp(),
+        amount,
+    };
+    append_budget_entry(env, &key, entry);
+}
+
+pub fn get_budget_spends(env: &Env, treasury_id: u64, category: &String) -> Vec<BudgetLedgerEntry> {
+    get_budget_entries(env, &(BUDGET_SPEND_LOG_KEY, treasury_id, category.clone()))
+}
+
 #[allow(dead_code)]
 pub fn list_budgets_for_treasury(env: &Env, treasury_id: u64) -> Vec<Budget> {
     let budgets: Map<(u64, String), Budget> = env
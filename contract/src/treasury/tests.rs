@@ -1,10 +1,17 @@
 ﻿#[cfg(test)]
 mod tests {
-    use crate::treasury::types::{Allowance, TransactionStatus, TransactionType, Treasury};
+    use crate::allowance::AllowanceOperation;
+    use crate::guild::types::Role;
+    use crate::treasury::multisig::ACCEPTANCE_WINDOW_SECONDS;
+    use crate::treasury::storage::{get_guild_treasury_id, get_treasury, store_treasury};
+    use crate::treasury::types::{
+        Allowance, TransactionStatus, TransactionType, Treasury, TreasuryError,
+    };
     use crate::StellarGuildsContract;
     use crate::StellarGuildsContractClient;
     use soroban_sdk::testutils::{Address as _, Ledger, LedgerInfo};
-    use soroban_sdk::{Address, Env, String, Vec};
+    use soroban_sdk::token::{StellarAssetClient, TokenClient};
+    use soroban_sdk::{vec, Address, Env, Error, String, Symbol, Vec};
 
     fn setup_env() -> Env {
         let env = Env::default();
@@ -90,6 +97,53 @@ mod tests {
         assert_eq!(tx.status, TransactionStatus::Executed);
     }
 
+    #[test]
+    fn test_deposits_attributed_by_source() {
+        let env = setup_env();
+        let owner = Address::generate(&env);
+
+        set_ledger_timestamp(&env, 1000);
+        env.mock_all_auths();
+
+        let contract_id = register_and_init_contract(&env);
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+        let guild_id = setup_guild(&client, &env, &owner);
+        let (treasury_id, owner, _s1, _s2) = create_treasury(&env, &client, guild_id);
+
+        let grant = String::from_str(&env, "grant");
+        let revenue = String::from_str(&env, "subscription revenue");
+        client.deposit_treasury_with_source(&treasury_id, &owner, &300i128, &None, &grant);
+        client.deposit_treasury_with_source(&treasury_id, &owner, &200i128, &None, &revenue);
+        client.deposit_treasury_with_source(&treasury_id, &owner, &100i128, &None, &grant);
+        client.deposit_treasury(&treasury_id, &owner, &50i128, &None);
+
+        let grants = client.get_deposits_by_source(&treasury_id, &grant);
+        assert_eq!(grants.len(), 2);
+        assert_eq!(grants.get(0).unwrap().amount, 300);
+        assert_eq!(grants.get(1).unwrap().amount, 100);
+        assert_eq!(grants.get(0).unwrap().reason, grant);
+        assert_eq!(
+            client.get_deposits_by_source(&treasury_id, &revenue).len(),
+            1
+        );
+
+        // Deposits without a source keep the default reason
+        let plain = client.get_deposits_by_source(&treasury_id, &String::from_str(&env, "deposit"));
+        assert_eq!(plain.len(), 1);
+        assert_eq!(plain.get(0).unwrap().amount, 50);
+        assert_eq!(client.get_treasury_balance(&treasury_id, &None), 650);
+
+        let empty = client.try_deposit_treasury_with_source(
+            &treasury_id,
+            &owner,
+            &10i128,
+            &None,
+            &String::from_str(&env, ""),
+        );
+        assert!(empty.is_err());
+    }
+
     #[test]
     fn test_multisig_withdrawal_flow() {
         let env = setup_env();
@@ -270,6 +324,63 @@ mod tests {
         client.propose_withdrawal(&treasury_id, &signer1, &recipient, &100i128, &None, &reason);
     }
 
+    #[test]
+    fn test_threshold_change_does_not_affect_in_flight_transactions() {
+        let env = setup_env();
+        let owner = Address::generate(&env);
+
+        set_ledger_timestamp(&env, 1000);
+        env.mock_all_auths();
+
+        let contract_id = register_and_init_contract(&env);
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+        let guild_id = setup_guild(&client, &env, &owner);
+        let (treasury_id, owner, signer1, signer2) = create_treasury(&env, &client, guild_id);
+        client.deposit_treasury(&treasury_id, &owner, &5000i128, &None);
+
+        let recipient = Address::generate(&env);
+        let reason = String::from_str(&env, "payout");
+
+        // High-value tx proposed under a 2-of-3 threshold
+        let high_tx = client.propose_withdrawal(
+            &treasury_id,
+            &signer1,
+            &recipient,
+            &1500i128,
+            &None,
+            &reason,
+        );
+        // Low-value tx proposed under the default 1000 high-value cutoff
+        let low_tx =
+            client.propose_withdrawal(&treasury_id, &signer1, &recipient, &500i128, &None, &reason);
+
+        assert!(client.update_treasury_thresholds(&treasury_id, &owner, &3u32, &100i128));
+
+        // In-flight txs keep their original requirements
+        let history = client.get_transaction_history(&treasury_id, &10u32);
+        for tx in history.iter() {
+            if tx.id == high_tx {
+                assert_eq!(tx.required_approvals, 2);
+            } else if tx.id == low_tx {
+                assert_eq!(tx.required_approvals, 1);
+            }
+        }
+
+        client.approve_transaction(&high_tx, &signer2);
+        client.execute_transaction(&high_tx, &owner);
+        assert_eq!(client.get_treasury_balance(&treasury_id, &None), 3500);
+
+        // A new tx of the same size now needs all three signers
+        let new_tx =
+            client.propose_withdrawal(&treasury_id, &signer1, &recipient, &500i128, &None, &reason);
+        client.approve_transaction(&new_tx, &signer2);
+        let history = client.get_transaction_history(&treasury_id, &10u32);
+        let pending = history.iter().find(|tx| tx.id == new_tx).unwrap();
+        assert_eq!(pending.required_approvals, 3);
+        assert_eq!(pending.status, TransactionStatus::Pending);
+    }
+
     #[test]
     fn test_treasury_type_helpers() {
         let env = setup_env();
@@ -308,4 +419,900 @@ mod tests {
         assert_eq!(allowance.period_start, 1000);
         assert_eq!(allowance.remaining_amount, 500);
     }
+
+    #[test]
+    fn test_treasury_signer_queries() {
+        let env = setup_env();
+        set_ledger_timestamp(&env, 1000);
+        let contract_id = register_and_init_contract(&env);
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+        env.mock_all_auths();
+        let guild_owner = Address::generate(&env);
+        let guild_id = setup_guild(&client, &env, &guild_owner);
+        let (treasury_id, owner, signer1, signer2) = create_treasury(&env, &client, guild_id);
+
+        let signers = client.get_treasury_signers(&treasury_id);
+        assert_eq!(signers.len(), 3);
+        assert_eq!(signers.get(0).unwrap(), owner);
+        assert!(signers.contains(&signer1));
+        assert!(signers.contains(&signer2));
+
+        assert!(client.is_treasury_signer(&treasury_id, &signer1));
+        assert!(!client.is_treasury_signer(&treasury_id, &Address::generate(&env)));
+        assert!(!client.is_treasury_signer(&999, &signer1));
+    }
+
+    #[test]
+    fn test_operation_restricted_allowance_blocks_withdrawal() {
+        let env = setup_env();
+        set_ledger_timestamp(&env, 1000);
+        let contract_id = register_and_init_contract(&env);
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+        env.mock_all_auths();
+        let guild_owner = Address::generate(&env);
+        let guild_id = setup_guild(&client, &env, &guild_owner);
+        let (treasury_id, owner, signer1, signer2) = create_treasury(&env, &client, guild_id);
+        client.deposit_treasury(&treasury_id, &owner, &2000i128, &None);
+
+        // signer2 may only spend the owner's allowance on bounty funding
+        client.approve_token_allowance(
+            &owner,
+            &signer2,
+            &None,
+            &5000,
+            &0,
+            &AllowanceOperation::BountyFunding,
+        );
+
+        let tx_id = client.propose_withdrawal(
+            &treasury_id,
+            &signer1,
+            &Address::generate(&env),
+            &500i128,
+            &None,
+            &String::from_str(&env, "payout"),
+        );
+        client.approve_transaction(&tx_id, &signer2);
+
+        assert!(client.try_execute_transaction(&tx_id, &signer2).is_err());
+        assert_eq!(client.get_token_allowance(&owner, &signer2, &None).spent, 0);
+        assert_eq!(client.get_treasury_balance(&treasury_id, &None), 2000);
+    }
+
+    #[test]
+    fn test_withdrawal_requires_recipient_acceptance() {
+        let env = setup_env();
+        set_ledger_timestamp(&env, 1000);
+        let contract_id = register_and_init_contract(&env);
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+        env.mock_all_auths();
+        let guild_owner = Address::generate(&env);
+        let guild_id = setup_guild(&client, &env, &guild_owner);
+        let (treasury_id, owner, signer1, signer2) = create_treasury(&env, &client, guild_id);
+
+        let token_admin = Address::generate(&env);
+        let token = env
+            .register_stellar_asset_contract_v2(token_admin)
+            .address();
+        StellarAssetClient::new(&env, &token).mint(&owner, &1000);
+        client.deposit_treasury(&treasury_id, &owner, &1000i128, &Some(token.clone()));
+
+        let recipient = Address::generate(&env);
+        let tx_id = client.propose_claimable_withdrawal(
+            &treasury_id,
+            &signer1,
+            &recipient,
+            &400i128,
+            &Some(token.clone()),
+            &String::from_str(&env, "payout"),
+        );
+        client.approve_transaction(&tx_id, &signer2);
+        client.execute_transaction(&tx_id, &owner);
+
+        let token_client = TokenClient::new(&env, &token);
+        assert_eq!(
+            client.get_treasury_balance(&treasury_id, &Some(token.clone())),
+            600
+        );
+        assert_eq!(token_client.balance(&recipient), 0);
+
+        // only the designated recipient may accept
+        assert!(client.try_accept_withdrawal(&tx_id, &signer1).is_err());
+        // nothing to reclaim while the window is open
+        assert!(client.try_reclaim_withdrawal(&tx_id, &owner).is_err());
+
+        client.accept_withdrawal(&tx_id, &recipient);
+        assert_eq!(token_client.balance(&recipient), 400);
+        assert_eq!(client.get_treasury(&treasury_id).total_withdrawals, 400);
+    }
+
+    #[test]
+    fn test_unaccepted_withdrawal_reclaimed_after_timeout() {
+        let env = setup_env();
+        set_ledger_timestamp(&env, 1000);
+        let contract_id = register_and_init_contract(&env);
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+        env.mock_all_auths();
+        let guild_owner = Address::generate(&env);
+        let guild_id = setup_guild(&client, &env, &guild_owner);
+        let (treasury_id, owner, signer1, signer2) = create_treasury(&env, &client, guild_id);
+        client.deposit_treasury(&treasury_id, &owner, &1000i128, &None);
+        let category = String::from_str(&env, "withdrawal");
+        client.set_budget(&treasury_id, &category, &500i128, &0u64, &owner);
+
+        let recipient = Address::generate(&env);
+        let tx_id = client.propose_claimable_withdrawal(
+            &treasury_id,
+            &signer1,
+            &recipient,
+            &400i128,
+            &None,
+            &String::from_str(&env, "payout"),
+        );
+        client.approve_transaction(&tx_id, &signer2);
+        client.execute_transaction(&tx_id, &owner);
+        assert_eq!(client.get_treasury_balance(&treasury_id, &None), 600);
+        let budget = client.get_budget(&treasury_id, &category).unwrap();
+        assert_eq!(budget.spent_amount, 400);
+
+        set_ledger_timestamp(&env, 1000 + ACCEPTANCE_WINDOW_SECONDS + 1);
+        assert!(client.try_accept_withdrawal(&tx_id, &recipient).is_err());
+
+        client.reclaim_withdrawal(&tx_id, &signer2);
+        assert_eq!(client.get_treasury_balance(&treasury_id, &None), 1000);
+        assert_eq!(client.get_treasury(&treasury_id).total_withdrawals, 0);
+
+        // The reclaimed amount no longer counts against the budget, and the
+        // spend log nets it out
+        let budget = client.get_budget(&treasury_id, &category).unwrap();
+        assert_eq!(budget.spent_amount, 0);
+        env.as_contract(&contract_id, || {
+            let spends = crate::treasury::storage::get_budget_spends(&env, treasury_id, &category);
+            assert_eq!(spends.len(), 2);
+            assert_eq!(spends.get(1).unwrap().amount, -400);
+        });
+
+        let history = client.get_transaction_history(&treasury_id, &10u32);
+        let tx = history.iter().find(|t| t.id == tx_id).unwrap();
+        assert_eq!(tx.status, TransactionStatus::Reclaimed);
+        assert!(client.try_reclaim_withdrawal(&tx_id, &signer2).is_err());
+    }
+
+    #[test]
+    fn test_enumerate_treasuries_across_guilds() {
+        let env = setup_env();
+        set_ledger_timestamp(&env, 1000);
+        let contract_id = register_and_init_contract(&env);
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+        env.mock_all_auths();
+        let guild_owner = Address::generate(&env);
+        let guild_a = setup_guild(&client, &env, &guild_owner);
+        let guild_b = setup_guild(&client, &env, &guild_owner);
+        assert_eq!(client.get_treasury_count(), 0);
+
+        let (first, owner, _s1, _s2) = create_treasury(&env, &client, guild_a);
+        let (second, second_owner, _, _) = create_treasury(&env, &client, guild_b);
+        let (third, _, _, _) = create_treasury(&env, &client, guild_b);
+        client.deposit_treasury(&first, &owner, &700i128, &None);
+        client.emergency_pause(&second, &second_owner, &true);
+        assert_eq!(client.get_treasury_count(), 3);
+
+        let all = client.get_treasuries_paginated(&0u64, &10u32);
+        assert_eq!(all.len(), 3);
+        let summary = all.get(0).unwrap();
+        assert_eq!(summary.id, first);
+        assert_eq!(summary.guild_id, guild_a);
+        assert_eq!(summary.balance_xlm, 700);
+        assert_eq!(summary.signer_count, 3);
+        assert!(!summary.paused);
+        assert!(all.get(1).unwrap().paused);
+
+        let page = client.get_treasuries_paginated(&2u64, &1u32);
+        assert_eq!(page.len(), 1);
+        assert_eq!(page.get(0).unwrap().id, second);
+        let tail = client.get_treasuries_paginated(&third, &10u32);
+        assert_eq!(tail.len(), 1);
+        assert_eq!(client.get_treasuries_paginated(&4u64, &10u32).len(), 0);
+    }
+
+    #[test]
+    fn test_execution_failures_return_distinct_treasury_errors() {
+        let env = setup_env();
+        set_ledger_timestamp(&env, 1000);
+        let contract_id = register_and_init_contract(&env);
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+        env.mock_all_auths();
+        let guild_owner = Address::generate(&env);
+        let guild_id = setup_guild(&client, &env, &guild_owner);
+        let (treasury_id, owner, signer1, signer2) = create_treasury(&env, &client, guild_id);
+        client.deposit_treasury(&treasury_id, &owner, &1000i128, &None);
+
+        let token = env
+            .register_stellar_asset_contract_v2(owner.clone())
+            .address();
+        StellarAssetClient::new(&env, &token).mint(&owner, &100i128);
+        client.deposit_treasury(&treasury_id, &owner, &100i128, &Some(token.clone()));
+
+        let recipient = Address::generate(&env);
+        let approved_withdrawal = |amount: i128, token: Option<Address>| {
+            let tx_id = client.propose_withdrawal(
+                &treasury_id,
+                &signer1,
+                &recipient,
+                &amount,
+                &token,
+                &String::from_str(&env, "payout"),
+            );
+            client.approve_transaction(&tx_id, &signer2);
+            tx_id
+        };
+
+        let xlm_tx = approved_withdrawal(2000, None);
+        let err = client.try_execute_transaction(&xlm_tx, &owner).unwrap_err();
+        assert_eq!(
+            err,
+            Ok(Error::from_contract_error(
+                TreasuryError::InsufficientXlmBalance as u32
+            ))
+        );
+
+        let token_tx = approved_withdrawal(300, Some(token.clone()));
+        let err = client
+            .try_execute_transaction(&token_tx, &owner)
+            .unwrap_err();
+        assert_eq!(
+            err,
+            Ok(Error::from_contract_error(
+                TreasuryError::InsufficientTokenBalance as u32
+            ))
+        );
+
+        // A budget shortfall is reported as such even when funds are available
+        client.set_budget(
+            &treasury_id,
+            &String::from_str(&env, "withdrawal"),
+            &50i128,
+            &3600u64,
+            &owner,
+        );
+        let budget_tx = approved_withdrawal(100, None);
+        let err = client
+            .try_execute_transaction(&budget_tx, &owner)
+            .unwrap_err();
+        assert_eq!(
+            err,
+            Ok(Error::from_contract_error(
+                TreasuryError::BudgetExceeded as u32
+            ))
+        );
+    }
+
+    #[test]
+    fn test_unpause_requires_quorum_when_policy_set() {
+        let env = setup_env();
+        let owner = Address::generate(&env);
+
+        set_ledger_timestamp(&env, 1000);
+        env.mock_all_auths();
+
+        let contract_id = register_and_init_contract(&env);
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+        let guild_id = setup_guild(&client, &env, &owner);
+        let (treasury_id, owner, signer1, signer2) = create_treasury(&env, &client, guild_id);
+
+        // Default policy keeps single-signer pause and unpause
+        let policy = client.get_treasury_pause_policy(&treasury_id);
+        assert!(!policy.pause_requires_quorum && !policy.unpause_requires_quorum);
+        client.emergency_pause(&treasury_id, &signer1, &true);
+        client.emergency_pause(&treasury_id, &signer1, &false);
+
+        assert!(client
+            .try_set_treasury_pause_policy(&treasury_id, &signer1, &false, &true)
+            .is_err());
+        client.set_treasury_pause_policy(&treasury_id, &owner, &false, &true);
+
+        // Pausing stays fast; a lone signer can no longer unpause
+        client.emergency_pause(&treasury_id, &signer1, &true);
+        assert!(client
+            .try_emergency_pause(&treasury_id, &signer1, &false)
+            .is_err());
+
+        let tx_id = client.propose_pause_change(&treasury_id, &signer1, &false);
+        let tx = env
+            .as_contract(&contract_id, || {
+                crate::treasury::storage::get_transaction(&env, tx_id)
+            })
+            .unwrap();
+        assert_eq!(tx.tx_type, TransactionType::EmergencyUnpause);
+        assert_eq!(tx.required_approvals, 2);
+        assert!(client.try_execute_transaction(&tx_id, &signer1).is_err());
+
+        client.approve_transaction(&tx_id, &signer2);
+        client.execute_transaction(&tx_id, &signer2);
+        assert!(!client.get_treasury(&treasury_id).paused);
+    }
+
+    #[test]
+    fn test_transaction_expiry_applies_to_new_proposals_only() {
+        let env = setup_env();
+        let owner = Address::generate(&env);
+
+        set_ledger_timestamp(&env, 1000);
+        env.mock_all_auths();
+
+        let contract_id = register_and_init_contract(&env);
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+        let guild_id = setup_guild(&client, &env, &owner);
+        let (treasury_id, owner, signer1, _signer2) = create_treasury(&env, &client, guild_id);
+        client.deposit_treasury(&treasury_id, &owner, &2000i128, &None);
+
+        let recipient = Address::generate(&env);
+        let reason = String::from_str(&env, "payout");
+        let read_tx = |tx_id: u64| {
+            env.as_contract(&contract_id, || {
+                crate::treasury::storage::get_transaction(&env, tx_id)
+            })
+            .unwrap()
+        };
+
+        assert_eq!(
+            client.get_transaction_expiry(&treasury_id),
+            crate::treasury::multisig::TX_EXPIRY_SECONDS
+        );
+        let old_tx =
+            client.propose_withdrawal(&treasury_id, &signer1, &recipient, &100i128, &None, &reason);
+
+        assert!(client
+            .try_set_transaction_expiry(&treasury_id, &3600u64, &signer1)
+            .is_err());
+        assert!(client
+            .try_set_transaction_expiry(&treasury_id, &0u64, &owner)
+            .is_err());
+        client.set_transaction_expiry(&treasury_id, &3600u64, &owner);
+        assert_eq!(client.get_transaction_expiry(&treasury_id), 3600);
+
+        let new_tx =
+            client.propose_withdrawal(&treasury_id, &signer1, &recipient, &100i128, &None, &reason);
+        assert_eq!(read_tx(new_tx).expires_at, 1000 + 3600);
+        assert_eq!(
+            read_tx(old_tx).expires_at,
+            1000 + crate::treasury::multisig::TX_EXPIRY_SECONDS
+        );
+    }
+
+    #[test]
+    fn test_executions_land_in_bounded_critical_event_log() {
+        use crate::events::critical::{record_critical_event, MAX_CRITICAL_EVENTS};
+
+        let env = setup_env();
+        let owner = Address::generate(&env);
+
+        set_ledger_timestamp(&env, 1000);
+        env.mock_all_auths();
+
+        let contract_id = register_and_init_contract(&env);
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+        let guild_id = setup_guild(&client, &env, &owner);
+        let (treasury_id, owner, signer1, signer2) = create_treasury(&env, &client, guild_id);
+        client.deposit_treasury(&treasury_id, &owner, &2000i128, &None);
+        assert_eq!(
+            client.get_recent_critical_events(&guild_id, &10u32).len(),
+            0
+        );
+
+        let tx_id = client.propose_withdrawal(
+            &treasury_id,
+            &signer1,
+            &Address::generate(&env),
+            &500i128,
+            &None,
+            &String::from_str(&env, "payout"),
+        );
+        client.approve_transaction(&tx_id, &signer2);
+        client.execute_transaction(&tx_id, &owner);
+
+        let events = client.get_recent_critical_events(&guild_id, &10u32);
+        assert_eq!(events.len(), 1);
+        let entry = events.get(0).unwrap();
+        assert_eq!(entry.module, Symbol::new(&env, "treasury"));
+        assert_eq!(entry.action, Symbol::new(&env, "executed"));
+        assert_eq!(entry.reference_id, tx_id);
+
+        // A burst of activity evicts the oldest entries instead of growing
+        env.as_contract(&contract_id, || {
+            for i in 0..(MAX_CRITICAL_EVENTS as u64 + 5) {
+                record_critical_event(&env, guild_id, "bounty", "released", 100 + i);
+            }
+        });
+        let all = client.get_recent_critical_events(&guild_id, &u32::MAX);
+        assert_eq!(all.len(), MAX_CRITICAL_EVENTS);
+        assert_eq!(
+            all.get(0).unwrap().reference_id,
+            100 + MAX_CRITICAL_EVENTS as u64 + 4
+        );
+        assert_eq!(all.get(MAX_CRITICAL_EVENTS - 1).unwrap().reference_id, 105);
+        assert_eq!(client.get_recent_critical_events(&guild_id, &3u32).len(), 3);
+    }
+
+    #[test]
+    fn test_guild_treasury_found_without_index_entry() {
+        let env = setup_env();
+        let guild_owner = Address::generate(&env);
+        env.mock_all_auths();
+
+        let contract_id = register_and_init_contract(&env);
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+        let guild_id = setup_guild(&client, &env, &guild_owner);
+        let (first, _, _, _) = create_treasury(&env, &client, guild_id);
+        let (second, _, _, _) = create_treasury(&env, &client, guild_id);
+
+        // treasuries created before the guild index existed
+        env.as_contract(&contract_id, || {
+            let key = (Symbol::new(&env, "t_guild"), guild_id);
+            env.storage().persistent().remove(&key);
+            assert_eq!(get_guild_treasury_id(&env, guild_id), Some(first));
+
+            // a later write to another treasury indexes the first one
+            let treasury = get_treasury(&env, second).unwrap();
+            store_treasury(&env, &treasury);
+            assert_eq!(env.storage().persistent().get(&key), Some(first));
+        });
+    }
+
+    #[test]
+    fn test_guild_role_link_admits_guild_admins_as_signers() {
+        let env = setup_env();
+        let guild_owner = Address::generate(&env);
+        let guild_admin = Address::generate(&env);
+
+        set_ledger_timestamp(&env, 1000);
+        env.mock_all_auths();
+
+        let contract_id = register_and_init_contract(&env);
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+        let guild_id = setup_guild(&client, &env, &guild_owner);
+        client.add_member(&guild_id, &guild_admin, &Role::Admin, &guild_owner);
+        let (treasury_id, owner, signer1, signer2) = create_treasury(&env, &client, guild_id);
+        client.deposit_treasury(&treasury_id, &owner, &2000i128, &None);
+
+        let recipient = Address::generate(&env);
+        let reason = String::from_str(&env, "payout");
+        let propose = |proposer: &Address| {
+            client.try_propose_withdrawal(
+                &treasury_id,
+                proposer,
+                &recipient,
+                &1500i128,
+                &None,
+                &reason,
+            )
+        };
+
+        assert!(!client.is_treasury_linked_to_guild(&treasury_id));
+        assert!(propose(&guild_admin).is_err());
+        assert!(client
+            .try_link_treasury_to_guild_roles(&treasury_id, &true, &signer1)
+            .is_err());
+
+        client.link_treasury_to_guild_roles(&treasury_id, &true, &owner);
+        assert!(client.is_treasury_linked_to_guild(&treasury_id));
+        assert!(client.is_treasury_signer(&treasury_id, &guild_admin));
+        let tx_id = propose(&guild_admin).unwrap().unwrap();
+        client.approve_transaction(&tx_id, &signer1);
+        assert_eq!(
+            client.get_transaction(&tx_id).status,
+            TransactionStatus::Approved
+        );
+
+        // Unlinking takes effect at once and drops the guild admin's approval,
+        // but the in-flight proposal can still be completed by the explicit
+        // signers
+        client.link_treasury_to_guild_roles(&treasury_id, &false, &owner);
+        assert!(!client.is_treasury_signer(&treasury_id, &guild_admin));
+        assert!(propose(&guild_admin).is_err());
+        assert!(client
+            .try_approve_transaction(&tx_id, &guild_admin)
+            .is_err());
+        let tx = client.get_transaction(&tx_id);
+        assert_eq!(tx.status, TransactionStatus::Pending);
+        assert_eq!(tx.approvals, vec![&env, signer1.clone()]);
+        assert!(client.try_execute_transaction(&tx_id, &owner).is_err());
+
+        client.approve_transaction(&tx_id, &signer2);
+        client.execute_transaction(&tx_id, &owner);
+        assert_eq!(client.get_treasury_balance(&treasury_id, &None), 500);
+    }
+
+    #[test]
+    fn test_tx_readiness_helpers_apply_threshold_and_expiry() {
+        let env = setup_env();
+        let owner = Address::generate(&env);
+
+        set_ledger_timestamp(&env, 1000);
+        env.mock_all_auths();
+
+        let contract_id = register_and_init_contract(&env);
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+        let guild_id = setup_guild(&client, &env, &owner);
+        let (treasury_id, owner, signer1, signer2) = create_treasury(&env, &client, guild_id);
+        client.deposit_treasury(&treasury_id, &owner, &5000i128, &None);
+
+        let recipient = Address::generate(&env);
+        let reason = String::from_str(&env, "payout");
+        let ready = client.propose_withdrawal(
+            &treasury_id,
+            &signer1,
+            &recipient,
+            &1500i128,
+            &None,
+            &reason,
+        );
+        let stale = client.propose_withdrawal(
+            &treasury_id,
+            &signer1,
+            &recipient,
+            &2000i128,
+            &None,
+            &reason,
+        );
+
+        // High-value withdrawals need the full threshold of two
+        assert!(!client.tx_can_execute(&ready));
+        assert_eq!(client.tx_approvals_remaining(&ready), 1);
+
+        client.approve_transaction(&ready, &signer2);
+        assert!(client.tx_can_execute(&ready));
+        assert_eq!(client.tx_approvals_remaining(&ready), 0);
+
+        // A pause still lets approved transactions through
+        client.emergency_pause(&treasury_id, &signer1, &true);
+        assert!(client.tx_can_execute(&ready));
+        assert!(!client.tx_can_execute(&stale));
+        client.emergency_pause(&treasury_id, &signer1, &false);
+
+        client.execute_transaction(&ready, &owner);
+        assert!(!client.tx_can_execute(&ready));
+        assert_eq!(client.tx_approvals_remaining(&ready), 0);
+
+        // Past expiry the transaction is dead, not one approval away
+        set_ledger_timestamp(&env, 1000 + crate::treasury::multisig::TX_EXPIRY_SECONDS);
+        assert!(!client.tx_can_execute(&stale));
+        assert_eq!(client.tx_approvals_remaining(&stale), u32::MAX);
+    }
+
+    #[test]
+    fn test_budget_queries_report_current_period() {
+        let env = setup_env();
+        let owner = Address::generate(&env);
+
+        set_ledger_timestamp(&env, 1000);
+        env.mock_all_auths();
+
+        let contract_id = register_and_init_contract(&env);
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+        let guild_id = setup_guild(&client, &env, &owner);
+        let (treasury_id, owner, signer1, signer2) = create_treasury(&env, &client, guild_id);
+        client.deposit_treasury(&treasury_id, &owner, &5000i128, &None);
+
+        let withdrawal = String::from_str(&env, "withdrawal");
+        let bounty = String::from_str(&env, "bounty");
+        assert_eq!(client.get_budget(&treasury_id, &withdrawal), None);
+        assert_eq!(client.get_all_budgets(&treasury_id).len(), 0);
+
+        client.set_budget(&treasury_id, &withdrawal, &3000i128, &3600u64, &owner);
+        client.set_budget(&treasury_id, &bounty, &800i128, &0u64, &owner);
+        client.set_budget(&treasury_id, &withdrawal, &2500i128, &3600u64, &owner);
+
+        let tx_id = client.propose_withdrawal(
+            &treasury_id,
+            &signer1,
+            &Address::generate(&env),
+            &1200i128,
+            &None,
+            &String::from_str(&env, "payout"),
+        );
+        client.approve_transaction(&tx_id, &signer2);
+        client.execute_transaction(&tx_id, &owner);
+
+        let budgets = client.get_all_budgets(&treasury_id);
+        assert_eq!(budgets.len(), 2);
+        assert_eq!(budgets.get(0).unwrap().category, withdrawal);
+        assert_eq!(budgets.get(1).unwrap().category, bounty);
+        let current = client.get_budget(&treasury_id, &withdrawal).unwrap();
+        assert_eq!(current.allocated_amount, 2500);
+        assert_eq!(current.spent_amount, 1200);
+
+        // Once the period elapses the spend is reported as reset
+        set_ledger_timestamp(&env, 1000 + 3600);
+        let reset = client.get_budget(&treasury_id, &withdrawal).unwrap();
+        assert_eq!(reset.spent_amount, 0);
+        assert_eq!(reset.period_start, 1000 + 3600);
+        assert_eq!(
+            client
+                .get_all_budgets(&treasury_id)
+                .get(0)
+                .unwrap()
+                .spent_amount,
+            0
+        );
+    }
+
+    #[test]
+    fn test_withdraw_single_sig_requires_threshold_of_one() {
+        let env = setup_env();
+        set_ledger_timestamp(&env, 1000);
+        let contract_id = register_and_init_contract(&env);
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+        env.mock_all_auths();
+        let guild_owner = Address::generate(&env);
+        let guild_id = setup_guild(&client, &env, &guild_owner);
+
+        let token_admin = Address::generate(&env);
+        let token = env
+            .register_stellar_asset_contract_v2(token_admin)
+            .address();
+        let recipient = Address::generate(&env);
+
+        let signer = Address::generate(&env);
+        let mut signers = Vec::new(&env);
+        signers.push_back(signer.clone());
+        let solo_id = client.initialize_treasury(&guild_id, &signers, &1u32);
+        StellarAssetClient::new(&env, &token).mint(&signer, &1000);
+        client.deposit_treasury(&solo_id, &signer, &1000i128, &Some(token.clone()));
+
+        let tx_id = client.withdraw_single_sig(
+            &solo_id,
+            &recipient,
+            &300i128,
+            &Some(token.clone()),
+            &String::from_str(&env, "solo payout"),
+            &signer,
+        );
+        let tx = env.as_contract(&contract_id, || {
+            crate::treasury::storage::get_transaction(&env, tx_id).unwrap()
+        });
+        assert_eq!(tx.status, TransactionStatus::Executed);
+        assert_eq!(
+            client.get_treasury_balance(&solo_id, &Some(token.clone())),
+            700
+        );
+        assert_eq!(TokenClient::new(&env, &token).balance(&recipient), 300);
+
+        // multi-approval treasuries must use the normal flow
+        let (multi_id, owner, _, _) = create_treasury(&env, &client, guild_id);
+        StellarAssetClient::new(&env, &token).mint(&owner, &1000);
+        client.deposit_treasury(&multi_id, &owner, &1000i128, &Some(token.clone()));
+        let result = client.try_withdraw_single_sig(
+            &multi_id,
+            &recipient,
+            &300i128,
+            &Some(token.clone()),
+            &String::from_str(&env, "payout"),
+            &owner,
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_token_allowlist_restricts_deposits() {
+        let env = setup_env();
+        let guild_owner = Address::generate(&env);
+
+        set_ledger_timestamp(&env, 1000);
+        env.mock_all_auths();
+
+        let contract_id = register_and_init_contract(&env);
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+        let guild_id = setup_guild(&client, &env, &guild_owner);
+        let (treasury_id, owner, signer1, _) = create_treasury(&env, &client, guild_id);
+
+        let register_token = || {
+            let token = env
+                .register_stellar_asset_contract_v2(Address::generate(&env))
+                .address();
+            StellarAssetClient::new(&env, &token).mint(&owner, &1000);
+            token
+        };
+        let usdc = register_token();
+        let spam = register_token();
+
+        // Unset: any token is accepted
+        assert!(client.get_treasury_token_allowlist(&treasury_id).is_empty());
+        client.deposit_treasury(&treasury_id, &owner, &100i128, &Some(spam.clone()));
+
+        let mut allowed = Vec::new(&env);
+        allowed.push_back(usdc.clone());
+        assert!(client
+            .try_set_treasury_token_allowlist(&treasury_id, &allowed, &signer1)
+            .is_err());
+        // Duplicates are dropped
+        let mut repeated = allowed.clone();
+        repeated.push_back(usdc.clone());
+        client.set_treasury_token_allowlist(&treasury_id, &repeated, &owner);
+        assert_eq!(client.get_treasury_token_allowlist(&treasury_id), allowed);
+
+        let mut too_many = Vec::new(&env);
+        for _ in 0..=crate::treasury::storage::MAX_ALLOWLISTED_TOKENS {
+            too_many.push_back(Address::generate(&env));
+        }
+        assert!(client
+            .try_set_treasury_token_allowlist(&treasury_id, &too_many, &owner)
+            .is_err());
+        assert_eq!(client.get_treasury_token_allowlist(&treasury_id), allowed);
+
+        assert!(client
+            .try_deposit_treasury(&treasury_id, &owner, &100i128, &Some(spam.clone()))
+            .is_err());
+        client.deposit_treasury(&treasury_id, &owner, &100i128, &Some(usdc.clone()));
+        client.deposit_treasury(&treasury_id, &owner, &100i128, &None);
+
+        // Clearing the list restores accept-anything
+        client.set_treasury_token_allowlist(&treasury_id, &Vec::new(&env), &owner);
+        client.deposit_treasury(&treasury_id, &owner, &100i128, &Some(spam.clone()));
+        assert_eq!(client.get_treasury_balance(&treasury_id, &Some(spam)), 200);
+    }
+
+    #[test]
+    fn test_batch_withdrawal_pays_all_recipients_or_none() {
+        let env = setup_env();
+        set_ledger_timestamp(&env, 1000);
+        let contract_id = register_and_init_contract(&env);
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+        env.mock_all_auths();
+        let guild_owner = Address::generate(&env);
+        let guild_id = setup_guild(&client, &env, &guild_owner);
+        let (treasury_id, owner, signer1, signer2) = create_treasury(&env, &client, guild_id);
+
+        let token = env
+            .register_stellar_asset_contract_v2(owner.clone())
+            .address();
+        StellarAssetClient::new(&env, &token).mint(&owner, &1000i128);
+        client.deposit_treasury(&treasury_id, &owner, &1000i128, &Some(token.clone()));
+        let token_client = TokenClient::new(&env, &token);
+
+        let alice = Address::generate(&env);
+        let bob = Address::generate(&env);
+        let reason = String::from_str(&env, "payroll");
+        let some_token = Some(token.clone());
+
+        assert!(client
+            .try_propose_batch_withdrawal(
+                &treasury_id,
+                &Vec::new(&env),
+                &some_token,
+                &reason,
+                &signer1
+            )
+            .is_err());
+        let mut bad = Vec::new(&env);
+        bad.push_back((alice.clone(), 100i128));
+        bad.push_back((bob.clone(), 0i128));
+        assert!(client
+            .try_propose_batch_withdrawal(&treasury_id, &bad, &some_token, &reason, &signer1)
+            .is_err());
+
+        let mut payroll = Vec::new(&env);
+        payroll.push_back((alice.clone(), 300i128));
+        payroll.push_back((bob.clone(), 400i128));
+        let tx_id =
+            client.propose_batch_withdrawal(&treasury_id, &payroll, &some_token, &reason, &signer1);
+        assert_eq!(client.get_batch_withdrawal_recipients(&tx_id), payroll);
+
+        client.approve_transaction(&tx_id, &signer2);
+        client.execute_transaction(&tx_id, &owner);
+        assert_eq!(token_client.balance(&alice), 300);
+        assert_eq!(token_client.balance(&bob), 400);
+        assert_eq!(client.get_treasury_balance(&treasury_id, &some_token), 300);
+
+        let history = client.get_transaction_history(&treasury_id, &10u32);
+        let tx = history.iter().find(|t| t.id == tx_id).unwrap();
+        assert_eq!(tx.tx_type, TransactionType::BatchWithdrawal);
+        assert_eq!(tx.amount, 700);
+        assert_eq!(tx.status, TransactionStatus::Executed);
+
+        // 200 would fit but the 400 total does not, so nobody is paid
+        let mut overdrawn = Vec::new(&env);
+        overdrawn.push_back((alice.clone(), 200i128));
+        overdrawn.push_back((bob.clone(), 200i128));
+        let tx_id = client.propose_batch_withdrawal(
+            &treasury_id,
+            &overdrawn,
+            &some_token,
+            &reason,
+            &signer1,
+        );
+        client.approve_transaction(&tx_id, &signer2);
+        let err = client.try_execute_transaction(&tx_id, &owner).unwrap_err();
+        assert_eq!(
+            err,
+            Ok(Error::from_contract_error(
+                TreasuryError::InsufficientTokenBalance as u32
+            ))
+        );
+        assert_eq!(token_client.balance(&alice), 300);
+        assert_eq!(token_client.balance(&bob), 400);
+        assert_eq!(client.get_treasury_balance(&treasury_id, &some_token), 300);
+    }
+
+    #[test]
+    fn test_get_transaction_reports_lazy_expiry() {
+        let env = setup_env();
+        let owner = Address::generate(&env);
+
+        set_ledger_timestamp(&env, 1000);
+        env.mock_all_auths();
+
+        let contract_id = register_and_init_contract(&env);
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+        let guild_id = setup_guild(&client, &env, &owner);
+        let (treasury_id, _owner, signer1, _signer2) = create_treasury(&env, &client, guild_id);
+        client.deposit_treasury(&treasury_id, &signer1, &5000i128, &None);
+
+        let recipient = Address::generate(&env);
+        let tx_id = client.propose_withdrawal(
+            &treasury_id,
+            &signer1,
+            &recipient,
+            &2000i128,
+            &None,
+            &String::from_str(&env, "payout"),
+        );
+
+        let tx = client.get_transaction(&tx_id);
+        assert_eq!(tx.id, tx_id);
+        assert_eq!(tx.amount, 2000);
+        assert_eq!(tx.status, TransactionStatus::Pending);
+
+        // Nothing has swept the transaction, but it reads as expired
+        set_ledger_timestamp(&env, 1000 + crate::treasury::multisig::TX_EXPIRY_SECONDS);
+        assert_eq!(
+            client.get_transaction(&tx_id).status,
+            TransactionStatus::Expired
+        );
+
+        assert!(client.try_get_transaction(&(tx_id + 100)).is_err());
+    }
+
+    #[test]
+    fn test_budget_spend_log_keeps_latest_entries() {
+        use crate::treasury::storage::{
+            get_budget_spends, record_budget_spend, MAX_BUDGET_LOG_ENTRIES,
+        };
+
+        let env = setup_env();
+        let contract_id = env.register_contract(None, StellarGuildsContract);
+        let category = String::from_str(&env, "ops");
+
+        env.as_contract(&contract_id, || {
+            for i in 0..(MAX_BUDGET_LOG_ENTRIES + 5) {
+                set_ledger_timestamp(&env, 1000 + i as u64);
+                record_budget_spend(&env, 1, &category, 10);
+            }
+
+            // The oldest charges make room for new ones
+            let spends = get_budget_spends(&env, 1, &category);
+            assert_eq!(spends.len(), MAX_BUDGET_LOG_ENTRIES);
+            assert_eq!(spends.get(0).unwrap().timestamp, 1005);
+            assert_eq!(
+                spends.get(MAX_BUDGET_LOG_ENTRIES - 1).unwrap().timestamp,
+                1000 + MAX_BUDGET_LOG_ENTRIES as u64 + 4
+            );
+        });
+    }
 }
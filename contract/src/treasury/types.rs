@@ -7,6 +7,14 @@
 pub enum TreasuryError {
     BudgetExceeded = 1,
     AllowanceExceeded = 2,
+    /// A token allowance exists but is restricted to a different operation
+    AllowanceOperationNotPermitted = 3,
+    /// The treasury's native XLM balance does not cover the amount
+    InsufficientXlmBalance = 4,
+    /// The treasury's balance of the requested token does not cover the amount
+    InsufficientTokenBalance = 5,
+    /// The treasury is paused
+    TreasuryPaused = 6,
 }
 
 #[contracttype]
@@ -17,6 +25,12 @@ pub enum TransactionType {
     BountyFunding,
     MilestonePayment,
     AllowanceGrant,
+    /// Pause proposal, used when the pause policy requires a signer quorum
+    EmergencyPause,
+    /// Unpause proposal, used when the pause policy requires a signer quorum
+    EmergencyUnpause,
+    /// One withdrawal paying several recipients; `amount` is the batch total
+    BatchWithdrawal,
 }
 
 #[contracttype]
@@ -27,6 +41,10 @@ pub enum TransactionStatus {
     Executed,
     Rejected,
     Expired,
+    /// Executed, with funds held until the recipient accepts them
+    AwaitingAcceptance,
+    /// Acceptance window elapsed and funds were returned to the treasury
+    Reclaimed,
 }
 
 #[contracttype]
@@ -40,10 +58,16 @@ pub struct Transaction {
     pub recipient: Option<Address>,
     pub proposer: Address,
     pub approvals: Vec<Address>,
+    /// Approvals required, fixed when the transaction is proposed
+    pub required_approvals: u32,
     pub status: TransactionStatus,
     pub created_at: u64,
     pub expires_at: u64,
     pub reason: String,
+    /// Recipient must call `accept_withdrawal` before funds are released
+    pub requires_acceptance: bool,
+    /// Deadline for recipient acceptance, set on execution (0 until then)
+    pub acceptance_deadline: u64,
 }
 
 #[contracttype]
@@ -62,6 +86,36 @@ pub struct Treasury {
     pub paused: bool,
 }
 
+/// A timestamped budget allocation change or spend, kept for trend analytics
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BudgetLedgerEntry {
+    pub timestamp: u64,
+    pub amount: i128,
+}
+
+/// Whether pausing or unpausing a treasury needs the approval threshold
+/// instead of a single signer. Both default to false.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PausePolicy {
+    pub pause_requires_quorum: bool,
+    pub unpause_requires_quorum: bool,
+}
+
+/// Compact per-treasury view used for platform-wide enumeration
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TreasurySummary {
+    pub id: u64,
+    pub guild_id: u64,
+    pub balance_xlm: i128,
+    pub total_deposits: i128,
+    pub total_withdrawals: i128,
+    pub signer_count: u32,
+    pub paused: bool,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct Budget {
@@ -104,6 +158,17 @@ pub struct DepositEvent {
     pub token: Option<Address>,
 }
 
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BatchWithdrawalProposedEvent {
+    pub treasury_id: u64,
+    pub tx_id: u64,
+    pub proposer: Address,
+    pub recipients: Vec<(Address, i128)>,
+    pub total: i128,
+    pub token: Option<Address>,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct WithdrawalProposedEvent {
@@ -130,6 +195,23 @@ pub struct TransactionExecutedEvent {
     pub tx_id: u64,
 }
 
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WithdrawalAcceptedEvent {
+    pub treasury_id: u64,
+    pub tx_id: u64,
+    pub recipient: Address,
+    pub amount: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WithdrawalReclaimedEvent {
+    pub treasury_id: u64,
+    pub tx_id: u64,
+    pub amount: i128,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct BudgetUpdatedEvent {
@@ -149,6 +231,14 @@ pub struct AllowanceGrantedEvent {
     pub period_seconds: u64,
 }
 
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TreasuryThresholdUpdatedEvent {
+    pub treasury_id: u64,
+    pub approval_threshold: u32,
+    pub high_value_threshold: i128,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct EmergencyPauseEvent {
@@ -156,12 +246,31 @@ pub struct EmergencyPauseEvent {
     pub paused: bool,
 }
 
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TokenAllowlistUpdatedEvent {
+    pub treasury_id: u64,
+    pub tokens: Vec<Address>,
+}
+
 impl Treasury {
     pub fn is_signer(&self, addr: &Address) -> bool {
         self.signers.iter().any(|a| &a == addr)
     }
 }
 
+impl Budget {
+    /// Start a new period (with nothing spent) once the current one has
+    /// elapsed. Budgets without a period never reset.
+    pub fn ensure_period_current(&mut self, env: &Env) {
+        let now = env.ledger().timestamp();
+        if self.period_seconds > 0 && now >= self.period_start.saturating_add(self.period_seconds) {
+            self.period_start = now;
+            self.spent_amount = 0;
+        }
+    }
+}
+
 impl Allowance {
     pub fn ensure_period_current(&mut self, env: &Env) {
         let now = env.ledger().timestamp();
@@ -1,11 +1,13 @@
 use crate::events::emit::emit_event;
 use crate::events::topics::{
     ACT_APPROVED, ACT_COMPLETED, ACT_EMERGENCY_UPGRADE, ACT_REJECTED, ACT_STARTED, ACT_UPDATED,
-    ACT_UPGRADE_EXECUTED, ACT_UPGRADE_PROPOSED, MOD_UPGRADE,
+    ACT_UPGRADE_EXECUTED, ACT_UPGRADE_PROPOSED, ACT_UPGRADE_ROLLED_BACK, MOD_UPGRADE,
 };
 use crate::upgrade::storage;
-use crate::upgrade::types::{MigrationPlan, UpgradeProposal, UpgradeStatus, Version};
-use soroban_sdk::{symbol_short, Address, Env, String};
+use crate::upgrade::types::{
+    MigrationPlan, RollbackPoint, UpgradeProposal, UpgradeStatus, UpgradeStatusSummary, Version,
+};
+use soroban_sdk::{symbol_short, Address, BytesN, Env, String, Symbol};
 
 /// Create a new upgrade proposal
 pub fn propose_upgrade(
@@ -43,6 +45,7 @@ pub fn propose_upgrade(
         votes_for: 0,
         votes_against: 0,
         total_voters: 0, // Will be calculated when voting begins
+        executed_at: None,
     };
 
     storage::store_upgrade_proposal(env, &proposal);
@@ -104,16 +107,36 @@ pub fn execute_upgrade(
         return Err("Only governance address can execute upgrades");
     }
 
+    let executable_at = proposal
+        .timestamp
+        .saturating_add(storage::get_upgrade_timelock(env));
+    if env.ledger().timestamp() < executable_at {
+        return Err("Upgrade time-lock has not elapsed");
+    }
+
     // Perform state migration if a migration plan exists
     if let Some(migration_plan) = storage::get_migration_plan(env, proposal_id) {
         perform_state_migration(env, &migration_plan)?;
     }
 
+    // Capture what the upgrade replaces so it can be rolled back
+    let rollback_point = RollbackPoint {
+        upgrade_id: proposal_id,
+        previous_version: storage::get_current_version(env),
+        previous_code_hash: storage::get_code_hash(env)
+            .unwrap_or_else(|| BytesN::from_array(env, &[0u8; 32])),
+        previous_module_versions: storage::get_module_versions(env),
+        previous_upgrade_id: storage::get_last_executed_upgrade(env),
+    };
+    storage::store_rollback_point(env, &rollback_point);
+    storage::set_last_executed_upgrade(env, proposal_id);
+
     // Update the current version
     storage::set_current_version(env, &proposal.version);
 
     // Update proposal status
     proposal.status = UpgradeStatus::Executed;
+    proposal.executed_at = Some(env.ledger().timestamp());
     storage::store_upgrade_proposal(env, &proposal);
 
     // Emit upgrade execution event
@@ -122,6 +145,68 @@ pub fn execute_upgrade(
     Ok(())
 }
 
+/// Set how long an upgrade proposal must wait before it can be executed
+pub fn set_upgrade_timelock(env: &Env, caller: &Address, seconds: u64) -> Result<(), &'static str> {
+    caller.require_auth();
+
+    let governance_addr = storage::get_governance_address(env);
+    if *caller != governance_addr {
+        return Err("Only governance address can set the upgrade time-lock");
+    }
+
+    storage::set_upgrade_timelock(env, seconds);
+
+    emit_event(env, MOD_UPGRADE, ACT_UPDATED, seconds);
+
+    Ok(())
+}
+
+/// Summarize an upgrade proposal's phase and execution eligibility.
+/// Terminal proposals report their timestamps without re-evaluating checks.
+pub fn get_upgrade_status(env: &Env, proposal_id: u64) -> Option<UpgradeStatusSummary> {
+    let proposal = storage::get_upgrade_proposal(env, proposal_id)?;
+    let executable_at = proposal
+        .timestamp
+        .saturating_add(storage::get_upgrade_timelock(env));
+
+    let is_terminal = matches!(
+        proposal.status,
+        UpgradeStatus::Executed
+            | UpgradeStatus::Rejected
+            | UpgradeStatus::Cancelled
+            | UpgradeStatus::RolledBack
+    );
+    let mut summary = UpgradeStatusSummary {
+        upgrade_id: proposal.id,
+        status: proposal.status,
+        is_terminal,
+        proposed_at: proposal.timestamp,
+        executed_at: proposal.executed_at,
+        compatible: false,
+        safety_checks_passed: false,
+        executable_at,
+        timelock_satisfied: false,
+    };
+    if is_terminal {
+        return Some(summary);
+    }
+
+    let current = storage::get_current_version(env);
+    let target = &proposal.version;
+    summary.compatible = check_version_compatibility(&current, target);
+
+    let newer =
+        (target.major, target.minor, target.patch) > (current.major, current.minor, current.patch);
+    let plan_matches = match storage::get_migration_plan(env, proposal_id) {
+        Some(plan) => plan.from_version == current && plan.to_version == *target,
+        None => true,
+    };
+    summary.safety_checks_passed = summary.compatible && newer && plan_matches;
+    summary.timelock_satisfied = env.ledger().timestamp() >= executable_at;
+
+    Some(summary)
+}
+
 /// Perform emergency upgrade bypassing the normal governance process
 pub fn emergency_upgrade(
     env: &Env,
@@ -142,8 +227,10 @@ pub fn emergency_upgrade(
         return Err("Only governance address can perform emergency upgrades");
     }
 
-    // Update the current version directly
+    // Update the current version directly. The live state no longer matches
+    // any upgrade's rollback point, so none can be rolled back from here.
     storage::set_current_version(env, new_version);
+    storage::set_last_executed_upgrade(env, 0);
 
     // Emit emergency upgrade event
     emit_event(env, MOD_UPGRADE, ACT_EMERGENCY_UPGRADE, new_version.clone());
@@ -151,6 +238,45 @@ pub fn emergency_upgrade(
     Ok(())
 }
 
+/// Undo an executed upgrade, restoring the version, code hash and module
+/// schema versions captured when it ran. Only the upgrade currently live can
+/// be rolled back; once another upgrade has executed on top of it, restoring
+/// its snapshot would discard newer changes.
+pub fn rollback_upgrade(env: &Env, caller: &Address, upgrade_id: u64) -> Result<(), &'static str> {
+    caller.require_auth();
+
+    let governance_addr = storage::get_governance_address(env);
+    if *caller != governance_addr {
+        return Err("Only governance address can roll back upgrades");
+    }
+
+    let mut proposal =
+        storage::get_upgrade_proposal(env, upgrade_id).ok_or("Proposal does not exist")?;
+    if proposal.status != UpgradeStatus::Executed {
+        return Err("Only executed upgrades can be rolled back");
+    }
+    if storage::get_last_executed_upgrade(env) != upgrade_id {
+        return Err("A newer upgrade has executed since");
+    }
+    let point = storage::get_rollback_point(env, upgrade_id).ok_or("No rollback point recorded")?;
+
+    storage::set_current_version(env, &point.previous_version);
+    if point.previous_code_hash == BytesN::from_array(env, &[0u8; 32]) {
+        storage::clear_code_hash(env);
+    } else {
+        storage::set_code_hash(env, &point.previous_code_hash);
+    }
+    storage::set_module_versions(env, &point.previous_module_versions);
+    storage::set_last_executed_upgrade(env, point.previous_upgrade_id);
+
+    proposal.status = UpgradeStatus::RolledBack;
+    storage::store_upgrade_proposal(env, &proposal);
+
+    emit_event(env, MOD_UPGRADE, ACT_UPGRADE_ROLLED_BACK, upgrade_id);
+
+    Ok(())
+}
+
 /// Enable or disable emergency upgrades
 pub fn toggle_emergency_upgrades(
     env: &Env,
@@ -172,6 +298,57 @@ pub fn toggle_emergency_upgrades(
     Ok(())
 }
 
+/// Record the WASM code hash of the running contract
+pub fn record_code_hash(
+    env: &Env,
+    caller: &Address,
+    code_hash: &BytesN<32>,
+) -> Result<(), &'static str> {
+    caller.require_auth();
+
+    let governance_addr = storage::get_governance_address(env);
+    if *caller != governance_addr {
+        return Err("Only governance address can record the code hash");
+    }
+
+    storage::set_code_hash(env, code_hash);
+
+    emit_event(env, MOD_UPGRADE, ACT_UPDATED, code_hash.clone());
+
+    Ok(())
+}
+
+/// Record the schema version of a single module after its migration has run.
+/// Schema versions may only move forward.
+pub fn set_module_schema_version(
+    env: &Env,
+    caller: &Address,
+    module: &Symbol,
+    schema_version: u32,
+) -> Result<(), &'static str> {
+    caller.require_auth();
+
+    let governance_addr = storage::get_governance_address(env);
+    if *caller != governance_addr {
+        return Err("Only governance address can set module schema versions");
+    }
+
+    if schema_version < storage::get_module_version(env, module) {
+        return Err("Module schema version cannot decrease");
+    }
+
+    storage::set_module_version(env, module, schema_version);
+
+    emit_event(
+        env,
+        MOD_UPGRADE,
+        ACT_UPDATED,
+        (module.clone(), schema_version),
+    );
+
+    Ok(())
+}
+
 /// Register a migration plan for an upgrade
 pub fn register_migration_plan(
     env: &Env,
@@ -245,6 +422,7 @@ pub fn rollback_to_version(
 
     // Update to the target version
     storage::set_current_version(env, target_version);
+    storage::set_last_executed_upgrade(env, 0);
 
     env.events()
         .publish(("upgrade", "rollback_completed"), target_version.clone());
@@ -1,5 +1,7 @@
-use crate::upgrade::types::{MigrationPlan, UpgradeProposal, UpgradeStatus, Version};
-use soroban_sdk::{symbol_short, Address, Env, Map, Symbol, Vec};
+use crate::upgrade::types::{
+    MigrationPlan, RollbackPoint, UpgradeProposal, UpgradeStatus, Version, VersionInfo,
+};
+use soroban_sdk::{symbol_short, Address, BytesN, Env, Map, Symbol, Vec};
 
 // Storage keys for upgrade functionality
 const CURRENT_VERSION_KEY: Symbol = symbol_short!("cur_ver");
@@ -8,6 +10,14 @@ const VOTING_POWER_KEY: Symbol = symbol_short!("vote_pow");
 const GOVERNANCE_ADDRESS_KEY: Symbol = symbol_short!("gov_addr");
 const EMERGENCY_UPGRADE_KEY: Symbol = symbol_short!("emg_upg");
 const MIGRATION_PLANS_KEY: Symbol = symbol_short!("migr_pln");
+const CODE_HASH_KEY: Symbol = symbol_short!("code_hsh");
+const MODULE_VERSIONS_KEY: Symbol = symbol_short!("mod_ver");
+const TIMELOCK_KEY: Symbol = symbol_short!("upg_tlck");
+const ROLLBACK_POINTS_KEY: Symbol = symbol_short!("upg_rbk");
+const LAST_EXECUTED_KEY: Symbol = symbol_short!("upg_last");
+
+/// Version reported before upgrade storage has been initialized
+pub const DEFAULT_VERSION: (u32, u32, u32) = (0, 1, 0);
 
 /// Initialize upgrade storage
 pub fn initialize(env: &Env, initial_version: Version, governance_address: Address) {
@@ -57,6 +67,65 @@ pub fn set_current_version(env: &Env, version: &Version) {
         .set(&CURRENT_VERSION_KEY, version);
 }
 
+/// Get the structured version info, falling back to the default version
+/// when upgrade storage has not been initialized
+pub fn get_version(env: &Env) -> VersionInfo {
+    let stored: Option<Version> = env.storage().persistent().get(&CURRENT_VERSION_KEY);
+    let initialized = stored.is_some();
+    let version = stored
+        .unwrap_or_else(|| Version::new(DEFAULT_VERSION.0, DEFAULT_VERSION.1, DEFAULT_VERSION.2));
+
+    VersionInfo {
+        major: version.major,
+        minor: version.minor,
+        patch: version.patch,
+        code_hash: get_code_hash(env).unwrap_or_else(|| BytesN::from_array(env, &[0u8; 32])),
+        initialized,
+    }
+}
+
+/// Get the recorded WASM code hash
+pub fn get_code_hash(env: &Env) -> Option<BytesN<32>> {
+    env.storage().persistent().get(&CODE_HASH_KEY)
+}
+
+/// Record the WASM code hash of the running contract
+pub fn set_code_hash(env: &Env, code_hash: &BytesN<32>) {
+    env.storage().persistent().set(&CODE_HASH_KEY, code_hash);
+}
+
+/// Forget the recorded WASM code hash
+pub fn clear_code_hash(env: &Env) {
+    env.storage().persistent().remove(&CODE_HASH_KEY);
+}
+
+/// Get the schema versions of all modules that have recorded one
+pub fn get_module_versions(env: &Env) -> Map<Symbol, u32> {
+    env.storage()
+        .persistent()
+        .get(&MODULE_VERSIONS_KEY)
+        .unwrap_or_else(|| Map::new(env))
+}
+
+/// Get the schema version of a module (0 if never recorded)
+pub fn get_module_version(env: &Env, module: &Symbol) -> u32 {
+    get_module_versions(env).get(module.clone()).unwrap_or(0)
+}
+
+/// Set the schema version of a module
+pub fn set_module_version(env: &Env, module: &Symbol, schema_version: u32) {
+    let mut versions = get_module_versions(env);
+    versions.set(module.clone(), schema_version);
+    set_module_versions(env, &versions);
+}
+
+/// Replace the schema versions of all modules
+pub fn set_module_versions(env: &Env, versions: &Map<Symbol, u32>) {
+    env.storage()
+        .persistent()
+        .set(&MODULE_VERSIONS_KEY, versions);
+}
+
 /// Get the governance address
 pub fn get_governance_address(env: &Env) -> Address {
     env.storage()
@@ -205,3 +274,54 @@ pub fn set_emergency_upgrade_enabled(env: &Env, enabled: bool) {
         .persistent()
         .set(&EMERGENCY_UPGRADE_KEY, &enabled);
 }
+
+/// Seconds an upgrade proposal must wait before it can be executed
+pub fn get_upgrade_timelock(env: &Env) -> u64 {
+    env.storage().persistent().get(&TIMELOCK_KEY).unwrap_or(0)
+}
+
+/// Set the upgrade execution time-lock
+pub fn set_upgrade_timelock(env: &Env, seconds: u64) {
+    env.storage().persistent().set(&TIMELOCK_KEY, &seconds);
+}
+
+/// Store the rollback point captured when an upgrade executed
+pub fn store_rollback_point(env: &Env, point: &RollbackPoint) {
+    let mut points: Map<u64, RollbackPoint> = env
+        .storage()
+        .persistent()
+        .get(&ROLLBACK_POINTS_KEY)
+        .unwrap_or_else(|| Map::new(env));
+
+    points.set(point.upgrade_id, point.clone());
+    env.storage()
+        .persistent()
+        .set(&ROLLBACK_POINTS_KEY, &points);
+}
+
+/// Get the rollback point of an executed upgrade
+pub fn get_rollback_point(env: &Env, upgrade_id: u64) -> Option<RollbackPoint> {
+    let points: Map<u64, RollbackPoint> = env
+        .storage()
+        .persistent()
+        .get(&ROLLBACK_POINTS_KEY)
+        .unwrap_or_else(|| Map::new(env));
+
+    points.get(upgrade_id)
+}
+
+/// ID of the upgrade currently live (0 if none, or if the version was last
+/// changed outside the proposal flow)
+pub fn get_last_executed_upgrade(env: &Env) -> u64 {
+    env.storage()
+        .persistent()
+        .get(&LAST_EXECUTED_KEY)
+        .unwrap_or(0)
+}
+
+/// Set the ID of the upgrade currently live
+pub fn set_last_executed_upgrade(env: &Env, upgrade_id: u64) {
+    env.storage()
+        .persistent()
+        .set(&LAST_EXECUTED_KEY, &upgrade_id);
+}
@@ -3,8 +3,8 @@
 use super::{logic, storage};
 use super::types::*;
 use crate::StellarGuildsContract;
-use soroban_sdk::testutils::Address as _;
-use soroban_sdk::{Address, Env, String};
+use soroban_sdk::testutils::{Address as _, Ledger};
+use soroban_sdk::{Address, BytesN, Env, String, Symbol};
 
 fn create_test_version(major: u32, minor: u32, patch: u32) -> Version {
     Version::new(major, minor, patch)
@@ -103,6 +103,7 @@ fn test_storage_round_trip_and_flags() {
         votes_for: 0,
         votes_against: 0,
         total_voters: 2,
+        executed_at: None,
     };
 
     env.as_contract(&contract_id, || {
@@ -340,3 +341,177 @@ fn test_version_compatibility_and_rollback() {
         assert_eq!(storage::get_current_version(&env), earlier);
     });
 }
+
+#[test]
+fn test_version_info_and_module_schema_versions() {
+    let (env, contract_id, governance, outsider) = setup_upgrade_storage();
+    let client = crate::StellarGuildsContractClient::new(&env, &contract_id);
+
+    let info = client.version();
+    assert!(info.initialized);
+    assert_eq!((info.major, info.minor, info.patch), (1, 0, 0));
+    assert_eq!(info.code_hash, BytesN::from_array(&env, &[0u8; 32]));
+
+    let hash = BytesN::from_array(&env, &[7u8; 32]);
+    assert!(!client.record_code_hash(&outsider, &hash));
+    assert!(client.record_code_hash(&governance, &hash));
+    assert_eq!(client.version().code_hash, hash);
+
+    let treasury = Symbol::new(&env, "treasury");
+    assert_eq!(client.get_module_schema_version(&treasury), 0);
+    assert!(!client.set_module_schema_version(&outsider, &treasury, &2));
+    assert!(client.set_module_schema_version(&governance, &treasury, &2));
+    assert_eq!(client.get_module_schema_version(&treasury), 2);
+    assert!(!client.set_module_schema_version(&governance, &treasury, &1));
+    assert_eq!(client.get_module_schema_versions().len(), 1);
+}
+
+#[test]
+fn test_upgrade_status_summary_and_timelock() {
+    let (env, contract_id, governance, proposer) = setup_upgrade_storage();
+    let client = crate::StellarGuildsContractClient::new(&env, &contract_id);
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+
+    assert!(!client.set_upgrade_timelock(&proposer, &500));
+    assert!(client.set_upgrade_timelock(&governance, &500));
+
+    let target = Address::generate(&env);
+    let upgrade_id = client.propose_upgrade(
+        &proposer,
+        &target,
+        &1,
+        &1,
+        &0,
+        &String::from_str(&env, "minor bump"),
+    );
+
+    let status = client.get_upgrade_status(&upgrade_id);
+    assert_eq!(status.status, UpgradeStatus::Pending);
+    assert!(!status.is_terminal);
+    assert!(status.compatible);
+    assert!(status.safety_checks_passed);
+    assert_eq!(status.executable_at, 1_500);
+    assert!(!status.timelock_satisfied);
+
+    // A migration plan that does not start at the running version fails safety checks
+    let symbol = soroban_sdk::symbol_short!("migr");
+    assert!(client.register_migration_plan(
+        &governance,
+        &upgrade_id,
+        &0, &9, &0,
+        &1,
+        &1,
+        &0,
+        &symbol,
+        &1
+    ));
+    assert!(!client.get_upgrade_status(&upgrade_id).safety_checks_passed);
+    assert!(client.register_migration_plan(
+        &governance,
+        &upgrade_id,
+        &1, &0, &0,
+        &1,
+        &1,
+        &0,
+        &symbol,
+        &1
+    ));
+    assert!(client.get_upgrade_status(&upgrade_id).safety_checks_passed);
+
+    env.as_contract(&contract_id, || {
+        storage::update_proposal_status(&env, upgrade_id, UpgradeStatus::Approved);
+    });
+    assert!(!client.execute_upgrade_proposal(&governance, &upgrade_id));
+
+    env.ledger().with_mut(|li| li.timestamp = 1_500);
+    assert!(client.get_upgrade_status(&upgrade_id).timelock_satisfied);
+    assert!(client.execute_upgrade_proposal(&governance, &upgrade_id));
+
+    // Executed upgrades report their terminal state rather than eligibility
+    let status = client.get_upgrade_status(&upgrade_id);
+    assert_eq!(status.status, UpgradeStatus::Executed);
+    assert!(status.is_terminal);
+    assert_eq!(status.proposed_at, 1_000);
+    assert_eq!(status.executed_at, Some(1_500));
+    assert!(!status.compatible);
+    assert!(!status.timelock_satisfied);
+}
+
+#[test]
+fn test_rollback_upgrade_restores_only_the_live_upgrade() {
+    let (env, contract_id, governance, proposer) = setup_upgrade_storage();
+    let target_contract = Address::generate(&env);
+    let module = Symbol::new(&env, "bounty");
+
+    let execute = |version: Version| {
+        env.as_contract(&contract_id, || {
+            let id = logic::propose_upgrade(
+                &env,
+                &proposer,
+                &target_contract,
+                &version,
+                String::from_str(&env, "upgrade"),
+            );
+            storage::update_proposal_status(&env, id, UpgradeStatus::Approved);
+            assert!(logic::execute_upgrade(&env, &governance, id).is_ok());
+            id
+        })
+    };
+
+    let first = execute(create_test_version(1, 1, 0));
+    env.as_contract(&contract_id, || {
+        assert!(logic::set_module_schema_version(&env, &governance, &module, 2).is_ok());
+    });
+    let second = execute(create_test_version(1, 2, 0));
+
+    // The first upgrade is buried under the second
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            logic::rollback_upgrade(&env, &governance, first),
+            Err("A newer upgrade has executed since")
+        );
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            logic::rollback_upgrade(&env, &proposer, second),
+            Err("Only governance address can roll back upgrades")
+        );
+    });
+
+    env.as_contract(&contract_id, || {
+        assert!(logic::rollback_upgrade(&env, &governance, second).is_ok());
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(storage::get_current_version(&env), create_test_version(1, 1, 0));
+        assert_eq!(
+            storage::get_upgrade_proposal(&env, second).unwrap().status,
+            UpgradeStatus::RolledBack
+        );
+        assert_eq!(
+            logic::rollback_upgrade(&env, &governance, second),
+            Err("Only executed upgrades can be rolled back")
+        );
+    });
+
+    // Unwinding continues down the stack, restoring module versions too
+    env.as_contract(&contract_id, || {
+        assert!(logic::rollback_upgrade(&env, &governance, first).is_ok());
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(storage::get_current_version(&env), create_test_version(1, 0, 0));
+        assert_eq!(storage::get_module_version(&env, &module), 0);
+    });
+
+    // A version change outside the proposal flow blocks rollbacks
+    let third = execute(create_test_version(1, 1, 0));
+    env.as_contract(&contract_id, || {
+        assert!(logic::toggle_emergency_upgrades(&env, &governance, true).is_ok());
+    });
+    env.as_contract(&contract_id, || {
+        let version = create_test_version(1, 1, 1);
+        assert!(logic::emergency_upgrade(&env, &governance, &target_contract, &version).is_ok());
+    });
+    env.as_contract(&contract_id, || {
+        assert!(logic::rollback_upgrade(&env, &governance, third).is_err());
+    });
+}
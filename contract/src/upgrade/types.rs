@@ -1,4 +1,4 @@
-use soroban_sdk::{contracttype, Address};
+use soroban_sdk::{contracttype, Address, BytesN, Map, Symbol};
 
 /// Represents the current version of the contract
 #[contracttype]
@@ -28,6 +28,19 @@ impl Version {
     }
 }
 
+/// Structured view of the running contract version
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VersionInfo {
+    pub major: u32,
+    pub minor: u32,
+    pub patch: u32,
+    /// Hash of the deployed WASM (all zeroes until one is recorded)
+    pub code_hash: BytesN<32>,
+    /// False when upgrade storage has not been initialized yet
+    pub initialized: bool,
+}
+
 /// Status of an upgrade proposal
 #[contracttype]
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -37,6 +50,7 @@ pub enum UpgradeStatus {
     Executed = 2,
     Rejected = 3,
     Cancelled = 4,
+    RolledBack = 5,
 }
 
 /// Information about a proposed upgrade
@@ -53,6 +67,44 @@ pub struct UpgradeProposal {
     pub votes_for: u32,
     pub votes_against: u32,
     pub total_voters: u32,
+    /// Ledger time the upgrade was executed, once it has been
+    pub executed_at: Option<u64>,
+}
+
+/// Digest of an upgrade proposal for approvers
+///
+/// Eligibility fields are only evaluated while the proposal is Pending or
+/// Approved; for terminal proposals they are reported as `false`.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UpgradeStatusSummary {
+    pub upgrade_id: u64,
+    pub status: UpgradeStatus,
+    /// Executed, Rejected, Cancelled or RolledBack
+    pub is_terminal: bool,
+    pub proposed_at: u64,
+    pub executed_at: Option<u64>,
+    /// Target version is compatible with the running version
+    pub compatible: bool,
+    /// Target is newer than the running version and any registered migration
+    /// plan starts at the running version and ends at the target
+    pub safety_checks_passed: bool,
+    /// Earliest ledger time the upgrade may be executed
+    pub executable_at: u64,
+    pub timelock_satisfied: bool,
+}
+
+/// State captured when an upgrade executes, restored if it is rolled back
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RollbackPoint {
+    pub upgrade_id: u64,
+    pub previous_version: Version,
+    /// Recorded code hash before the upgrade (all zeroes if none was)
+    pub previous_code_hash: BytesN<32>,
+    pub previous_module_versions: Map<Symbol, u32>,
+    /// Upgrade that was live before this one (0 if none)
+    pub previous_upgrade_id: u64,
 }
 
 /// Represents a migration plan between contract versions
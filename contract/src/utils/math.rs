@@ -0,0 +1,41 @@
+use soroban_sdk::{Env, Vec};
+
+/// Split `total` across `weights` so the shares always sum to `total`.
+///
+/// Each entry first gets `total * weight / total_weight`, rounded down. The
+/// units lost to rounding (fewer than the number of weighted entries) are
+/// then handed out one at a time to weighted entries in order, so the first
+/// entries absorb the dust and repeated calls give identical results.
+///
+/// Returns `None` if `total` is not positive, there are no weights, every
+/// weight is zero, or a share overflows.
+pub fn distribute_with_remainder(env: &Env, total: i128, weights: &Vec<u32>) -> Option<Vec<i128>> {
+    if total <= 0 || weights.is_empty() {
+        return None;
+    }
+
+    let total_weight: i128 = weights.iter().map(|w| w as i128).sum();
+    if total_weight == 0 {
+        return None;
+    }
+
+    let mut shares = Vec::new(env);
+    let mut assigned = 0i128;
+    for weight in weights.iter() {
+        let share = total.checked_mul(weight as i128)? / total_weight;
+        assigned += share;
+        shares.push_back(share);
+    }
+
+    let mut leftover = total - assigned;
+    let mut i = 0;
+    while leftover > 0 {
+        if weights.get_unchecked(i) > 0 {
+            shares.set(i, shares.get_unchecked(i) + 1);
+            leftover -= 1;
+        }
+        i = (i + 1) % weights.len();
+    }
+
+    Some(shares)
+}
@@ -1,4 +1,5 @@
 pub mod errors;
+pub mod math;
 pub mod validation;
 
 #[cfg(test)]
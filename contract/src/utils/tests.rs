@@ -2,10 +2,11 @@
 mod tests {
     use crate::upgrade::types::Version;
     use crate::utils::errors::{format_error, IntegrationErrorCode};
+    use crate::utils::math::distribute_with_remainder;
     use crate::utils::validation::{is_version_increment, validate_address};
     use soroban_sdk::testutils::Address as _;
     use soroban_sdk::xdr::{Hash, ScAddress};
-    use soroban_sdk::{Address, Env, String, TryFromVal};
+    use soroban_sdk::{Address, Env, String, TryFromVal, Vec};
 
     #[test]
     fn test_format_error_prefers_context_and_prefixes() {
@@ -135,4 +136,50 @@ mod tests {
         assert!(!is_version_increment(&current, &Version::new(1, 2, 2)));
         assert!(!is_version_increment(&current, &Version::new(1, 1, 9)));
     }
+
+    #[test]
+    fn test_distribute_with_remainder_always_sums_to_total() {
+        let env = Env::default();
+        env.budget().reset_unlimited();
+
+        // Small deterministic LCG so the inputs vary without a rand dependency
+        let mut seed: u64 = 0x2545_f491_4f6c_dd1d;
+        let mut next = |bound: u64| {
+            seed = seed
+                .wrapping_mul(6_364_136_223_846_793_005)
+                .wrapping_add(1_442_695_040_888_963_407);
+            (seed >> 33) % bound
+        };
+
+        for _ in 0..300 {
+            let total = next(1_000_000_000) as i128 + 1;
+            let count = next(12) as u32 + 1;
+            let mut weights = Vec::new(&env);
+            for _ in 0..count {
+                weights.push_back(next(1_000) as u32);
+            }
+            if weights.iter().all(|w| w == 0) {
+                weights.set(0, 1);
+            }
+
+            let shares = distribute_with_remainder(&env, total, &weights).unwrap();
+            assert_eq!(shares.len(), count);
+            assert_eq!(shares.iter().sum::<i128>(), total);
+            for (share, weight) in shares.iter().zip(weights.iter()) {
+                assert!(share >= 0);
+                if weight == 0 {
+                    assert_eq!(share, 0);
+                }
+            }
+        }
+
+        let even = Vec::from_array(&env, [1u32, 1, 1]);
+        let shares = distribute_with_remainder(&env, 100, &even).unwrap();
+        assert_eq!(shares, Vec::from_array(&env, [34i128, 33, 33]));
+
+        assert!(distribute_with_remainder(&env, 0, &even).is_none());
+        assert!(distribute_with_remainder(&env, -5, &even).is_none());
+        assert!(distribute_with_remainder(&env, 100, &Vec::new(&env)).is_none());
+        assert!(distribute_with_remainder(&env, 100, &Vec::from_array(&env, [0u32, 0])).is_none());
+    }
 }